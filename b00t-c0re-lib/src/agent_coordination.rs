@@ -1,19 +1,53 @@
-//! Agent coordination system using Redis pub/sub
+//! Agent coordination system over any ACP transport (Redis pub/sub by default)
+//!
+//! Coordination messages travel as `COORDINATION` payloads in the same versioned
+//! `ACPMessage` envelope as ACP step-sync traffic, on the same `{namespace}.acp.` subjects
+//! (`ACPMessage::inbox_subject` / `channel_subject`), so coordinators and ACP `Agent`s on
+//! NATS, Redis or in-process transports hear each other.
 //!
 //! Provides comprehensive agent-to-agent communication including:
 //! - Agent discovery and presence tracking
 //! - Message routing and blocking reception
-//! - Team captain delegation and voting systems
+//! - Team captain delegation (with leased, reassignable tasks) and voting systems
 //! - Progress reporting and notifications
 
+use crate::agent_selection::{proficiency_for, AgentSelector, CandidateScore};
+use crate::agent_tasks::{registry_field, TaskExpiry, TaskRecord, TaskRegistry, TaskState, TASK_REGISTRY_KEY};
+use crate::agent_voting::{VoteTally, VotingResult, VotingRules};
 use crate::redis::{RedisComms, AgentMessage, AgentStatus};
+use crate::datum_ai_model::ModelSize;
 use crate::B00tResult;
+use b00t_acp::{ACPMessage, InMemoryTransport, MessageType, MessageVerifier, RedisTransport, SigningKey, Transport, UnsignedPolicy, Verification, VerifyingKey, COORDINATION_CHANNELS};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
-use tokio::sync::{mpsc, oneshot};
+use tokio::sync::{mpsc, oneshot, OnceCell};
+use tokio::task::JoinHandle;
 use tokio::time::timeout;
 
+/// How often a captain checks leases and deadlines
+const SUPERVISE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How often a started agent refreshes its entry in the agent registry
+const PRESENCE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Registry entries older than this are left out of discovery (ten missed refreshes)
+const PRESENCE_TTL_SECS: u64 = 300;
+
+/// How long finished tasks stay in the registry (and its Redis mirror)
+const FINISHED_TASK_RETENTION_SECS: u64 = 3600;
+
+fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default()
+}
+
+/// How long `request_capability` collects responses by default
+const CAPABILITY_WINDOW: Duration = Duration::from_secs(2);
+
+/// Namespace coordinators talk in until `with_namespace`
+pub const DEFAULT_NAMESPACE: &str = "b00t";
+
 /// Agent metadata for discovery and capabilities
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AgentMetadata {
@@ -25,6 +59,8 @@ pub struct AgentMetadata {
     pub last_seen: u64, // Unix timestamp
     pub load: f32, // Current workload 0.0-1.0
     pub specializations: HashMap<String, f32>, // Domain -> proficiency score
+    #[serde(default)]
+    pub model_size: Option<ModelSize>, // sm0l or ch0nky, when known
 }
 
 /// Message types for agent coordination
@@ -98,6 +134,12 @@ pub enum CoordinationMessage {
         reasoning: Option<String>,
     },
 
+    /// Tallied outcome of a voting proposal
+    VotingResult {
+        captain_id: String,
+        result: VotingResult,
+    },
+
     /// Notification about external events (files, PRs, etc.)
     EventNotification {
         event_type: String, // "file_created", "pr_opened", "build_failed", etc.
@@ -109,6 +151,8 @@ pub enum CoordinationMessage {
 
     /// Request for specific agent capabilities
     CapabilityRequest {
+        #[serde(default)]
+        request_id: String,
         requesting_agent: String,
         required_capabilities: Vec<String>,
         task_description: String,
@@ -125,6 +169,92 @@ pub enum CoordinationMessage {
     },
 }
 
+impl CoordinationMessage {
+    /// Variant name, as serialized in `msg_type`
+    pub fn kind(&self) -> &'static str {
+        match self {
+            CoordinationMessage::Presence { .. } => "Presence",
+            CoordinationMessage::DirectMessage { .. } => "DirectMessage",
+            CoordinationMessage::TaskDelegation { .. } => "TaskDelegation",
+            CoordinationMessage::TaskCompletion { .. } => "TaskCompletion",
+            CoordinationMessage::ProgressUpdate { .. } => "ProgressUpdate",
+            CoordinationMessage::VotingProposal { .. } => "VotingProposal",
+            CoordinationMessage::Vote { .. } => "Vote",
+            CoordinationMessage::VotingResult { .. } => "VotingResult",
+            CoordinationMessage::EventNotification { .. } => "EventNotification",
+            CoordinationMessage::CapabilityRequest { .. } => "CapabilityRequest",
+            CoordinationMessage::CapabilityResponse { .. } => "CapabilityResponse",
+        }
+    }
+
+    /// Agent (or, for events, system) that sent the message
+    pub fn sender(&self) -> &str {
+        match self {
+            CoordinationMessage::Presence { metadata } => &metadata.agent_id,
+            CoordinationMessage::DirectMessage { from_agent, .. } => from_agent,
+            CoordinationMessage::TaskDelegation { captain_id, .. } => captain_id,
+            CoordinationMessage::TaskCompletion { worker_id, .. } => worker_id,
+            CoordinationMessage::ProgressUpdate { agent_id, .. } => agent_id,
+            CoordinationMessage::VotingProposal { captain_id, .. } => captain_id,
+            CoordinationMessage::Vote { voter_id, .. } => voter_id,
+            CoordinationMessage::VotingResult { captain_id, .. } => captain_id,
+            CoordinationMessage::EventNotification { source, .. } => source,
+            CoordinationMessage::CapabilityRequest { requesting_agent, .. } => requesting_agent,
+            CoordinationMessage::CapabilityResponse { responding_agent, .. } => responding_agent,
+        }
+    }
+
+    /// Task, proposal or request id the message is about
+    pub fn correlation_id(&self) -> Option<&str> {
+        match self {
+            CoordinationMessage::TaskDelegation { task_id, .. }
+            | CoordinationMessage::TaskCompletion { task_id, .. }
+            | CoordinationMessage::ProgressUpdate { task_id, .. } => Some(task_id),
+            CoordinationMessage::VotingProposal { proposal_id, .. }
+            | CoordinationMessage::Vote { proposal_id, .. } => Some(proposal_id),
+            CoordinationMessage::VotingResult { result, .. } => Some(&result.proposal_id),
+            CoordinationMessage::CapabilityRequest { request_id, .. }
+            | CoordinationMessage::CapabilityResponse { request_id, .. } => Some(request_id),
+            _ => None,
+        }
+    }
+
+    /// Subject line; events use their event type
+    pub fn subject(&self) -> Option<&str> {
+        match self {
+            CoordinationMessage::DirectMessage { subject, .. }
+            | CoordinationMessage::VotingProposal { subject, .. } => Some(subject),
+            CoordinationMessage::EventNotification { event_type, .. } => Some(event_type),
+            _ => None,
+        }
+    }
+
+    /// Wrap in a versioned ACP envelope sent by `sender()`
+    pub fn to_envelope(&self, step: u64) -> B00tResult<ACPMessage> {
+        Ok(ACPMessage::coordination(self.sender().to_string(), step, serde_json::to_value(self)?))
+    }
+
+    /// Unwrap an ACP envelope; `None` for STATUS/PROPOSE/STEP or unknown kinds
+    pub fn from_envelope(envelope: &ACPMessage) -> Option<Self> {
+        match envelope.message_type {
+            MessageType::Coordination => serde_json::from_value(envelope.payload.clone()).ok(),
+            _ => None,
+        }
+    }
+
+    /// Decode a published payload: an ACP envelope, a legacy `AgentMessage` wrapper or a bare message
+    pub fn from_payload(payload: &str) -> Option<Self> {
+        if let Ok(envelope) = serde_json::from_str::<ACPMessage>(payload) {
+            return Self::from_envelope(&envelope);
+        }
+        if let Ok(AgentMessage::Session { data, .. }) = serde_json::from_str::<AgentMessage>(payload) {
+            return data.get("coordination_message")
+                .and_then(|value| serde_json::from_value(value.clone()).ok());
+        }
+        serde_json::from_str(payload).ok()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum TaskPriority {
     Low,
@@ -156,6 +286,21 @@ pub enum VotingType {
     VetoCapable,      // Any agent can veto
 }
 
+impl std::str::FromStr for VotingType {
+    type Err = anyhow::Error;
+
+    /// Accepts the CLI names (`single`, `ranked`, `approval`, `veto`) or the variant names
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace(['-', '_'], "").as_str() {
+            "single" | "singlechoice" => Ok(VotingType::SingleChoice),
+            "ranked" | "rankedchoice" => Ok(VotingType::RankedChoice),
+            "approval" => Ok(VotingType::Approval),
+            "veto" | "vetocapable" => Ok(VotingType::VetoCapable),
+            _ => anyhow::bail!("Unknown voting type '{}' (expected single, ranked, approval or veto)", s),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum VoteChoice {
     Single(String),                    // Option ID
@@ -172,27 +317,183 @@ pub enum RequestUrgency {
     Emergency,
 }
 
+/// Routes incoming coordination messages to the waiters and subscribers whose filter matches
+#[derive(Clone, Default)]
+pub struct MessageDispatcher {
+    waiters: Arc<Mutex<Vec<(MessageFilter, oneshot::Sender<CoordinationMessage>)>>>,
+    subscribers: Arc<Mutex<Vec<(MessageFilter, mpsc::UnboundedSender<CoordinationMessage>)>>>,
+}
+
+impl MessageDispatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wait for the next message matching `filter`
+    pub fn register(&self, filter: MessageFilter) -> oneshot::Receiver<CoordinationMessage> {
+        let (tx, rx) = oneshot::channel();
+        let mut waiters = self.waiters.lock().unwrap_or_else(|e| e.into_inner());
+        // 🤓 Waiters that timed out dropped their receiver
+        waiters.retain(|(_, tx)| !tx.is_closed());
+        waiters.push((filter, tx));
+        rx
+    }
+
+    /// Receive every message matching `filter` until the receiver is dropped
+    pub fn subscribe(&self, filter: MessageFilter) -> mpsc::UnboundedReceiver<CoordinationMessage> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let mut subscribers = self.subscribers.lock().unwrap_or_else(|e| e.into_inner());
+        subscribers.retain(|(_, tx)| !tx.is_closed());
+        subscribers.push((filter, tx));
+        rx
+    }
+
+    /// Resolve every waiter (and feed every subscriber) matching `message`; returns how many were reached
+    pub fn dispatch(&self, message: &CoordinationMessage) -> usize {
+        let mut subscribers = self.subscribers.lock().unwrap_or_else(|e| e.into_inner());
+        subscribers.retain(|(_, tx)| !tx.is_closed());
+        let streamed = subscribers.iter()
+            .filter(|(filter, tx)| filter.matches(message) && tx.send(message.clone()).is_ok())
+            .count();
+        drop(subscribers);
+
+        let mut waiters = self.waiters.lock().unwrap_or_else(|e| e.into_inner());
+        let (matching, rest): (Vec<_>, Vec<_>) = waiters.drain(..).partition(|(filter, _)| filter.matches(message));
+        *waiters = rest;
+        matching.into_iter()
+            .filter(|(_, tx)| !tx.is_closed())
+            .filter_map(|(_, tx)| tx.send(message.clone()).ok())
+            .count() + streamed
+    }
+
+    /// Number of live waiters
+    pub fn waiting(&self) -> usize {
+        let waiters = self.waiters.lock().unwrap_or_else(|e| e.into_inner());
+        waiters.iter().filter(|(_, tx)| !tx.is_closed()).count()
+    }
+}
+
 /// Agent coordinator handles all agent communication and coordination
 pub struct AgentCoordinator {
-    redis: RedisComms,
+    redis: Arc<RedisComms>,
+    transport: Arc<dyn Transport>,
+    /// Hive namespace subjects live under
+    namespace: String,
     agent_metadata: AgentMetadata,
     _message_handlers: HashMap<String, mpsc::UnboundedSender<CoordinationMessage>>,
-    pending_tasks: HashMap<String, oneshot::Sender<TaskCompletion>>,
-    pending_votes: HashMap<String, oneshot::Sender<HashMap<String, VoteChoice>>>,
+    dispatcher: MessageDispatcher,
+    /// Tasks this agent delegated (or was handed), fed by the listener
+    tasks: Arc<Mutex<TaskRegistry>>,
+    /// How long `request_capability` waits for responses
+    capability_window: Duration,
+    /// Subscription tasks, once started
+    listener: OnceCell<Vec<JoinHandle<()>>>,
+    /// Background lease and deadline enforcement, once this agent delegates
+    supervisor: OnceLock<JoinHandle<()>>,
+    /// Periodic registry refresh, once started
+    presence: OnceLock<JoinHandle<()>>,
+    /// Signs outbound envelopes and screens inbound ones
+    security: EnvelopeSecurity,
 }
 
 impl AgentCoordinator {
-    /// Create new agent coordinator
+    /// Create new agent coordinator; messages go over Redis pub/sub until `with_transport`
     pub fn new(redis: RedisComms, agent_metadata: AgentMetadata) -> Self {
+        let transport: Arc<dyn Transport> = match redis.get_pubsub_client() {
+            Ok(client) => Arc::new(RedisTransport::from_client(client)),
+            Err(_) => Arc::new(InMemoryTransport::new()),
+        };
         Self {
-            redis,
+            redis: Arc::new(redis),
+            transport,
+            namespace: DEFAULT_NAMESPACE.to_string(),
             agent_metadata,
             _message_handlers: HashMap::new(),
-            pending_tasks: HashMap::new(),
-            pending_votes: HashMap::new(),
+            dispatcher: MessageDispatcher::new(),
+            tasks: Arc::new(Mutex::new(TaskRegistry::new())),
+            capability_window: CAPABILITY_WINDOW,
+            listener: OnceCell::new(),
+            supervisor: OnceLock::new(),
+            presence: OnceLock::new(),
+            security: EnvelopeSecurity::from_env(DEFAULT_NAMESPACE),
         }
     }
 
+    /// Seconds a worker may go without reporting progress before its task is reassigned
+    pub fn with_task_lease(self, lease_secs: u64) -> Self {
+        *self.tasks.lock().unwrap_or_else(|e| e.into_inner()) = TaskRegistry::new().with_lease_secs(lease_secs);
+        self
+    }
+
+    /// How long `request_capability` collects responses
+    pub fn with_capability_window(mut self, window: Duration) -> Self {
+        self.capability_window = window;
+        self
+    }
+
+    /// Tracked tasks, most recently updated first
+    pub fn tasks(&self) -> Vec<TaskRecord> {
+        self.tasks.lock().unwrap_or_else(|e| e.into_inner()).tasks()
+    }
+
+    /// One tracked task
+    pub fn task(&self, task_id: &str) -> Option<TaskRecord> {
+        self.tasks.lock().unwrap_or_else(|e| e.into_inner()).get(task_id).cloned()
+    }
+
+    /// Send and receive over `transport` instead (NATS, in-memory, ...)
+    pub fn with_transport(mut self, transport: Arc<dyn Transport>) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    /// Transport coordination messages travel over
+    pub fn transport(&self) -> &Arc<dyn Transport> {
+        &self.transport
+    }
+
+    /// Talk in `namespace` (e.g. `account.<user>`), the one ACP agents of the same hive use
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = namespace.into();
+        self.security.rekey(&self.namespace);
+        self
+    }
+
+    /// Sign outbound envelopes with `key` (and trust it for inbound ones)
+    pub fn with_signing_key(mut self, key: SigningKey) -> Self {
+        self.security.verifier.trust(key.key_id(), key.verifying_key());
+        self.security.verifier.allow(key.key_id(), self.agent_metadata.agent_id.clone());
+        self.security.signing_key = Some(key);
+        self
+    }
+
+    /// Accept signatures from another agent's (or namespace's) key
+    pub fn with_trusted_key(mut self, key_id: impl Into<String>, key: VerifyingKey) -> Self {
+        self.security.verifier.trust(key_id, key);
+        self
+    }
+
+    /// Accept, flag or drop unsigned inbound envelopes
+    pub fn with_unsigned_policy(mut self, policy: UnsignedPolicy) -> Self {
+        self.security.unsigned_policy = policy;
+        self
+    }
+
+    /// Namespace coordination subjects live under
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    /// This agent's id
+    pub fn agent_id(&self) -> &str {
+        &self.agent_metadata.agent_id
+    }
+
+    /// Dispatcher fed by the transport subscription
+    pub fn dispatcher(&self) -> &MessageDispatcher {
+        &self.dispatcher
+    }
+
     /// Start agent coordination (announce presence, start listening)
     pub async fn start(&mut self) -> B00tResult<()> {
         // Announce presence
@@ -207,42 +508,18 @@ impl AgentCoordinator {
         Ok(())
     }
 
-    /// Announce agent presence to the network
+    /// Announce agent presence to the network, register for discovery and start answering requests
     pub async fn announce_presence(&self) -> B00tResult<()> {
-        let message = CoordinationMessage::Presence {
-            metadata: self.agent_metadata.clone(),
-        };
+        let metadata = register(&self.redis, &self.agent_metadata)?;
+        self.ensure_listener().await?;
 
-        self.redis.publish("b00t:agents:presence", &AgentMessage::Session {
-            session_id: self.agent_metadata.agent_id.clone(),
-            event: crate::redis::SessionEvent::Created,
-            data: HashMap::from([
-                ("coordination_message".to_string(), serde_json::to_value(&message)?),
-            ]),
-        })?;
-
-        Ok(())
+        let message = CoordinationMessage::Presence { metadata };
+        self.send_coordination_message(&self.channel("presence"), &message).await
     }
 
     /// Discover other agents in the network
     pub async fn discover_agents(&self) -> B00tResult<Vec<AgentMetadata>> {
-        // Get all agents from Redis hash
-        let agents_data = self.redis.hgetall("b00t:agents:registry")?;
-        let mut agents = Vec::new();
-
-        for (agent_id, metadata_json) in agents_data {
-            if agent_id != self.agent_metadata.agent_id {
-                if let Ok(metadata) = serde_json::from_str::<AgentMetadata>(&metadata_json) {
-                    // Filter out stale agents (haven't been seen in 5 minutes)
-                    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
-                    if now - metadata.last_seen < 300 {
-                        agents.push(metadata);
-                    }
-                }
-            }
-        }
-
-        Ok(agents)
+        registered_agents(&self.redis, &self.agent_metadata.agent_id)
     }
 
     /// Send direct message to another agent
@@ -264,7 +541,7 @@ impl AgentCoordinator {
             requires_ack,
         };
 
-        self.send_coordination_message(&format!("b00t:agent:{}", to_agent), &message).await?;
+        self.send_coordination_message(&self.inbox(to_agent), &message).await?;
         Ok(message_id)
     }
 
@@ -279,9 +556,7 @@ impl AgentCoordinator {
         required_capabilities: Vec<String>,
         blocking: bool,
     ) -> B00tResult<Option<TaskCompletion>> {
-        let deadline_timestamp = deadline.map(|d| {
-            SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() + d.as_secs()
-        });
+        let deadline_timestamp = deadline.map(|d| unix_now() + d.as_secs());
 
         let message = CoordinationMessage::TaskDelegation {
             captain_id: self.agent_metadata.agent_id.clone(),
@@ -294,30 +569,102 @@ impl AgentCoordinator {
             blocking,
         };
 
-        // Set up completion listener if blocking
-        let completion_receiver = if blocking {
-            let (tx, rx) = oneshot::channel();
-            self.pending_tasks.insert(task_id.to_string(), tx);
-            Some(rx)
-        } else {
-            None
-        };
+        // Watch for completions before sending, so a fast worker can't be missed
+        let completions = blocking.then(|| {
+            self.dispatcher.subscribe(MessageFilter::default()
+                .with_message_types(["TaskCompletion"])
+                .with_task_ids([task_id]))
+        });
+        self.ensure_listener().await?;
+        self.tasks.lock().unwrap_or_else(|e| e.into_inner()).observe(&message, unix_now());
+        self.task_supervisor().mirror(&[]);
+        self.ensure_supervisor();
 
         // Send delegation message
-        self.send_coordination_message(&format!("b00t:agent:{}", worker_id), &message).await?;
-
-        // If blocking, wait for completion
-        if let Some(receiver) = completion_receiver {
-            match timeout(Duration::from_secs(3600), receiver).await { // 1 hour timeout
-                Ok(Ok(completion)) => Ok(Some(completion)),
-                Ok(Err(_)) => anyhow::bail!("Task completion channel closed unexpectedly"),
-                Err(_) => anyhow::bail!("Task delegation timed out after 1 hour"),
+        self.send_coordination_message(&self.inbox(worker_id), &message).await?;
+
+        // If blocking, wait for completion while the supervisor reassigns a quiet worker
+        let Some(completions) = completions else { return Ok(None) };
+        self.await_completion(task_id, completions, Duration::from_secs(3600)).await.map(Some)
+    }
+
+    /// Wait for `task_id`'s current holder to complete it
+    ///
+    /// The listener folds each completion into the registry before passing it on, so only the
+    /// one the registry accepted counts; a reassigned worker's late report is ignored.
+    async fn await_completion(
+        &self,
+        task_id: &str,
+        mut completions: mpsc::UnboundedReceiver<CoordinationMessage>,
+        limit: Duration,
+    ) -> B00tResult<TaskCompletion> {
+        let give_up = tokio::time::Instant::now() + limit;
+        let mut check = tokio::time::interval(SUPERVISE_INTERVAL);
+        loop {
+            tokio::select! {
+                completion = completions.recv() => match completion {
+                    Some(CoordinationMessage::TaskCompletion { worker_id, task_id, status, result, artifacts, .. }) => {
+                        let accepted = self.task(&task_id).is_some_and(|task| {
+                            task.state == TaskState::completed(&status) && task.worker_id.as_deref() == Some(worker_id.as_str())
+                        });
+                        if accepted {
+                            return Ok(TaskCompletion { task_id, status, result, artifacts, worker_id });
+                        }
+                        eprintln!("⚠️ Ignoring completion of {} from {}: not the task's holder", task_id, worker_id);
+                    }
+                    Some(other) => anyhow::bail!("Expected TaskCompletion, got {}", other.kind()),
+                    None => anyhow::bail!("Task completion channel closed unexpectedly"),
+                },
+                _ = check.tick() => {
+                    if let Some(task) = self.task(task_id).filter(|task| task.state == TaskState::Failed) {
+                        anyhow::bail!("Task {} failed: {}", task_id, task.result.unwrap_or_default());
+                    }
+                }
+                _ = tokio::time::sleep_until(give_up) => anyhow::bail!("Task delegation timed out after {}s", limit.as_secs()),
             }
-        } else {
-            Ok(None)
         }
     }
 
+    /// Enforce leases and deadlines on tracked tasks (captain functionality)
+    ///
+    /// Fails tasks past their deadline, hands tasks whose lease lapsed to the least-loaded
+    /// discovered agent with the required capabilities, forgets tasks finished over an hour
+    /// ago and mirrors the registry to Redis. Runs in the background once this agent delegates.
+    pub async fn supervise_tasks(&self) -> B00tResult<Vec<TaskExpiry>> {
+        let candidates = self.discover_agents().await.unwrap_or_default();
+        self.supervise_tasks_at(unix_now(), &candidates).await
+    }
+
+    async fn supervise_tasks_at(&self, now: u64, candidates: &[AgentMetadata]) -> B00tResult<Vec<TaskExpiry>> {
+        self.task_supervisor().supervise_at(now, candidates).await
+    }
+
+    /// Supervise this captain's tasks, including ones an earlier process mirrored, until the
+    /// process ends (e.g. after non-blocking delegations from `b00t-cli agent delegate`)
+    pub async fn supervise(&self) -> B00tResult<()> {
+        self.ensure_listener().await?;
+        let supervisor = self.task_supervisor();
+        supervisor.adopt_mirrored();
+        supervisor.run().await;
+        Ok(())
+    }
+
+    fn task_supervisor(&self) -> TaskSupervisor {
+        TaskSupervisor {
+            redis: Arc::clone(&self.redis),
+            transport: Arc::clone(&self.transport),
+            namespace: self.namespace.clone(),
+            captain_id: self.agent_metadata.agent_id.clone(),
+            tasks: Arc::clone(&self.tasks),
+            security: self.security.clone(),
+        }
+    }
+
+    /// Keep supervising delegated tasks in the background, blocking or not
+    fn ensure_supervisor(&self) {
+        self.supervisor.get_or_init(|| tokio::spawn(self.task_supervisor().run()));
+    }
+
     /// Report task completion (worker functionality)
     pub async fn complete_task(
         &self,
@@ -336,7 +683,7 @@ impl AgentCoordinator {
             artifacts,
         };
 
-        self.send_coordination_message(&format!("b00t:agent:{}", captain_id), &message).await?;
+        self.send_coordination_message(&self.inbox(captain_id), &message).await?;
         Ok(())
     }
 
@@ -348,9 +695,7 @@ impl AgentCoordinator {
         status_message: &str,
         estimated_completion: Option<Duration>,
     ) -> B00tResult<()> {
-        let estimated_timestamp = estimated_completion.map(|d| {
-            SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() + d.as_secs()
-        });
+        let estimated_timestamp = estimated_completion.map(|d| unix_now() + d.as_secs());
 
         let message = CoordinationMessage::ProgressUpdate {
             agent_id: self.agent_metadata.agent_id.clone(),
@@ -361,22 +706,30 @@ impl AgentCoordinator {
         };
 
         // Broadcast progress to all interested parties
-        self.send_coordination_message("b00t:progress:updates", &message).await?;
+        self.send_coordination_message(&self.channel("progress"), &message).await?;
         Ok(())
     }
 
     /// Create voting proposal (captain functionality)
+    ///
+    /// Collects votes until every eligible voter has voted or the deadline passes, then
+    /// publishes the tallied `VotingResult` on the `votes` channel and returns it.
+    /// With no eligible voters the proposal itself is broadcast there and anyone may vote.
+    #[allow(clippy::too_many_arguments)]
     pub async fn create_voting_proposal(
-        &mut self,
+        &self,
         subject: &str,
         description: &str,
         options: Vec<VotingOption>,
         voting_type: VotingType,
         deadline: Duration,
         eligible_voters: Vec<String>,
-    ) -> B00tResult<HashMap<String, VoteChoice>> {
+        rules: VotingRules,
+    ) -> B00tResult<VotingResult> {
         let proposal_id = uuid::Uuid::new_v4().to_string();
         let deadline_timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() + deadline.as_secs();
+        let mut tally = VoteTally::new(&proposal_id, voting_type.clone(), &options, eligible_voters.clone(), deadline_timestamp)
+            .with_rules(rules);
 
         let message = CoordinationMessage::VotingProposal {
             captain_id: self.agent_metadata.agent_id.clone(),
@@ -389,21 +742,41 @@ impl AgentCoordinator {
             eligible_voters: eligible_voters.clone(),
         };
 
-        // Set up vote collection
-        let (tx, rx) = oneshot::channel();
-        self.pending_votes.insert(proposal_id.clone(), tx);
+        // Set up vote collection before anyone can answer
+        let mut votes = self.dispatcher.subscribe(MessageFilter::default()
+            .with_message_types(["Vote"])
+            .with_task_ids([proposal_id.as_str()]));
+        self.ensure_listener().await?;
 
-        // Send proposal to eligible voters
+        // Send proposal to eligible voters; an open vote goes to everyone listening
+        if eligible_voters.is_empty() {
+            self.send_coordination_message(&self.channel("votes"), &message).await?;
+        }
         for voter in &eligible_voters {
-            self.send_coordination_message(&format!("b00t:agent:{}", voter), &message).await?;
+            self.send_coordination_message(&self.inbox(voter), &message).await?;
         }
 
-        // Wait for voting to complete or timeout
-        match timeout(deadline, rx).await {
-            Ok(Ok(votes)) => Ok(votes),
-            Ok(Err(_)) => anyhow::bail!("Voting channel closed unexpectedly"),
-            Err(_) => anyhow::bail!("Voting deadline exceeded"),
+        // Collect until everyone has voted or the deadline passes
+        let closes_at = tokio::time::Instant::now() + deadline;
+        while !tally.is_complete() {
+            match tokio::time::timeout_at(closes_at, votes.recv()).await {
+                Ok(Some(CoordinationMessage::Vote { voter_id, vote, .. })) => {
+                    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+                    // 🤓 Refused ballots are kept in `result.rejected`
+                    let _ = tally.record(&voter_id, vote, now);
+                }
+                Ok(Some(_)) => {}
+                Ok(None) => anyhow::bail!("Voting channel closed unexpectedly"),
+                Err(_) => break,
+            }
         }
+
+        let result = tally.outcome();
+        self.send_coordination_message(&self.channel("votes"), &CoordinationMessage::VotingResult {
+            captain_id: self.agent_metadata.agent_id.clone(),
+            result: result.clone(),
+        }).await?;
+        Ok(result)
     }
 
     /// Submit vote for a proposal
@@ -421,7 +794,7 @@ impl AgentCoordinator {
         };
 
         // Send vote back to captain
-        self.send_coordination_message("b00t:votes:collection", &message).await?;
+        self.send_coordination_message(&self.channel("votes"), &message).await?;
         Ok(())
     }
 
@@ -442,7 +815,7 @@ impl AgentCoordinator {
         };
 
         // Broadcast to all agents or specific targets
-        self.send_coordination_message("b00t:events:notifications", &message).await?;
+        self.send_coordination_message(&self.channel("events"), &message).await?;
         Ok(())
     }
 
@@ -450,12 +823,10 @@ impl AgentCoordinator {
     pub async fn wait_for_message(
         &self,
         timeout_duration: Duration,
-        _filter: MessageFilter,
+        filter: MessageFilter,
     ) -> B00tResult<CoordinationMessage> {
-        let (_tx, rx) = oneshot::channel();
-
-        // TODO: Set up filtered message listener
-        // This would require extending the message handling system
+        let rx = self.dispatcher.register(filter);
+        self.ensure_listener().await?;
 
         match timeout(timeout_duration, rx).await {
             Ok(Ok(message)) => Ok(message),
@@ -465,63 +836,448 @@ impl AgentCoordinator {
     }
 
     /// Request agents with specific capabilities
+    ///
+    /// Collects responses for the capability window; returns the available responders with
+    /// their proficiency scores, in arrival order.
     pub async fn request_capability(
         &self,
         required_capabilities: Vec<String>,
         task_description: &str,
         urgency: RequestUrgency,
     ) -> B00tResult<Vec<(String, HashMap<String, f32>)>> {
+        let request_id = uuid::Uuid::new_v4().to_string();
         let message = CoordinationMessage::CapabilityRequest {
+            request_id: request_id.clone(),
             requesting_agent: self.agent_metadata.agent_id.clone(),
             required_capabilities,
             task_description: task_description.to_string(),
             urgency,
         };
 
+        let mut responses = self.dispatcher.subscribe(MessageFilter::default()
+            .with_message_types(["CapabilityResponse"])
+            .with_task_ids([request_id.as_str()]));
+        self.ensure_listener().await?;
+
         // Broadcast capability request
-        self.send_coordination_message("b00t:capabilities:requests", &message).await?;
+        self.send_coordination_message(&self.channel("capabilities"), &message).await?;
+
+        let mut available = Vec::new();
+        let window_ends = tokio::time::Instant::now() + self.capability_window;
+        while let Ok(Some(response)) = tokio::time::timeout_at(window_ends, responses.recv()).await {
+            if let CoordinationMessage::CapabilityResponse { responding_agent, available: true, proficiency_scores, .. } = response {
+                if !available.iter().any(|(agent, _)| agent == &responding_agent) {
+                    available.push((responding_agent, proficiency_scores));
+                }
+            }
+        }
+        Ok(available)
+    }
+
+    /// Ask who can help and rank the responders with `selector`, best first
+    pub async fn select_agents(
+        &self,
+        selector: &AgentSelector,
+        task_description: &str,
+        urgency: RequestUrgency,
+    ) -> B00tResult<Vec<CandidateScore>> {
+        let responses = self.request_capability(selector.required_capabilities().to_vec(), task_description, urgency).await?;
+        let known = self.discover_agents().await.unwrap_or_default();
+        Ok(selector.rank(&responses, &known))
+    }
 
-        // TODO: Collect responses with timeout
-        Ok(vec![])
+    /// Delegate a task to the best available agent for `selector` (captain functionality)
+    #[allow(clippy::too_many_arguments)]
+    pub async fn delegate_to_best(
+        &mut self,
+        selector: &AgentSelector,
+        task_id: &str,
+        task_description: &str,
+        priority: TaskPriority,
+        deadline: Option<Duration>,
+        blocking: bool,
+    ) -> B00tResult<(CandidateScore, Option<TaskCompletion>)> {
+        let urgency = match priority {
+            TaskPriority::Low => RequestUrgency::Low,
+            TaskPriority::Normal => RequestUrgency::Normal,
+            TaskPriority::High => RequestUrgency::High,
+            TaskPriority::Critical => RequestUrgency::Emergency,
+        };
+        let Some(best) = self.select_agents(selector, task_description, urgency).await?.into_iter().next() else {
+            anyhow::bail!("No available agent offers {}", selector.required_capabilities().join(", "));
+        };
+        let completion = self.delegate_task(
+            &best.agent_id,
+            task_id,
+            task_description,
+            priority,
+            deadline,
+            selector.required_capabilities().to_vec(),
+            blocking,
+        ).await?;
+        Ok((best, completion))
     }
 
     // Private helper methods
 
-    async fn send_coordination_message(&self, channel: &str, message: &CoordinationMessage) -> B00tResult<()> {
-        let agent_message = AgentMessage::Session {
-            session_id: uuid::Uuid::new_v4().to_string(),
-            event: crate::redis::SessionEvent::Updated,
-            data: HashMap::from([
-                ("coordination_message".to_string(), serde_json::to_value(message)?),
-            ]),
-        };
+    fn inbox(&self, agent_id: &str) -> String {
+        ACPMessage::inbox_subject(&self.namespace, agent_id)
+    }
 
-        self.redis.publish(channel, &agent_message)?;
+    fn channel(&self, channel: &str) -> String {
+        ACPMessage::channel_subject(&self.namespace, channel)
+    }
+
+    async fn send_coordination_message(&self, channel: &str, message: &CoordinationMessage) -> B00tResult<()> {
+        self.transport.publish(channel, &self.security.seal(message)?).await?;
         Ok(())
     }
 
     async fn start_message_listener(&mut self) -> B00tResult<()> {
-        // TODO: Implement Redis subscription listener
-        // This would handle incoming coordination messages and route them to handlers
+        self.ensure_listener().await
+    }
+
+    /// Subscribe (once) to this agent's inbox and the broadcast channels, feeding the dispatcher
+    ///
+    /// Fails if the broker can't be reached, so waits and votes report it instead of timing out.
+    /// 🦨 Pub/sub doesn't buffer: messages published before the subscription lands are missed
+    async fn ensure_listener(&self) -> B00tResult<()> {
+        self.listener.get_or_try_init(|| async {
+            let subjects = std::iter::once(self.inbox(&self.agent_metadata.agent_id))
+                .chain(COORDINATION_CHANNELS.iter().map(|c| self.channel(c)));
+            let mut subscribers = Vec::new();
+            for subject in subjects {
+                subscribers.push(self.transport.subscribe(&subject).await
+                    .map_err(|e| anyhow::anyhow!("Failed to subscribe to {}: {}", subject, e))?);
+            }
+
+            let mut tasks = Vec::new();
+            for mut subscriber in subscribers {
+                let dispatcher = self.dispatcher.clone();
+                let registry = Arc::clone(&self.tasks);
+                let transport = Arc::clone(&self.transport);
+                let metadata = self.agent_metadata.clone();
+                let namespace = self.namespace.clone();
+                let security = self.security.clone();
+                tasks.push(tokio::spawn(async move {
+                    while let Ok(Some(envelope)) = subscriber.next_message_blocking().await {
+                        if let Some(message) = security.admit(&envelope) {
+                            if let Some((to_agent, response)) = answer_capability_request(&metadata, &message) {
+                                if let Ok(envelope) = security.seal(&response) {
+                                    let _ = transport.publish(&ACPMessage::inbox_subject(&namespace, &to_agent), &envelope).await;
+                                }
+                            }
+                            registry.lock().unwrap_or_else(|e| e.into_inner()).observe(&message, unix_now());
+                            dispatcher.dispatch(&message);
+                        }
+                    }
+                }));
+            }
+            Ok::<_, anyhow::Error>(tasks)
+        }).await?;
         Ok(())
     }
 
+    /// Keep this agent's registry entry fresh every `PRESENCE_INTERVAL` so discovery keeps finding it
     async fn start_presence_heartbeat(&self) -> B00tResult<()> {
-        // TODO: Implement periodic presence updates
-        // Every 30 seconds, update agent metadata in Redis
+        let redis = Arc::clone(&self.redis);
+        let metadata = self.agent_metadata.clone();
+        self.presence.get_or_init(|| tokio::spawn(async move {
+            let mut ticks = tokio::time::interval(PRESENCE_INTERVAL);
+            ticks.tick().await; // 🤓 the first tick is immediate, and `announce_presence` just registered us
+            loop {
+                ticks.tick().await;
+                if let Err(e) = register(&redis, &metadata) {
+                    eprintln!("⚠️ Failed to refresh presence for {}: {}", metadata.agent_id, e);
+                }
+            }
+        }));
         Ok(())
     }
 }
 
-/// Message filter for selective waiting
-#[derive(Debug, Clone)]
+/// Our response to someone else's capability request, if we hold any of what they need
+fn answer_capability_request(metadata: &AgentMetadata, message: &CoordinationMessage) -> Option<(String, CoordinationMessage)> {
+    let CoordinationMessage::CapabilityRequest { request_id, requesting_agent, required_capabilities, .. } = message else {
+        return None;
+    };
+    if requesting_agent == &metadata.agent_id {
+        return None;
+    }
+    let proficiency_scores = proficiency_for(metadata, required_capabilities)?;
+    let available = !matches!(metadata.status, AgentStatus::Offline | AgentStatus::Error(_)) && metadata.load < 1.0;
+    Some((requesting_agent.clone(), CoordinationMessage::CapabilityResponse {
+        responding_agent: metadata.agent_id.clone(),
+        request_id: request_id.clone(),
+        available,
+        estimated_availability: None,
+        proficiency_scores,
+    }))
+}
+
+impl Drop for AgentCoordinator {
+    fn drop(&mut self) {
+        for task in self.listener.get().into_iter().flatten().chain(self.supervisor.get()).chain(self.presence.get()) {
+            task.abort();
+        }
+    }
+}
+
+/// Signing and verification of coordination envelopes, as `b00t_acp::Agent` does for its own
+#[derive(Clone, Default)]
+struct EnvelopeSecurity {
+    signing_key: Option<SigningKey>,
+    /// Keys inbound signatures are checked against; empty skips verification
+    verifier: MessageVerifier,
+    unsigned_policy: UnsignedPolicy,
+}
+
+impl EnvelopeSecurity {
+    /// The namespace HMAC key from B00T_ACP_SIGNING_SECRET and the B00T_ACP_UNSIGNED policy, like `AgentConfig::new`
+    fn from_env(namespace: &str) -> Self {
+        let mut security = Self {
+            unsigned_policy: std::env::var("B00T_ACP_UNSIGNED").ok()
+                .and_then(|policy| policy.parse().ok())
+                .unwrap_or_default(),
+            ..Self::default()
+        };
+        security.rekey(namespace);
+        security
+    }
+
+    /// Move the namespace HMAC key to `namespace`, unless an explicit key replaced it
+    fn rekey(&mut self, namespace: &str) {
+        let Ok(secret) = std::env::var("B00T_ACP_SIGNING_SECRET") else {
+            return;
+        };
+        let key = SigningKey::hmac(namespace, secret.as_bytes());
+        self.verifier.trust(key.key_id(), key.verifying_key());
+        if self.signing_key.as_ref().is_none_or(|current| matches!(current, SigningKey::Hmac { .. })) {
+            self.signing_key = Some(key);
+        }
+    }
+
+    /// Envelope for `message`, signed when there is a key
+    fn seal(&self, message: &CoordinationMessage) -> B00tResult<ACPMessage> {
+        // 🤓 Coordination isn't bound to ACP steps, so it rides on step 0
+        let mut envelope = message.to_envelope(0)?;
+        if let Some(key) = &self.signing_key {
+            envelope.sign(key)?;
+        }
+        Ok(envelope)
+    }
+
+    /// The coordination message in `envelope`, if its signature passes and it speaks for its own sender
+    fn admit(&self, envelope: &ACPMessage) -> Option<CoordinationMessage> {
+        let message = CoordinationMessage::from_envelope(envelope)?;
+        // 🦨 The signature covers `agent_id`, not who the payload claims to be: they must agree
+        if message.sender() != envelope.agent_id {
+            eprintln!("⚠️ Dropped coordination message from '{}' claiming to be '{}'", envelope.agent_id, message.sender());
+            return None;
+        }
+        if self.verifier.is_empty() {
+            return Some(message);
+        }
+        match self.verifier.verify(envelope) {
+            Verification::Verified => Some(message),
+            Verification::Unsigned => match self.unsigned_policy {
+                UnsignedPolicy::Accept => Some(message),
+                UnsignedPolicy::Flag => {
+                    eprintln!("⚠️ Unsigned coordination message from '{}'", envelope.agent_id);
+                    Some(message)
+                }
+                UnsignedPolicy::Drop => {
+                    eprintln!("⚠️ Dropped unsigned coordination message from '{}'", envelope.agent_id);
+                    None
+                }
+            },
+            Verification::Invalid(reason) => {
+                eprintln!("⚠️ Dropped coordination message from '{}': {}", envelope.agent_id, reason);
+                None
+            }
+        }
+    }
+}
+
+/// Put `metadata` in the Redis agent registry, seen now; returns what was registered
+///
+/// 🤓 Best effort: discovery degrades to presence messages without Redis
+fn register(redis: &RedisComms, metadata: &AgentMetadata) -> B00tResult<AgentMetadata> {
+    let metadata = AgentMetadata { last_seen: unix_now(), ..metadata.clone() };
+    let _ = redis.hset("b00t:agents:registry", &metadata.agent_id, &serde_json::to_string(&metadata)?);
+    Ok(metadata)
+}
+
+/// Agents in the Redis registry seen in the last `PRESENCE_TTL_SECS`, except `except`
+fn registered_agents(redis: &RedisComms, except: &str) -> B00tResult<Vec<AgentMetadata>> {
+    let now = unix_now();
+    Ok(redis.hgetall("b00t:agents:registry")?
+        .into_iter()
+        .filter(|(agent_id, _)| agent_id != except)
+        .filter_map(|(_, metadata_json)| serde_json::from_str::<AgentMetadata>(&metadata_json).ok())
+        .filter(|metadata| now.saturating_sub(metadata.last_seen) < PRESENCE_TTL_SECS)
+        .collect())
+}
+
+/// Lease and deadline enforcement for one captain's tasks, shared with its background loop
+#[derive(Clone)]
+struct TaskSupervisor {
+    redis: Arc<RedisComms>,
+    transport: Arc<dyn Transport>,
+    namespace: String,
+    captain_id: String,
+    tasks: Arc<Mutex<TaskRegistry>>,
+    security: EnvelopeSecurity,
+}
+
+impl TaskSupervisor {
+    /// Supervise every `SUPERVISE_INTERVAL`, forever
+    async fn run(self) {
+        let mut ticks = tokio::time::interval(SUPERVISE_INTERVAL);
+        loop {
+            ticks.tick().await;
+            let candidates = registered_agents(&self.redis, &self.captain_id).unwrap_or_default();
+            if let Err(e) = self.supervise_at(unix_now(), &candidates).await {
+                eprintln!("⚠️ Task supervision failed: {}", e);
+            }
+        }
+    }
+
+    async fn supervise_at(&self, now: u64, candidates: &[AgentMetadata]) -> B00tResult<Vec<TaskExpiry>> {
+        let captain_id = self.captain_id.clone();
+        let (expired, pruned, reassignments, missed) = {
+            let mut registry = self.tasks.lock().unwrap_or_else(|e| e.into_inner());
+            let expired = registry.expire(now);
+            let pruned = registry.prune(now, FINISHED_TASK_RETENTION_SECS);
+            let mut reassignments = Vec::new();
+            for task in registry.tasks().into_iter().filter(|t| t.state == TaskState::Pending && t.captain_id == captain_id) {
+                let Some(worker_id) = registry.pick_worker(&task.task_id, candidates).map(|a| a.agent_id.clone()) else {
+                    continue;
+                };
+                registry.assign(&task.task_id, &worker_id, now)?;
+                reassignments.push(CoordinationMessage::TaskDelegation {
+                    captain_id: captain_id.clone(),
+                    worker_id,
+                    task_id: task.task_id,
+                    task_description: task.description,
+                    priority: task.priority,
+                    deadline: task.deadline,
+                    required_capabilities: task.required_capabilities,
+                    blocking: false,
+                });
+            }
+            let missed: Vec<(String, Option<String>)> = expired.iter()
+                .filter_map(|expiry| match expiry {
+                    TaskExpiry::DeadlineExceeded { task_id } => {
+                        Some((task_id.clone(), registry.get(task_id).and_then(|task| task.worker_id.clone())))
+                    }
+                    TaskExpiry::LeaseExpired { .. } => None,
+                })
+                .collect();
+            (expired, pruned, reassignments, missed)
+        };
+
+        for delegation in &reassignments {
+            if let CoordinationMessage::TaskDelegation { worker_id, .. } = delegation {
+                self.send(&ACPMessage::inbox_subject(&self.namespace, worker_id), delegation).await?;
+            }
+        }
+        for (task_id, worker) in missed {
+            let event = CoordinationMessage::EventNotification {
+                event_type: "task_deadline_exceeded".to_string(),
+                source: captain_id.clone(),
+                details: serde_json::json!({ "task_id": task_id }),
+                timestamp: now,
+                affected_agents: worker.map(|worker| vec![worker]),
+            };
+            self.send(&ACPMessage::channel_subject(&self.namespace, "events"), &event).await?;
+        }
+
+        self.mirror(&pruned);
+        Ok(expired)
+    }
+
+    async fn send(&self, subject: &str, message: &CoordinationMessage) -> B00tResult<()> {
+        self.transport.publish(subject, &self.security.seal(message)?).await?;
+        Ok(())
+    }
+
+    /// 🤓 Best effort: the mirror only feeds `b00t-cli acp tasks` and later `supervise` runs
+    fn mirror(&self, pruned: &[String]) {
+        if !pruned.is_empty() {
+            let fields: Vec<String> = pruned.iter().map(|task_id| registry_field(&self.captain_id, task_id)).collect();
+            let _ = self.redis.hdel(TASK_REGISTRY_KEY, &fields);
+        }
+        let tasks = self.tasks.lock().unwrap_or_else(|e| e.into_inner()).tasks();
+        for task in tasks.iter().filter(|task| task.captain_id == self.captain_id) {
+            if let Ok(json) = serde_json::to_string(task) {
+                let _ = self.redis.hset(TASK_REGISTRY_KEY, &registry_field(&task.captain_id, &task.task_id), &json);
+            }
+        }
+    }
+
+    /// Pick up this captain's unfinished tasks from the mirror
+    fn adopt_mirrored(&self) {
+        let Ok(mirrored) = self.redis.hgetall(TASK_REGISTRY_KEY) else { return };
+        let mut registry = self.tasks.lock().unwrap_or_else(|e| e.into_inner());
+        for task in mirrored.values().filter_map(|json| serde_json::from_str::<TaskRecord>(json).ok()) {
+            if task.captain_id == self.captain_id && !task.state.is_terminal() {
+                registry.adopt(task);
+            }
+        }
+    }
+}
+
+/// Message filter for selective waiting; `None` matches anything, a list matches any entry
+#[derive(Debug, Clone, Default)]
 pub struct MessageFilter {
+    /// Variant names, e.g. "TaskCompletion" (case and `_` insensitive)
     pub message_types: Option<Vec<String>>,
     pub from_agents: Option<Vec<String>>,
+    /// Task, proposal or request ids
     pub task_ids: Option<Vec<String>>,
     pub subjects: Option<Vec<String>>,
 }
 
+impl MessageFilter {
+    pub fn with_message_types(mut self, types: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.message_types = Some(types.into_iter().map(Into::into).collect());
+        self
+    }
+
+    pub fn with_from_agents(mut self, agents: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.from_agents = Some(agents.into_iter().map(Into::into).collect());
+        self
+    }
+
+    pub fn with_task_ids(mut self, ids: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.task_ids = Some(ids.into_iter().map(Into::into).collect());
+        self
+    }
+
+    pub fn with_subjects(mut self, subjects: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.subjects = Some(subjects.into_iter().map(Into::into).collect());
+        self
+    }
+
+    pub fn matches(&self, message: &CoordinationMessage) -> bool {
+        fn normalize(kind: &str) -> String {
+            kind.chars().filter(|c| *c != '_').flat_map(char::to_lowercase).collect()
+        }
+        fn allows(list: &Option<Vec<String>>, value: Option<&str>, eq: impl Fn(&str, &str) -> bool) -> bool {
+            match list {
+                None => true,
+                Some(list) => value.is_some_and(|v| list.iter().any(|item| eq(item, v))),
+            }
+        }
+
+        allows(&self.message_types, Some(message.kind()), |a, b| normalize(a) == normalize(b))
+            && allows(&self.from_agents, Some(message.sender()), |a, b| a == b)
+            && allows(&self.task_ids, message.correlation_id(), |a, b| a == b)
+            && allows(&self.subjects, message.subject(), |a, b| a == b)
+    }
+}
+
 /// Task completion result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TaskCompletion {
@@ -537,20 +1293,36 @@ mod tests {
     use super::*;
     use crate::redis::RedisConfig;
 
+    fn metadata(agent_id: &str, capabilities: &[&str]) -> AgentMetadata {
+        AgentMetadata {
+            agent_id: agent_id.to_string(),
+            agent_role: "worker".to_string(),
+            capabilities: capabilities.iter().map(|c| c.to_string()).collect(),
+            crew: None,
+            status: AgentStatus::Online,
+            last_seen: 0,
+            load: 0.0,
+            specializations: HashMap::new(),
+            model_size: None,
+        }
+    }
+
+    fn coordinator(bus: &Arc<dyn Transport>, metadata: AgentMetadata) -> AgentCoordinator {
+        let redis = RedisComms::new(RedisConfig::default(), metadata.agent_id.clone()).unwrap();
+        AgentCoordinator::new(redis, metadata).with_transport(Arc::clone(bus))
+    }
+
     #[test]
     fn test_agent_metadata_serialization() {
         let metadata = AgentMetadata {
-            agent_id: "test-agent".to_string(),
-            agent_role: "worker".to_string(),
-            capabilities: vec!["rust".to_string(), "testing".to_string()],
             crew: Some("backend".to_string()),
-            status: AgentStatus::Online,
             last_seen: 1234567890,
             load: 0.5,
             specializations: HashMap::from([
                 ("rust".to_string(), 0.9),
                 ("testing".to_string(), 0.8),
             ]),
+            ..metadata("test-agent", &["rust", "testing"])
         };
 
         let json = serde_json::to_string(&metadata).unwrap();
@@ -575,23 +1347,313 @@ mod tests {
         }
     }
 
+    fn completion(worker: &str, task: &str) -> CoordinationMessage {
+        CoordinationMessage::TaskCompletion {
+            worker_id: worker.to_string(),
+            captain_id: "captain".to_string(),
+            task_id: task.to_string(),
+            status: TaskCompletionStatus::Success,
+            result: Some("done".to_string()),
+            artifacts: vec![],
+        }
+    }
+
+    #[test]
+    fn test_message_filter_matches() {
+        let message = completion("worker-1", "task-42");
+        assert!(MessageFilter::default().matches(&message));
+        assert!(MessageFilter::default().with_message_types(["task_completion"]).matches(&message));
+        assert!(MessageFilter::default().with_from_agents(["worker-1"]).with_task_ids(["task-42"]).matches(&message));
+        assert!(!MessageFilter::default().with_task_ids(["task-7"]).matches(&message));
+        assert!(!MessageFilter::default().with_subjects(["deploy"]).matches(&message));
+        assert!(!MessageFilter::default().with_message_types(["Vote", "ProgressUpdate"]).matches(&message));
+
+        let dm = CoordinationMessage::DirectMessage {
+            from_agent: "alice".to_string(),
+            to_agent: "bob".to_string(),
+            subject: "deploy".to_string(),
+            content: "go".to_string(),
+            message_id: "m1".to_string(),
+            reply_to: None,
+            requires_ack: false,
+        };
+        assert!(MessageFilter::default().with_subjects(["deploy"]).with_from_agents(["alice"]).matches(&dm));
+
+        // the Redis envelope round-trips
+        let payload = serde_json::to_string(&AgentMessage::Session {
+            session_id: "s".to_string(),
+            event: crate::redis::SessionEvent::Updated,
+            data: HashMap::from([("coordination_message".to_string(), serde_json::to_value(&dm).unwrap())]),
+        }).unwrap();
+        assert_eq!(CoordinationMessage::from_payload(&payload).unwrap().subject(), Some("deploy"));
+    }
+
+    #[test]
+    fn test_acp_envelope_roundtrip() {
+        let envelope = completion("worker-1", "task-42").to_envelope(3).unwrap();
+        assert_eq!(envelope.agent_id, "worker-1");
+        assert_eq!(envelope.coordination_kind(), Some("TaskCompletion"));
+
+        let payload = serde_json::to_string(&envelope).unwrap();
+        assert_eq!(CoordinationMessage::from_payload(&payload).unwrap().correlation_id(), Some("task-42"));
+
+        // step-sync traffic isn't coordination
+        let step = ACPMessage::step_complete("worker-1".to_string(), 3);
+        assert!(CoordinationMessage::from_envelope(&step).is_none());
+    }
+
     #[tokio::test]
-    async fn test_agent_coordinator_creation() {
-        let config = RedisConfig::default();
-        let redis = RedisComms::new(config, "test-agent".to_string()).unwrap();
+    async fn test_coordinators_share_in_memory_transport() {
+        let bus: Arc<dyn Transport> = Arc::new(InMemoryTransport::new());
+        let captain = coordinator(&bus, metadata("captain", &[]));
+        let worker = coordinator(&bus, metadata("worker-1", &[]));
 
-        let metadata = AgentMetadata {
-            agent_id: "test-agent".to_string(),
-            agent_role: "test".to_string(),
-            capabilities: vec!["testing".to_string()],
-            crew: None,
-            status: AgentStatus::Online,
-            last_seen: 1234567890,
-            load: 0.0,
-            specializations: HashMap::new(),
+        let waiting = tokio::spawn(async move {
+            worker.wait_for_message(Duration::from_secs(5), MessageFilter::default().with_subjects(["deploy"])).await
+        });
+        // the worker subscribes as it starts waiting; keep sending until it's listening
+        let message = loop {
+            captain.send_message("worker-1", "deploy", "go", false).await.unwrap();
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            if waiting.is_finished() {
+                break waiting.await.unwrap().unwrap();
+            }
         };
+        assert_eq!(message.sender(), "captain");
+    }
 
-        let coordinator = AgentCoordinator::new(redis, metadata);
+    #[tokio::test]
+    async fn test_acp_agent_receives_delegation_and_completes_it() {
+        let bus: Arc<dyn Transport> = Arc::new(InMemoryTransport::new());
+        let mut captain = coordinator(&bus, metadata("captain", &[])).with_namespace("account.hive");
+
+        let config = b00t_acp::AgentConfig::new("worker-1".to_string(), "memory://".to_string(), "account.hive".to_string());
+        let worker = Arc::new(b00t_acp::Agent::with_transport(config, Arc::clone(&bus)).await.unwrap());
+        let (tx, mut delegations) = mpsc::unbounded_channel();
+        worker.on_message(MessageType::Coordination, move |envelope| {
+            let _ = tx.send(CoordinationMessage::from_envelope(envelope));
+        }).await;
+        worker.start().await.unwrap();
+
+        let hive_agent = Arc::clone(&worker);
+        tokio::spawn(async move {
+            let Some(Some(CoordinationMessage::TaskDelegation { captain_id, task_id, .. })) = delegations.recv().await else { return };
+            let completion = CoordinationMessage::TaskCompletion {
+                worker_id: "worker-1".to_string(),
+                captain_id: captain_id.clone(),
+                task_id,
+                status: TaskCompletionStatus::Success,
+                result: None,
+                artifacts: vec![],
+            };
+            let subject = ACPMessage::inbox_subject("account.hive", &captain_id);
+            hive_agent.send_message(&subject, &completion.to_envelope(0).unwrap()).await.unwrap();
+        });
+
+        let completion = captain.delegate_task("worker-1", "task-5", "lint", TaskPriority::Normal, None, vec![], true)
+            .await.unwrap().unwrap();
+        assert_eq!(completion.worker_id, "worker-1");
+        assert_eq!(worker.current_step().await, 0, "coordination doesn't move the step barrier");
+    }
+
+    #[tokio::test]
+    async fn test_forged_and_unsigned_completions_are_dropped() {
+        let bus: Arc<dyn Transport> = Arc::new(InMemoryTransport::new());
+        let key = SigningKey::hmac("account.hive", b"hive secret");
+        let signed = |agent_id: &str| coordinator(&bus, metadata(agent_id, &[]))
+            .with_namespace("account.hive")
+            .with_signing_key(key.clone())
+            .with_unsigned_policy(UnsignedPolicy::Drop);
+        let mut captain = signed("captain");
+        let worker = signed("worker-1");
+        captain.delegate_task("worker-1", "task-7", "lint", TaskPriority::Normal, None, vec![], false).await.unwrap();
+
+        // A rogue process completes task-7 for worker-1: once unsigned, once with the wrong secret
+        let completion = CoordinationMessage::TaskCompletion {
+            worker_id: "worker-1".to_string(),
+            captain_id: "captain".to_string(),
+            task_id: "task-7".to_string(),
+            status: TaskCompletionStatus::Success,
+            result: None,
+            artifacts: vec![],
+        };
+        let inbox = ACPMessage::inbox_subject("account.hive", "captain");
+        let unsigned = completion.to_envelope(0).unwrap();
+        let mut forged = unsigned.clone();
+        forged.sign(&SigningKey::hmac("account.hive", b"guessed")).unwrap();
+        bus.publish(&inbox, &unsigned).await.unwrap();
+        bus.publish(&inbox, &forged).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(captain.task("task-7").unwrap().state, TaskState::Assigned);
+
+        worker.complete_task("captain", "task-7", TaskCompletionStatus::Success, None, vec![]).await.unwrap();
+        for _ in 0..100 {
+            if captain.task("task-7").unwrap().state == TaskState::Done {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(captain.task("task-7").unwrap().state, TaskState::Done);
+    }
+
+    #[tokio::test]
+    async fn test_listener_reports_unreachable_broker() {
+        let unreachable: Arc<dyn Transport> = Arc::new(RedisTransport::new("redis://127.0.0.1:1").unwrap());
+        let coordinator = coordinator(&unreachable, metadata("captain", &[]));
+        let waited = coordinator.wait_for_message(Duration::from_secs(5), MessageFilter::default()).await;
+        assert!(waited.unwrap_err().to_string().contains("Failed to subscribe"));
+    }
+
+    #[tokio::test]
+    async fn test_lapsed_task_is_reassigned_and_completed() {
+        let bus: Arc<dyn Transport> = Arc::new(InMemoryTransport::new());
+        let worker = |agent_id: &str| metadata(agent_id, &["rust"]);
+        let mut captain = coordinator(&bus, worker("captain")).with_task_lease(60);
+        let worker_2 = coordinator(&bus, worker("worker-2"));
+
+        captain.delegate_task("worker-1", "task-9", "fix build", TaskPriority::High, None, vec!["rust".to_string()], false)
+            .await.unwrap();
+        assert_eq!(captain.task("task-9").unwrap().state, TaskState::Assigned);
+
+        let handed = tokio::spawn(async move {
+            let filter = MessageFilter::default().with_message_types(["TaskDelegation"]);
+            let delegation = worker_2.wait_for_message(Duration::from_secs(5), filter).await;
+            worker_2.complete_task("captain", "task-9", TaskCompletionStatus::Success, None, vec![]).await.unwrap();
+            delegation
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        // worker-1 never reported back; its lease is long gone
+        let expired = captain.supervise_tasks_at(unix_now() + 120, &[worker("worker-1"), worker("worker-2")]).await.unwrap();
+        assert_eq!(expired.len(), 1);
+        assert_eq!(handed.await.unwrap().unwrap().sender(), "captain");
+
+        for _ in 0..100 {
+            if captain.task("task-9").unwrap().state == TaskState::Done {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let task = captain.task("task-9").unwrap();
+        assert_eq!(task.state, TaskState::Done);
+        assert_eq!(task.worker_id.as_deref(), Some("worker-2"));
+        assert_eq!(task.previous_workers, vec!["worker-1".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_blocking_delegation_ignores_previous_holder() {
+        let bus: Arc<dyn Transport> = Arc::new(InMemoryTransport::new());
+        let worker = |agent_id: &str| metadata(agent_id, &["rust"]);
+        let mut captain = coordinator(&bus, worker("captain")).with_task_lease(60);
+        let (previous, holder) = (coordinator(&bus, worker("worker-1")), coordinator(&bus, worker("worker-2")));
+
+        captain.delegate_task("worker-1", "task-3", "fix build", TaskPriority::High, None, vec!["rust".to_string()], false)
+            .await.unwrap();
+        captain.supervise_tasks_at(unix_now() + 120, &[worker("worker-2")]).await.unwrap();
+        assert_eq!(captain.task("task-3").unwrap().worker_id.as_deref(), Some("worker-2"));
+
+        let completions = captain.dispatcher().subscribe(MessageFilter::default()
+            .with_message_types(["TaskCompletion"])
+            .with_task_ids(["task-3"]));
+        let reports = async {
+            previous.complete_task("captain", "task-3", TaskCompletionStatus::Success, Some("zombie".to_string()), vec![]).await.unwrap();
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            assert!(!captain.task("task-3").unwrap().state.is_terminal(), "the previous holder can't finish it");
+            holder.complete_task("captain", "task-3", TaskCompletionStatus::Success, Some("fixed".to_string()), vec![]).await.unwrap();
+        };
+        let (completion, ()) = tokio::join!(captain.await_completion("task-3", completions, Duration::from_secs(5)), reports);
+        let completion = completion.unwrap();
+        assert_eq!((completion.worker_id.as_str(), completion.result.as_deref()), ("worker-2", Some("fixed")));
+    }
+
+    #[tokio::test]
+    async fn test_open_vote_reaches_every_listener() {
+        let bus: Arc<dyn Transport> = Arc::new(InMemoryTransport::new());
+        let captain = coordinator(&bus, metadata("captain", &[]));
+        let voter = coordinator(&bus, metadata("voter-1", &[]));
+
+        let voting = tokio::spawn(async move {
+            let filter = MessageFilter::default().with_message_types(["VotingProposal"]);
+            let CoordinationMessage::VotingProposal { proposal_id, .. } =
+                voter.wait_for_message(Duration::from_secs(5), filter).await.unwrap() else { unreachable!() };
+            voter.submit_vote(&proposal_id, VoteChoice::Single("ship".to_string()), None).await.unwrap();
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let options = vec![VotingOption { id: "ship".to_string(), title: "Ship".to_string(), description: String::new() }];
+        let result = captain.create_voting_proposal("release", "ship it?", options, VotingType::SingleChoice,
+            Duration::from_millis(500), vec![], VotingRules::default()).await.unwrap();
+        voting.await.unwrap();
+        assert_eq!(result.winner.as_deref(), Some("ship"));
+    }
+
+    #[tokio::test]
+    async fn test_delegate_to_best_capable_responder() {
+        let bus: Arc<dyn Transport> = Arc::new(InMemoryTransport::new());
+        let novice = coordinator(&bus, metadata("novice", &["rust"]));
+        let expert = coordinator(&bus, AgentMetadata {
+            specializations: HashMap::from([("rust".to_string(), 0.95)]),
+            ..metadata("expert", &["rust"])
+        });
+        let pythonista = coordinator(&bus, metadata("pythonista", &["python"]));
+        for worker in [&novice, &expert, &pythonista] {
+            worker.announce_presence().await.unwrap();
+        }
+        let mut captain = coordinator(&bus, metadata("captain", &[])).with_capability_window(Duration::from_millis(200));
+
+        let selector = AgentSelector::new(vec!["rust".to_string()]);
+        let ranked = captain.select_agents(&selector, "fix the build", RequestUrgency::Normal).await.unwrap();
+        assert_eq!(ranked.iter().map(|c| c.agent_id.as_str()).collect::<Vec<_>>(), vec!["expert", "novice"]);
+
+        let (best, completion) = captain.delegate_to_best(&selector, "task-1", "fix the build", TaskPriority::High, None, false)
+            .await.unwrap();
+        assert_eq!(best.agent_id, "expert");
+        assert!(completion.is_none());
+        assert_eq!(captain.task("task-1").unwrap().worker_id.as_deref(), Some("expert"));
+    }
+
+    #[tokio::test]
+    async fn test_dispatcher_subscription_streams_matches() {
+        let dispatcher = MessageDispatcher::new();
+        let mut votes = dispatcher.subscribe(MessageFilter::default().with_task_ids(["task-42"]));
+        assert_eq!(dispatcher.dispatch(&completion("worker-1", "task-42")), 1);
+        assert_eq!(dispatcher.dispatch(&completion("worker-1", "task-7")), 0);
+        assert_eq!(dispatcher.dispatch(&completion("worker-2", "task-42")), 1);
+
+        assert_eq!(votes.recv().await.unwrap().sender(), "worker-1");
+        assert_eq!(votes.recv().await.unwrap().sender(), "worker-2");
+        drop(votes);
+        assert_eq!(dispatcher.dispatch(&completion("worker-1", "task-42")), 0);
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_message_resolves_matching_only() {
+        let bus: Arc<dyn Transport> = Arc::new(InMemoryTransport::new());
+        let coordinator = coordinator(&bus, metadata("captain", &[]));
+        let dispatcher = coordinator.dispatcher().clone();
+
+        tokio::spawn(async move {
+            // wait until the waiter is registered, then deliver noise before the real completion
+            while dispatcher.waiting() == 0 {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+            assert_eq!(dispatcher.dispatch(&completion("worker-2", "task-42")), 0);
+            assert_eq!(dispatcher.dispatch(&completion("worker-1", "task-42")), 1);
+        });
+
+        let filter = MessageFilter::default().with_from_agents(["worker-1"]).with_task_ids(["task-42"]);
+        let message = coordinator.wait_for_message(Duration::from_secs(5), filter).await.unwrap();
+        assert_eq!(message.sender(), "worker-1");
+        assert_eq!(coordinator.dispatcher().waiting(), 0);
+
+        let timed_out = coordinator.wait_for_message(Duration::from_millis(20), MessageFilter::default()).await;
+        assert!(timed_out.unwrap_err().to_string().contains("timed out"));
+    }
+
+    #[tokio::test]
+    async fn test_agent_coordinator_creation() {
+        let redis = RedisComms::new(RedisConfig::default(), "test-agent".to_string()).unwrap();
+        let coordinator = AgentCoordinator::new(redis, metadata("test-agent", &["testing"]));
         assert_eq!(coordinator.agent_metadata.agent_id, "test-agent");
     }
 }
\ No newline at end of file
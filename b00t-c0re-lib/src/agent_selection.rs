@@ -0,0 +1,224 @@
+//! Ranking agents for a capability request
+//!
+//! A captain broadcasts a `CapabilityRequest`, collects `CapabilityResponse`s for a short window,
+//! then ranks the responders by how much of the request they cover, how proficient they claim
+//! to be, how loaded they are and whether their model size fits.
+
+use crate::agent_coordination::AgentMetadata;
+use crate::datum_ai_model::ModelSize;
+use crate::redis::AgentStatus;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Relative weight of each scoring factor; they needn't sum to 1
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelectionWeights {
+    pub coverage: f32,
+    pub proficiency: f32,
+    pub load: f32,
+    pub model_size: f32,
+}
+
+impl Default for SelectionWeights {
+    fn default() -> Self {
+        Self { coverage: 0.4, proficiency: 0.3, load: 0.2, model_size: 0.1 }
+    }
+}
+
+/// A responder and how it scored, best first in `AgentSelector::rank`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CandidateScore {
+    pub agent_id: String,
+    /// Fraction of the required capabilities held, 0.0-1.0
+    pub coverage: f32,
+    /// Mean proficiency over the required capabilities, 0.0-1.0
+    pub proficiency: f32,
+    /// Effective load, 0.0-1.0 (busy agents count as at least 0.75)
+    pub load: f32,
+    pub model_size: Option<ModelSize>,
+    pub score: f32,
+}
+
+/// Scores capability responses against what a task needs
+#[derive(Debug, Clone)]
+pub struct AgentSelector {
+    required_capabilities: Vec<String>,
+    weights: SelectionWeights,
+    model_size: Option<ModelSize>,
+    require_all: bool,
+}
+
+impl AgentSelector {
+    pub fn new(required_capabilities: Vec<String>) -> Self {
+        Self {
+            required_capabilities,
+            weights: SelectionWeights::default(),
+            model_size: None,
+            require_all: false,
+        }
+    }
+
+    pub fn with_weights(mut self, weights: SelectionWeights) -> Self {
+        self.weights = weights;
+        self
+    }
+
+    /// Prefer agents running a model of this size
+    pub fn with_model_size(mut self, model_size: ModelSize) -> Self {
+        self.model_size = Some(model_size);
+        self
+    }
+
+    /// Drop agents that don't hold every required capability
+    pub fn require_all(mut self, require_all: bool) -> Self {
+        self.require_all = require_all;
+        self
+    }
+
+    pub fn required_capabilities(&self) -> &[String] {
+        &self.required_capabilities
+    }
+
+    /// Rank responders (`(agent_id, proficiency_scores)`), best first
+    ///
+    /// `known` supplies load, status and model size; responders missing from it are scored
+    /// on their response alone, at middling load.
+    pub fn rank(&self, responses: &[(String, HashMap<String, f32>)], known: &[AgentMetadata]) -> Vec<CandidateScore> {
+        let mut ranked: Vec<CandidateScore> = responses.iter()
+            .filter_map(|(agent_id, scores)| self.score(agent_id, scores, known.iter().find(|a| &a.agent_id == agent_id)))
+            .collect();
+        ranked.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.agent_id.cmp(&b.agent_id)));
+        ranked
+    }
+
+    fn score(&self, agent_id: &str, scores: &HashMap<String, f32>, metadata: Option<&AgentMetadata>) -> Option<CandidateScore> {
+        if metadata.is_some_and(|m| matches!(m.status, AgentStatus::Offline | AgentStatus::Error(_))) {
+            return None;
+        }
+
+        let holds = |capability: &String| {
+            scores.get(capability).is_some_and(|score| *score > 0.0)
+                || metadata.is_some_and(|m| m.capabilities.contains(capability))
+        };
+        let (coverage, proficiency) = if self.required_capabilities.is_empty() {
+            (1.0, 1.0)
+        } else {
+            let required = self.required_capabilities.len() as f32;
+            let held = self.required_capabilities.iter().filter(|c| holds(c)).count() as f32;
+            let proficiency: f32 = self.required_capabilities.iter()
+                .map(|capability| {
+                    scores.get(capability)
+                        .or_else(|| metadata.and_then(|m| m.specializations.get(capability)))
+                        .copied()
+                        // 🤓 Listed without a score: assume competent, not expert
+                        .unwrap_or(if holds(capability) { 0.5 } else { 0.0 })
+                        .clamp(0.0, 1.0)
+                })
+                .sum();
+            (held / required, proficiency / required)
+        };
+        if coverage == 0.0 || (self.require_all && coverage < 1.0) {
+            return None;
+        }
+
+        let load = match metadata {
+            Some(m) if matches!(m.status, AgentStatus::Busy) => m.load.max(0.75),
+            Some(m) => m.load,
+            None => 0.5,
+        }.clamp(0.0, 1.0);
+        let model_size = metadata.and_then(|m| m.model_size.clone());
+        let size_fit = match (&self.model_size, &model_size) {
+            (None, _) => 1.0,
+            (Some(wanted), Some(size)) if wanted == size => 1.0,
+            (Some(_), None) => 0.5,
+            (Some(_), Some(_)) => 0.0,
+        };
+
+        let w = &self.weights;
+        let total = (w.coverage + w.proficiency + w.load + w.model_size).max(f32::EPSILON);
+        let score = (w.coverage * coverage + w.proficiency * proficiency + w.load * (1.0 - load) + w.model_size * size_fit) / total;
+
+        Some(CandidateScore { agent_id: agent_id.to_string(), coverage, proficiency, load, model_size, score })
+    }
+}
+
+/// How `metadata` would answer a request for `required_capabilities`, if it can help at all
+pub fn proficiency_for(metadata: &AgentMetadata, required_capabilities: &[String]) -> Option<HashMap<String, f32>> {
+    let scores: HashMap<String, f32> = required_capabilities.iter()
+        .filter_map(|capability| {
+            let score = metadata.specializations.get(capability).copied()
+                .or_else(|| metadata.capabilities.contains(capability).then_some(0.5))?;
+            Some((capability.clone(), score.clamp(0.0, 1.0)))
+        })
+        .collect();
+    (!scores.is_empty()).then_some(scores)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn agent(agent_id: &str, capabilities: &[&str], load: f32, status: AgentStatus, model_size: Option<ModelSize>) -> AgentMetadata {
+        AgentMetadata {
+            agent_id: agent_id.to_string(),
+            agent_role: "worker".to_string(),
+            capabilities: capabilities.iter().map(|c| c.to_string()).collect(),
+            crew: None,
+            status,
+            last_seen: 0,
+            load,
+            specializations: HashMap::new(),
+            model_size,
+        }
+    }
+
+    fn response(agent_id: &str, scores: &[(&str, f32)]) -> (String, HashMap<String, f32>) {
+        (agent_id.to_string(), scores.iter().map(|(c, s)| (c.to_string(), *s)).collect())
+    }
+
+    #[test]
+    fn test_rank_prefers_coverage_proficiency_and_idle_agents() {
+        let selector = AgentSelector::new(vec!["rust".to_string(), "docker".to_string()]);
+        let known = vec![
+            agent("both", &["rust", "docker"], 0.2, AgentStatus::Online, None),
+            agent("rust-only", &["rust"], 0.0, AgentStatus::Online, None),
+            agent("busy", &["rust", "docker"], 0.1, AgentStatus::Busy, None),
+            agent("gone", &["rust", "docker"], 0.0, AgentStatus::Offline, None),
+        ];
+        let responses = vec![
+            response("rust-only", &[("rust", 0.9)]),
+            response("busy", &[("rust", 0.8), ("docker", 0.8)]),
+            response("both", &[("rust", 0.8), ("docker", 0.8)]),
+            response("gone", &[("rust", 1.0), ("docker", 1.0)]),
+            response("unknown", &[("python", 1.0)]),
+        ];
+
+        let ranked = selector.rank(&responses, &known);
+        let order: Vec<&str> = ranked.iter().map(|c| c.agent_id.as_str()).collect();
+        assert_eq!(order, vec!["both", "busy", "rust-only"]);
+        assert_eq!(ranked[1].load, 0.75);
+        assert_eq!(ranked[2].coverage, 0.5);
+
+        let strict = selector.clone().require_all(true).rank(&responses, &known);
+        assert!(strict.iter().all(|c| c.coverage == 1.0));
+    }
+
+    #[test]
+    fn test_model_size_preference_and_self_assessment() {
+        let selector = AgentSelector::new(vec!["rust".to_string()]).with_model_size(ModelSize::Large);
+        let known = vec![
+            agent("small", &["rust"], 0.0, AgentStatus::Online, Some(ModelSize::Small)),
+            agent("large", &["rust"], 0.0, AgentStatus::Online, Some(ModelSize::Large)),
+        ];
+        let responses = vec![response("small", &[("rust", 0.7)]), response("large", &[("rust", 0.7)])];
+        assert_eq!(selector.rank(&responses, &known)[0].agent_id, "large");
+
+        let mut rustacean = agent("r", &["rust", "go"], 0.0, AgentStatus::Online, None);
+        rustacean.specializations.insert("rust".to_string(), 0.9);
+        let scores = proficiency_for(&rustacean, &["rust".to_string(), "go".to_string(), "cobol".to_string()]).unwrap();
+        assert_eq!(scores.get("rust"), Some(&0.9));
+        assert_eq!(scores.get("go"), Some(&0.5));
+        assert!(!scores.contains_key("cobol"));
+        assert!(proficiency_for(&rustacean, &["cobol".to_string()]).is_none());
+    }
+}
@@ -0,0 +1,416 @@
+//! Task lifecycle tracking for delegated tasks
+//!
+//! A captain's `TaskRegistry` follows each task it delegates through
+//! pending → assigned → in-progress → done/failed. Workers hold a lease that every
+//! `ProgressUpdate` renews; when a lease lapses the task returns to pending for
+//! reassignment to another agent with the required capabilities, and a passed
+//! `TaskDelegation.deadline` fails it.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::agent_coordination::{AgentMetadata, CoordinationMessage, TaskCompletionStatus, TaskPriority};
+use crate::redis::AgentStatus;
+
+/// Redis hash mirroring every captain's registry (`registry_field` → `TaskRecord` JSON)
+pub const TASK_REGISTRY_KEY: &str = "b00t:tasks:registry";
+
+/// Field of a task in `TASK_REGISTRY_KEY`: `{captain_id}:{task_id}`, since task ids are only
+/// unique per captain
+pub fn registry_field(captain_id: &str, task_id: &str) -> String {
+    format!("{}:{}", captain_id, task_id)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TaskState {
+    /// Waiting for a worker
+    Pending,
+    /// Sent to a worker that hasn't reported progress yet
+    Assigned,
+    /// Worker is reporting progress
+    InProgress,
+    Done,
+    Failed,
+}
+
+impl TaskState {
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, TaskState::Done | TaskState::Failed)
+    }
+
+    /// Where a completion with `status` leaves a task; partial success counts as done
+    pub fn completed(status: &TaskCompletionStatus) -> Self {
+        match status {
+            TaskCompletionStatus::Success | TaskCompletionStatus::PartialSuccess(_) => TaskState::Done,
+            TaskCompletionStatus::Failed(_) | TaskCompletionStatus::Cancelled => TaskState::Failed,
+        }
+    }
+}
+
+impl std::fmt::Display for TaskState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            TaskState::Pending => "pending",
+            TaskState::Assigned => "assigned",
+            TaskState::InProgress => "in-progress",
+            TaskState::Done => "done",
+            TaskState::Failed => "failed",
+        };
+        f.write_str(name)
+    }
+}
+
+/// One delegated task as the captain sees it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskRecord {
+    pub task_id: String,
+    pub description: String,
+    pub captain_id: String,
+    pub priority: TaskPriority,
+    pub required_capabilities: Vec<String>,
+    pub state: TaskState,
+    /// Current (or last) holder
+    pub worker_id: Option<String>,
+    /// Workers that let the lease lapse; not picked again
+    pub previous_workers: Vec<String>,
+    pub progress_percent: f32,
+    /// Unix seconds
+    pub deadline: Option<u64>,
+    /// Unix seconds; the holder must report progress before this
+    pub lease_expires: Option<u64>,
+    pub result: Option<String>,
+    pub updated_at: u64,
+}
+
+/// What `TaskRegistry::expire` did to a task
+#[derive(Debug, Clone, PartialEq)]
+pub enum TaskExpiry {
+    /// Lease lapsed; the task is pending again
+    LeaseExpired { task_id: String, worker_id: String },
+    /// Deadline passed; the task failed
+    DeadlineExceeded { task_id: String },
+}
+
+/// Lease and lifecycle bookkeeping for delegated tasks
+#[derive(Debug, Clone)]
+pub struct TaskRegistry {
+    tasks: HashMap<String, TaskRecord>,
+    /// Seconds a worker may go without a progress update
+    lease_secs: u64,
+}
+
+impl Default for TaskRegistry {
+    fn default() -> Self {
+        Self { tasks: HashMap::new(), lease_secs: 300 }
+    }
+}
+
+impl TaskRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_lease_secs(mut self, lease_secs: u64) -> Self {
+        self.lease_secs = lease_secs;
+        self
+    }
+
+    pub fn get(&self, task_id: &str) -> Option<&TaskRecord> {
+        self.tasks.get(task_id)
+    }
+
+    /// All tasks, most recently updated first
+    pub fn tasks(&self) -> Vec<TaskRecord> {
+        let mut tasks: Vec<TaskRecord> = self.tasks.values().cloned().collect();
+        tasks.sort_by(|a, b| b.updated_at.cmp(&a.updated_at).then_with(|| a.task_id.cmp(&b.task_id)));
+        tasks
+    }
+
+    /// Track a new task as pending
+    #[allow(clippy::too_many_arguments)]
+    pub fn create(
+        &mut self,
+        task_id: &str,
+        description: &str,
+        captain_id: &str,
+        priority: TaskPriority,
+        deadline: Option<u64>,
+        required_capabilities: Vec<String>,
+        now: u64,
+    ) -> &TaskRecord {
+        self.tasks.entry(task_id.to_string()).or_insert_with(|| TaskRecord {
+            task_id: task_id.to_string(),
+            description: description.to_string(),
+            captain_id: captain_id.to_string(),
+            priority,
+            required_capabilities,
+            state: TaskState::Pending,
+            worker_id: None,
+            previous_workers: vec![],
+            progress_percent: 0.0,
+            deadline,
+            lease_expires: None,
+            result: None,
+            updated_at: now,
+        })
+    }
+
+    /// Hand a pending task to `worker_id` and start its lease
+    pub fn assign(&mut self, task_id: &str, worker_id: &str, now: u64) -> Result<&TaskRecord> {
+        let lease_secs = self.lease_secs;
+        let task = self.task_mut(task_id)?;
+        if task.state.is_terminal() {
+            anyhow::bail!("task {} is already {}", task_id, task.state);
+        }
+        task.state = TaskState::Assigned;
+        task.worker_id = Some(worker_id.to_string());
+        task.lease_expires = Some(now + lease_secs);
+        task.updated_at = now;
+        Ok(task)
+    }
+
+    /// Progress from the holder renews its lease
+    pub fn heartbeat(&mut self, task_id: &str, worker_id: &str, progress_percent: f32, now: u64) -> Result<&TaskRecord> {
+        let lease_secs = self.lease_secs;
+        let task = self.task_mut(task_id)?;
+        Self::check_holder(task, worker_id)?;
+        task.state = TaskState::InProgress;
+        task.progress_percent = progress_percent.clamp(0.0, 100.0);
+        task.lease_expires = Some(now + lease_secs);
+        task.updated_at = now;
+        Ok(task)
+    }
+
+    /// Completion from the holder ends the task; partial success counts as done
+    pub fn complete(
+        &mut self,
+        task_id: &str,
+        worker_id: &str,
+        status: &TaskCompletionStatus,
+        result: Option<String>,
+        now: u64,
+    ) -> Result<&TaskRecord> {
+        let task = self.task_mut(task_id)?;
+        Self::check_holder(task, worker_id)?;
+        let state = TaskState::completed(status);
+        let detail = match status {
+            TaskCompletionStatus::Success => None,
+            TaskCompletionStatus::PartialSuccess(detail) | TaskCompletionStatus::Failed(detail) => Some(detail.clone()),
+            TaskCompletionStatus::Cancelled => Some("cancelled".to_string()),
+        };
+        task.state = state;
+        if state == TaskState::Done {
+            task.progress_percent = 100.0;
+        }
+        task.result = result.or(detail);
+        task.lease_expires = None;
+        task.updated_at = now;
+        Ok(task)
+    }
+
+    /// Fail tasks past their deadline and release lapsed leases back to pending
+    pub fn expire(&mut self, now: u64) -> Vec<TaskExpiry> {
+        let mut expired = Vec::new();
+        for task in self.tasks.values_mut().filter(|task| !task.state.is_terminal()) {
+            if task.deadline.is_some_and(|deadline| now > deadline) {
+                task.state = TaskState::Failed;
+                task.result = Some("deadline exceeded".to_string());
+                task.lease_expires = None;
+                task.updated_at = now;
+                expired.push(TaskExpiry::DeadlineExceeded { task_id: task.task_id.clone() });
+            } else if task.lease_expires.is_some_and(|lease| now > lease) {
+                let worker_id = task.worker_id.take().unwrap_or_default();
+                task.previous_workers.push(worker_id.clone());
+                task.state = TaskState::Pending;
+                task.lease_expires = None;
+                task.updated_at = now;
+                expired.push(TaskExpiry::LeaseExpired { task_id: task.task_id.clone(), worker_id });
+            }
+        }
+        expired.sort_by_key(|expiry| match expiry {
+            TaskExpiry::LeaseExpired { task_id, .. } | TaskExpiry::DeadlineExceeded { task_id } => task_id.clone(),
+        });
+        expired
+    }
+
+    /// Forget tasks that finished more than `retain_secs` ago; returns their ids
+    pub fn prune(&mut self, now: u64, retain_secs: u64) -> Vec<String> {
+        let mut pruned: Vec<String> = self.tasks.values()
+            .filter(|task| task.state.is_terminal() && task.updated_at + retain_secs < now)
+            .map(|task| task.task_id.clone())
+            .collect();
+        pruned.sort();
+        for task_id in &pruned {
+            self.tasks.remove(task_id);
+        }
+        pruned
+    }
+
+    /// Take over a task tracked elsewhere (e.g. mirrored by an earlier process); known tasks win
+    pub fn adopt(&mut self, task: TaskRecord) {
+        self.tasks.entry(task.task_id.clone()).or_insert(task);
+    }
+
+    /// Least-loaded online agent with every required capability that hasn't dropped this task
+    pub fn pick_worker<'a>(&self, task_id: &str, candidates: &'a [AgentMetadata]) -> Option<&'a AgentMetadata> {
+        let task = self.tasks.get(task_id)?;
+        candidates.iter()
+            .filter(|agent| !matches!(agent.status, AgentStatus::Offline | AgentStatus::Error(_)))
+            .filter(|agent| agent.agent_id != task.captain_id && !task.previous_workers.contains(&agent.agent_id))
+            .filter(|agent| task.required_capabilities.iter().all(|cap| agent.capabilities.contains(cap)))
+            .min_by(|a, b| a.load.total_cmp(&b.load))
+    }
+
+    /// Fold an observed coordination message into the registry; returns the touched task
+    pub fn observe(&mut self, message: &CoordinationMessage, now: u64) -> Option<&TaskRecord> {
+        match message {
+            CoordinationMessage::TaskDelegation {
+                captain_id, worker_id, task_id, task_description, priority, deadline, required_capabilities, ..
+            } => {
+                self.create(task_id, task_description, captain_id, priority.clone(), *deadline, required_capabilities.clone(), now);
+                self.assign(task_id, worker_id, now).ok()
+            }
+            CoordinationMessage::ProgressUpdate { agent_id, task_id, progress_percent, .. } => {
+                self.heartbeat(task_id, agent_id, *progress_percent, now).ok()
+            }
+            CoordinationMessage::TaskCompletion { worker_id, task_id, status, result, .. } => {
+                self.complete(task_id, worker_id, status, result.clone(), now).ok()
+            }
+            _ => None,
+        }
+    }
+
+    fn task_mut(&mut self, task_id: &str) -> Result<&mut TaskRecord> {
+        self.tasks.get_mut(task_id).ok_or_else(|| anyhow::anyhow!("unknown task {}", task_id))
+    }
+
+    /// 🦨 Reports from a worker whose lease lapsed are ignored, or a zombie could finish a reassigned task
+    fn check_holder(task: &TaskRecord, worker_id: &str) -> Result<()> {
+        if task.state.is_terminal() {
+            anyhow::bail!("task {} is already {}", task.task_id, task.state);
+        }
+        if task.worker_id.as_deref() != Some(worker_id) {
+            anyhow::bail!("{} doesn't hold task {}", worker_id, task.task_id);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn agent(agent_id: &str, capabilities: &[&str], load: f32) -> AgentMetadata {
+        AgentMetadata {
+            agent_id: agent_id.to_string(),
+            agent_role: "worker".to_string(),
+            capabilities: capabilities.iter().map(|c| c.to_string()).collect(),
+            crew: None,
+            status: AgentStatus::Online,
+            last_seen: 0,
+            load,
+            specializations: HashMap::new(),
+            model_size: None,
+        }
+    }
+
+    fn registry_with_task(deadline: Option<u64>) -> TaskRegistry {
+        let mut registry = TaskRegistry::new().with_lease_secs(60);
+        registry.create("t1", "build", "captain", TaskPriority::Normal, deadline, vec!["rust".to_string()], 0);
+        registry
+    }
+
+    #[test]
+    fn test_task_lifecycle() {
+        let mut registry = registry_with_task(None);
+        assert_eq!(registry.get("t1").unwrap().state, TaskState::Pending);
+
+        registry.assign("t1", "w1", 10).unwrap();
+        assert_eq!(registry.get("t1").unwrap().lease_expires, Some(70));
+
+        let task = registry.heartbeat("t1", "w1", 40.0, 50).unwrap();
+        assert_eq!((task.state, task.lease_expires), (TaskState::InProgress, Some(110)));
+        assert!(registry.heartbeat("t1", "w2", 50.0, 60).is_err(), "only the holder renews");
+
+        let task = registry.complete("t1", "w1", &TaskCompletionStatus::Success, Some("ok".to_string()), 90).unwrap();
+        assert_eq!((task.state, task.progress_percent), (TaskState::Done, 100.0));
+        assert!(registry.assign("t1", "w2", 100).is_err());
+        assert!(registry.expire(1_000).is_empty());
+    }
+
+    #[test]
+    fn test_lapsed_lease_is_reassigned_to_capable_agent() {
+        let mut registry = registry_with_task(None);
+        registry.assign("t1", "w1", 0).unwrap();
+        assert!(registry.expire(60).is_empty());
+        assert_eq!(
+            registry.expire(61),
+            vec![TaskExpiry::LeaseExpired { task_id: "t1".to_string(), worker_id: "w1".to_string() }]
+        );
+        assert_eq!(registry.get("t1").unwrap().state, TaskState::Pending);
+
+        let candidates = [
+            agent("w1", &["rust"], 0.0),
+            agent("w2", &["python"], 0.0),
+            agent("w3", &["rust", "python"], 0.8),
+            agent("w4", &["rust"], 0.3),
+        ];
+        assert_eq!(registry.pick_worker("t1", &candidates).unwrap().agent_id, "w4");
+
+        // the old holder's late completion doesn't count
+        registry.assign("t1", "w4", 70).unwrap();
+        assert!(registry.complete("t1", "w1", &TaskCompletionStatus::Success, None, 75).is_err());
+    }
+
+    #[test]
+    fn test_deadline_fails_task_and_observe_tracks_messages() {
+        let mut registry = TaskRegistry::new();
+        registry.observe(&CoordinationMessage::TaskDelegation {
+            captain_id: "captain".to_string(),
+            worker_id: "w1".to_string(),
+            task_id: "t2".to_string(),
+            task_description: "deploy".to_string(),
+            priority: TaskPriority::High,
+            deadline: Some(100),
+            required_capabilities: vec![],
+            blocking: false,
+        }, 0);
+        registry.observe(&CoordinationMessage::ProgressUpdate {
+            agent_id: "w1".to_string(),
+            task_id: "t2".to_string(),
+            progress_percent: 20.0,
+            status_message: "halfway-ish".to_string(),
+            estimated_completion: None,
+        }, 10);
+        assert_eq!(registry.get("t2").unwrap().state, TaskState::InProgress);
+
+        assert_eq!(registry.expire(101), vec![TaskExpiry::DeadlineExceeded { task_id: "t2".to_string() }]);
+        let task = registry.get("t2").unwrap();
+        assert_eq!((task.state, task.result.as_deref()), (TaskState::Failed, Some("deadline exceeded")));
+    }
+
+    #[test]
+    fn test_finished_tasks_are_pruned_after_retention() {
+        let mut registry = registry_with_task(None);
+        registry.create("t2", "lint", "captain", TaskPriority::Low, None, vec![], 0);
+        registry.assign("t1", "w1", 0).unwrap();
+        registry.complete("t1", "w1", &TaskCompletionStatus::Success, None, 10).unwrap();
+
+        assert!(registry.prune(100, 100).is_empty());
+        assert_eq!(registry.prune(111, 100), vec!["t1".to_string()]);
+        assert!(registry.get("t1").is_none());
+        assert!(registry.get("t2").is_some(), "unfinished tasks stay");
+
+        let mut mirrored = registry.get("t2").unwrap().clone();
+        mirrored.description = "stale copy".to_string();
+        registry.adopt(mirrored);
+        assert_eq!(registry.get("t2").unwrap().description, "lint");
+    }
+
+    #[test]
+    fn test_mirror_fields_are_per_captain() {
+        assert_eq!(registry_field("captain-a", "task-1"), "captain-a:task-1");
+        assert_ne!(registry_field("captain-a", "task-1"), registry_field("captain-b", "task-1"));
+    }
+}
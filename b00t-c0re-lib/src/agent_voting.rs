@@ -0,0 +1,414 @@
+//! Vote tallying for agent voting proposals
+//!
+//! Accumulates `Vote` ballots for one proposal, enforces the eligible voters and deadline,
+//! and computes the outcome for each `VotingType` with a quorum, a decision threshold and
+//! deterministic tie-breaking.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+use crate::agent_coordination::{VoteChoice, VotingOption, VotingType};
+
+/// How much support the winning option needs (ranked choice always runs to a majority)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum DecisionThreshold {
+    /// Most votes wins
+    #[default]
+    Plurality,
+    /// More than half of the ballots cast
+    Majority,
+    /// Every ballot cast
+    Unanimous,
+}
+
+/// What to do when options tie for first place
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum TieBreak {
+    /// The option listed first in the proposal wins
+    #[default]
+    OptionOrder,
+    /// No winner; the result is `Tied`
+    Undecided,
+}
+
+/// Quorum, threshold and tie-breaking for a proposal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VotingRules {
+    /// Fraction of eligible voters (0.0-1.0) that must vote
+    pub quorum: f32,
+    pub threshold: DecisionThreshold,
+    pub tie_break: TieBreak,
+}
+
+impl Default for VotingRules {
+    fn default() -> Self {
+        Self {
+            quorum: 0.5,
+            threshold: DecisionThreshold::default(),
+            tie_break: TieBreak::default(),
+        }
+    }
+}
+
+impl VotingRules {
+    pub fn with_quorum(mut self, quorum: f32) -> Self {
+        self.quorum = quorum.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn with_threshold(mut self, threshold: DecisionThreshold) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    pub fn with_tie_break(mut self, tie_break: TieBreak) -> Self {
+        self.tie_break = tie_break;
+        self
+    }
+}
+
+impl std::str::FromStr for DecisionThreshold {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "plurality" => Ok(DecisionThreshold::Plurality),
+            "majority" => Ok(DecisionThreshold::Majority),
+            "unanimous" => Ok(DecisionThreshold::Unanimous),
+            _ => anyhow::bail!("Unknown threshold '{}' (expected plurality, majority or unanimous)", s),
+        }
+    }
+}
+
+impl std::str::FromStr for TieBreak {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace(['-', '_'], "").as_str() {
+            "optionorder" | "first" => Ok(TieBreak::OptionOrder),
+            "undecided" | "none" => Ok(TieBreak::Undecided),
+            _ => anyhow::bail!("Unknown tie-break '{}' (expected option-order or undecided)", s),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VotingStatus {
+    Decided,
+    /// Too few eligible voters took part
+    NoQuorum,
+    Tied,
+    /// No option reached the decision threshold
+    ThresholdNotMet,
+    /// Every option with support was vetoed
+    Vetoed,
+}
+
+/// Outcome of a proposal, published as a `VotingResult` coordination message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VotingResult {
+    pub proposal_id: String,
+    pub voting_type: VotingType,
+    pub status: VotingStatus,
+    pub winner: Option<String>,
+    /// Votes per option (final round for ranked choice)
+    pub counts: HashMap<String, u32>,
+    pub votes_cast: usize,
+    pub eligible: usize,
+    pub vetoed: Vec<String>,
+    pub ballots: HashMap<String, VoteChoice>,
+    /// Ballots that were refused, as "<voter>: <reason>"
+    pub rejected: Vec<String>,
+}
+
+/// Ballots collected for one proposal
+#[derive(Debug, Clone)]
+pub struct VoteTally {
+    proposal_id: String,
+    voting_type: VotingType,
+    options: Vec<String>,
+    eligible_voters: Vec<String>,
+    deadline: u64,
+    rules: VotingRules,
+    ballots: HashMap<String, VoteChoice>,
+    rejected: Vec<String>,
+}
+
+impl VoteTally {
+    /// An empty `eligible_voters` lets any agent vote
+    pub fn new(
+        proposal_id: &str,
+        voting_type: VotingType,
+        options: &[VotingOption],
+        eligible_voters: Vec<String>,
+        deadline: u64,
+    ) -> Self {
+        Self {
+            proposal_id: proposal_id.to_string(),
+            voting_type,
+            options: options.iter().map(|o| o.id.clone()).collect(),
+            eligible_voters,
+            deadline,
+            rules: VotingRules::default(),
+            ballots: HashMap::new(),
+            rejected: Vec::new(),
+        }
+    }
+
+    pub fn with_rules(mut self, rules: VotingRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// Record a ballot cast at unix time `at`; a later ballot from the same voter replaces the earlier one
+    pub fn record(&mut self, voter_id: &str, vote: VoteChoice, at: u64) -> Result<()> {
+        if let Err(e) = self.check(voter_id, &vote, at) {
+            self.rejected.push(format!("{}: {}", voter_id, e));
+            return Err(e);
+        }
+        self.ballots.insert(voter_id.to_string(), vote);
+        Ok(())
+    }
+
+    fn check(&self, voter_id: &str, vote: &VoteChoice, at: u64) -> Result<()> {
+        if !self.eligible_voters.is_empty() && !self.eligible_voters.iter().any(|v| v == voter_id) {
+            anyhow::bail!("not an eligible voter");
+        }
+        if at > self.deadline {
+            anyhow::bail!("voting closed at {}", self.deadline);
+        }
+
+        let chosen: Vec<&String> = match (&self.voting_type, vote) {
+            (VotingType::SingleChoice | VotingType::VetoCapable, VoteChoice::Single(id)) => vec![id],
+            (VotingType::RankedChoice, VoteChoice::Ranked(ids))
+            | (VotingType::Approval, VoteChoice::Approval(ids)) => ids.iter().collect(),
+            (VotingType::VetoCapable, VoteChoice::Veto { option_id, alternative }) => {
+                std::iter::once(option_id).chain(alternative).collect()
+            }
+            (voting_type, _) => anyhow::bail!("ballot doesn't fit a {:?} vote", voting_type),
+        };
+        if let Some(unknown) = chosen.iter().find(|id| !self.options.contains(id)) {
+            anyhow::bail!("unknown option '{}'", unknown);
+        }
+        if chosen.iter().collect::<HashSet<_>>().len() != chosen.len() {
+            anyhow::bail!("an option appears more than once");
+        }
+        Ok(())
+    }
+
+    /// Every eligible voter has voted (never true for open votes)
+    pub fn is_complete(&self) -> bool {
+        !self.eligible_voters.is_empty()
+            && self.eligible_voters.iter().all(|v| self.ballots.contains_key(v))
+    }
+
+    pub fn votes_cast(&self) -> usize {
+        self.ballots.len()
+    }
+
+    pub fn outcome(&self) -> VotingResult {
+        let mut result = VotingResult {
+            proposal_id: self.proposal_id.clone(),
+            voting_type: self.voting_type.clone(),
+            status: VotingStatus::ThresholdNotMet,
+            winner: None,
+            counts: HashMap::new(),
+            votes_cast: self.ballots.len(),
+            eligible: self.eligible_voters.len(),
+            vetoed: Vec::new(),
+            ballots: self.ballots.clone(),
+            rejected: self.rejected.clone(),
+        };
+
+        let required = if self.eligible_voters.is_empty() {
+            1.0
+        } else {
+            (self.rules.quorum * self.eligible_voters.len() as f32).ceil()
+        };
+        if (self.ballots.len() as f32) < required {
+            result.status = VotingStatus::NoQuorum;
+            return result;
+        }
+
+        if let VotingType::RankedChoice = self.voting_type {
+            let (status, winner, counts) = self.instant_runoff();
+            result.status = status;
+            result.winner = winner;
+            result.counts = counts;
+            return result;
+        }
+
+        let mut counts: HashMap<String, u32> = self.options.iter().map(|id| (id.clone(), 0)).collect();
+        let mut vetoed = HashSet::new();
+        for vote in self.ballots.values() {
+            match vote {
+                VoteChoice::Single(id) => *counts.entry(id.clone()).or_default() += 1,
+                VoteChoice::Approval(ids) => ids.iter().for_each(|id| *counts.entry(id.clone()).or_default() += 1),
+                VoteChoice::Veto { option_id, alternative } => {
+                    vetoed.insert(option_id.clone());
+                    if let Some(id) = alternative {
+                        *counts.entry(id.clone()).or_default() += 1;
+                    }
+                }
+                VoteChoice::Ranked(_) => {}
+            }
+        }
+        result.vetoed = self.options.iter().filter(|id| vetoed.contains(*id)).cloned().collect();
+
+        // 🤓 Options keep proposal order so tie-breaking is deterministic
+        let standing: Vec<&String> = self.options.iter()
+            .filter(|id| !vetoed.contains(*id) && counts[*id] > 0)
+            .collect();
+        result.counts = counts;
+
+        let Some(best) = standing.iter().map(|id| result.counts[*id]).max() else {
+            result.status = if vetoed.is_empty() { VotingStatus::ThresholdNotMet } else { VotingStatus::Vetoed };
+            return result;
+        };
+        let leaders: Vec<&String> = standing.into_iter().filter(|id| result.counts[*id] == best).collect();
+        if leaders.len() > 1 && self.rules.tie_break == TieBreak::Undecided {
+            result.status = VotingStatus::Tied;
+            return result;
+        }
+
+        let ballots = self.ballots.len() as u32;
+        let meets = match self.rules.threshold {
+            DecisionThreshold::Plurality => true,
+            DecisionThreshold::Majority => best * 2 > ballots,
+            DecisionThreshold::Unanimous => best == ballots,
+        };
+        if meets {
+            result.status = VotingStatus::Decided;
+            result.winner = Some(leaders[0].clone());
+        }
+        result
+    }
+
+    /// Eliminate the weakest option until one holds a majority of the live ballots
+    fn instant_runoff(&self) -> (VotingStatus, Option<String>, HashMap<String, u32>) {
+        let mut remaining: Vec<&String> = self.options.iter().collect();
+        loop {
+            let mut counts: HashMap<String, u32> = remaining.iter().map(|id| ((*id).clone(), 0)).collect();
+            let mut live = 0;
+            for vote in self.ballots.values() {
+                if let VoteChoice::Ranked(ids) = vote {
+                    if let Some(top) = ids.iter().find(|id| remaining.contains(id)) {
+                        *counts.get_mut(top).unwrap() += 1;
+                        live += 1;
+                    }
+                }
+            }
+            if live == 0 {
+                return (VotingStatus::ThresholdNotMet, None, counts);
+            }
+
+            let best = remaining.iter().map(|id| counts[*id]).max().unwrap_or(0);
+            if best * 2 > live {
+                let winner = remaining.iter().find(|id| counts[**id] == best).map(|id| (*id).clone());
+                return (VotingStatus::Decided, winner, counts);
+            }
+
+            let worst = remaining.iter().map(|id| counts[*id]).min().unwrap_or(0);
+            if worst == best {
+                // Every remaining option is level
+                return match self.rules.tie_break {
+                    TieBreak::OptionOrder => (VotingStatus::Decided, Some(remaining[0].clone()), counts),
+                    TieBreak::Undecided => (VotingStatus::Tied, None, counts),
+                };
+            }
+            // 🦨 Among the weakest, the last-listed option goes first
+            let eliminated = remaining.iter().rposition(|id| counts[*id] == worst).unwrap();
+            remaining.remove(eliminated);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(ids: &[&str]) -> Vec<VotingOption> {
+        ids.iter()
+            .map(|id| VotingOption { id: id.to_string(), title: id.to_string(), description: String::new() })
+            .collect()
+    }
+
+    fn voters(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn test_single_choice_with_quorum_and_ties() {
+        let mut tally = VoteTally::new("p1", VotingType::SingleChoice, &options(&["a", "b"]), voters(&["x", "y", "z", "w"]), 100);
+        tally.record("x", VoteChoice::Single("b".to_string()), 10).unwrap();
+        assert_eq!(tally.outcome().status, VotingStatus::NoQuorum);
+
+        tally.record("y", VoteChoice::Single("a".to_string()), 10).unwrap();
+        let result = tally.outcome();
+        assert_eq!(result.status, VotingStatus::Decided);
+        assert_eq!(result.winner.as_deref(), Some("a"), "ties go to the first-listed option");
+
+        let undecided = tally.clone().with_rules(VotingRules::default().with_tie_break(TieBreak::Undecided));
+        assert_eq!(undecided.outcome().status, VotingStatus::Tied);
+
+        let majority = tally.clone().with_rules(VotingRules::default().with_threshold(DecisionThreshold::Majority));
+        assert_eq!(majority.outcome().status, VotingStatus::ThresholdNotMet);
+    }
+
+    #[test]
+    fn test_rejects_ineligible_late_and_malformed_ballots() {
+        let mut tally = VoteTally::new("p1", VotingType::SingleChoice, &options(&["a", "b"]), voters(&["x", "y"]), 100);
+        assert!(tally.record("mallory", VoteChoice::Single("a".to_string()), 10).is_err());
+        assert!(tally.record("x", VoteChoice::Single("a".to_string()), 101).is_err());
+        assert!(tally.record("x", VoteChoice::Single("c".to_string()), 10).is_err());
+        assert!(tally.record("x", VoteChoice::Approval(vec!["a".to_string()]), 10).is_err());
+        assert_eq!(tally.votes_cast(), 0);
+        assert_eq!(tally.outcome().rejected.len(), 4);
+
+        tally.record("x", VoteChoice::Single("a".to_string()), 10).unwrap();
+        tally.record("x", VoteChoice::Single("b".to_string()), 20).unwrap();
+        tally.record("y", VoteChoice::Single("b".to_string()), 20).unwrap();
+        assert!(tally.is_complete());
+        let result = tally.outcome();
+        assert_eq!(result.winner.as_deref(), Some("b"));
+        assert_eq!(result.counts["b"], 2);
+    }
+
+    #[test]
+    fn test_ranked_choice_runoff() {
+        let ranked = |ids: &[&str]| VoteChoice::Ranked(ids.iter().map(|s| s.to_string()).collect());
+        let mut tally = VoteTally::new("p2", VotingType::RankedChoice, &options(&["a", "b", "c"]), voters(&["v1", "v2", "v3", "v4", "v5"]), 100);
+        tally.record("v1", ranked(&["a", "b"]), 1).unwrap();
+        tally.record("v2", ranked(&["a", "c"]), 1).unwrap();
+        tally.record("v3", ranked(&["b", "c"]), 1).unwrap();
+        tally.record("v4", ranked(&["c", "b"]), 1).unwrap();
+        tally.record("v5", ranked(&["c", "b"]), 1).unwrap();
+
+        // c and a lead with 2, b is eliminated and its ballot moves to c
+        let result = tally.outcome();
+        assert_eq!(result.status, VotingStatus::Decided);
+        assert_eq!(result.winner.as_deref(), Some("c"));
+        assert_eq!(result.counts["c"], 3);
+    }
+
+    #[test]
+    fn test_approval_and_veto() {
+        let mut approval = VoteTally::new("p3", VotingType::Approval, &options(&["a", "b"]), voters(&["x", "y"]), 100)
+            .with_rules(VotingRules::default().with_threshold(DecisionThreshold::Unanimous));
+        approval.record("x", VoteChoice::Approval(vec!["a".to_string(), "b".to_string()]), 1).unwrap();
+        approval.record("y", VoteChoice::Approval(vec!["b".to_string()]), 1).unwrap();
+        assert_eq!(approval.outcome().winner.as_deref(), Some("b"));
+
+        let mut veto = VoteTally::new("p4", VotingType::VetoCapable, &options(&["a", "b"]), voters(&["x", "y", "z"]), 100);
+        veto.record("x", VoteChoice::Single("a".to_string()), 1).unwrap();
+        veto.record("y", VoteChoice::Single("a".to_string()), 1).unwrap();
+        veto.record("z", VoteChoice::Veto { option_id: "a".to_string(), alternative: Some("b".to_string()) }, 1).unwrap();
+        let result = veto.outcome();
+        assert_eq!(result.vetoed, vec!["a"]);
+        assert_eq!(result.winner.as_deref(), Some("b"));
+
+        veto.record("z", VoteChoice::Veto { option_id: "a".to_string(), alternative: None }, 2).unwrap();
+        assert_eq!(veto.outcome().status, VotingStatus::Vetoed);
+    }
+}
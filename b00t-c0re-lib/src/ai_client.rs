@@ -5,10 +5,11 @@
 
 use anyhow::anyhow;
 use rig::client::CompletionClient;
-use rig::completion::Prompt;
+use rig::completion::{AssistantContent, CompletionError, CompletionModel};
 use rig::providers::{anthropic, openai};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use crate::B00tResult;
 
@@ -65,11 +66,85 @@ impl Default for AiClientConfig {
     }
 }
 
+impl AiClientConfig {
+    /// Fill missing API keys from `<PROVIDER>_API_KEY` and enable those providers
+    ///
+    /// If the default provider still has no key, the highest-priority provider that
+    /// does becomes the default.
+    pub fn with_env_keys(mut self) -> Self {
+        for config in self.providers.values_mut() {
+            if config.api_key.is_none() {
+                let var = format!("{}_API_KEY", config.provider.to_uppercase());
+                if let Ok(key) = std::env::var(&var) {
+                    config.api_key = Some(key);
+                    config.enabled = true;
+                }
+            }
+        }
+
+        let ready = |p: &AiProviderConfig| p.enabled && p.api_key.is_some();
+        if !self.providers.get(&self.default_provider).is_some_and(ready) {
+            let mut candidates: Vec<(&String, &AiProviderConfig)> = self.providers.iter()
+                .filter(|(_, p)| ready(p))
+                .collect();
+            candidates.sort_by_key(|(name, p)| (p.priority, (*name).clone()));
+            if let Some((name, _)) = candidates.first() {
+                self.default_provider = (*name).clone();
+            }
+        }
+        self
+    }
+}
+
+/// Token usage consumed by a single completion
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TokenUsage {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    /// True when counts come from the local tiktoken counter instead of the provider
+    #[serde(default)]
+    pub estimated: bool,
+}
+
+impl TokenUsage {
+    pub fn total(&self) -> u64 {
+        self.input_tokens + self.output_tokens
+    }
+
+    /// Estimate usage locally with tiktoken when the provider reports nothing
+    pub fn estimate(prompt: &str, completion: &str) -> Self {
+        Self {
+            input_tokens: crate::tiktoken::estimate_tokens(prompt) as u64,
+            output_tokens: crate::tiktoken::estimate_tokens(completion) as u64,
+            estimated: true,
+        }
+    }
+}
+
+/// Completion text together with the provider, model and usage that produced it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AiCompletion {
+    pub content: String,
+    pub provider: String,
+    pub model: String,
+    pub usage: TokenUsage,
+}
+
+/// Hook consulted around every AI call, e.g. to enforce session budgets
+pub trait UsageGuard: Send + Sync {
+    /// Return an error to refuse the call before it reaches the provider
+    fn check(&self) -> B00tResult<()>;
+
+    /// Account for usage after a successful call
+    fn record(&self, provider: &str, model: &str, usage: &TokenUsage) -> B00tResult<()>;
+}
+
 /// Unified AI client using Rig.rs for multiple providers
 pub struct B00tAiClient {
     config: AiClientConfig,
     // 🤓 Store pre-configured clients to avoid recreating on each request
     _clients: HashMap<String, Box<dyn std::any::Any + Send + Sync>>,
+    usage_guard: Option<Arc<dyn UsageGuard>>,
 }
 
 impl B00tAiClient {
@@ -78,6 +153,7 @@ impl B00tAiClient {
         Ok(Self {
             config,
             _clients: HashMap::new(),
+            usage_guard: None,
         })
     }
 
@@ -86,6 +162,12 @@ impl B00tAiClient {
         Self::new(AiClientConfig::default())
     }
 
+    /// Attach a guard that can refuse calls and is told about token usage
+    pub fn with_usage_guard(mut self, guard: Arc<dyn UsageGuard>) -> Self {
+        self.usage_guard = Some(guard);
+        self
+    }
+
     /// Update configuration from cloud dashboard
     pub fn update_config(&mut self, config: AiClientConfig) -> B00tResult<()> {
         self.config = config;
@@ -127,34 +209,12 @@ impl B00tAiClient {
 
     /// Create completion with specific provider
     pub async fn complete_with_provider(&self, prompt: &str, provider: Option<&str>) -> B00tResult<String> {
-        let provider_name = provider.unwrap_or(&self.config.default_provider);
-        let provider_config = self.config.providers.get(provider_name)
-            .ok_or_else(|| anyhow!("Provider '{}' not found", provider_name))?;
-
-        if !provider_config.enabled {
-            return Err(anyhow!("Provider '{}' is disabled", provider_name).into());
-        }
-
-        let api_key = provider_config.api_key.as_ref()
-            .ok_or_else(|| anyhow!("No API key configured for provider '{}'", provider_name))?;
+        Ok(self.complete_with_usage(prompt, provider).await?.content)
+    }
 
-        match provider_config.provider.as_str() {
-            "openai" => {
-                let client = openai::Client::new(api_key);
-                let agent = client.agent(&provider_config.model).build();
-                let response = agent.prompt(prompt).await
-                    .map_err(|e| anyhow!("OpenAI completion failed: {}", e))?;
-                Ok(response)
-            }
-            "anthropic" => {
-                let client = anthropic::Client::new(api_key);
-                let agent = client.agent(&provider_config.model).build();
-                let response = agent.prompt(prompt).await
-                    .map_err(|e| anyhow!("Anthropic completion failed: {}", e))?;
-                Ok(response)
-            }
-            _ => Err(anyhow!("Unsupported provider: {}", provider_config.provider).into())
-        }
+    /// Create completion and report the tokens it consumed
+    pub async fn complete_with_usage(&self, prompt: &str, provider: Option<&str>) -> B00tResult<AiCompletion> {
+        self.run_prompt(prompt, provider, "completion").await
     }
 
     /// Create chat session
@@ -164,40 +224,75 @@ impl B00tAiClient {
 
     /// Create chat with specific provider
     pub async fn chat_with_provider(&self, messages: &[ChatMessage], provider: Option<&str>) -> B00tResult<String> {
+        Ok(self.chat_with_usage(messages, provider).await?.content)
+    }
+
+    /// Create chat and report the tokens it consumed
+    pub async fn chat_with_usage(&self, messages: &[ChatMessage], provider: Option<&str>) -> B00tResult<AiCompletion> {
+        // For now, convert chat to a simple prompt (Rig.rs chat API requires more research)
+        let prompt = messages.iter()
+            .map(|msg| format!("{}: {}", msg.role, msg.content))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        self.run_prompt(&prompt, provider, "chat").await
+    }
+
+    async fn run_prompt(&self, prompt: &str, provider: Option<&str>, kind: &str) -> B00tResult<AiCompletion> {
         let provider_name = provider.unwrap_or(&self.config.default_provider);
         let provider_config = self.config.providers.get(provider_name)
             .ok_or_else(|| anyhow!("Provider '{}' not found", provider_name))?;
 
         if !provider_config.enabled {
-            return Err(anyhow!("Provider '{}' is disabled", provider_name).into());
+            return Err(anyhow!("Provider '{}' is disabled", provider_name));
         }
 
         let api_key = provider_config.api_key.as_ref()
             .ok_or_else(|| anyhow!("No API key configured for provider '{}'", provider_name))?;
 
-        // For now, convert chat to a simple prompt (Rig.rs chat API requires more research)
-        let prompt = messages.iter()
-            .map(|msg| format!("{}: {}", msg.role, msg.content))
-            .collect::<Vec<_>>()
-            .join("\n");
+        // 🤓 Refuse before spending anything if the session budget is exhausted
+        if let Some(guard) = &self.usage_guard {
+            guard.check()?;
+        }
 
-        match provider_config.provider.as_str() {
+        let (content, usage) = match provider_config.provider.as_str() {
             "openai" => {
                 let client = openai::Client::new(api_key);
-                let agent = client.agent(&provider_config.model).build();
-                let response = agent.prompt(&prompt).await
-                    .map_err(|e| anyhow!("OpenAI chat failed: {}", e))?;
-                Ok(response)
+                send_prompt(client.completion_model(&provider_config.model), prompt).await
+                    .map_err(|e| anyhow!("OpenAI {} failed: {}", kind, e))?
             }
             "anthropic" => {
                 let client = anthropic::Client::new(api_key);
-                let agent = client.agent(&provider_config.model).build();
-                let response = agent.prompt(&prompt).await
-                    .map_err(|e| anyhow!("Anthropic chat failed: {}", e))?;
-                Ok(response)
+                send_prompt(client.completion_model(&provider_config.model), prompt).await
+                    .map_err(|e| anyhow!("Anthropic {} failed: {}", kind, e))?
+            }
+            _ => return Err(anyhow!("Unsupported provider: {}", provider_config.provider))
+        };
+
+        // Some providers omit usage, fall back to the local tokenizer
+        let usage = if usage.input_tokens == 0 && usage.output_tokens == 0 {
+            TokenUsage::estimate(prompt, &content)
+        } else {
+            TokenUsage {
+                input_tokens: usage.input_tokens,
+                output_tokens: usage.output_tokens,
+                estimated: false,
+            }
+        };
+
+        // 🦨 The completion is already paid for; losing the bookkeeping beats losing the answer
+        if let Some(guard) = &self.usage_guard {
+            if let Err(e) = guard.record(&provider_config.provider, &provider_config.model, &usage) {
+                eprintln!("⚠️ Failed to record {} usage for {}: {}", provider_config.provider, provider_config.model, e);
             }
-            _ => Err(anyhow!("Unsupported provider: {}", provider_config.provider).into())
         }
+
+        Ok(AiCompletion {
+            content,
+            provider: provider_config.provider.clone(),
+            model: provider_config.model.clone(),
+            usage,
+        })
     }
 
     /// Try completion with fallback to secondary provider
@@ -228,6 +323,22 @@ impl B00tAiClient {
     }
 }
 
+/// Send a single prompt and collect the text answer plus provider-reported usage
+async fn send_prompt<M: CompletionModel>(
+    model: M,
+    prompt: &str,
+) -> Result<(String, rig::completion::Usage), CompletionError> {
+    let response = model.completion_request(prompt).send().await?;
+    let content = response.choice.iter()
+        .filter_map(|c| match c {
+            AssistantContent::Text(text) => Some(text.text.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("");
+    Ok((content, response.usage))
+}
+
 /// Simple chat message structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatMessage {
@@ -291,4 +402,36 @@ mod tests {
         assert_eq!(system_msg.role, "system");
         assert_eq!(system_msg.content, "You are a helpful assistant");
     }
+
+    struct DenyAll;
+
+    impl UsageGuard for DenyAll {
+        fn check(&self) -> B00tResult<()> {
+            Err(anyhow!("budget exhausted"))
+        }
+
+        fn record(&self, _provider: &str, _model: &str, _usage: &TokenUsage) -> B00tResult<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_usage_guard_refuses_call() {
+        let mut config = AiClientConfig::default();
+        let openai = config.providers.get_mut("openai").unwrap();
+        openai.enabled = true;
+        openai.api_key = Some("sk-test".to_string());
+
+        let client = B00tAiClient::new(config).unwrap().with_usage_guard(Arc::new(DenyAll));
+        let err = client.complete("hello").await.unwrap_err();
+        assert!(err.to_string().contains("budget exhausted"));
+    }
+
+    #[test]
+    fn test_token_usage_estimate() {
+        let usage = TokenUsage::estimate("How many tokens is this?", "Six or so.");
+        assert!(usage.estimated);
+        assert!(usage.input_tokens > 0);
+        assert_eq!(usage.total(), usage.input_tokens + usage.output_tokens);
+    }
 }
\ No newline at end of file
@@ -12,6 +12,7 @@ use anyhow::{anyhow, Result};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::Path;
 use ts_rs::TS;
 
 /// Central configuration datum for the entire b00t ecosystem
@@ -108,8 +109,11 @@ pub struct AwsConfig {
 #[ts(export, export_to = "/home/brianh/promptexecution/infrastructure/b00t-website/dashboard/src/types/")]
 #[serde(rename_all = "camelCase")]
 pub struct QdrantConfig {
-    /// Qdrant endpoint URL
+    /// Qdrant endpoint URL (HTTP REST, used by b00t-grok-py)
     pub endpoint: String,
+    /// Qdrant gRPC endpoint used by the Rust client (usually port 6334)
+    #[serde(default)]
+    pub grpc_endpoint: Option<String>,
     /// API key (stored separately in keyring)
     pub api_key_keyring_key: String,
     /// Collection configurations
@@ -456,6 +460,22 @@ impl Default for B00tUnifiedConfig {
 }
 
 impl B00tUnifiedConfig {
+    /// File name of the unified config inside the b00t config directory
+    pub const FILE_NAME: &'static str = "b00t.json";
+
+    /// Load `b00t.json` from `dir`; `None` when the file doesn't exist
+    pub fn load_from_dir(dir: &Path) -> Result<Option<Self>> {
+        let path = dir.join(Self::FILE_NAME);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| anyhow!("Failed to read {}: {}", path.display(), e))?;
+        let config = serde_json::from_str(&content)
+            .map_err(|e| anyhow!("Failed to parse {}: {}", path.display(), e))?;
+        Ok(Some(config))
+    }
+
     /// Create a new configuration with user information
     pub fn new_for_user(username: &str, email: Option<String>) -> Self {
         let mut config = Self::default();
@@ -561,4 +581,26 @@ mod tests {
         assert_eq!(config.user.email, Some("test@example.com".to_string()));
         assert_eq!(config.metadata.source, "user");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_load_from_dir() {
+        let dir = tempfile::TempDir::new().unwrap();
+        assert!(B00tUnifiedConfig::load_from_dir(dir.path()).unwrap().is_none());
+
+        let mut config = B00tUnifiedConfig::new_for_user("testuser", None);
+        config.cloud.qdrant = Some(QdrantConfig {
+            endpoint: "https://qdrant.example.com:6333".to_string(),
+            grpc_endpoint: None,
+            api_key_keyring_key: "qdrant".to_string(),
+            collections: vec![],
+        });
+        std::fs::write(dir.path().join(B00tUnifiedConfig::FILE_NAME), serde_json::to_string(&config).unwrap()).unwrap();
+
+        let loaded = B00tUnifiedConfig::load_from_dir(dir.path()).unwrap().unwrap();
+        assert_eq!(loaded.user.username, "testuser");
+        assert_eq!(loaded.cloud.qdrant.unwrap().endpoint, "https://qdrant.example.com:6333");
+
+        std::fs::write(dir.path().join(B00tUnifiedConfig::FILE_NAME), "{").unwrap();
+        assert!(B00tUnifiedConfig::load_from_dir(dir.path()).is_err());
+    }
+}
@@ -6,7 +6,10 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use anyhow::Result;
+use std::path::Path;
+use anyhow::{Context, Result};
+
+use crate::ai_client::TokenUsage;
 
 /// Model size classification for resource planning and capability routing  
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -138,6 +141,8 @@ impl ModelProvider {
 /// [ai_model.metadata]
 /// family = "claude-3.5"
 /// training_cutoff = "2024-04"
+/// input_cost_per_1k_tokens = "0.003"
+/// output_cost_per_1k_tokens = "0.015"
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct AiModelDatum {
@@ -187,6 +192,22 @@ fn default_true() -> bool {
     true
 }
 
+/// USD pricing per 1k tokens, read from `[ai_model.metadata]`
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct ModelPricing {
+    pub input_cost_per_1k: f64,
+    pub output_cost_per_1k: f64,
+}
+
+impl ModelPricing {
+    /// Cost in USD of the given token usage
+    pub fn cost(&self, usage: &TokenUsage) -> f64 {
+        (usage.input_tokens as f64 * self.input_cost_per_1k
+            + usage.output_tokens as f64 * self.output_cost_per_1k)
+            / 1000.0
+    }
+}
+
 impl AiModelDatum {
     /// Generate litellm model list entry for proxy configuration
     pub fn to_litellm_config(&self, model_name: &str) -> serde_json::Value {
@@ -274,6 +295,35 @@ impl AiModelDatum {
     pub fn full_litellm_id(&self) -> String {
         self.litellm_model.clone()
     }
+
+    /// Pricing from metadata (`input_cost_per_1k_tokens`/`output_cost_per_1k_tokens`).
+    /// A flat `provider_cost_per_1k_tokens` applies to both directions when the split is absent.
+    pub fn pricing(&self) -> Option<ModelPricing> {
+        let read = |key: &str| self.metadata.get(key).and_then(|v| v.trim().parse::<f64>().ok());
+        let flat = read("provider_cost_per_1k_tokens");
+        let input = read("input_cost_per_1k_tokens").or(flat);
+        let output = read("output_cost_per_1k_tokens").or(flat);
+
+        match (input, output) {
+            (None, None) => None,
+            (input, output) => Some(ModelPricing {
+                input_cost_per_1k: input.unwrap_or(0.0),
+                output_cost_per_1k: output.unwrap_or(0.0),
+            }),
+        }
+    }
+
+    /// Check whether a provider model id (e.g. `gpt-4o-mini`) refers to this datum
+    pub fn matches_model_id(&self, model: &str) -> bool {
+        self.litellm_model == model
+            || self.litellm_model.rsplit('/').next() == Some(model)
+    }
+}
+
+/// On-disk layout of a `<name>.ai_model.toml` datum
+#[derive(Debug, Deserialize)]
+struct AiModelDatumFile {
+    ai_model: AiModelDatum,
 }
 
 /// Model registry for aggregating multiple model datums
@@ -402,6 +452,51 @@ impl ModelRegistry {
     pub fn get_model(&self, name: &str) -> Option<&AiModelDatum> {
         self.models.get(name)
     }
+
+    /// Get model by registry name or provider model id
+    pub fn find_model(&self, model: &str) -> Option<&AiModelDatum> {
+        self.get_model(model).or_else(|| {
+            self.models.values().find(|datum| datum.matches_model_id(model))
+        })
+    }
+
+    /// Load every `*.ai_model.toml` datum in `dir`, keyed by file stem
+    ///
+    /// A datum that can't be read or parsed is reported and skipped, so one bad file
+    /// doesn't take every model (and every AI call) down with it.
+    pub fn load_from_dir(dir: &Path) -> Result<Self> {
+        let mut registry = Self::new();
+        if !dir.exists() {
+            return Ok(registry);
+        }
+
+        for entry in std::fs::read_dir(dir)
+            .with_context(|| format!("Failed to read {}", dir.display()))?
+        {
+            let path = entry?.path();
+            let Some(name) = path.file_name()
+                .and_then(|n| n.to_str())
+                .and_then(|n| n.strip_suffix(".ai_model.toml"))
+            else {
+                continue;
+            };
+
+            match Self::load_datum(&path) {
+                Ok(datum) => registry.add_model(name.to_string(), datum),
+                Err(e) => eprintln!("⚠️ Skipping AI model datum: {:#}", e),
+            }
+        }
+
+        Ok(registry)
+    }
+
+    fn load_datum(path: &Path) -> Result<AiModelDatum> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let file: AiModelDatumFile = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        Ok(file.ai_model)
+    }
 }
 
 impl Default for ModelRegistry {
@@ -533,4 +628,57 @@ mod tests {
         
         assert_eq!(datum, deserialized);
     }
+
+    #[test]
+    fn test_pricing_from_metadata() {
+        let mut metadata = HashMap::new();
+        metadata.insert("input_cost_per_1k_tokens".to_string(), "0.003".to_string());
+        metadata.insert("output_cost_per_1k_tokens".to_string(), "0.015".to_string());
+        let datum = AiModelDatum {
+            provider: ModelProvider::Anthropic,
+            size: ModelSize::Large,
+            capabilities: vec![ModelCapability::Chat],
+            litellm_model: "anthropic/claude-3-5-sonnet-20241022".to_string(),
+            api_base: None,
+            api_key_env: None,
+            parameters: HashMap::new(),
+            metadata,
+            rpm_limit: None,
+            context_window: None,
+            enabled: true,
+            access_groups: vec![],
+        };
+
+        let pricing = datum.pricing().expect("pricing present");
+        let usage = TokenUsage { input_tokens: 1000, output_tokens: 2000, estimated: false };
+        assert!((pricing.cost(&usage) - 0.033).abs() < 1e-9);
+        assert!(datum.matches_model_id("claude-3-5-sonnet-20241022"));
+        assert!(!datum.matches_model_id("claude-3-haiku"));
+    }
+
+    #[test]
+    fn test_load_from_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("mini.ai_model.toml"), r#"
+[b00t]
+name = "mini"
+type = "ai_model"
+hint = "test"
+
+[ai_model]
+provider = "openai"
+size = "small"
+litellm_model = "openai/gpt-4o-mini"
+
+[ai_model.metadata]
+provider_cost_per_1k_tokens = "0.0002"
+"#).unwrap();
+        std::fs::write(dir.path().join("ignored.ai.toml"), "not a model").unwrap();
+        std::fs::write(dir.path().join("broken.ai_model.toml"), "[ai_model\nprovider =").unwrap();
+
+        let registry = ModelRegistry::load_from_dir(dir.path()).unwrap();
+        assert_eq!(registry.models.len(), 1);
+        let datum = registry.find_model("gpt-4o-mini").expect("found by model id");
+        assert_eq!(datum.pricing().unwrap().output_cost_per_1k, 0.0002);
+    }
 }
\ No newline at end of file
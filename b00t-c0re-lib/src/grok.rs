@@ -3,6 +3,13 @@
 //! Provides a DRY implementation of grok functionality using rmcp client
 //! to connect to b00t-grok-py MCP server. Can be used by both b00t-cli 
 //! and b00t-mcp.
+//!
+//! When the MCP server can't be started, falls back to the in-process
+//! `b00t-grok` crate, which uses Qdrant if reachable and otherwise a local
+//! vector store file (see `b00t_grok::StoreConfig`).
+//!
+//! Backend selection, Qdrant endpoint and server location come from
+//! [`GrokConfig`] (`B00tUnifiedConfig` + environment), never from host paths.
 
 use anyhow::Result;
 use rmcp::{
@@ -13,15 +20,188 @@ use rmcp::{
 };
 use std::borrow::Cow;
 use serde_json::{json, Value, Map};
+use serde::{Deserialize, Serialize};
 use std::env;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use tokio::process::Command;
+use tokio::sync::OnceCell;
+
+use crate::B00tUnifiedConfig;
+use crate::datum_ai_model::{ModelCapability, ModelProvider, ModelRegistry};
 
 /// Concrete MCP client running service type
 type McpRunningService = RunningService<rmcp::service::RoleClient, ()>;
 
+/// Which grok implementation `GrokClient::initialize` uses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GrokBackend {
+    /// b00t-grok-py MCP server, falling back to in-process grok
+    Auto,
+    /// b00t-grok-py MCP server only
+    Mcp,
+    /// The `b00t-grok` Rust crate, no Python child process
+    #[serde(alias = "local", alias = "rust")]
+    InProcess,
+}
+
+/// Grok backend configuration
+///
+/// | variable | default |
+/// | --- | --- |
+/// | `B00T_GROK_BACKEND` | `auto`, or `mcp` / `in_process` |
+/// | `QDRANT_URL` | `cloud.qdrant.endpoint` from `B00tUnifiedConfig`, else `http://localhost:6333` (HTTP, b00t-grok-py) |
+/// | `QDRANT_GRPC_URL` | `cloud.qdrant.grpcEndpoint`, else `http://localhost:6334` (gRPC, in-process grok) |
+/// | `QDRANT_API_KEY` | none |
+/// | `B00T_GROK_PY_DIR` | `b00t-grok-py` in an enclosing workspace, else `~/.dotfiles/b00t-grok-py` |
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GrokConfig {
+    pub backend: GrokBackend,
+    /// Qdrant HTTP REST endpoint passed to b00t-grok-py
+    pub qdrant_http_url: Option<String>,
+    /// Qdrant gRPC endpoint used by the in-process `b00t-grok` client
+    pub qdrant_grpc_url: Option<String>,
+    pub qdrant_api_key: Option<String>,
+    /// b00t-grok-py checkout run with `uv run`; `None` runs the installed package
+    pub server_dir: Option<PathBuf>,
+}
+
+impl Default for GrokConfig {
+    fn default() -> Self {
+        Self {
+            backend: GrokBackend::Auto,
+            qdrant_http_url: None,
+            qdrant_grpc_url: None,
+            qdrant_api_key: None,
+            server_dir: None,
+        }
+    }
+}
+
+impl GrokConfig {
+    /// Environment only
+    pub fn from_env() -> Result<Self> {
+        Self::resolve(None)
+    }
+
+    /// `b00t.json` in the b00t config directory (if present), overridden by the environment
+    pub fn load(b00t_dir: &Path) -> Result<Self> {
+        let unified = B00tUnifiedConfig::load_from_dir(b00t_dir)?;
+        Self::resolve(unified.as_ref())
+    }
+
+    /// Unified config settings, overridden by the environment
+    pub fn resolve(unified: Option<&B00tUnifiedConfig>) -> Result<Self> {
+        let cwd = env::current_dir().ok();
+        let home = dirs::home_dir();
+        Self::from_lookup(unified, |key| env::var(key).ok(), cwd.as_deref(), home.as_deref())
+    }
+
+    fn from_lookup(
+        unified: Option<&B00tUnifiedConfig>,
+        get: impl Fn(&str) -> Option<String>,
+        cwd: Option<&Path>,
+        home: Option<&Path>,
+    ) -> Result<Self> {
+        let qdrant = unified.and_then(|u| u.cloud.qdrant.as_ref());
+        let mut config = Self {
+            qdrant_http_url: qdrant.map(|q| q.endpoint.clone()),
+            qdrant_grpc_url: qdrant.and_then(|q| q.grpc_endpoint.clone()),
+            ..Self::default()
+        };
+
+        if let Some(backend) = get("B00T_GROK_BACKEND") {
+            config.backend = serde_json::from_value(Value::String(backend.to_lowercase()))
+                .map_err(|_| anyhow::anyhow!("Unknown B00T_GROK_BACKEND '{}': use auto, mcp or in_process", backend))?;
+        }
+        if let Some(url) = get("QDRANT_URL").filter(|u| !u.is_empty()) {
+            config.qdrant_http_url = Some(url);
+        }
+        if let Some(url) = get("QDRANT_GRPC_URL").filter(|u| !u.is_empty()) {
+            config.qdrant_grpc_url = Some(url);
+        }
+        config.qdrant_api_key = get("QDRANT_API_KEY").filter(|k| !k.is_empty());
+
+        config.server_dir = match get("B00T_GROK_PY_DIR") {
+            Some(dir) => Some(crate::utils::expand_path(dir)?),
+            None => Self::find_server_dir(cwd, home),
+        };
+        Ok(config)
+    }
+
+    // 🤓 Workspace checkout first (developing b00t), then the standard ~/.dotfiles install
+    fn find_server_dir(cwd: Option<&Path>, home: Option<&Path>) -> Option<PathBuf> {
+        let in_workspace = cwd.into_iter().flat_map(Path::ancestors).map(|dir| dir.join("b00t-grok-py"));
+        let installed = home.map(|h| h.join(".dotfiles").join("b00t-grok-py"));
+        in_workspace.chain(installed).find(|dir| dir.join("pyproject.toml").exists())
+    }
+}
+
+/// Reranker for the first enabled `rerank`-capable model in the registry (by name)
+///
+/// Models with an `api_base` (e.g. a LiteLLM proxy) are called with their full
+/// `litellm_model` id; Cohere models without one use Cohere's API directly.
+pub fn reranker_from_registry(registry: &ModelRegistry) -> Option<b00t_grok::HttpReranker> {
+    let mut models = registry.models_with_capability(&ModelCapability::Rerank);
+    models.sort_by(|a, b| a.0.cmp(b.0));
+
+    models.into_iter().find_map(|(_, datum)| {
+        let (api_base, model) = match (&datum.api_base, &datum.provider) {
+            (Some(base), _) => (base.clone(), datum.litellm_model.clone()),
+            (None, ModelProvider::Cohere) => (
+                "https://api.cohere.com/v1".to_string(),
+                datum.litellm_model.trim_start_matches(datum.provider.litellm_prefix()).to_string(),
+            ),
+            (None, _) => return None,
+        };
+        let api_key = datum.api_key_env.as_deref().and_then(|var| env::var(var).ok());
+        Some(b00t_grok::HttpReranker::new(&api_base, api_key.as_deref(), &model))
+    })
+}
+
+/// Prompt restricting the model to numbered sources and asking for `[n]` citations
+pub fn grounded_prompt(query: &str, sources: &[ChunkResult]) -> String {
+    let mut prompt = format!(
+        "Answer the question using ONLY the numbered sources below. Cite every claim inline \
+         with the source number in square brackets, e.g. [1] or [2][3]. If the sources do not \
+         contain the answer, reply with exactly {}.\n\n",
+        INSUFFICIENT_CONTEXT
+    );
+    for (i, source) in sources.iter().enumerate() {
+        prompt.push_str(&format!(
+            "[{}] (source: {}, date: {})\n{}\n\n",
+            i + 1,
+            source.source.as_deref().unwrap_or("unknown"),
+            source.created_at,
+            source.content.trim()
+        ));
+    }
+    prompt.push_str(&format!("Question: {}\nAnswer:", query));
+    prompt
+}
+
+/// Distinct `[n]` citations in `answer`, in order of first use, limited to 1..=`max`
+pub fn cited_indices(answer: &str, max: usize) -> Vec<usize> {
+    let citation = regex::Regex::new(r"\[(\d+)\]").expect("valid citation regex");
+    let mut seen = Vec::new();
+    for cap in citation.captures_iter(answer) {
+        if let Ok(n) = cap[1].parse::<usize>() {
+            if (1..=max).contains(&n) && !seen.contains(&n) {
+                seen.push(n);
+            }
+        }
+    }
+    seen
+}
+
 /// Grok MCP client for knowledgebase operations
 pub struct GrokClient {
+    config: GrokConfig,
     mcp_client: Option<McpRunningService>,
+    local_client: Option<b00t_grok::GrokClient>,
+    /// In-process client beside the MCP server, for verbs the server lacks; started on first use
+    companion: OnceCell<b00t_grok::GrokClient>,
 }
 
 /// Result structure for digest operations
@@ -54,6 +234,41 @@ pub struct ChunkResult {
     pub tags: Vec<String>,
     pub source: Option<String>,
     pub created_at: String,
+    /// Retrieval score (0..1 for in-process search; whatever the MCP server reports)
+    pub score: Option<f32>,
+    /// Absolute relevance (reranker score or cosine similarity)
+    pub confidence: Option<f32>,
+    /// Raw cosine similarity from the vector leg, used to gate answers; `None` for
+    /// keyword-only hits and MCP results
+    pub similarity: Option<f32>,
+}
+
+/// Retrieval confidence below which `answer` refuses instead of calling the model
+pub const DEFAULT_MIN_ANSWER_CONFIDENCE: f32 = 0.3;
+
+/// Reply the model is told to give when the sources don't contain the answer
+const INSUFFICIENT_CONTEXT: &str = "INSUFFICIENT_CONTEXT";
+
+/// A synthesized answer grounded in retrieved chunks
+#[derive(Debug, Clone)]
+pub struct GroundedAnswer {
+    pub query: String,
+    /// `None` when refused: low retrieval confidence or the model found no support
+    pub answer: Option<String>,
+    /// Best vector similarity among the sources
+    pub confidence: f32,
+    /// Sources cited inline as `[n]`
+    pub citations: Vec<Citation>,
+    pub message: Option<String>,
+}
+
+/// A source referenced by `[index]` in a grounded answer
+#[derive(Debug, Clone)]
+pub struct Citation {
+    pub index: usize,
+    pub chunk_id: String,
+    pub source: Option<String>,
+    pub date: String,
 }
 
 /// Result structure for learn operations
@@ -76,27 +291,120 @@ pub struct ChunkSummary {
 }
 
 impl GrokClient {
-    /// Create a new GrokClient
-    pub fn new() -> Self {
+    /// Create a new GrokClient configured from the environment
+    pub fn new() -> Result<Self> {
+        Ok(Self::with_config(GrokConfig::from_env()?))
+    }
+
+    pub fn with_config(config: GrokConfig) -> Self {
         Self {
+            config,
             mcp_client: None,
+            local_client: None,
+            companion: OnceCell::new(),
         }
     }
 
-    /// Initialize the MCP client connection to b00t-grok-py server
+    pub fn config(&self) -> &GrokConfig {
+        &self.config
+    }
+
+    /// Initialize the configured backend; `auto` tries the MCP server, then in-process grok
     pub async fn initialize(&mut self) -> Result<()> {
-        // 🤓 b00t pattern: use environment variables for configuration
-        let qdrant_url = env::var("QDRANT_URL")
-            .unwrap_or_else(|_| "http://192.168.2.13:6333".to_string());
-        let qdrant_api_key = env::var("QDRANT_API_KEY").unwrap_or_default();
-
-        // Create the child process transport
-        let transport = TokioChildProcess::new(Command::new("uv").configure(|cmd| {
-            cmd.arg("run")
-                .arg("python")
-                .arg("-m")
+        match self.config.backend {
+            GrokBackend::Mcp => return self.initialize_mcp().await,
+            GrokBackend::InProcess => return self.initialize_local().await,
+            GrokBackend::Auto => {}
+        }
+
+        match self.initialize_mcp().await {
+            Ok(()) => Ok(()),
+            Err(mcp_err) => {
+                self.initialize_local().await.map_err(|local_err| {
+                    anyhow::anyhow!("grok MCP server unavailable ({}) and in-process grok failed ({})", mcp_err, local_err)
+                })?;
+                eprintln!("⚠️ grok MCP server unavailable ({}), using in-process grok ({} store)",
+                    mcp_err, self.backend_name());
+                Ok(())
+            }
+        }
+    }
+
+    /// Use the in-process b00t-grok crate instead of the MCP server
+    ///
+    /// Embeddings come from `EmbedderConfig::from_env`; when no embedding endpoint is
+    /// configured the offline hashing embedder is used with the local store only, so
+    /// hashed vectors never end up in a shared Qdrant collection.
+    pub async fn initialize_local(&mut self) -> Result<()> {
+        self.local_client = Some(Self::build_local(&self.config).await?);
+        Ok(())
+    }
+
+    async fn build_local(config: &GrokConfig) -> Result<b00t_grok::GrokClient> {
+        // 🤓 qdrant-client speaks gRPC on 6334
+        let qdrant_url = config.qdrant_grpc_url.clone()
+            .unwrap_or_else(|| "http://localhost:6334".to_string());
+        let qdrant_api_key = config.qdrant_api_key.clone().unwrap_or_default();
+
+        // 🤓 Only "nothing configured" falls back; a malformed setting is an error
+        let configured = ["B00T_EMBEDDING_BACKEND", "B00T_EMBEDDING_API_BASE", "OLLAMA_API_URL"]
+            .iter()
+            .any(|var| env::var(var).is_ok());
+        let (embedder_config, store_config) = if configured {
+            (b00t_grok::EmbedderConfig::from_env()?, b00t_grok::StoreConfig::from_env()?)
+        } else {
+            eprintln!("⚠️ No embedding endpoint configured (OLLAMA_API_URL or B00T_EMBEDDING_API_BASE): \
+                using the offline hashing embedder and local store, which only match shared words. \
+                Set B00T_EMBEDDING_BACKEND=hashing to make this explicit.");
+            (
+                b00t_grok::EmbedderConfig::hashing(b00t_grok::embedder::DEFAULT_EMBEDDING_DIMENSION),
+                b00t_grok::StoreConfig::local(b00t_grok::LocalStore::default_path()),
+            )
+        };
+
+        let mut client = b00t_grok::GrokClient::new(qdrant_url, qdrant_api_key)
+            .with_embedder(Arc::from(embedder_config.build()?));
+        if store_config.backend == b00t_grok::StoreBackend::Local {
+            let store = b00t_grok::LocalStore::open(&store_config.local_path, "b00t_chunks")?;
+            client = client.with_store(Arc::new(store));
+        }
+        let registry = crate::utils::get_b00t_config_dir()
+            .and_then(|dir| ModelRegistry::load_from_dir(&dir))
+            .unwrap_or_default();
+        if let Some(reranker) = reranker_from_registry(&registry) {
+            client = client.with_reranker(Arc::new(reranker));
+        }
+        client.initialize().await?;
+        Ok(client)
+    }
+
+    /// Which backend answered `initialize`: "mcp", "qdrant", "local" or "none"
+    pub fn backend_name(&self) -> &str {
+        if self.mcp_client.is_some() {
+            "mcp"
+        } else {
+            self.local_client.as_ref()
+                .and_then(|c| c.store())
+                .map_or("none", |s| s.backend_name())
+        }
+    }
+
+    /// Initialize the MCP client connection to b00t-grok-py server
+    pub async fn initialize_mcp(&mut self) -> Result<()> {
+        // 🤓 b00t-grok-py talks to Qdrant over HTTP on 6333
+        let qdrant_url = self.config.qdrant_http_url.clone()
+            .unwrap_or_else(|| "http://localhost:6333".to_string());
+        let qdrant_api_key = self.config.qdrant_api_key.clone().unwrap_or_default();
+        let server_dir = self.config.server_dir.clone();
+
+        // Create the child process transport: a checkout runs under uv, otherwise the installed package
+        let program = if server_dir.is_some() { "uv" } else { "python3" };
+        let transport = TokioChildProcess::new(Command::new(program).configure(|cmd| {
+            if let Some(dir) = &server_dir {
+                cmd.arg("run").arg("python").current_dir(dir);
+            }
+            cmd.arg("-m")
                 .arg("b00t_grok_guru.server")
-                .current_dir("/home/brianh/.dotfiles/b00t-grok-py")
                 .env("QDRANT_URL", qdrant_url)
                 .env("QDRANT_API_KEY", qdrant_api_key);
         }))?;
@@ -111,6 +419,18 @@ impl GrokClient {
 
     /// Digest content into a knowledge chunk about a specific topic
     pub async fn digest(&self, topic: &str, content: &str) -> Result<DigestResult> {
+        if let Some(local) = &self.local_client {
+            let chunk = local.digest(topic, content).await?;
+            return Ok(DigestResult {
+                success: true,
+                chunk_id: chunk.id.to_string(),
+                topic: chunk.metadata.topic,
+                content_preview: chunk.content.chars().take(100).collect(),
+                created_at: chunk.metadata.created_at,
+                message: None,
+            });
+        }
+
         let client = self.mcp_client.as_ref()
             .ok_or_else(|| anyhow::anyhow!("GrokClient not initialized - call initialize() first"))?;
 
@@ -128,18 +448,44 @@ impl GrokClient {
 
     /// Search the knowledgebase for information related to a query
     pub async fn ask(&self, query: &str, topic: Option<&str>, limit: Option<usize>) -> Result<AskResult> {
+        let mut options = b00t_grok::SearchOptions::default().with_topic(topic);
+        if let Some(limit) = limit {
+            options = options.with_limit(limit);
+        }
+        self.search(query, &options).await
+    }
+
+    /// Search with explicit mode, limit, score cutoff and reranking
+    ///
+    /// The MCP server only honours topic, limit and min_score.
+    pub async fn search(&self, query: &str, options: &b00t_grok::SearchOptions) -> Result<AskResult> {
+        if let Some(local) = &self.local_client {
+            let results: Vec<ChunkResult> = local.search(query, options).await?
+                .into_iter()
+                .map(ChunkResult::from)
+                .collect();
+            return Ok(AskResult {
+                success: true,
+                query: query.to_string(),
+                total_found: results.len(),
+                results,
+                message: None,
+            });
+        }
+
         let client = self.mcp_client.as_ref()
             .ok_or_else(|| anyhow::anyhow!("GrokClient not initialized - call initialize() first"))?;
 
         let mut params = Map::new();
         params.insert("query".to_string(), json!(query));
+        params.insert("limit".to_string(), json!(options.limit));
 
-        if let Some(topic) = topic {
+        if let Some(topic) = &options.topic {
             params.insert("topic".to_string(), json!(topic));
         }
 
-        if let Some(limit) = limit {
-            params.insert("limit".to_string(), json!(limit));
+        if let Some(min_score) = options.min_score {
+            params.insert("min_score".to_string(), json!(min_score));
         }
 
         let request = CallToolRequestParam {
@@ -150,8 +496,89 @@ impl GrokClient {
         self.parse_ask_response(response)
     }
 
+    /// Answer `query` from retrieved chunks only, citing them inline as `[n]`
+    ///
+    /// Retrieves in-process (the MCP server returns no similarities) and refuses without
+    /// calling the model when no chunk's vector similarity reaches `min_confidence`, or when
+    /// no source has one (keyword-only hits): rank scores from RRF, BM25 or a reranker
+    /// aren't calibrated enough to gate on.
+    pub async fn answer(
+        &self,
+        query: &str,
+        options: &b00t_grok::SearchOptions,
+        ai: &crate::B00tAiClient,
+        min_confidence: f32,
+    ) -> Result<GroundedAnswer> {
+        let sources: Vec<ChunkResult> = self.in_process().await?.search(query, options).await?
+            .into_iter()
+            .map(ChunkResult::from)
+            .collect();
+        let similarity = sources.iter().filter_map(|c| c.similarity).reduce(f32::max);
+        let confidence = similarity.unwrap_or(0.0);
+
+        let mut result = GroundedAnswer {
+            query: query.to_string(),
+            answer: None,
+            confidence,
+            citations: Vec::new(),
+            message: None,
+        };
+
+        if !sources.is_empty() && similarity.is_none() {
+            result.message = Some("No source has a vector similarity to judge grounding; \
+                use --mode hybrid or vector".to_string());
+            return Ok(result);
+        }
+        if sources.is_empty() || confidence < min_confidence {
+            result.message = Some(format!(
+                "Not enough grounded context to answer (confidence {:.2} < {:.2}); try grok learn or grok ingest first",
+                confidence, min_confidence
+            ));
+            return Ok(result);
+        }
+
+        let reply = ai.complete_with_fallback(&grounded_prompt(query, &sources)).await?;
+        let reply = reply.trim();
+        if reply.is_empty() || reply.contains(INSUFFICIENT_CONTEXT) {
+            result.message = Some("The retrieved sources don't answer this question".to_string());
+            return Ok(result);
+        }
+
+        result.citations = cited_indices(reply, sources.len()).into_iter().map(|index| {
+            let chunk = &sources[index - 1];
+            Citation {
+                index,
+                chunk_id: chunk.id.clone(),
+                source: chunk.source.clone(),
+                date: chunk.created_at.clone(),
+            }
+        }).collect();
+        if result.citations.is_empty() {
+            result.message = Some("⚠️ The answer cites no sources; verify it before relying on it".to_string());
+        }
+        result.answer = Some(reply.to_string());
+        Ok(result)
+    }
+
     /// Learn from content by breaking it into chunks and storing in knowledgebase
     pub async fn learn(&self, content: &str, source: Option<&str>) -> Result<LearnResult> {
+        if let Some(local) = &self.local_client {
+            let source = source.unwrap_or("direct_input");
+            let chunks = local.learn(source, content).await?;
+            return Ok(LearnResult {
+                success: true,
+                source: source.to_string(),
+                chunks_created: chunks.len(),
+                chunk_summaries: chunks.into_iter().map(|chunk| ChunkSummary {
+                    id: chunk.id.to_string(),
+                    topic: chunk.metadata.topic,
+                    content_preview: chunk.content.chars().take(100).collect(),
+                    tags: chunk.metadata.tags,
+                }).collect(),
+                message: None,
+            });
+        }
+
         let client = self.mcp_client.as_ref()
             .ok_or_else(|| anyhow::anyhow!("GrokClient not initialized - call initialize() first"))?;
 
@@ -170,8 +597,55 @@ impl GrokClient {
         self.parse_learn_response(response)
     }
 
+    /// Incrementally ingest a file, directory, glob or git repository
+    ///
+    /// Always runs in-process (the MCP server has no ingest tool), as do the verbs below.
+    pub async fn ingest(&self, target: &str) -> Result<b00t_grok::IngestReport> {
+        Ok(self.in_process().await?.ingest(target).await?)
+    }
+
+    /// Topics in the knowledge base with chunk counts, largest first (in-process)
+    pub async fn topics(&self) -> Result<Vec<b00t_grok::TopicSummary>> {
+        Ok(self.in_process().await?.topics().await?)
+    }
+
+    /// Delete a chunk by id or every chunk of a topic; returns how many were removed (in-process)
+    pub async fn forget(&self, target: &str) -> Result<usize> {
+        Ok(self.in_process().await?.forget(target).await?)
+    }
+
+    /// Write a portable JSONL snapshot, optionally limited to one topic (in-process)
+    pub async fn export<W: std::io::Write>(&self, writer: W, topic: Option<&str>, include_vectors: bool) -> Result<usize> {
+        Ok(self.in_process().await?.export(writer, topic, include_vectors).await?)
+    }
+
+    /// Restore a JSONL snapshot written by `export` (in-process)
+    pub async fn import<R: std::io::BufRead>(&self, reader: R) -> Result<b00t_grok::ImportReport> {
+        Ok(self.in_process().await?.import(reader).await?)
+    }
+
+    /// The in-process client; beside the MCP server one is started on first use
+    async fn in_process(&self) -> Result<&b00t_grok::GrokClient> {
+        if let Some(local) = &self.local_client {
+            return Ok(local);
+        }
+        if self.mcp_client.is_none() {
+            return Err(anyhow::anyhow!("GrokClient not initialized - call initialize() first"));
+        }
+        self.companion.get_or_try_init(|| Self::build_local(&self.config)).await
+    }
+
     /// Get the current status of the grok system
     pub async fn status(&self) -> Result<Value> {
+        if let Some(store) = self.local_client.as_ref().and_then(|c| c.store()) {
+            return Ok(json!({
+                "success": true,
+                "backend": store.backend_name(),
+                "collection": store.collection(),
+                "total_chunks": store.count().await?,
+            }));
+        }
+
         let client = self.mcp_client.as_ref()
             .ok_or_else(|| anyhow::anyhow!("GrokClient not initialized - call initialize() first"))?;
 
@@ -243,6 +717,9 @@ impl GrokClient {
                                 .collect(),
                             source: chunk.get("source").and_then(|v| v.as_str()).map(|s| s.to_string()),
                             created_at: chunk.get("created_at").and_then(|v| v.as_str()).unwrap_or("").to_string(),
+                            score: chunk.get("score").and_then(|v| v.as_f64()).map(|f| f as f32),
+                            confidence: chunk.get("confidence").and_then(|v| v.as_f64()).map(|f| f as f32),
+                            similarity: None,
                         })
                     } else {
                         None
@@ -308,9 +785,19 @@ impl GrokClient {
     }
 }
 
-impl Default for GrokClient {
-    fn default() -> Self {
-        Self::new()
+impl From<b00t_grok::Chunk> for ChunkResult {
+    fn from(chunk: b00t_grok::Chunk) -> Self {
+        Self {
+            id: chunk.id.to_string(),
+            content: chunk.content,
+            topic: chunk.metadata.topic,
+            tags: chunk.metadata.tags,
+            source: chunk.attribution.filename.or(chunk.attribution.url),
+            created_at: chunk.metadata.created_at,
+            score: chunk.score,
+            confidence: chunk.metadata.confidence,
+            similarity: chunk.similarity,
+        }
     }
 }
 
@@ -320,17 +807,100 @@ mod tests {
 
     #[tokio::test]
     async fn test_grok_client_creation() {
-        let client = GrokClient::new();
+        let client = GrokClient::with_config(GrokConfig::default());
         assert!(client.mcp_client.is_none());
+        assert!(client.local_client.is_none());
+        assert_eq!(client.backend_name(), "none");
+    }
+
+    #[test]
+    fn test_grok_config_resolution() {
+        let workspace = tempfile::TempDir::new().unwrap();
+        let server_dir = workspace.path().join("b00t-grok-py");
+        std::fs::create_dir_all(server_dir.join("python")).unwrap();
+        std::fs::write(server_dir.join("pyproject.toml"), "[project]\n").unwrap();
+        let cwd = workspace.path().join("b00t-cli");
+
+        let mut unified = B00tUnifiedConfig::default();
+        unified.cloud.qdrant = Some(crate::b00t_config::QdrantConfig {
+            endpoint: "https://qdrant.example.com:6333".to_string(),
+            grpc_endpoint: Some("https://qdrant.example.com:6334".to_string()),
+            api_key_keyring_key: "qdrant".to_string(),
+            collections: vec![],
+        });
+
+        let config = GrokConfig::from_lookup(Some(&unified), |_| None, Some(&cwd), None).unwrap();
+        assert_eq!(config.backend, GrokBackend::Auto);
+        assert_eq!(config.qdrant_http_url.as_deref(), Some("https://qdrant.example.com:6333"));
+        assert_eq!(config.qdrant_grpc_url.as_deref(), Some("https://qdrant.example.com:6334"));
+        assert_eq!(config.server_dir, Some(server_dir));
+
+        let env = |key: &str| match key {
+            "B00T_GROK_BACKEND" => Some("rust".to_string()),
+            "QDRANT_URL" => Some("http://qdrant:6333".to_string()),
+            "QDRANT_GRPC_URL" => Some("http://qdrant:6334".to_string()),
+            _ => None,
+        };
+        let config = GrokConfig::from_lookup(Some(&unified), env, None, None).unwrap();
+        assert_eq!(config.backend, GrokBackend::InProcess);
+        assert_eq!(config.qdrant_http_url.as_deref(), Some("http://qdrant:6333"));
+        assert_eq!(config.qdrant_grpc_url.as_deref(), Some("http://qdrant:6334"));
+        assert_eq!(config.server_dir, None);
+
+        let bad = |key: &str| (key == "B00T_GROK_BACKEND").then(|| "python".to_string());
+        assert!(GrokConfig::from_lookup(None, bad, None, None).is_err());
+    }
+
+    #[test]
+    fn test_grounded_prompt_and_citations() {
+        let source = |content: &str, file: &str| ChunkResult {
+            id: file.to_string(),
+            content: content.to_string(),
+            topic: "rust".to_string(),
+            tags: vec![],
+            source: Some(file.to_string()),
+            created_at: "2025-01-01T00:00:00Z".to_string(),
+            score: Some(1.0),
+            confidence: Some(0.7),
+            similarity: Some(0.7),
+        };
+        let sources = vec![source("Qdrant uses port 6334 for gRPC.", "qdrant.md"), source("Use lld.", "linker.md")];
+
+        let prompt = grounded_prompt("Which port?", &sources);
+        assert!(prompt.contains("[1] (source: qdrant.md, date: 2025-01-01T00:00:00Z)\nQdrant uses port 6334"));
+        assert!(prompt.contains("[2] (source: linker.md"));
+        assert!(prompt.ends_with("Question: Which port?\nAnswer:"));
+
+        assert_eq!(cited_indices("Port 6334 [1]. Also [2][1], not [7] or [x].", 2), vec![1, 2]);
+        assert!(cited_indices("no citations", 2).is_empty());
+    }
+
+    #[test]
+    fn test_reranker_from_registry() {
+        use b00t_grok::Reranker;
+
+        let mut registry = ModelRegistry::new();
+        assert!(reranker_from_registry(&registry).is_none());
+
+        let datum: crate::datum_ai_model::AiModelDatum = toml::from_str(r#"
+            provider = "cohere"
+            size = "small"
+            capabilities = ["rerank"]
+            litellm_model = "cohere/rerank-english-v3.0"
+        "#).unwrap();
+        registry.add_model("rerank".to_string(), datum);
+
+        let reranker = reranker_from_registry(&registry).unwrap();
+        assert_eq!(reranker.model_name(), "rerank-english-v3.0");
     }
 
     #[tokio::test]
     async fn test_grok_client_initialization() {
-        let mut client = GrokClient::new();
+        let mut client = GrokClient::with_config(GrokConfig::default());
         
         // This test will fail if b00t-grok-py server is not available
         // That's expected in CI/testing environments
-        let result = client.initialize().await;
+        let result = client.initialize_mcp().await;
         
         // Don't assert success - just verify the method exists and returns a Result
         match result {
@@ -364,6 +934,9 @@ mod tests {
             tags: vec!["test".to_string()],
             source: Some("test.md".to_string()),
             created_at: "2025-01-01T00:00:00Z".to_string(),
+            score: Some(0.8),
+            confidence: Some(0.6),
+            similarity: Some(0.6),
         };
 
         let result = AskResult {
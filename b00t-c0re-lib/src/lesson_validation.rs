@@ -0,0 +1,334 @@
+//! Lesson validation for lfmf
+//!
+//! Enforces the authoring rules from the `lfmf` help text before a lesson reaches the hive:
+//! a short topic and body (counted with tiktoken), affirmative phrasing, and tool wisdom
+//! rather than notes about one repo or machine. An optional AI review catches what the
+//! heuristics can't.
+
+use anyhow::Result;
+use regex::Regex;
+use std::fmt;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use crate::tiktoken::count_tokens;
+use crate::B00tAiClient;
+
+/// Topics must stay under this many tokens
+pub const MAX_TOPIC_TOKENS: usize = 25;
+
+/// Bodies must stay under this many tokens
+pub const MAX_BODY_TOKENS: usize = 250;
+
+/// Which authoring rule a lesson breaks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LessonRule {
+    Format,
+    TopicLength,
+    BodyLength,
+    Affirmative,
+    RepoSpecific,
+    AiReview,
+}
+
+/// A broken rule and how to fix it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LessonIssue {
+    pub rule: LessonRule,
+    pub message: String,
+}
+
+impl fmt::Display for LessonIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+/// Raised by `LfmfSystem::record_lesson` when validation fails
+#[derive(Debug, Clone)]
+pub struct LessonRejected {
+    pub issues: Vec<LessonIssue>,
+}
+
+impl fmt::Display for LessonRejected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Lesson rejected:")?;
+        for issue in &self.issues {
+            writeln!(f, "  - {}", issue)?;
+        }
+        write!(f, "Rewrite the lesson, or pass --force to record it anyway.")
+    }
+}
+
+impl std::error::Error for LessonRejected {}
+
+// 🤓 "avoid"/"instead of" stay allowed: good lessons name the benefit ("to avoid disk bloat")
+const NEGATIVE_PHRASES: &[&str] = &[
+    "don't", "dont", "do not", "never", "shouldn't", "should not", "must not", "mustn't", "can't", "cannot",
+];
+
+// 🤓 Anecdotes ("I'm using", "my repo") describe one person's setup, not the tool.
+//    A bare "i" is left out: it's a flag (`sed -i`) or part of "i.e." far more often
+const FIRST_PERSON: &[&str] = &["i'm", "i've", "my", "me", "we", "our"];
+
+/// NEGATIVE_PHRASES as whole words, so "whenever" or "cannotation" don't trip it
+fn negative_phrase_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        let phrases: Vec<String> = NEGATIVE_PHRASES.iter().map(|p| regex::escape(p)).collect();
+        Regex::new(&format!(r"\b(?:{})\b", phrases.join("|"))).unwrap()
+    })
+}
+
+/// Paths into one person's home; `~/.cargo/config.toml` and other dot-dir configs are generic
+fn local_path_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(
+        r"(?:^|[\s`'(])(/home/[^\s`']+|/Users/[^\s`']+|[A-Za-z]:\\Users\\[^\s`']+|\$\{?USER\}?[^\s`']*|(?:~|/root|\$\{?HOME\}?)/[^.\s`'][^\s`']*)"
+    ).unwrap())
+}
+
+fn source_location_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\b[\w./-]+\.(?:rs|py|ts|tsx|js|go|java|rb|toml|json|ya?ml|md|sh):\d+").unwrap())
+}
+
+fn repo_url_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"(?:github\.com|gitlab\.com|bitbucket\.org)[/:][\w.-]+/[\w.-]+").unwrap())
+}
+
+/// Checks lessons against the lfmf authoring rules
+#[derive(Debug, Clone, Default)]
+pub struct LessonValidator {
+    /// Repo and project names that mark a lesson as repo-specific
+    local_names: Vec<String>,
+    /// User names, flagged only inside home paths (`/home/<user>`, `~<user>`)
+    local_users: Vec<String>,
+    /// Absolute paths (the repo checkout) that mark a lesson as repo-specific
+    local_paths: Vec<String>,
+}
+
+impl LessonValidator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Flag lessons that mention any of these names (case-insensitive, whole words)
+    pub fn with_local_names(mut self, names: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.local_names.extend(names.into_iter()
+            .map(Into::into)
+            .filter(|name: &String| name.len() >= 4));
+        self
+    }
+
+    /// Flag home paths of these users; the bare name is fine ("alice" may well be a tool option)
+    pub fn with_local_users(mut self, users: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.local_users.extend(users.into_iter()
+            .map(Into::into)
+            .filter(|user: &String| !user.is_empty()));
+        self
+    }
+
+    /// Flag lessons that mention any of these paths
+    pub fn with_local_paths(mut self, paths: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.local_paths.extend(paths.into_iter()
+            .map(Into::into)
+            .filter(|path: &String| path.len() > 1));
+        self
+    }
+
+    /// Validator that also flags the enclosing git repo's name and path, and `$USER`'s home paths
+    pub fn for_dir(dir: &Path) -> Self {
+        let repo = dir.ancestors().find(|d| d.join(".git").exists());
+        let name = repo.and_then(|d| d.file_name()).map(|name| name.to_string_lossy().to_string());
+        let path = repo.map(|d| d.to_string_lossy().to_string());
+        let user = std::env::var("USER").ok();
+        Self::new().with_local_names(name).with_local_paths(path).with_local_users(user)
+    }
+
+    /// Heuristic checks; an empty result means the lesson may be recorded
+    pub fn validate(&self, tool: &str, lesson: &str) -> Result<Vec<LessonIssue>> {
+        let Some((topic, body)) = lesson.split_once(':').map(|(t, b)| (t.trim(), b.trim())) else {
+            return Ok(vec![LessonIssue {
+                rule: LessonRule::Format,
+                message: "Lesson must be in '<topic>: <body>' format, e.g. \"container cleanup: Use 'docker system prune' regularly to avoid disk bloat.\"".to_string(),
+            }]);
+        };
+        if topic.is_empty() || body.is_empty() {
+            return Ok(vec![LessonIssue {
+                rule: LessonRule::Format,
+                message: "Topic and body must not be empty.".to_string(),
+            }]);
+        }
+
+        let mut issues = Vec::new();
+
+        let topic_tokens = count_tokens(topic)?;
+        if topic_tokens >= MAX_TOPIC_TOKENS {
+            issues.push(LessonIssue {
+                rule: LessonRule::TopicLength,
+                message: format!("Topic is {} tokens; keep it under {} (tiktoken, not words) - name the symptom, e.g. \"linker error\".",
+                    topic_tokens, MAX_TOPIC_TOKENS),
+            });
+        }
+        let body_tokens = count_tokens(body)?;
+        if body_tokens >= MAX_BODY_TOKENS {
+            issues.push(LessonIssue {
+                rule: LessonRule::BodyLength,
+                message: format!("Body is {} tokens; keep it under {} (tiktoken, not words) - state the fix and why in one or two sentences.",
+                    body_tokens, MAX_BODY_TOKENS),
+            });
+        }
+
+        let lower = body.to_lowercase();
+        if let Some(phrase) = negative_phrase_regex().find(&lower) {
+            issues.push(LessonIssue {
+                rule: LessonRule::Affirmative,
+                message: format!("Body uses negative phrasing (\"{}\"); say what to do instead: 'Do X for Y benefit'.", phrase.as_str()),
+            });
+        }
+
+        let words: Vec<String> = lesson.to_lowercase()
+            .split(|c: char| !(c.is_alphanumeric() || c == '\'' || c == '-' || c == '_'))
+            .filter(|w| !w.is_empty())
+            .map(str::to_string)
+            .collect();
+        if let Some(word) = words.iter().find(|w| FIRST_PERSON.contains(&w.as_str())) {
+            issues.push(LessonIssue {
+                rule: LessonRule::RepoSpecific,
+                message: format!("Lesson reads as an anecdote (\"{}\"); describe how {} behaves for everyone.", word, tool),
+            });
+        }
+
+        let mut specifics: Vec<String> = [local_path_regex(), source_location_regex(), repo_url_regex()].iter()
+            .filter_map(|re| re.captures(lesson))
+            .map(|c| c.get(1).unwrap_or_else(|| c.get(0).unwrap()).as_str().to_string())
+            .collect();
+        let repo_paths: Vec<String> = self.local_paths.iter()
+            .filter(|path| lesson.contains(path.as_str()) && !specifics.iter().any(|s| s.contains(path.as_str())))
+            .cloned()
+            .collect();
+        specifics.extend(repo_paths);
+        specifics.extend(self.local_names.iter()
+            .filter(|name| !name.eq_ignore_ascii_case(tool) && words.contains(&name.to_lowercase()))
+            .cloned());
+        // 🤓 Plain /home paths are already caught above; this adds `~user` and skips repeats
+        let home_paths: Vec<String> = self.local_users.iter()
+            .filter_map(|user| Regex::new(&format!(r"(?i)(?:/home/|/Users/|~){}\b", regex::escape(user))).ok())
+            .filter_map(|re| re.find(lesson).map(|m| m.as_str().to_string()))
+            .filter(|path| !specifics.iter().any(|s| s.contains(path.as_str())))
+            .collect();
+        specifics.extend(home_paths);
+        if !specifics.is_empty() {
+            issues.push(LessonIssue {
+                rule: LessonRule::RepoSpecific,
+                message: format!("Lesson is repo-specific ({}); generalize paths, file locations and project names.",
+                    specifics.join(", ")),
+            });
+        }
+
+        Ok(issues)
+    }
+
+    /// Ask a model whether the lesson follows the rules; returns at most one `AiReview` issue
+    pub async fn review_with_ai(&self, ai: &B00tAiClient, tool: &str, lesson: &str) -> Result<Vec<LessonIssue>> {
+        let reply = ai.complete_with_fallback(&review_prompt(tool, lesson)).await?;
+        Ok(parse_review(&reply).into_iter()
+            .map(|reason| LessonIssue {
+                rule: LessonRule::AiReview,
+                message: format!("AI review: {}", reason),
+            })
+            .collect())
+    }
+}
+
+/// Prompt asking for `OK` or `REJECT: <reason>`
+pub fn review_prompt(tool: &str, lesson: &str) -> String {
+    format!(
+        "You review entries for a shared knowledge base of tool tips.\n\
+         Rules: the entry is '<topic>: <body>'; it must be general wisdom about the tool '{}', \
+         useful to anyone, written affirmatively ('Do X for Y benefit'), and free of repo-specific \
+         paths, file names, project names or personal anecdotes.\n\
+         Reply with exactly `OK`, or `REJECT: <one actionable sentence on how to fix it>`.\n\n\
+         Entry: {}\nVerdict:",
+        tool, lesson
+    )
+}
+
+/// Rejection reason from a review reply, `None` if the model accepted the lesson
+pub fn parse_review(reply: &str) -> Option<String> {
+    let reply = reply.trim().trim_matches('`').trim();
+    let (verdict, reason) = reply.split_once(':').unwrap_or((reply, ""));
+    verdict.trim().eq_ignore_ascii_case("reject")
+        .then(|| reason.trim().to_string())
+        .map(|reason| if reason.is_empty() { "lesson does not follow the lfmf rules".to_string() } else { reason })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(issues: &[LessonIssue]) -> Vec<LessonRule> {
+        issues.iter().map(|i| i.rule).collect()
+    }
+
+    #[test]
+    fn test_good_lessons_pass() {
+        let validator = LessonValidator::new().with_local_names(["dotfiles"]);
+        for (tool, lesson) in [
+            ("just", "modules & workdir: Use modules and workdir to avoid cd; keeps recipes portable and context-safe."),
+            ("docker", "container cleanup: Use 'docker system prune' regularly to avoid disk bloat."),
+            ("git", "atomic commits: Commit small, focused changes for easier review and rollback."),
+            ("cargo", "offline builds: Set `~/.cargo/config.toml` net.offline = true for air-gapped machines."),
+            ("ssh", "key permissions: chmod 600 /root/.ssh/id_ed25519 so ssh accepts the key."),
+        ] {
+            assert_eq!(validator.validate(tool, lesson).unwrap(), vec![], "{}", lesson);
+        }
+    }
+
+    #[test]
+    fn test_bad_lessons_rejected() {
+        let validator = LessonValidator::new().with_local_names(["dotfiles"]);
+
+        assert_eq!(rules(&validator.validate("just", "cd: I always use cd in my recipes.").unwrap()),
+            vec![LessonRule::RepoSpecific]);
+        assert_eq!(rules(&validator.validate("rust", "linking: Don't forget to set RUSTFLAGS").unwrap()),
+            vec![LessonRule::Affirmative]);
+        assert_eq!(rules(&validator.validate("git", "no colon here").unwrap()), vec![LessonRule::Format]);
+
+        let specific = validator.validate("cargo", "build cache: Delete /home/alice/dotfiles/target when dotfiles builds stall").unwrap();
+        assert_eq!(rules(&specific), vec![LessonRule::RepoSpecific]);
+        assert!(specific[0].message.contains("/home/alice/dotfiles/target") && specific[0].message.contains("dotfiles"));
+        assert_eq!(rules(&validator.validate("rust", "borrowck: Clone the Arc as in src/main.rs:42 to satisfy the checker").unwrap()),
+            vec![LessonRule::RepoSpecific]);
+
+        // $USER only counts inside a home path; "I" alone and "never" inside a word are fine
+        let validator = validator.with_local_users(["alice"]);
+        assert_eq!(rules(&validator.validate("ssh", "agent forwarding: Run ssh-add ~alice/.ssh/id_ed25519 first").unwrap()),
+            vec![LessonRule::RepoSpecific]);
+        assert_eq!(validator.validate("sed", "in-place edit: Use sed -i to edit files, i.e. without a temp copy.").unwrap(), vec![]);
+        assert_eq!(validator.validate("git", "alice remote: Use the alice remote name whenever forks diverge.").unwrap(), vec![]);
+        for lesson in [
+            "stale build: Delete ~/projects/app/target when builds stall.",
+            "stale build: Delete $USER/target when builds stall.",
+            "stale build: Delete /srv/checkouts/app/target when builds stall.",
+        ] {
+            let validator = validator.clone().with_local_paths(["/srv/checkouts/app"]);
+            assert_eq!(rules(&validator.validate("cargo", lesson).unwrap()), vec![LessonRule::RepoSpecific], "{}", lesson);
+        }
+
+        let long_topic = "word ".repeat(30);
+        assert_eq!(rules(&validator.validate("git", &format!("{}: Use rebase for linear history", long_topic)).unwrap()),
+            vec![LessonRule::TopicLength]);
+    }
+
+    #[test]
+    fn test_parse_review() {
+        assert_eq!(parse_review("OK"), None);
+        assert_eq!(parse_review("`ok`"), None);
+        assert_eq!(parse_review("REJECT: mentions a private path"), Some("mentions a private path".to_string()));
+        assert!(parse_review("reject").is_some());
+        assert!(review_prompt("just", "a: b").contains("Entry: a: b"));
+    }
+}
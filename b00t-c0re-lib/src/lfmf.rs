@@ -3,15 +3,19 @@
 //! Shared implementation of the syntax therapist system that provides:
 //! - Recording tribal knowledge from failures/lessons learned
 //! - Vector database integration with b00t-grok for semantic search
+//!   (Qdrant, or a local vector store file when Qdrant is unreachable)
 //! - Filesystem fallback for immediate functionality
 //! - Configurable advice retrieval with TOML/env configuration
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::{env, fs, path::Path};
+use std::collections::HashSet;
+use std::io::Write;
+use std::{env, fs, path::{Path, PathBuf}};
 use chrono::{DateTime, Utc};
 
-use crate::grok::GrokClient;
+use crate::grok::{GrokClient, GrokConfig};
+use crate::lesson_validation::{LessonRejected, LessonValidator};
 
 /// Configuration for advice/LFMF system
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -75,23 +79,114 @@ fn default_learn_dir() -> String {
     "learn".to_string()
 }
 
+/// Lessons whose normalized token sets overlap this much are the same lesson
+pub const DUPLICATE_SIMILARITY: f32 = 0.8;
+
+/// Unconfirmed lessons lose half their confidence every this many days
+pub const UNCONFIRMED_HALF_LIFE_DAYS: f32 = 90.0;
+
+const LESSON_LEDGER_VERSION: u32 = 1;
+
 /// A lesson learned from failures - stored in both vector DB and filesystem
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Lesson {
+    /// Stable id derived from tool, topic and content (see `Lesson::stable_id`)
+    #[serde(default)]
+    pub id: String,
     pub tool: String,
     pub topic: String,
     pub content: String,
     pub timestamp: DateTime<Utc>,
+    /// Effective confidence when returned by `rank_advice`/`list`; derived from votes and age
     pub confidence: Option<f32>,
     pub error_pattern: Option<String>,
     pub solution: Option<String>,
     pub tags: Vec<String>,
+    /// Upvotes, including re-recordings of the same lesson
+    #[serde(default)]
+    pub upvotes: u32,
+    #[serde(default)]
+    pub downvotes: u32,
+    /// Last upvote or re-recording; unconfirmed lessons decay from `timestamp`
+    #[serde(default)]
+    pub last_confirmed: Option<DateTime<Utc>>,
+}
+
+impl Lesson {
+    /// 12 hex chars of sha256 over the normalized lesson, so the same lesson gets the same id everywhere
+    pub fn stable_id(tool: &str, topic: &str, content: &str) -> String {
+        let key = format!("{}\n{}\n{}", tool.to_lowercase(), normalize_lesson_text(topic), normalize_lesson_text(content));
+        b00t_grok::ingest::content_hash(&key)[..12].to_string()
+    }
+
+    /// Laplace-smoothed vote ratio; lessons nobody confirmed halve every `UNCONFIRMED_HALF_LIFE_DAYS`
+    pub fn effective_confidence(&self, now: DateTime<Utc>) -> f32 {
+        let votes = (self.upvotes + 1) as f32 / (self.upvotes + self.downvotes + 2) as f32;
+        if self.last_confirmed.is_some() {
+            return votes;
+        }
+        let age_days = (now - self.timestamp).num_seconds().max(0) as f32 / 86_400.0;
+        votes * 0.5_f32.powf(age_days / UNCONFIRMED_HALF_LIFE_DAYS)
+    }
+
+    fn summary(&self, now: DateTime<Utc>) -> String {
+        format!("[{}] {} (id {}, confidence {:.2}, +{}/-{})", self.topic, self.content, self.id,
+            self.effective_confidence(now), self.upvotes, self.downvotes)
+    }
+
+    fn text(&self) -> String {
+        format!("{} {}", self.topic, self.content)
+    }
+}
+
+/// Lowercase alphanumeric words separated by single spaces
+pub fn normalize_lesson_text(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Jaccard overlap of the normalized word sets (1.0 = same words)
+pub fn lesson_similarity(a: &str, b: &str) -> f32 {
+    let a = normalize_lesson_text(a);
+    let b = normalize_lesson_text(b);
+    let a: HashSet<&str> = a.split(' ').filter(|w| !w.is_empty()).collect();
+    let b: HashSet<&str> = b.split(' ').filter(|w| !w.is_empty()).collect();
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    a.intersection(&b).count() as f32 / a.union(&b).count() as f32
+}
+
+/// What `record_lesson` did
+#[derive(Debug, Clone)]
+pub enum RecordOutcome {
+    Recorded(Lesson),
+    /// A near-duplicate existed; it was upvoted instead of storing a copy
+    Duplicate(Lesson),
+}
+
+/// Tool name as a single file name, so `--tool ../x` can't escape the learn directory
+fn tool_file_stem(tool: &str) -> String {
+    tool.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') { c } else { '_' })
+        .collect()
+}
+
+/// Structured lessons for one tool, kept at `<learn_dir>/.lfmf/<tool>.json`
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct LessonLedger {
+    version: u32,
+    lessons: Vec<Lesson>,
 }
 
 /// LFMF system for recording and retrieving tribal knowledge
 pub struct LfmfSystem {
     config: LfmfConfig,
     grok_client: Option<GrokClient>,
+    validator: Option<LessonValidator>, // 🤓 None = --force
 }
 
 impl LfmfSystem {
@@ -100,9 +195,16 @@ impl LfmfSystem {
         Self {
             config,
             grok_client: None,
+            validator: Some(LessonValidator::new()),
         }
     }
 
+    /// Check lessons with `validator` before recording; `None` records anything (`--force`)
+    pub fn with_validator(mut self, validator: Option<LessonValidator>) -> Self {
+        self.validator = validator;
+        self
+    }
+
     /// Load configuration from TOML file or environment variables
     pub fn load_config(path: &str) -> Result<LfmfConfig> {
         let config_path = Path::new(path).join("lfmf.toml");
@@ -117,7 +219,7 @@ impl LfmfSystem {
         };
 
         // Override with environment variables if present
-        if let Ok(url) = env::var("QDRANT_URL") {
+        if let Ok(url) = env::var("QDRANT_GRPC_URL") {
             config.qdrant.url = url;
         }
         
@@ -138,14 +240,14 @@ impl LfmfSystem {
 
     /// Initialize the system with vector database connection
     pub async fn initialize(&mut self) -> Result<()> {
-        let mut client = GrokClient::new();
-        
-        // Set up environment variables for the grok client
+        // 🤓 lfmf.toml's url is the Rust client's gRPC endpoint
+        let mut grok_config = GrokConfig::from_env()?;
+        grok_config.qdrant_grpc_url = Some(self.config.qdrant.url.clone());
         if let Some(ref api_key) = self.config.qdrant.api_key {
-            unsafe { std::env::set_var("QDRANT_API_KEY", api_key); }
+            grok_config.qdrant_api_key = Some(api_key.clone());
         }
-        unsafe { std::env::set_var("QDRANT_URL", &self.config.qdrant.url); }
-        
+        let mut client = GrokClient::with_config(grok_config);
+
         match client.initialize().await {
             Ok(_) => {
                 self.grok_client = Some(client);
@@ -159,8 +261,29 @@ impl LfmfSystem {
     }
 
     /// Record a lesson learned from failure
-    pub async fn record_lesson(&mut self, tool: &str, lesson: &str) -> Result<()> {
+    ///
+    /// Fails with `LessonRejected` if the validator finds issues. A near-duplicate of an existing
+    /// lesson (same id, or `DUPLICATE_SIMILARITY` word overlap) confirms that lesson instead of adding a copy.
+    pub async fn record_lesson(&mut self, tool: &str, lesson: &str) -> Result<RecordOutcome> {
+        if let Some(validator) = &self.validator {
+            let issues = validator.validate(tool, lesson)?;
+            if !issues.is_empty() {
+                return Err(LessonRejected { issues }.into());
+            }
+        }
+
         let lesson_obj = self.parse_lesson(tool, lesson)?;
+        let mut lessons = self.load_lessons(tool)?;
+
+        let duplicate = lessons.iter_mut().find(|existing| existing.id == lesson_obj.id
+            || lesson_similarity(&existing.text(), &lesson_obj.text()) >= DUPLICATE_SIMILARITY);
+        if let Some(existing) = duplicate {
+            existing.upvotes += 1;
+            existing.last_confirmed = Some(Utc::now());
+            let existing = existing.clone();
+            self.save_lessons(tool, &lessons)?;
+            return Ok(RecordOutcome::Duplicate(existing));
+        }
 
         // Try to store in vector database first
         if let Some(ref client) = self.grok_client {
@@ -170,9 +293,39 @@ impl LfmfSystem {
         }
 
         // Always store in filesystem as backup
+        lessons.push(lesson_obj.clone());
+        self.save_lessons(tool, &lessons)?;
         self.store_in_filesystem(&lesson_obj)?;
-        
-        Ok(())
+
+        Ok(RecordOutcome::Recorded(lesson_obj))
+    }
+
+    /// Upvote (confirm) or downvote a lesson by id or unique id prefix
+    pub fn vote(&self, tool: &str, id: &str, up: bool) -> Result<Lesson> {
+        let mut lessons = self.load_lessons(tool)?;
+        let matches: Vec<usize> = lessons.iter().enumerate()
+            .filter(|(_, lesson)| !id.is_empty() && lesson.id.starts_with(id))
+            .map(|(i, _)| i)
+            .collect();
+
+        let index = match matches.as_slice() {
+            [index] => *index,
+            [] => anyhow::bail!("No lesson with id '{}' for tool '{}'. Find ids with: advice {} list", id, tool, tool),
+            _ => anyhow::bail!("Lesson id '{}' is ambiguous ({} matches); use more characters", id, matches.len()),
+        };
+
+        let lesson = &mut lessons[index];
+        if up {
+            lesson.upvotes += 1;
+            lesson.last_confirmed = Some(Utc::now());
+        } else {
+            lesson.downvotes += 1;
+        }
+        let mut lesson = lesson.clone();
+        self.save_lessons(tool, &lessons)?;
+
+        lesson.confidence = Some(lesson.effective_confidence(Utc::now()));
+        Ok(lesson)
     }
 
     /// Parse lesson string into structured format
@@ -187,14 +340,18 @@ impl LfmfSystem {
         };
 
         Ok(Lesson {
+            id: Lesson::stable_id(tool, &topic, &content),
             tool: tool.to_string(),
             topic: topic.clone(),
             content: content.clone(),
             timestamp,
-            confidence: Some(1.0), // High confidence for manually recorded lessons
+            confidence: None, // 🤓 Earned through votes, see effective_confidence
             error_pattern: Some(topic),
             solution: Some(content),
             tags: vec![tool.to_string(), "lfmf".to_string()],
+            upvotes: 0,
+            downvotes: 0,
+            last_confirmed: None,
         })
     }
 
@@ -211,10 +368,10 @@ impl LfmfSystem {
         Ok(())
     }
 
-    /// Store lesson in filesystem
+    /// Append lesson to the human-readable `<tool>.md`
     fn store_in_filesystem(&self, lesson: &Lesson) -> Result<()> {
         let learn_dir = Path::new(&self.config.filesystem.learn_dir);
-        let tool_file = learn_dir.join(format!("{}.md", lesson.tool));
+        let tool_file = learn_dir.join(format!("{}.md", tool_file_stem(&lesson.tool)));
 
         // Ensure directory exists
         if let Some(parent) = tool_file.parent() {
@@ -223,68 +380,161 @@ impl LfmfSystem {
         }
 
         // Format lesson entry
-        let entry = format!("---\n{}: {}\n", lesson.topic, lesson.content);
+        let entry = format!("\n---\n{}: {}\n", lesson.topic, lesson.content);
 
-        // Append to tool file
-        fs::write(&tool_file, if tool_file.exists() {
-            let existing = fs::read_to_string(&tool_file)?;
-            format!("{}\n{}", existing, entry)
-        } else {
-            entry
-        })
-        .context(format!("Failed to write lesson to {}", tool_file.display()))?;
+        let mut file = fs::OpenOptions::new().create(true).append(true).open(&tool_file)
+            .context(format!("Failed to open {}", tool_file.display()))?;
+        file.write_all(entry.as_bytes())
+            .context(format!("Failed to write lesson to {}", tool_file.display()))?;
+
+        Ok(())
+    }
+
+    fn ledger_path(&self, tool: &str) -> PathBuf {
+        Path::new(&self.config.filesystem.learn_dir).join(".lfmf").join(format!("{}.json", tool_file_stem(tool)))
+    }
+
+    /// Structured lessons for `tool`, migrating `---` entries from `<tool>.md` on first use
+    fn load_lessons(&self, tool: &str) -> Result<Vec<Lesson>> {
+        let ledger_path = self.ledger_path(tool);
+        if ledger_path.exists() {
+            let content = fs::read_to_string(&ledger_path)
+                .context(format!("Failed to read lessons from {}", ledger_path.display()))?;
+            let ledger: LessonLedger = serde_json::from_str(&content)
+                .context(format!("Failed to parse {}", ledger_path.display()))?;
+            return Ok(ledger.lessons);
+        }
+
+        let tool_file = Path::new(&self.config.filesystem.learn_dir).join(format!("{}.md", tool_file_stem(tool)));
+        if !tool_file.exists() {
+            return Ok(Vec::new());
+        }
+
+        // 🦨 Legacy entries carry no timestamp; the file mtime is the best guess for decay
+        let timestamp = fs::metadata(&tool_file).and_then(|m| m.modified())
+            .map(DateTime::<Utc>::from)
+            .unwrap_or_else(|_| Utc::now());
+        let content = fs::read_to_string(&tool_file)?;
+
+        let mut lessons: Vec<Lesson> = Vec::new();
+        // Text before the first `---` is the tool's learn doc, not a lesson
+        for entry in content.split("---\n").skip(1) {
+            let Ok(mut lesson) = self.parse_lesson(tool, entry.trim()) else { continue };
+            if entry.trim().is_empty() || lessons.iter().any(|l| l.id == lesson.id) {
+                continue;
+            }
+            lesson.timestamp = timestamp;
+            lessons.push(lesson);
+        }
+        Ok(lessons)
+    }
+
+    fn save_lessons(&self, tool: &str, lessons: &[Lesson]) -> Result<()> {
+        let ledger_path = self.ledger_path(tool);
+        if let Some(parent) = ledger_path.parent() {
+            fs::create_dir_all(parent).context("Failed to create lesson ledger directory")?;
+        }
 
+        let ledger = LessonLedger { version: LESSON_LEDGER_VERSION, lessons: lessons.to_vec() };
+        let tmp = ledger_path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_string_pretty(&ledger)?)
+            .context(format!("Failed to write {}", tmp.display()))?;
+        fs::rename(&tmp, &ledger_path)
+            .context(format!("Failed to write {}", ledger_path.display()))?;
         Ok(())
     }
 
     /// Get advice for a specific error/query
     pub async fn get_advice(&mut self, tool: &str, query: &str, count: Option<usize>) -> Result<Vec<String>> {
         let max_results = count.unwrap_or(5);
+        let ranked = self.rank_advice(tool, query, max_results).await?;
+
+        if ranked.is_empty() {
+            if !self.ledger_path(tool).exists() && !Path::new(&self.config.filesystem.learn_dir).join(format!("{}.md", tool_file_stem(tool))).exists() {
+                return Ok(vec![format!("💡 No lessons found for tool '{}'. Record lessons with: lfmf {} \"<topic>: <lesson>\"", tool, tool)]);
+            }
+            return Ok(vec![format!("No similar patterns found for: {}\n💡 Record this pattern with: lfmf {} \"<topic>: <solution>\"", query, tool)]);
+        }
+
+        let now = Utc::now();
+        Ok(ranked.into_iter()
+            .map(|(lesson, score)| format!("Match: {:.2} - {}", score, lesson.summary(now)))
+            .collect())
+    }
+
+    /// Lessons relevant to `query`, ranked by relevance × effective confidence
+    ///
+    /// Relevance comes from the vector database when available, else from word matching.
+    pub async fn rank_advice(&self, tool: &str, query: &str, max_results: usize) -> Result<Vec<(Lesson, f32)>> {
+        let lessons = self.load_lessons(tool)?;
 
-        // Try vector database first if available
+        let mut relevance = Vec::new();
         if let Some(ref client) = self.grok_client {
-            match self.get_vector_advice(client, tool, query, max_results).await {
-                Ok(advice) => return Ok(advice),
+            match self.get_vector_advice(client, tool, query, &lessons, max_results).await {
+                Ok(found) => relevance = found,
                 Err(e) => {
                     eprintln!("🔄 Vector database query failed: {}, using filesystem fallback", e);
                 }
             }
         }
+        // Nothing indexed yet, lessons may only exist on disk
+        if relevance.is_empty() {
+            relevance = Self::text_relevance(query, &lessons);
+        }
 
-        // Fallback to filesystem
-        self.get_filesystem_advice(tool, query, max_results)
-    }
-
-    /// Get advice from vector database
-    async fn get_vector_advice(&self, client: &GrokClient, tool: &str, query: &str, max_results: usize) -> Result<Vec<String>> {
-        let results = client.ask(query, Some(tool), Some(max_results)).await?;
-        
-        Ok(results.results.into_iter()
-            .take(max_results)
-            .map(|r| r.content)
-            .collect())
+        let now = Utc::now();
+        let mut ranked: Vec<(Lesson, f32)> = relevance.into_iter()
+            .map(|(mut lesson, relevance)| {
+                let confidence = lesson.effective_confidence(now);
+                lesson.confidence = Some(confidence);
+                (lesson, relevance * confidence)
+            })
+            .collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+        ranked.truncate(max_results);
+        Ok(ranked)
     }
 
-    /// Get advice from filesystem using pattern matching
-    fn get_filesystem_advice(&self, tool: &str, query: &str, max_results: usize) -> Result<Vec<String>> {
-        let learn_dir = Path::new(&self.config.filesystem.learn_dir);
-        let tool_file = learn_dir.join(format!("{}.md", tool));
-
-        if !tool_file.exists() {
-            return Ok(vec![format!("💡 No lessons found for tool '{}'. Record lessons with: lfmf {} \"<topic>: <lesson>\"", tool, tool)]);
+    /// Vector hits mapped back to ledger lessons via their stable id
+    async fn get_vector_advice(&self, client: &GrokClient, tool: &str, query: &str, lessons: &[Lesson], max_results: usize) -> Result<Vec<(Lesson, f32)>> {
+        // 🤓 Over-fetch so confidence can reorder beyond the top vector hits
+        let results = client.ask(query, Some(tool), Some(max_results * 3)).await?;
+
+        let mut found: Vec<(Lesson, f32)> = Vec::new();
+        for result in results.results {
+            let field = |name: &str| result.content.lines()
+                .find_map(|line| line.strip_prefix(name))
+                .map(|v| v.trim().to_string());
+            let (Some(topic), Some(solution)) = (field("Topic:"), field("Solution:")) else { continue };
+
+            let id = Lesson::stable_id(tool, &topic, &solution);
+            let lesson = match lessons.iter().find(|l| l.id == id) {
+                Some(lesson) => lesson.clone(),
+                None => {
+                    let mut lesson = self.parse_lesson(tool, &format!("{}: {}", topic, solution))?;
+                    lesson.timestamp = DateTime::parse_from_rfc3339(&result.created_at)
+                        .map(|t| t.with_timezone(&Utc))
+                        .unwrap_or(lesson.timestamp);
+                    lesson
+                }
+            };
+            if found.iter().any(|(l, _)| l.id == lesson.id) {
+                continue;
+            }
+            // 🤓 Relevance is the retrieval score only; rank_advice applies the lesson's own confidence
+            let Some(relevance) = result.similarity.or(result.score) else { continue };
+            found.push((lesson, relevance));
         }
+        Ok(found)
+    }
 
-        let content = fs::read_to_string(&tool_file)
-            .context(format!("Failed to read lessons from {}", tool_file.display()))?;
-
-        let lessons: Vec<&str> = content.split("---\n").filter(|s| !s.trim().is_empty()).collect();
+    /// Word-overlap relevance of each lesson to `query`
+    fn text_relevance(query: &str, lessons: &[Lesson]) -> Vec<(Lesson, f32)> {
         let query_lower = query.to_lowercase();
+        let query_words: Vec<&str> = query_lower.split_whitespace().collect();
 
-        let mut matches: Vec<(String, f32)> = Vec::new();
-
-        // Search for similar patterns
-        for lesson in &lessons {
-            let lesson_lower = lesson.to_lowercase();
+        lessons.iter().filter_map(|lesson| {
+            let lesson_lower = lesson.text().to_lowercase();
             let mut score = 0.0;
 
             // Direct match bonus
@@ -293,87 +543,35 @@ impl LfmfSystem {
             }
 
             // Word-level matching
-            let query_words: Vec<&str> = query_lower.split_whitespace().collect();
             let lesson_words: Vec<&str> = lesson_lower.split_whitespace().collect();
-
-            let mut word_matches = 0;
-            for query_word in &query_words {
-                if query_word.len() > 2 {
-                    for lesson_word in &lesson_words {
-                        if lesson_word.contains(query_word) || query_word.contains(lesson_word) {
-                            word_matches += 1;
-                            break;
-                        }
-                    }
-                }
-            }
+            let word_matches = query_words.iter()
+                .filter(|query_word| query_word.len() > 2)
+                .filter(|query_word| lesson_words.iter()
+                    .any(|lesson_word| lesson_word.contains(*query_word) || query_word.contains(lesson_word)))
+                .count();
 
             if word_matches > 0 {
                 score += (word_matches as f32) / (query_words.len() as f32);
             }
 
-            if score > 0.0 {
-                matches.push((lesson.trim().to_string(), score));
-            }
-        }
-
-        if matches.is_empty() {
-            return Ok(vec![format!("No similar patterns found for: {}\n💡 Record this pattern with: lfmf {} \"<topic>: <solution>\"", query, tool)]);
-        }
-
-        // Sort by score descending and format results
-        matches.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
-
-        Ok(matches.into_iter()
-            .take(max_results)
-            .map(|(lesson, score)| {
-                if let Some((topic, body)) = lesson.split_once(':') {
-                    format!("Match: {:.2} - [{}] {}", score, topic.trim(), body.trim())
-                } else {
-                    format!("Match: {:.2} - {}", score, lesson)
-                }
-            })
-            .collect())
+            (score > 0.0).then(|| (lesson.clone(), score))
+        }).collect()
     }
 
-    /// List all lessons for a tool
+    /// List all lessons for a tool, most trusted first
     pub async fn list_lessons(&mut self, tool: &str, count: Option<usize>) -> Result<Vec<String>> {
         let max_results = count.unwrap_or(10);
 
-        // Try vector database first
-        if let Some(ref client) = self.grok_client {
-            match client.ask(&format!("lessons for {}", tool), Some(tool), Some(max_results)).await {
-                Ok(results) => {
-                    return Ok(results.results.into_iter()
-                        .take(max_results)
-                        .map(|r| r.content)
-                        .collect());
-                },
-                Err(_) => {} // Fall through to filesystem
-            }
-        }
-
-        // Fallback to filesystem
-        let learn_dir = Path::new(&self.config.filesystem.learn_dir);
-        let tool_file = learn_dir.join(format!("{}.md", tool));
-
-        if !tool_file.exists() {
+        let now = Utc::now();
+        let mut lessons = self.load_lessons(tool)?;
+        if lessons.is_empty() {
             return Ok(vec![format!("No lessons found for tool '{}'", tool)]);
         }
-
-        let content = fs::read_to_string(&tool_file)?;
-        let lessons: Vec<&str> = content.split("---\n").filter(|s| !s.trim().is_empty()).collect();
+        lessons.sort_by(|a, b| b.effective_confidence(now).total_cmp(&a.effective_confidence(now)));
 
         Ok(lessons.into_iter()
             .take(max_results)
-            .map(|lesson| {
-                let lesson = lesson.trim();
-                if let Some((topic, body)) = lesson.split_once(':') {
-                    format!("[{}] {}", topic.trim(), body.trim())
-                } else {
-                    lesson.to_string()
-                }
-            })
+            .map(|lesson| lesson.summary(now))
             .collect())
     }
 
@@ -390,7 +588,9 @@ impl LfmfSystem {
         println!("learn_dir = \"learn\"");
         println!();
         println!("Environment variables:");
-        println!("export QDRANT_URL=http://localhost:6334");
+        println!("export QDRANT_GRPC_URL=http://localhost:6334");
+        println!("export B00T_GROK_STORE=auto  # or qdrant, local");
+        println!("export B00T_GROK_STORE_PATH=~/.b00t/grok_vectors.json");
         println!("export B00T_LEARN_DIR=~/.dotfiles/learn");
     }
 }
@@ -430,6 +630,92 @@ mod tests {
         assert!(content.contains("linker error: Check LD_LIBRARY_PATH"));
     }
 
+    #[test]
+    fn test_tool_paths_stay_in_learn_dir() {
+        let mut config = LfmfConfig::default();
+        config.filesystem.learn_dir = "/tmp/learn".to_string();
+        let system = LfmfSystem::new(config);
+
+        assert_eq!(system.ledger_path("b00t-cli"), Path::new("/tmp/learn/.lfmf/b00t-cli.json"));
+        assert_eq!(system.ledger_path("../../etc/x"), Path::new("/tmp/learn/.lfmf/.._.._etc_x.json"));
+    }
+
+    #[tokio::test]
+    async fn test_dedup_votes_and_ranking() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = LfmfConfig::default();
+        config.filesystem.learn_dir = temp_dir.path().join("learn").to_string_lossy().to_string();
+        let mut system = LfmfSystem::new(config);
+
+        let RecordOutcome::Recorded(first) = system.record_lesson("rust", "linker error: Check LD_LIBRARY_PATH before cargo build").await.unwrap() else {
+            panic!("first recording must be stored");
+        };
+        assert_eq!(first.id, Lesson::stable_id("rust", "Linker Error", "check ld_library_path before cargo build"));
+
+        // Near-duplicate confirms the existing lesson instead of adding a copy
+        let outcome = system.record_lesson("rust", "Linker error: check LD_LIBRARY_PATH before cargo build!").await.unwrap();
+        assert!(matches!(outcome, RecordOutcome::Duplicate(ref l) if l.id == first.id && l.upvotes == 1));
+        system.record_lesson("rust", "linker flags: Set RUSTFLAGS for the linker search path").await.unwrap();
+        assert_eq!(system.load_lessons("rust").unwrap().len(), 2);
+
+        // <tool>.md is appended to, never rewritten
+        let md = fs::read_to_string(temp_dir.path().join("learn/rust.md")).unwrap();
+        assert_eq!(md.matches("---").count(), 2);
+
+        // Downvotes sink a lesson below a confirmed one
+        let flags = system.load_lessons("rust").unwrap()[1].id.clone();
+        system.vote("rust", &first.id[..6], true).unwrap();
+        let downvoted = system.vote("rust", &flags, false).unwrap();
+        assert!(downvoted.confidence.unwrap() < 0.5);
+        let ranked = system.rank_advice("rust", "linker", 5).await.unwrap();
+        assert_eq!(ranked[0].0.id, first.id);
+        assert!(system.vote("rust", "zzz", true).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_record_rejects_invalid_unless_forced() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = LfmfConfig::default();
+        config.filesystem.learn_dir = temp_dir.path().to_string_lossy().to_string();
+
+        let mut system = LfmfSystem::new(config.clone());
+        let err = system.record_lesson("docker", "disk full: My disk filled up once.").await.unwrap_err();
+        assert!(err.downcast_ref::<LessonRejected>().is_some());
+        assert!(!temp_dir.path().join("docker.md").exists());
+
+        let mut forced = LfmfSystem::new(config).with_validator(None);
+        assert!(matches!(forced.record_lesson("docker", "disk full: My disk filled up once.").await.unwrap(),
+            RecordOutcome::Recorded(_)));
+    }
+
+    #[test]
+    fn test_confidence_decay() {
+        let system = LfmfSystem::new(LfmfConfig::default());
+        let mut lesson = system.parse_lesson("just", "recipes: Quote arguments").unwrap();
+        let now = lesson.timestamp;
+        assert!((lesson.effective_confidence(now) - 0.5).abs() < 1e-6);
+
+        let later = now + chrono::Duration::days(UNCONFIRMED_HALF_LIFE_DAYS as i64);
+        assert!((lesson.effective_confidence(later) - 0.25).abs() < 1e-3);
+
+        // Confirmed lessons don't decay
+        lesson.upvotes = 1;
+        lesson.last_confirmed = Some(now);
+        assert!((lesson.effective_confidence(later) - 2.0 / 3.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_legacy_markdown_migration() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("just.md"),
+            "# just\n\nIntro docs: not a lesson\n\n---\nTemplate syntax: Use grep/cut instead\n\n---\nDuplicate recipe: Rename to name-legacy\n").unwrap();
+        let mut config = LfmfConfig::default();
+        config.filesystem.learn_dir = temp_dir.path().to_string_lossy().to_string();
+
+        let lessons = LfmfSystem::new(config).load_lessons("just").unwrap();
+        assert_eq!(lessons.iter().map(|l| l.topic.as_str()).collect::<Vec<_>>(), vec!["Template syntax", "Duplicate recipe"]);
+    }
+
     #[test]
     fn test_config_loading() {
         let temp_dir = TempDir::new().unwrap();
@@ -31,6 +31,9 @@ pub mod context;
 pub mod utils;
 pub mod redis;
 pub mod agent_coordination;
+pub mod agent_voting;
+pub mod agent_tasks;
+pub mod agent_selection;
 pub mod grok;
 pub mod learn;
 pub mod datum_ai_model;
@@ -39,16 +42,19 @@ pub mod rhai_engine;
 pub mod ai_client;
 pub mod b00t_config;
 pub mod secret_validation;
+pub mod lesson_validation;
+pub mod tiktoken;
 
 // Re-export commonly used types
 pub use template::TemplateRenderer;
 pub use context::B00tContext;
-pub use grok::{GrokClient, DigestResult, AskResult, LearnResult, ChunkResult, ChunkSummary};
-pub use lfmf::{LfmfSystem, LfmfConfig, Lesson};
+pub use grok::{GrokClient, GrokBackend, GrokConfig, DigestResult, AskResult, LearnResult, ChunkResult, ChunkSummary, GroundedAnswer, Citation};
+pub use lfmf::{LfmfSystem, LfmfConfig, Lesson, RecordOutcome};
 pub use rhai_engine::RhaiEngine;
-pub use ai_client::{B00tAiClient, AiClientConfig, AiProviderConfig, ChatMessage};
+pub use ai_client::{B00tAiClient, AiClientConfig, AiProviderConfig, AiCompletion, ChatMessage, TokenUsage, UsageGuard};
 pub use b00t_config::{B00tUnifiedConfig, UserConfig, CloudServicesConfig, AiConfiguration};
 pub use secret_validation::{SecretValidator, CloudflareValidation, AwsValidation, QdrantValidation};
+pub use lesson_validation::{LessonValidator, LessonIssue, LessonRule, LessonRejected};
 
 /// Common configuration structure for b00t components
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -131,6 +131,18 @@ impl RedisComms {
         })
     }
 
+    /// Connect to the Redis at `url` (e.g. a `redis://` transport URL)
+    pub fn from_url(url: &str, agent_id: String) -> B00tResult<Self> {
+        let client = Client::open(url)
+            .with_context(|| format!("Failed to create Redis client for {}", url))?;
+
+        Ok(Self {
+            client,
+            config: RedisConfig::default(),
+            agent_id,
+        })
+    }
+
     /// Get Redis connection
     fn get_connection(&self) -> B00tResult<Connection> {
         let conn = self.client.get_connection()
@@ -370,6 +382,17 @@ impl RedisComms {
             .context("Failed to HGETALL from Redis hash")?;
         Ok(result)
     }
+
+    /// Delete hash fields
+    pub fn hdel(&self, key: &str, fields: &[String]) -> B00tResult<i32> {
+        let mut conn = self.get_connection()?;
+        let result: i32 = redis::cmd("HDEL")
+            .arg(key)
+            .arg(fields)
+            .query(&mut conn)
+            .context("Failed to HDEL from Redis hash")?;
+        Ok(result)
+    }
 }
 
 /// Redis-based session storage backend
@@ -0,0 +1,41 @@
+//! Token counting shared across the b00t ecosystem
+//!
+//! Wraps tiktoken's `o200k_base` encoding so budget tracking, chunking and
+//! lesson validation all agree on what a "token" is.
+
+use anyhow::anyhow;
+use tiktoken_rs::CoreBPE;
+
+use crate::B00tResult;
+
+// 🤓 One BPE per process, shared with the b00t-grok chunker
+fn bpe() -> B00tResult<&'static CoreBPE> {
+    b00t_grok::chunker::tokenizer().ok_or_else(|| anyhow!("Failed to load tokenizer"))
+}
+
+/// Count tokens in `text` using the o200k tokenizer
+pub fn count_tokens(text: &str) -> B00tResult<usize> {
+    Ok(bpe()?.encode_with_special_tokens(text).len())
+}
+
+/// Count tokens, falling back to a ~4 chars/token heuristic if the tokenizer is unavailable
+pub fn estimate_tokens(text: &str) -> usize {
+    count_tokens(text).unwrap_or_else(|_| text.len().div_ceil(4))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_tokens() {
+        assert_eq!(count_tokens("").unwrap(), 0);
+        assert!(count_tokens("This is a test sentence.").unwrap() > 0);
+    }
+
+    #[test]
+    fn test_estimate_matches_count() {
+        let text = "fn main() { println!(\"hello\"); }";
+        assert_eq!(estimate_tokens(text), count_tokens(text).unwrap());
+    }
+}
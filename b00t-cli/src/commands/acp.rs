@@ -1,13 +1,20 @@
 //! ACP (Agent Coordination Protocol) commands for b00t-cli
 //! 
-//! Allows operators to send messages to agents and coordinate step-based workflows
+//! Allows operators to send messages to agents and coordinate step-based workflows.
+//! Messages go over `--transport` (`nats://`, `redis://`, `memory://`), defaulting to
+//! `default_transport_url()` like `b00t-cli agent`, so both share a broker and namespace.
 
 use anyhow::{Result, Context};
 use clap::Subcommand;
 use serde_json::{json, Value};
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
 use tracing::{info, warn, error};
-use b00t_acp::{AgentConfig, ACPMessage, MessageType};
+use b00t_acp::{AgentConfig, ACPMessage, MessageType, Transport, default_transport_url, history_from_url, transport_from_url};
+use b00t_c0re_lib::agent_coordination::CoordinationMessage;
+use b00t_c0re_lib::agent_tasks::{TaskRecord, TASK_REGISTRY_KEY};
+use b00t_c0re_lib::redis::{RedisComms, RedisConfig};
 
 /// ACP (Agent Coordination Protocol) commands
 #[derive(Subcommand, Debug)]
@@ -61,7 +68,7 @@ pub enum AcpCommands {
         /// Step to listen to (all steps if not specified)
         #[arg(short, long)]
         step: Option<u64>,
-        /// Message type filter (STATUS, PROPOSE, STEP)
+        /// Message type filter (STATUS, PROPOSE, STEP, COORDINATION)
         #[arg(short, long)]
         message_type: Option<String>,
         /// Timeout in seconds
@@ -79,11 +86,37 @@ pub enum AcpCommands {
         step: Option<u64>,
     },
     
+    /// Show a mission's recorded history as a timeline
+    Log {
+        /// Mission identifier
+        mission_id: String,
+        /// Replay from this step
+        #[arg(short, long, default_value = "0")]
+        from_step: u64,
+        /// History URL: nats:// (JetStream) or redis:// (Streams); defaults to the transport
+        #[arg(long, env = "B00T_ACP_HISTORY")]
+        history: Option<String>,
+        /// Output JSON lines
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show delegated tasks with their state, holder and lease, from the Redis captains mirror
+    /// them to (the `--transport` one when that is redis://)
+    Tasks {
+        /// Only tasks in this state (pending, assigned, in-progress, done, failed)
+        #[arg(short, long)]
+        state: Option<String>,
+        /// Output JSON lines
+        #[arg(long)]
+        json: bool,
+    },
+
     /// Send custom message to specific subject
     Send {
-        /// NATS subject to send to
+        /// Subject to send to
         subject: String,
-        /// Message type (STATUS, PROPOSE, STEP)
+        /// Message type (STATUS, PROPOSE, STEP, COORDINATION)
         #[arg(short, long, default_value = "STATUS")]
         message_type: String,
         /// Message payload as JSON
@@ -96,30 +129,35 @@ pub enum AcpCommands {
 }
 
 impl AcpCommands {
-    pub async fn execute(&self) -> Result<()> {
+    /// Run against `transport` (a URL), or NATS_URL when unset
+    pub async fn execute(&self, transport: Option<&str>) -> Result<()> {
         match self {
             AcpCommands::Status { description, payload, step, namespace } => {
-                self.send_status(description, payload.as_deref(), *step, namespace.as_deref()).await
+                self.send_status(transport, description, payload.as_deref(), *step, namespace.as_deref()).await
             }
             AcpCommands::Propose { action, payload, step, namespace } => {
-                self.send_propose(action, payload.as_deref(), *step, namespace.as_deref()).await
+                self.send_propose(transport, action, payload.as_deref(), *step, namespace.as_deref()).await
             }
             AcpCommands::Step { step, namespace } => {
-                self.send_step(*step, namespace.as_deref()).await
+                self.send_step(transport, *step, namespace.as_deref()).await
             }
             AcpCommands::Listen { namespace, step, message_type, timeout } => {
-                self.listen_messages(namespace.as_deref(), *step, message_type.as_deref(), *timeout).await
+                self.listen_messages(transport, namespace.as_deref(), *step, message_type.as_deref(), *timeout).await
             }
             AcpCommands::Show { namespace, step } => {
-                self.show_status(namespace.as_deref(), *step).await
+                self.show_status(transport, namespace.as_deref(), *step).await
+            }
+            AcpCommands::Log { mission_id, from_step, history, json } => {
+                self.show_log(history.as_deref().or(transport), mission_id, *from_step, *json).await
             }
+            AcpCommands::Tasks { state, json } => self.show_tasks(transport, state.as_deref(), *json),
             AcpCommands::Send { subject, message_type, payload, step } => {
-                self.send_custom(subject, message_type, payload, *step).await
+                self.send_custom(transport, subject, message_type, payload, *step).await
             }
         }
     }
 
-    async fn send_status(&self, description: &str, payload: Option<&str>, step: u64, namespace: Option<&str>) -> Result<()> {
+    async fn send_status(&self, transport: Option<&str>, description: &str, payload: Option<&str>, step: u64, namespace: Option<&str>) -> Result<()> {
         let config = self.get_agent_config("operator", namespace).await?;
         let agent_id = format!("operator.{}", whoami::username());
         
@@ -135,21 +173,22 @@ impl AcpCommands {
         };
 
         let message = ACPMessage::status(agent_id, step, payload_json);
+        let subject = format!("{}.{}", config.namespace, message.subject());
         
         info!("📢 Sending STATUS to step {}: {}", step, description);
-        info!("🎯 Subject: {}", message.subject());
+        info!("🎯 Subject: {}", subject);
+        publish(transport, &config, &subject, &message).await?;
         
-        // For now, just display the message (stub implementation)
         println!("📨 ACP STATUS Message:");
         println!("   Step: {}", message.step);
         println!("   Agent: {}", message.agent_id);
-        println!("   Subject: {}", message.subject());
+        println!("   Subject: {}", subject);
         println!("   Payload: {}", serde_json::to_string_pretty(&message.payload)?);
         
         Ok(())
     }
 
-    async fn send_propose(&self, action: &str, payload: Option<&str>, step: u64, namespace: Option<&str>) -> Result<()> {
+    async fn send_propose(&self, transport: Option<&str>, action: &str, payload: Option<&str>, step: u64, namespace: Option<&str>) -> Result<()> {
         let config = self.get_agent_config("operator", namespace).await?;
         let agent_id = format!("operator.{}", whoami::username());
         
@@ -166,57 +205,52 @@ impl AcpCommands {
         payload_json["operator"] = json!(whoami::username());
 
         let message = ACPMessage::propose(agent_id, step, payload_json);
+        let subject = format!("{}.{}", config.namespace, message.subject());
         
         info!("💡 Sending PROPOSE to step {}: {}", step, action);
-        info!("🎯 Subject: {}", message.subject());
+        info!("🎯 Subject: {}", subject);
+        publish(transport, &config, &subject, &message).await?;
         
         println!("📨 ACP PROPOSE Message:");
         println!("   Step: {}", message.step);
         println!("   Agent: {}", message.agent_id);
         println!("   Action: {}", action);
-        println!("   Subject: {}", message.subject());
+        println!("   Subject: {}", subject);
         println!("   Payload: {}", serde_json::to_string_pretty(&message.payload)?);
         
         Ok(())
     }
 
-    async fn send_step(&self, step: u64, namespace: Option<&str>) -> Result<()> {
+    async fn send_step(&self, transport: Option<&str>, step: u64, namespace: Option<&str>) -> Result<()> {
         let config = self.get_agent_config("operator", namespace).await?;
         let agent_id = format!("operator.{}", whoami::username());
 
         let message = ACPMessage::step_complete(agent_id, step);
+        let subject = format!("{}.{}", config.namespace, message.subject());
         
         info!("✅ Completing step {}", step);
-        info!("🎯 Subject: {}", message.subject());
+        info!("🎯 Subject: {}", subject);
+        publish(transport, &config, &subject, &message).await?;
         
         println!("📨 ACP STEP Message:");
         println!("   Step: {}", message.step);
         println!("   Agent: {}", message.agent_id);
-        println!("   Subject: {}", message.subject());
+        println!("   Subject: {}", subject);
         println!("   Payload: {}", serde_json::to_string_pretty(&message.payload)?);
         
         Ok(())
     }
 
-    async fn listen_messages(&self, namespace: Option<&str>, step: Option<u64>, message_type: Option<&str>, timeout: u64) -> Result<()> {
+    async fn listen_messages(&self, transport: Option<&str>, namespace: Option<&str>, step: Option<u64>, message_type: Option<&str>, timeout: u64) -> Result<()> {
         let config = self.get_agent_config("listener", namespace).await?;
         
-        let subject_pattern = match (step, message_type) {
-            (Some(s), Some(mt)) => {
-                let mt_lower = mt.to_lowercase();
-                format!("{}.acp.{}.*.{}", config.namespace, s, mt_lower)
-            }
-            (Some(s), None) => {
-                format!("{}.acp.{}.>", config.namespace, s)
-            }
-            (None, Some(mt)) => {
-                let mt_lower = mt.to_lowercase();
-                format!("{}.acp.*.*.{}", config.namespace, mt_lower)
-            }
-            (None, None) => {
-                format!("{}.acp.>", config.namespace)
-            }
+        // 🤓 Coordination shares the namespace but not the step token, so message types
+        // are filtered here rather than in the subject
+        let subject_pattern = match step {
+            Some(s) => format!("{}.acp.{}.>", config.namespace, s),
+            None => format!("{}.acp.>", config.namespace),
         };
+        let wanted = message_type.map(str::to_uppercase);
 
         info!("👂 Listening for messages on: {}", subject_pattern);
         info!("⏱️  Timeout: {} seconds", timeout);
@@ -225,21 +259,32 @@ impl AcpCommands {
         println!("   Subject Pattern: {}", subject_pattern);
         println!("   Timeout: {} seconds", timeout);
         println!("   Namespace: {}", config.namespace);
-        
-        // Stub implementation - in real version would connect to NATS
-        println!("⚠️  Stub implementation - would listen for real NATS messages");
-        println!("📡 To enable real NATS listening, configure connection to c010.promptexecution.com:4222");
+
+        let transport = connect(transport, &config).await?;
+        let mut subscriber = transport.subscribe(&subject_pattern).await?;
+
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(timeout);
+        let mut received = 0;
+        while let Ok(Ok(Some(message))) = tokio::time::timeout_at(deadline, subscriber.next_message_blocking()).await {
+            let kind = format!("{:?}", message.message_type).to_uppercase();
+            if wanted.as_ref().is_some_and(|wanted| *wanted != kind) {
+                continue;
+            }
+            received += 1;
+            print_envelope(&message)?;
+        }
+        println!("⏹️  {} message(s) received", received);
         
         Ok(())
     }
 
-    async fn show_status(&self, namespace: Option<&str>, step: Option<u64>) -> Result<()> {
+    async fn show_status(&self, transport: Option<&str>, namespace: Option<&str>, step: Option<u64>) -> Result<()> {
         let config = self.get_agent_config("status", namespace).await?;
         
         println!("📊 ACP Coordination Status");
         println!("   Namespace: {}", config.namespace);
         println!("   Agent ID: {}", config.agent_id);
-        println!("   NATS URL: {}", config.nats_url);
+        println!("   Transport: {}", transport.unwrap_or(&config.nats_url));
         
         if let Some(s) = step {
             println!("   Step Filter: {}", s);
@@ -258,7 +303,71 @@ impl AcpCommands {
         Ok(())
     }
 
-    async fn send_custom(&self, subject: &str, message_type: &str, payload: &str, step: u64) -> Result<()> {
+    async fn show_log(&self, history: Option<&str>, mission_id: &str, from_step: u64, json: bool) -> Result<()> {
+        let config = self.get_agent_config("operator", None).await?;
+        let url = history.unwrap_or(&config.nats_url);
+        let history = history_from_url(url).await
+            .with_context(|| format!("Failed to open mission history at {}", url))?;
+        let messages = history.replay(mission_id, from_step).await?;
+
+        if json {
+            for message in &messages {
+                println!("{}", serde_json::to_string(message)?);
+            }
+            return Ok(());
+        }
+
+        println!("📜 Mission {} from step {} ({} message(s))", mission_id, from_step, messages.len());
+        let mut step = None;
+        for message in &messages {
+            if step != Some(message.step) {
+                step = Some(message.step);
+                println!("── step {} ──", message.step);
+            }
+            println!("   {}  {:<24} {}",
+                message.timestamp.format("%Y-%m-%d %H:%M:%S"), message.agent_id, summarize(message));
+        }
+        Ok(())
+    }
+
+    fn show_tasks(&self, transport: Option<&str>, state: Option<&str>, json: bool) -> Result<()> {
+        let redis = registry_redis(transport, get_operator_agent_id())?;
+        let mut tasks: Vec<TaskRecord> = redis.hgetall(TASK_REGISTRY_KEY)
+            .context("Failed to read the task registry from Redis")?
+            .values()
+            .filter_map(|record| serde_json::from_str(record).ok())
+            .filter(|task: &TaskRecord| state.is_none_or(|state| task.state.to_string() == state))
+            .collect();
+        tasks.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+
+        if json {
+            for task in &tasks {
+                println!("{}", serde_json::to_string(task)?);
+            }
+            return Ok(());
+        }
+
+        if tasks.is_empty() {
+            println!("📋 No tracked tasks (captains publish them while supervising delegations)");
+            return Ok(());
+        }
+        let now = chrono::Utc::now().timestamp().max(0) as u64;
+        let remaining = |at: Option<u64>| match at {
+            Some(at) if at > now => format!("{}s", at - now),
+            Some(_) => "lapsed".to_string(),
+            None => "-".to_string(),
+        };
+        println!("{:<20} {:<12} {:<20} {:>8} {:>8} {:>10}", "TASK", "STATE", "WORKER", "PROGRESS", "LEASE", "DEADLINE");
+        for task in &tasks {
+            let lease = if task.state.is_terminal() { "-".to_string() } else { remaining(task.lease_expires) };
+            println!("{:<20} {:<12} {:<20} {:>7.0}% {:>8} {:>10}",
+                task.task_id, task.state.to_string(), task.worker_id.as_deref().unwrap_or("-"),
+                task.progress_percent, lease, remaining(task.deadline));
+        }
+        Ok(())
+    }
+
+    async fn send_custom(&self, transport: Option<&str>, subject: &str, message_type: &str, payload: &str, step: u64) -> Result<()> {
         let agent_id = format!("operator.{}", whoami::username());
         
         // Parse message type
@@ -266,20 +375,24 @@ impl AcpCommands {
             "STATUS" => MessageType::Status,
             "PROPOSE" => MessageType::Propose,
             "STEP" => MessageType::Step,
-            _ => return Err(anyhow::anyhow!("Invalid message type. Use STATUS, PROPOSE, or STEP")),
+            "COORDINATION" => MessageType::Coordination,
+            _ => return Err(anyhow::anyhow!("Invalid message type. Use STATUS, PROPOSE, STEP or COORDINATION")),
         };
         
         // Parse payload
         let payload_json: Value = serde_json::from_str(payload)
             .context("Invalid JSON payload")?;
+        let config = self.get_agent_config("operator", None).await?;
         
         let message = match msg_type {
             MessageType::Status => ACPMessage::status(agent_id, step, payload_json),
             MessageType::Propose => ACPMessage::propose(agent_id, step, payload_json),
             MessageType::Step => ACPMessage::step_complete(agent_id, step),
+            MessageType::Coordination => ACPMessage::coordination(agent_id, step, payload_json),
         };
         
         info!("📤 Sending custom message to: {}", subject);
+        publish(transport, &config, subject, &message).await?;
         
         println!("📨 ACP Custom Message:");
         println!("   Subject: {}", subject);
@@ -299,9 +412,8 @@ impl AcpCommands {
             .map(|ns| ns.to_string())
             .unwrap_or_else(|| format!("account.{}", username));
         
-        // TODO: Get NATS URL and JWT from configuration
-        let nats_url = std::env::var("NATS_URL")
-            .unwrap_or_else(|_| "nats://c010.promptexecution.com:4222".to_string());
+        // TODO: Get JWT from configuration
+        let nats_url = default_transport_url();
         
         let config = AgentConfig::new(agent_id, nats_url, namespace)
             .with_role(role.to_string())
@@ -316,6 +428,18 @@ impl AcpCommands {
     }
 }
 
+/// Redis holding the agent and task registries: the one at `transport` (or the default
+/// transport) when that is `redis://`, otherwise the local default
+pub fn registry_redis(transport: Option<&str>, agent_id: String) -> Result<RedisComms> {
+    let url = transport.map(str::to_string).unwrap_or_else(default_transport_url);
+    let redis = if url.starts_with("redis://") || url.starts_with("rediss://") {
+        RedisComms::from_url(&url, agent_id)
+    } else {
+        RedisComms::new(RedisConfig::default(), agent_id)
+    };
+    redis.context("Failed to connect to Redis")
+}
+
 /// Helper function to get current user's namespace
 pub fn get_current_namespace() -> String {
     let username = whoami::username();
@@ -326,4 +450,41 @@ pub fn get_current_namespace() -> String {
 pub fn get_operator_agent_id() -> String {
     let username = whoami::username();
     format!("operator.{}", username)
-}
\ No newline at end of file
+}
+
+/// Connect to `transport`, or the NATS server in `config`
+async fn connect(transport: Option<&str>, config: &AgentConfig) -> Result<Arc<dyn Transport>> {
+    let url = transport.unwrap_or(&config.nats_url);
+    transport_from_url(url).await
+        .with_context(|| format!("Failed to connect to transport {}", url))
+}
+
+async fn publish(transport: Option<&str>, config: &AgentConfig, subject: &str, message: &ACPMessage) -> Result<()> {
+    let transport = connect(transport, config).await?;
+    transport.publish(subject, message).await?;
+    // 🤓 NATS buffers publishes; flush before the process exits
+    transport.flush().await?;
+    Ok(())
+}
+
+/// Type and headline of a message for the timeline
+fn summarize(message: &ACPMessage) -> String {
+    let text = |key: &str| message.payload[key].as_str().unwrap_or_default().to_string();
+    match message.message_type {
+        MessageType::Status => format!("STATUS   {}", text("description")),
+        MessageType::Propose => format!("PROPOSE  {}", text("action")),
+        MessageType::Step => "STEP     ✅ ready".to_string(),
+        MessageType::Coordination => format!("COORD    {}", message.coordination_kind().unwrap_or("?")),
+    }
+}
+
+/// One line per envelope; coordination payloads are decoded to their kind
+fn print_envelope(message: &ACPMessage) -> Result<()> {
+    let kind = match CoordinationMessage::from_envelope(message) {
+        Some(coordination) => format!("COORDINATION/{}", coordination.kind()),
+        None => format!("{:?}", message.message_type).to_uppercase(),
+    };
+    println!("📥 [v{} step {}] {} from {}: {}",
+        message.version, message.step, kind, message.agent_id, serde_json::to_string(&message.payload)?);
+    Ok(())
+}
@@ -16,6 +16,14 @@ pub async fn handle_advice(path: &str, tool: &str, query: &str, count: Option<us
     // Handle different query types  
     let results = match query.to_lowercase().as_str() {
         "list" => lfmf_system.list_lessons(tool, count).await?,
+        query if query.starts_with("upvote ") || query.starts_with("downvote ") => {
+            let (verb, id) = query.split_once(' ').unwrap_or_default();
+            let lesson = lfmf_system.vote(tool, id.trim(), verb == "upvote")?;
+            println!("{} {} [{}] now at confidence {:.2} (+{}/-{})",
+                if verb == "upvote" { "👍" } else { "👎" }, lesson.id, lesson.topic,
+                lesson.confidence.unwrap_or_default(), lesson.upvotes, lesson.downvotes);
+            return Ok(());
+        },
         query if query.starts_with("search ") => {
             let search_query = query.strip_prefix("search ").unwrap_or("");
             if search_query.trim().is_empty() {
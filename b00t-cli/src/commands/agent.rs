@@ -0,0 +1,451 @@
+//! Agent coordination commands for b00t-cli
+//!
+//! Discovery, messaging, task delegation, voting and capability-based agent selection over
+//! `--transport` (default: `default_transport_url()`, the same broker as `b00t-cli acp`) in
+//! `--namespace` (default: `account.<user>`), so ACP hive agents in that namespace take part.
+//! `b00t-mcp` exposes every subcommand as a `b00t_agent_*` tool (`b00t_agent_wait` for
+//! `agent wait`, and so on).
+
+use anyhow::{Context, Result};
+use b00t_c0re_lib::agent_coordination::{AgentCoordinator, AgentMetadata, MessageFilter, RequestUrgency, TaskCompletionStatus, TaskPriority, VoteChoice, VotingOption, VotingType};
+use b00t_c0re_lib::agent_selection::AgentSelector;
+use b00t_c0re_lib::datum_ai_model::ModelSize;
+use b00t_c0re_lib::agent_voting::{DecisionThreshold, TieBreak, VotingRules};
+use b00t_c0re_lib::redis::AgentStatus;
+use clap::Parser;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::Duration;
+
+#[derive(Parser, Clone)]
+pub enum AgentCommands {
+    #[clap(about = "List agents seen in the last five minutes")]
+    Discover {
+        #[clap(long, help = "Filter by agent role")]
+        role: Option<String>,
+        #[clap(long, help = "Filter by crew membership")]
+        crew: Option<String>,
+        #[clap(long, help = "Required capabilities (comma-separated)")]
+        capabilities: Option<String>,
+        #[clap(long, help = "Output in JSON format")]
+        json: bool,
+    },
+    #[clap(about = "Send a direct message to an agent")]
+    Message {
+        #[clap(help = "Target agent ID")]
+        to_agent: String,
+        #[clap(help = "Message subject")]
+        subject: String,
+        #[clap(help = "Message content")]
+        content: String,
+        #[clap(long, help = "Require acknowledgment")]
+        ack: bool,
+    },
+    #[clap(about = "Delegate a task to a worker agent")]
+    Delegate {
+        #[clap(help = "Worker agent ID")]
+        worker: String,
+        #[clap(help = "Task ID")]
+        task_id: String,
+        #[clap(help = "Task description")]
+        description: String,
+        #[clap(long, help = "Priority: low, normal, high or critical", default_value = "normal")]
+        priority: String,
+        #[clap(long, help = "Deadline in minutes")]
+        deadline: Option<u64>,
+        #[clap(long, help = "Required capabilities (comma-separated)")]
+        capabilities: Option<String>,
+        #[clap(long, help = "Block until the task completes and print the completion")]
+        blocking: bool,
+    },
+    #[clap(about = "Enforce leases and deadlines on a captain's delegated tasks, reassigning lapsed ones")]
+    Supervise {
+        #[clap(long, help = "Captain agent ID whose tasks to supervise (default: this agent)")]
+        captain: Option<String>,
+        #[clap(long, help = "Stop after this many seconds (default: run until interrupted)")]
+        duration: Option<u64>,
+    },
+    #[clap(about = "Report a delegated task as finished (worker)")]
+    Complete {
+        #[clap(help = "Captain agent ID")]
+        captain: String,
+        #[clap(help = "Task ID")]
+        task_id: String,
+        #[clap(long, help = "Completion status: success, failed, partial or cancelled")]
+        status: String,
+        #[clap(long, help = "Result description")]
+        result: Option<String>,
+        #[clap(long, help = "Output artifacts (comma-separated paths)")]
+        artifacts: Option<String>,
+    },
+    #[clap(about = "Broadcast progress on a task")]
+    Progress {
+        #[clap(help = "Task ID")]
+        task_id: String,
+        #[clap(help = "Progress percentage (0-100)")]
+        progress: f32,
+        #[clap(help = "Status message")]
+        message: String,
+        #[clap(long, help = "Estimated completion in minutes")]
+        eta: Option<u64>,
+    },
+    #[clap(about = "Wait for a coordination message matching the filters and print it as JSON")]
+    Wait {
+        #[clap(long, help = "Timeout in seconds", default_value = "300")]
+        timeout: u64,
+        #[clap(long, help = "Filter by message type, e.g. TaskCompletion (comma-separated)")]
+        message_type: Option<String>,
+        #[clap(long, help = "Filter by sender agent (comma-separated)")]
+        from_agent: Option<String>,
+        #[clap(long, help = "Filter by task, proposal or request ID (comma-separated)")]
+        task_id: Option<String>,
+        #[clap(long, help = "Filter by subject (comma-separated)")]
+        subject: Option<String>,
+    },
+    #[clap(about = "Broadcast an event notification")]
+    Notify {
+        #[clap(help = "Event type (e.g., 'file_created', 'pr_opened')")]
+        event_type: String,
+        #[clap(help = "Event source")]
+        source: String,
+        #[clap(help = "Event details (JSON)")]
+        details: String,
+        #[clap(long, help = "Target specific agents (comma-separated)")]
+        agents: Option<String>,
+    },
+    #[clap(about = "Propose and vote on decisions between agents")]
+    Vote {
+        #[clap(subcommand)]
+        vote_command: AgentVoteCommands,
+    },
+    #[clap(about = "Ask which agents can handle a task, rank them and optionally delegate to the best")]
+    Capability {
+        #[clap(help = "Required capabilities (comma-separated)")]
+        capabilities: String,
+        #[clap(help = "Task description")]
+        description: String,
+        #[clap(long, help = "Request urgency: low, normal, high or emergency", default_value = "normal")]
+        urgency: String,
+        #[clap(long, help = "Prefer agents running this model size: small (sm0l) or large (ch0nky)")]
+        model_size: Option<String>,
+        #[clap(long, help = "Seconds to collect responses", default_value = "2")]
+        window: u64,
+        #[clap(long, help = "Only agents holding every capability")]
+        require_all: bool,
+        #[clap(long, help = "Delegate the task with this id to the best agent")]
+        delegate: Option<String>,
+        #[clap(long, help = "Print the ranking as JSON")]
+        json: bool,
+    },
+}
+
+#[derive(Parser, Clone)]
+pub enum AgentVoteCommands {
+    #[clap(about = "Create a voting proposal, wait for the votes and print the tallied result")]
+    Create {
+        #[clap(help = "Proposal subject")]
+        subject: String,
+        #[clap(help = "Proposal description")]
+        description: String,
+        #[clap(help = "Voting options (JSON array of ids or {id, title, description} objects)")]
+        options: String,
+        #[clap(help = "Eligible voters (comma-separated agent IDs)")]
+        voters: String,
+        #[clap(long, help = "Voting type: single, ranked, approval or veto", default_value = "single")]
+        vote_type: String,
+        #[clap(long, help = "Deadline in minutes")]
+        deadline: u64,
+        #[clap(long, help = "Fraction of eligible voters that must vote", default_value = "0.5")]
+        quorum: f32,
+        #[clap(long, help = "Support the winner needs: plurality, majority or unanimous", default_value = "plurality")]
+        threshold: String,
+        #[clap(long, help = "Tie-break: option-order (first listed wins) or undecided", default_value = "option-order")]
+        tie_break: String,
+    },
+    #[clap(about = "Submit a vote for a proposal")]
+    Submit {
+        #[clap(help = "Proposal ID")]
+        proposal_id: String,
+        #[clap(help = "Vote choice (JSON), e.g. '{\"Single\":\"a\"}' or '{\"Ranked\":[\"a\",\"b\"]}'")]
+        vote: String,
+        #[clap(long, help = "Vote reasoning")]
+        reasoning: Option<String>,
+    },
+}
+
+impl AgentCommands {
+    pub async fn execute(&self, agent_id: Option<&str>, transport: Option<&str>, namespace: Option<&str>) -> Result<()> {
+        match self {
+            AgentCommands::Discover { role, crew, capabilities, json } => {
+                let wanted = capabilities.as_deref().map(split_list).unwrap_or_default();
+                let agents: Vec<AgentMetadata> = coordinator(agent_id, transport, namespace).await?.discover_agents().await?
+                    .into_iter()
+                    .filter(|agent| role.as_ref().is_none_or(|role| &agent.agent_role == role))
+                    .filter(|agent| crew.is_none() || agent.crew == *crew)
+                    .filter(|agent| wanted.iter().all(|c| agent.capabilities.contains(c)))
+                    .collect();
+                if *json {
+                    println!("{}", serde_json::to_string_pretty(&agents)?);
+                } else if agents.is_empty() {
+                    println!("🤷 No agents found");
+                } else {
+                    println!("{:<28} {:<10} {:<12} {:>5}  CAPABILITIES", "AGENT", "ROLE", "CREW", "LOAD");
+                    for agent in &agents {
+                        println!("{:<28} {:<10} {:<12} {:>5.2}  {}", agent.agent_id, agent.agent_role,
+                            agent.crew.as_deref().unwrap_or("-"), agent.load, agent.capabilities.join(","));
+                    }
+                }
+                Ok(())
+            }
+            AgentCommands::Message { to_agent, subject, content, ack } => {
+                let message_id = coordinator(agent_id, transport, namespace).await?.send_message(to_agent, subject, content, *ack).await?;
+                println!("📨 Sent {} to {}", message_id, to_agent);
+                Ok(())
+            }
+            AgentCommands::Delegate { worker, task_id, description, priority, deadline, capabilities, blocking } => {
+                let priority = parse_priority(priority)?;
+                let capabilities = capabilities.as_deref().map(split_list).unwrap_or_default();
+                let mut coordinator = coordinator(agent_id, transport, namespace).await?;
+                let completion = coordinator.delegate_task(worker, task_id, description, priority,
+                    deadline.map(|minutes| Duration::from_secs(minutes * 60)), capabilities, *blocking).await?;
+                match completion {
+                    Some(completion) => println!("{}", serde_json::to_string_pretty(&completion)?),
+                    None => print_delegated(task_id, worker, &coordinator),
+                }
+                Ok(())
+            }
+            AgentCommands::Supervise { captain, duration } => {
+                let captain = match captain {
+                    Some(captain) => captain.clone(),
+                    None => resolve_agent_id(agent_id)?,
+                };
+                let coordinator = coordinator_as(captain.clone(), transport, namespace).await?;
+                println!("👮 Supervising tasks of {}", captain);
+                match duration {
+                    Some(seconds) => {
+                        let _ = tokio::time::timeout(Duration::from_secs(*seconds), coordinator.supervise()).await;
+                    }
+                    None => coordinator.supervise().await?,
+                }
+                Ok(())
+            }
+            AgentCommands::Complete { captain, task_id, status, result, artifacts } => {
+                let status = parse_status(status, result.as_deref())?;
+                let artifacts = artifacts.as_deref().map(split_list).unwrap_or_default();
+                coordinator(agent_id, transport, namespace).await?.complete_task(captain, task_id, status, result.clone(), artifacts).await?;
+                println!("✅ Reported {} to {}", task_id, captain);
+                Ok(())
+            }
+            AgentCommands::Progress { task_id, progress, message, eta } => {
+                coordinator(agent_id, transport, namespace).await?.report_progress(task_id, *progress, message,
+                    eta.map(|minutes| Duration::from_secs(minutes * 60))).await?;
+                println!("📈 {}: {:.0}% {}", task_id, progress, message);
+                Ok(())
+            }
+            AgentCommands::Wait { timeout, message_type, from_agent, task_id, subject } => {
+                let message = coordinator(agent_id, transport, namespace).await?
+                    .wait_for_message(Duration::from_secs(*timeout), message_filter(message_type, from_agent, task_id, subject))
+                    .await?;
+                println!("{}", serde_json::to_string_pretty(&message)?);
+                Ok(())
+            }
+            AgentCommands::Notify { event_type, source, details, agents } => {
+                let details: Value = serde_json::from_str(details).context("Event details must be JSON")?;
+                coordinator(agent_id, transport, namespace).await?
+                    .notify_event(event_type, source, details, agents.as_deref().map(split_list))
+                    .await?;
+                println!("📣 Notified {}", event_type);
+                Ok(())
+            }
+            AgentCommands::Vote { vote_command } => vote_command.execute(agent_id, transport, namespace).await,
+            AgentCommands::Capability { capabilities, description, urgency, model_size, window, require_all, delegate, json } => {
+                let mut selector = AgentSelector::new(split_list(capabilities)).require_all(*require_all);
+                if let Some(size) = model_size {
+                    let size: ModelSize = serde_json::from_value(Value::String(size.to_lowercase()))
+                        .with_context(|| format!("Unknown model size '{}' (small, large)", size))?;
+                    selector = selector.with_model_size(size);
+                }
+                let (urgency, priority) = match urgency.to_lowercase().as_str() {
+                    "low" => (RequestUrgency::Low, TaskPriority::Low),
+                    "normal" => (RequestUrgency::Normal, TaskPriority::Normal),
+                    "high" => (RequestUrgency::High, TaskPriority::High),
+                    "emergency" => (RequestUrgency::Emergency, TaskPriority::Critical),
+                    other => anyhow::bail!("Unknown urgency '{}' (low, normal, high, emergency)", other),
+                };
+
+                let mut coordinator = coordinator(agent_id, transport, namespace).await?.with_capability_window(Duration::from_secs(*window));
+                if let Some(task_id) = delegate {
+                    let (best, _) = coordinator.delegate_to_best(&selector, task_id, description, priority, None, false).await?;
+                    print_delegated(task_id, &format!("{} (score {:.2})", best.agent_id, best.score), &coordinator);
+                    return Ok(());
+                }
+
+                let ranked = coordinator.select_agents(&selector, description, urgency).await?;
+                if *json {
+                    println!("{}", serde_json::to_string_pretty(&ranked)?);
+                } else if ranked.is_empty() {
+                    println!("🤷 No available agent offers {}", capabilities);
+                } else {
+                    println!("{:<28} {:>6} {:>9} {:>12} {:>5}", "AGENT", "SCORE", "COVERAGE", "PROFICIENCY", "LOAD");
+                    for candidate in &ranked {
+                        println!("{:<28} {:>6.2} {:>8.0}% {:>12.2} {:>5.2}",
+                            candidate.agent_id, candidate.score, candidate.coverage * 100.0, candidate.proficiency, candidate.load);
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl AgentVoteCommands {
+    pub async fn execute(&self, agent_id: Option<&str>, transport: Option<&str>, namespace: Option<&str>) -> Result<()> {
+        match self {
+            AgentVoteCommands::Create { subject, description, options, voters, vote_type, deadline, quorum, threshold, tie_break } => {
+                let voting_type: VotingType = vote_type.parse()?;
+                let rules = VotingRules::default()
+                    .with_quorum(*quorum)
+                    .with_threshold(threshold.parse::<DecisionThreshold>()?)
+                    .with_tie_break(tie_break.parse::<TieBreak>()?);
+                let eligible = split_list(voters);
+
+                let coordinator = coordinator(agent_id, transport, namespace).await?;
+                let result = coordinator.create_voting_proposal(
+                    subject,
+                    description,
+                    parse_options(options)?,
+                    voting_type,
+                    Duration::from_secs(deadline * 60),
+                    eligible,
+                    rules,
+                ).await?;
+                println!("{}", serde_json::to_string_pretty(&result)?);
+                Ok(())
+            }
+            AgentVoteCommands::Submit { proposal_id, vote, reasoning } => {
+                let vote: VoteChoice = serde_json::from_str(vote)
+                    .context("Vote must be JSON, e.g. '{\"Single\":\"<option id>\"}'")?;
+                coordinator(agent_id, transport, namespace).await?.submit_vote(proposal_id, vote, reasoning.clone()).await?;
+                println!("🗳️ Vote submitted for {}", proposal_id);
+                Ok(())
+            }
+        }
+    }
+}
+
+fn split_list(list: &str) -> Vec<String> {
+    list.split(',')
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .collect()
+}
+
+fn parse_priority(priority: &str) -> Result<TaskPriority> {
+    match priority.to_lowercase().as_str() {
+        "low" => Ok(TaskPriority::Low),
+        "normal" => Ok(TaskPriority::Normal),
+        "high" => Ok(TaskPriority::High),
+        "critical" => Ok(TaskPriority::Critical),
+        other => anyhow::bail!("Unknown priority '{}' (low, normal, high, critical)", other),
+    }
+}
+
+/// `failed` and `partial` carry the result text as their detail
+fn parse_status(status: &str, result: Option<&str>) -> Result<TaskCompletionStatus> {
+    let detail = result.unwrap_or_default().to_string();
+    match status.to_lowercase().as_str() {
+        "success" => Ok(TaskCompletionStatus::Success),
+        "failed" => Ok(TaskCompletionStatus::Failed(detail)),
+        "partial" => Ok(TaskCompletionStatus::PartialSuccess(detail)),
+        "cancelled" => Ok(TaskCompletionStatus::Cancelled),
+        other => anyhow::bail!("Unknown status '{}' (success, failed, partial, cancelled)", other),
+    }
+}
+
+/// Each flag is a comma-separated list; unset flags match anything
+fn message_filter(
+    message_type: &Option<String>,
+    from_agent: &Option<String>,
+    task_id: &Option<String>,
+    subject: &Option<String>,
+) -> MessageFilter {
+    let mut filter = MessageFilter::default();
+    if let Some(types) = message_type {
+        filter = filter.with_message_types(split_list(types));
+    }
+    if let Some(agents) = from_agent {
+        filter = filter.with_from_agents(split_list(agents));
+    }
+    if let Some(ids) = task_id {
+        filter = filter.with_task_ids(split_list(ids));
+    }
+    if let Some(subjects) = subject {
+        filter = filter.with_subjects(split_list(subjects));
+    }
+    filter
+}
+
+/// Who this invocation acts as: `--agent-id`, else `_B00T_Agent`, else `operator.<user>`
+///
+/// 🤓 Stable across invocations, so a worker delegated to in one call can complete in another
+fn resolve_agent_id(explicit: Option<&str>) -> Result<String> {
+    if let Some(agent_id) = explicit {
+        if !b00t_acp::ACPMessage::is_valid_agent_id(agent_id) {
+            anyhow::bail!("Invalid agent id '{}': use letters, digits, '-', '_' and '.'-separated parts", agent_id);
+        }
+        return Ok(agent_id.to_string());
+    }
+    // _B00T_Agent is a display name ("🤖 Custom Agent"); keep only what a subject token allows
+    let named = std::env::var("_B00T_Agent").ok().map(|agent| subject_token(&agent)).filter(|agent| !agent.is_empty());
+    Ok(named.unwrap_or_else(|| format!("operator.{}", subject_token(&whoami::username()))))
+}
+
+/// `name` reduced to letters, digits, '-' and '_': "🤖 Custom Agent" → "custom-agent"
+fn subject_token(name: &str) -> String {
+    name.split(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+        .to_lowercase()
+}
+
+/// A non-blocking delegation's lease is only enforced while its captain supervises
+fn print_delegated(task_id: &str, worker: &str, coordinator: &AgentCoordinator) {
+    println!("📋 Delegated {} to {}", task_id, worker);
+    println!("   `b00t-cli agent supervise --captain {}` reassigns it if the worker goes quiet", coordinator.agent_id());
+}
+
+async fn coordinator(agent_id: Option<&str>, transport: Option<&str>, namespace: Option<&str>) -> Result<AgentCoordinator> {
+    coordinator_as(resolve_agent_id(agent_id)?, transport, namespace).await
+}
+
+async fn coordinator_as(agent_id: String, transport: Option<&str>, namespace: Option<&str>) -> Result<AgentCoordinator> {
+    let redis = super::acp::registry_redis(transport, agent_id.clone())?;
+    let metadata = AgentMetadata {
+        agent_id,
+        agent_role: "captain".to_string(),
+        capabilities: vec![],
+        crew: None,
+        status: AgentStatus::Online,
+        last_seen: 0,
+        load: 0.0,
+        specializations: HashMap::new(),
+        model_size: None,
+    };
+    let url = transport.map(str::to_string).unwrap_or_else(b00t_acp::default_transport_url);
+    let transport = b00t_acp::transport_from_url(&url).await
+        .with_context(|| format!("Failed to connect to transport {}", url))?;
+    let namespace = namespace.map(str::to_string).unwrap_or_else(super::acp::get_current_namespace);
+    Ok(AgentCoordinator::new(redis, metadata).with_transport(transport).with_namespace(namespace))
+}
+
+/// Options as a JSON array of ids (`["a","b"]`) or full `VotingOption` objects
+fn parse_options(json: &str) -> Result<Vec<VotingOption>> {
+    let values: Vec<Value> = serde_json::from_str(json).context("Options must be a JSON array")?;
+    values.into_iter()
+        .map(|value| match value {
+            Value::String(id) => Ok(VotingOption { id: id.clone(), title: id, description: String::new() }),
+            other => serde_json::from_value(other).context("Option must be an id or {id, title, description}"),
+        })
+        .collect()
+}
@@ -1,6 +1,10 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::Subcommand;
-use b00t_c0re_lib::GrokClient;
+use b00t_c0re_lib::grok::DEFAULT_MIN_ANSWER_CONFIDENCE;
+use b00t_c0re_lib::{GrokClient, GrokConfig};
+use b00t_grok::SearchOptions;
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
 
 #[derive(Subcommand, Clone)]
 pub enum GrokCommands {
@@ -19,6 +23,24 @@ pub enum GrokCommands {
         /// Optional topic to filter by
         #[arg(short, long)]
         topic: Option<String>,
+        /// Maximum number of results to return
+        #[arg(long, default_value = "10")]
+        limit: usize,
+        /// Drop results whose vector similarity is below this (0..1); not valid with --mode keyword
+        #[arg(long)]
+        min_score: Option<f32>,
+        /// Ranking: hybrid (BM25 + vector), vector or keyword
+        #[arg(long, default_value = "hybrid", value_parser = ["hybrid", "vector", "keyword"])]
+        mode: String,
+        /// Skip the reranker even if a rerank model is configured
+        #[arg(long)]
+        no_rerank: bool,
+        /// Synthesize an answer with inline citations instead of listing chunks
+        #[arg(long)]
+        answer: bool,
+        /// Refuse to answer when no source reaches this vector similarity (0..1)
+        #[arg(long, default_value_t = DEFAULT_MIN_ANSWER_CONFIDENCE)]
+        min_confidence: f32,
     },
     /// Learn from URLs or files
     Learn {
@@ -28,22 +50,75 @@ pub enum GrokCommands {
         /// Content to learn from
         content: String,
     },
+    /// Incrementally index a directory, glob or git repository
+    Ingest {
+        /// Path, glob (e.g. "docs/**/*.md") or git repository URL
+        target: String,
+    },
+    /// List topics in the knowledgebase with chunk counts
+    Topics,
+    /// Delete a chunk by id, or every chunk of a topic
+    Forget {
+        /// Chunk UUID or topic name
+        target: String,
+    },
+    /// Export the knowledgebase as portable JSONL
+    Export {
+        /// Output file (stdout if omitted)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Only export this topic
+        #[arg(short, long)]
+        topic: Option<String>,
+        /// Include embeddings so import can skip re-embedding with the same model
+        #[arg(long)]
+        vectors: bool,
+    },
+    /// Import a JSONL snapshot written by export
+    Import {
+        /// Snapshot file ("-" for stdin)
+        file: PathBuf,
+    },
 }
 
-pub async fn handle_grok_command(command: GrokCommands) -> Result<()> {
-    let mut client = GrokClient::new();
+pub async fn handle_grok_command(command: GrokCommands, path: &str) -> Result<()> {
+    let mut client = GrokClient::with_config(GrokConfig::load(&b00t_cli::get_expanded_path(path)?)?);
+    // 🤓 One backend for every verb, so ingest and ask always see the same store
     client.initialize().await?;
 
     match command {
         GrokCommands::Digest { topic, content } => {
             handle_digest(&client, &topic, &content).await
         }
-        GrokCommands::Ask { query, topic } => {
-            handle_ask(&client, &query, topic.as_deref()).await
+        GrokCommands::Ask { query, topic, limit, min_score, mode, no_rerank, answer, min_confidence } => {
+            let options = SearchOptions::default()
+                .with_topic(topic.as_deref())
+                .with_limit(limit)
+                .with_min_score(min_score)
+                .with_mode(serde_json::from_value(serde_json::Value::String(mode))?)
+                .with_rerank(!no_rerank);
+            if answer {
+                handle_answer(&client, &query, &options, min_confidence, path).await
+            } else {
+                handle_ask(&client, &query, &options).await
+            }
         }
         GrokCommands::Learn { source, content } => {
             handle_learn(&client, source.as_deref(), &content).await
         }
+        GrokCommands::Ingest { target } => {
+            handle_ingest(&client, &target).await
+        }
+        GrokCommands::Topics => handle_topics(&client).await,
+        GrokCommands::Forget { target } => {
+            handle_forget(&client, &target).await
+        }
+        GrokCommands::Export { output, topic, vectors } => {
+            handle_export(&client, output.as_deref(), topic.as_deref(), vectors).await
+        }
+        GrokCommands::Import { file } => {
+            handle_import(&client, &file).await
+        }
     }
 }
 
@@ -65,19 +140,22 @@ async fn handle_digest(client: &GrokClient, topic: &str, content: &str) -> Resul
     Ok(())
 }
 
-async fn handle_ask(client: &GrokClient, query: &str, topic: Option<&str>) -> Result<()> {
+async fn handle_ask(client: &GrokClient, query: &str, options: &SearchOptions) -> Result<()> {
     println!("🔍 Searching knowledgebase for: '{}'", query);
-    if let Some(topic) = topic {
+    if let Some(ref topic) = options.topic {
         println!("🎯 Filtering by topic: '{}'", topic);
     }
     
-    let result = client.ask(query, topic, Some(10)).await?;
+    let result = client.search(query, options).await?;
     
     if result.success {
         println!("📊 Found {} results:", result.total_found);
         
         for (i, chunk) in result.results.iter().enumerate() {
-            println!("\n{}. 📄 {}", i + 1, chunk.topic);
+            match chunk.score {
+                Some(score) => println!("\n{}. 📄 {} (score {:.3})", i + 1, chunk.topic, score),
+                None => println!("\n{}. 📄 {}", i + 1, chunk.topic),
+            }
             println!("   💬 {}", chunk.content.chars().take(100).collect::<String>());
             if let Some(ref source) = chunk.source {
                 println!("   🔗 Source: {}", source);
@@ -92,6 +170,32 @@ async fn handle_ask(client: &GrokClient, query: &str, topic: Option<&str>) -> Re
     Ok(())
 }
 
+async fn handle_answer(client: &GrokClient, query: &str, options: &SearchOptions, min_confidence: f32, path: &str) -> Result<()> {
+    println!("🔍 Answering from knowledgebase: '{}'", query);
+
+    let ai = b00t_cli::session_budget::session_ai_client(path)?;
+    let result = client.answer(query, options, &ai, min_confidence).await?;
+
+    let Some(answer) = result.answer else {
+        eprintln!("🤷 {}", result.message.unwrap_or_else(|| "No answer".to_string()));
+        return Err(anyhow::anyhow!("No grounded answer for '{}'", query));
+    };
+
+    println!("\n{}\n", answer);
+    if !result.citations.is_empty() {
+        println!("📚 Sources (best similarity {:.2}):", result.confidence);
+        for citation in &result.citations {
+            println!("  [{}] {} ({})", citation.index,
+                citation.source.as_deref().unwrap_or(&citation.chunk_id), citation.date);
+        }
+    }
+    if let Some(message) = result.message {
+        eprintln!("{}", message);
+    }
+
+    Ok(())
+}
+
 async fn handle_learn(client: &GrokClient, source: Option<&str>, content: &str) -> Result<()> {
     let source_str = source.unwrap_or("direct_input");
     println!("📚 Learning from source: '{}'", source_str);
@@ -117,5 +221,73 @@ async fn handle_learn(client: &GrokClient, source: Option<&str>, content: &str)
     Ok(())
 }
 
+async fn handle_ingest(client: &GrokClient, target: &str) -> Result<()> {
+    println!("📥 Ingesting '{}' ({} store)...", target, client.backend_name());
+
+    let report = client.ingest(target).await?;
+
+    println!("✅ Ingested '{}':", report.root);
+    println!("📁 Files: {} scanned, {} skipped, {} removed",
+        report.files_scanned, report.files_skipped, report.files_removed);
+    println!("📦 Chunks: {} added, {} unchanged, {} removed",
+        report.chunks_added, report.chunks_unchanged, report.chunks_removed);
+
+    Ok(())
+}
+
+async fn handle_topics(client: &GrokClient) -> Result<()> {
+    let topics = client.topics().await?;
+    if topics.is_empty() {
+        println!("📭 Knowledgebase is empty ({} store)", client.backend_name());
+        return Ok(());
+    }
+
+    println!("📚 {} topics ({} store):", topics.len(), client.backend_name());
+    for topic in &topics {
+        println!("  {:>6}  {}  (updated {})", topic.chunks, topic.topic,
+            topic.last_updated.as_deref().unwrap_or("unknown"));
+    }
+    Ok(())
+}
+
+async fn handle_forget(client: &GrokClient, target: &str) -> Result<()> {
+    let removed = client.forget(target).await?;
+    if removed == 0 {
+        return Err(anyhow::anyhow!("No chunk or topic matches '{}'", target));
+    }
+    println!("🗑️ Forgot {} chunks matching '{}'", removed, target);
+    Ok(())
+}
+
+async fn handle_export(client: &GrokClient, output: Option<&Path>, topic: Option<&str>, vectors: bool) -> Result<()> {
+    // 🤓 Progress goes to stderr so `grok export > kb.jsonl` stays clean
+    let count = match output {
+        Some(path) => {
+            let file = std::fs::File::create(path)
+                .with_context(|| format!("Failed to create {}", path.display()))?;
+            client.export(BufWriter::new(file), topic, vectors).await?
+        }
+        None => client.export(std::io::stdout().lock(), topic, vectors).await?,
+    };
+
+    eprintln!("📤 Exported {} chunks{}", count,
+        output.map(|p| format!(" to {}", p.display())).unwrap_or_default());
+    Ok(())
+}
+
+async fn handle_import(client: &GrokClient, file: &Path) -> Result<()> {
+    let report = if file == Path::new("-") {
+        client.import(std::io::stdin().lock()).await?
+    } else {
+        let reader = std::fs::File::open(file)
+            .with_context(|| format!("Failed to open {}", file.display()))?;
+        client.import(BufReader::new(reader)).await?
+    };
+
+    println!("📥 Imported {} chunks ({} re-embedded) into {} store",
+        report.records, report.reembedded, client.backend_name());
+    Ok(())
+}
+
 // 🤓 Helper functions removed - configuration now handled by b00t-c0re-lib::GrokClient
-// which reads QDRANT_URL and QDRANT_API_KEY from environment variables
\ No newline at end of file
+// which resolves its backend via GrokConfig (b00t.json, B00T_GROK_BACKEND, QDRANT_URL, QDRANT_GRPC_URL, B00T_GROK_PY_DIR)
\ No newline at end of file
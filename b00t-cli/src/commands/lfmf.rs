@@ -1,56 +1,54 @@
-use anyhow::{Context, Result};
-use b00t_c0re_lib::{LfmfSystem};
-use tiktoken_rs::o200k_base;
+use anyhow::Result;
+use b00t_c0re_lib::{LessonRejected, LessonValidator, LfmfSystem, RecordOutcome};
 
 /// Handle LFMF (Lessons From My Failures) recording
 /// Uses shared LFMF system from b00t-c0re-lib for consistency
-pub fn handle_lfmf(path: &str, tool: &str, lesson: &str, scope: &str) -> Result<()> {
-    // Expect lesson in "<topic>: <body>" format
-    let parts: Vec<&str> = lesson.splitn(2, ':').map(|s| s.trim()).collect();
-    if parts.len() != 2 {
-        anyhow::bail!("Lesson must be in '<topic>: <body>' format. See --help for examples.");
-    }
-    let topic = parts[0];
-    let body = parts[1];
+///
+/// Lessons are checked against the authoring rules (see `LessonValidator`) unless `force` is set;
+/// `ai_review` (which conflicts with `force`) additionally asks a model to review the lesson.
+pub async fn handle_lfmf(path: &str, tool: &str, lesson: &str, scope: &str, force: bool, ai_review: bool) -> Result<()> {
+    // 🤓 Repo-specific checks flag the repo we're running in and $USER
+    let validator = (!force).then(|| {
+        let cwd = std::env::current_dir().unwrap_or_default();
+        LessonValidator::for_dir(&cwd)
+    });
 
-    // Token count enforcement (using tiktoken, not words)
-    // 🤓: This enforces limits using OpenAI tiktoken, not word count. See src/commands/tiktoken.rs for details.
-    let bpe = o200k_base().map_err(|e| anyhow::anyhow!("Failed to load tokenizer: {e}"))?;
-    let topic_tokens = bpe.encode_with_special_tokens(topic).len();
-    let body_tokens = bpe.encode_with_special_tokens(body).len();
-    if topic_tokens > 25 {
-        anyhow::bail!("Topic must be <25 tokens (OpenAI tiktoken, not words). Yours: {}. See --help for guidance.", topic_tokens);
-    }
-    if body_tokens > 250 {
-        anyhow::bail!("Body must be <250 tokens (OpenAI tiktoken, not words). Yours: {}. See --help for guidance.", body_tokens);
-    }
-    if topic.is_empty() || body.is_empty() {
-        anyhow::bail!("Topic and body must not be empty. See --help for examples.");
-    }
-    // Affirmative style check (simple heuristic)
-    if body.to_lowercase().contains("don't") || body.to_lowercase().contains("never") {
-        println!("⚠️ Please use positive, affirmative style (e.g., 'Do X for Y benefit'). See --help for examples.");
-    }
+    // 🤓 With --ai-review the lesson is fully checked here, so record_lesson skips the heuristics
+    let validator = match validator {
+        Some(validator) if ai_review => {
+            // Heuristics first, so an obviously bad lesson doesn't cost a model call
+            let mut issues = validator.validate(tool, lesson)?;
+            if issues.is_empty() {
+                let ai = b00t_cli::session_budget::session_ai_client(path)?;
+                issues = validator.review_with_ai(&ai, tool, lesson).await?;
+            }
+            if !issues.is_empty() {
+                return Err(LessonRejected { issues }.into());
+            }
+            None
+        }
+        other => other,
+    };
 
-    // Use shared LFMF system for recording
-    let rt = tokio::runtime::Runtime::new()
-        .context("Failed to create async runtime")?;
+    let config = LfmfSystem::load_config(path)?;
+    let mut lfmf_system = LfmfSystem::new(config).with_validator(validator);
 
-    rt.block_on(async {
-        let config = LfmfSystem::load_config(path)?;
-        let mut lfmf_system = LfmfSystem::new(config);
+    // Try to initialize vector database (non-fatal if fails)
+    if let Err(e) = lfmf_system.initialize().await {
+        println!("⚠️ Vector database unavailable: {}. Lesson will be saved to filesystem only.", e);
+    }
 
-        // Try to initialize vector database (non-fatal if fails)
-        if let Err(e) = lfmf_system.initialize().await {
-            println!("⚠️ Vector database unavailable: {}. Lesson will be saved to filesystem only.", e);
+    // Record the lesson using shared system
+    // Scope handling: currently only memoized, extend LfmfSystem for future
+    println!("Scope: {}", scope);
+    match lfmf_system.record_lesson(tool, lesson).await? {
+        RecordOutcome::Recorded(recorded) => {
+            println!("✅ Lesson recorded for {}: {} (id {})", tool, recorded.topic, recorded.id);
         }
-
-        // Record the lesson using shared system
-        // Scope handling: currently only memoized, extend LfmfSystem for future
-        println!("Scope: {}", scope);
-        lfmf_system.record_lesson(tool, lesson).await?;
-
-        println!("✅ Lesson recorded for {}: {}", tool, topic);
-        Ok(())
-    })
-}
\ No newline at end of file
+        RecordOutcome::Duplicate(existing) => {
+            println!("♻️ Already known as {} ({}: {}); upvoted to +{}",
+                existing.id, existing.topic, existing.content, existing.upvotes);
+        }
+    }
+    Ok(())
+}
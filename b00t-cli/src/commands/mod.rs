@@ -13,6 +13,8 @@ pub mod tiktoken;
 pub mod grok;
 pub mod script;
 pub mod acp;
+pub mod agent;
+pub mod run;
 
 pub use mcp::McpCommands;
 pub use k8s::K8sCommands;
@@ -23,4 +25,5 @@ pub use init::InitCommands;
 pub use session::SessionCommands;
 pub use whatismy::WhatismyCommands;
 pub use grok::GrokCommands;
-pub use acp::AcpCommands;
\ No newline at end of file
+pub use acp::AcpCommands;
+pub use agent::AgentCommands;
\ No newline at end of file
@@ -0,0 +1,98 @@
+use anyhow::Result;
+use b00t_c0re_lib::LfmfSystem;
+use b00t_cli::failure_capture::{self, PendingFailure};
+use b00t_cli::session_memory::SessionMemory;
+use chrono::Utc;
+use std::io::{BufRead, IsTerminal, Write};
+
+/// Handle `b00t-cli run` - run a command (or report one the shell already ran) and learn from it
+///
+/// On failure, matching lfmf lessons are shown and the failure is remembered for the session.
+/// When the same tool later succeeds, offers to record what fixed it as a lesson.
+/// Returns the exit code to propagate, even when the bookkeeping fails.
+pub async fn handle_run(path: &str, command: &[String], exit_code: Option<i32>, no_prompt: bool) -> Result<i32> {
+    // 🤓 The shell hook passes --exit-code: the command already ran and its stderr went to the
+    //    terminal, so there's no error line and the command line stands in for it
+    let (exit_code, stderr) = match exit_code {
+        Some(code) => (code, String::new()),
+        None => {
+            let run = failure_capture::run_captured(command)?;
+            (run.exit_code, run.stderr)
+        }
+    };
+
+    // 🦨 The command already ran; a broken session file must never replace its exit code
+    if let Err(e) = learn_from_run(path, command, exit_code, &stderr, no_prompt).await {
+        eprintln!("⚠️ b00t-cli run couldn't update session memory: {:#}", e);
+    }
+    Ok(exit_code)
+}
+
+async fn learn_from_run(path: &str, command: &[String], exit_code: i32, stderr: &str, no_prompt: bool) -> Result<()> {
+    let command_line = command.join(" ");
+    let tool = failure_capture::tool_name(&command_line);
+    let mut memory = SessionMemory::load()?;
+
+    if exit_code != 0 {
+        let error_pattern = failure_capture::error_pattern(stderr).unwrap_or_else(|| command_line.clone());
+        memory.incr("failed_commands")?;
+        PendingFailure {
+            session_id: memory.metadata.session_id.clone(),
+            tool: tool.clone(),
+            command: command_line,
+            error_pattern: error_pattern.clone(),
+            exit_code,
+            failed_at: Utc::now(),
+        }.store(&mut memory)?;
+
+        show_advice(path, &tool, &error_pattern).await;
+        return Ok(());
+    }
+
+    if let Some(pending) = PendingFailure::load(&memory).filter(|p| p.tool == tool) {
+        PendingFailure::clear(&mut memory)?;
+        memory.incr("fixed_failures")?;
+        offer_lesson(path, &pending, no_prompt).await;
+    }
+    Ok(())
+}
+
+/// Lessons from the local ledger only: this runs after every failed shell command, so it
+/// must not start the grok server or wait on Qdrant
+async fn show_advice(path: &str, tool: &str, error_pattern: &str) {
+    let Ok(config) = LfmfSystem::load_config(path) else { return };
+    let lfmf_system = LfmfSystem::new(config);
+
+    match lfmf_system.rank_advice(tool, error_pattern, 3).await {
+        Ok(ranked) if !ranked.is_empty() => {
+            eprintln!("💡 lfmf lessons for {}:", tool);
+            for (lesson, _) in ranked {
+                eprintln!("  [{}] {}: {}", lesson.id, lesson.topic, lesson.content);
+            }
+            eprintln!("   Helped? b00t-cli advice {} \"upvote <id>\"", tool);
+        }
+        _ => {}
+    }
+}
+
+async fn offer_lesson(path: &str, pending: &PendingFailure, no_prompt: bool) {
+    let topic = pending.lesson_topic();
+    eprintln!("🎉 {} passes now; it failed earlier with: {}", pending.tool, pending.error_pattern);
+
+    if no_prompt || !std::io::stdin().is_terminal() {
+        eprintln!("💡 Record the fix: b00t-cli lfmf --tool {} --lesson \"{}: <what fixed it>\"", pending.tool, topic);
+        return;
+    }
+
+    eprint!("What fixed it? (one sentence, empty to skip): ");
+    let _ = std::io::stderr().flush();
+    let mut answer = String::new();
+    if std::io::stdin().lock().read_line(&mut answer).is_err() || answer.trim().is_empty() {
+        return;
+    }
+
+    let lesson = format!("{}: {}", topic, answer.trim());
+    if let Err(e) = super::lfmf::handle_lfmf(path, &pending.tool, &lesson, "repo", false, false).await {
+        eprintln!("{}", e);
+    }
+}
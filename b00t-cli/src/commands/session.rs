@@ -11,6 +11,10 @@ pub enum SessionCommands {
     Init {
         #[clap(long, help = "Budget limit in USD")]
         budget: Option<f64>,
+        #[clap(long, help = "Time limit in minutes")]
+        time_limit: Option<u32>,
+        #[clap(long, help = "Agent name")]
+        agent: Option<String>,
         #[clap(long, help = "Session name")]
         name: Option<String>,
     },
@@ -63,10 +67,9 @@ pub enum SessionCommands {
 impl SessionCommands {
     pub fn execute(&self, _path: &str) -> Result<()> {
         match self {
-            SessionCommands::Init { budget, name: _name } => {
-                // For now, using budget directly and no time_limit support in CLI args
+            SessionCommands::Init { budget, time_limit, agent, name: _name } => {
                 // name parameter exists but not used in handler yet
-                handle_session_init(budget, &None, None)
+                handle_session_init(budget, time_limit, agent.as_deref())
             }
             SessionCommands::Status => {
                 handle_session_status()
@@ -276,6 +279,8 @@ mod tests {
     fn test_session_commands_exist() {
         let init_cmd = SessionCommands::Init {
             budget: Some(10.0),
+            time_limit: None,
+            agent: None,
             name: Some("test-session".to_string()),
         };
         
@@ -1,16 +1,15 @@
 use anyhow::Result;
-use tiktoken_rs::o200k_base;
+use b00t_c0re_lib::tiktoken::count_tokens;
 
 pub fn handle_tiktoken(text: &str) -> Result<()> {
-    let bpe = o200k_base().map_err(|e| anyhow::anyhow!("Failed to load tokenizer: {e}"))?;
-    let tokens = bpe.encode_with_special_tokens(text);
-    println!("Token count: {}", tokens.len());
+    println!("Token count: {}", count_tokens(text)?);
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tiktoken_rs::o200k_base;
 
     #[test]
     fn test_token_count_basic() {
@@ -19,5 +18,6 @@ mod tests {
         let tokens = bpe.encode_with_special_tokens(text);
         // The expected token count may vary by tokenizer version, but should be > 0
         assert!(tokens.len() > 0);
+        assert_eq!(count_tokens(text).unwrap(), tokens.len());
     }
 }
@@ -0,0 +1,211 @@
+//! Failure capture for `b00t-cli run`
+//!
+//! Runs a command with its stderr tee'd to the terminal, distills an error pattern from what
+//! it printed, and remembers the failure in `SessionMemory` so a later success of the same
+//! tool in the same session can be recorded as an lfmf lesson.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+
+use crate::session_memory::SessionMemory;
+
+/// Only the tail of stderr is kept; the error summary is usually at the end
+pub const MAX_CAPTURED_STDERR: usize = 64 * 1024;
+
+/// Longest error pattern stored or sent to `advice`
+const MAX_PATTERN_CHARS: usize = 200;
+
+const PENDING_FAILURE_KEY: &str = "run_pending_failure";
+
+// 🤓 Wrappers that don't name the tool that failed
+const COMMAND_WRAPPERS: &[&str] = &["sudo", "env", "time", "nice", "nohup", "command", "exec"];
+
+fn ansi_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\x1b\[[0-9;?]*[A-Za-z]").unwrap())
+}
+
+fn error_line_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"(?i)\b(error|fatal|panicked|exception|failed|failure|not found|denied|cannot|unable)\b").unwrap())
+}
+
+fn error_prefix_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"(?i)^(?:error|fatal|e)(?:\[(\w+)\])?:\s*").unwrap())
+}
+
+/// A failed command waiting for the fix that makes it pass
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingFailure {
+    pub session_id: String,
+    pub tool: String,
+    pub command: String,
+    pub error_pattern: String,
+    pub exit_code: i32,
+    pub failed_at: DateTime<Utc>,
+}
+
+impl PendingFailure {
+    /// The pending failure recorded in this session, if any
+    pub fn load(memory: &SessionMemory) -> Option<Self> {
+        memory.get(PENDING_FAILURE_KEY)
+            .and_then(|json| serde_json::from_str::<Self>(json).ok())
+            .filter(|pending| pending.session_id == memory.metadata.session_id)
+    }
+
+    /// Remember this failure, replacing any earlier one
+    pub fn store(&self, memory: &mut SessionMemory) -> Result<()> {
+        memory.set(PENDING_FAILURE_KEY, &serde_json::to_string(self)?)
+    }
+
+    pub fn clear(memory: &mut SessionMemory) -> Result<()> {
+        memory.strings.remove(PENDING_FAILURE_KEY);
+        memory.save()
+    }
+
+    /// lfmf topic for this failure, e.g. "E0382 borrow of moved value"
+    pub fn lesson_topic(&self) -> String {
+        lesson_topic(&self.error_pattern)
+    }
+}
+
+/// Exit code and stderr tail of a finished command
+#[derive(Debug, Clone)]
+pub struct CapturedRun {
+    pub exit_code: i32,
+    pub stderr: String,
+}
+
+/// Run `command`, passing stdin/stdout through and tee-ing stderr to ours while capturing it
+pub fn run_captured(command: &[String]) -> Result<CapturedRun> {
+    let (program, args) = command.split_first()
+        .ok_or_else(|| anyhow::anyhow!("No command given. Usage: b00t-cli run -- <cmd> [args...]"))?;
+
+    let mut child = Command::new(program)
+        .args(args)
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to start '{}'", program))?;
+
+    let mut pipe = child.stderr.take().context("Failed to capture stderr")?;
+    let tee = std::thread::spawn(move || {
+        let mut captured = Vec::new();
+        let mut buf = [0u8; 8192];
+        let mut terminal = std::io::stderr();
+        while let Ok(n) = pipe.read(&mut buf) {
+            if n == 0 {
+                break;
+            }
+            let _ = terminal.write_all(&buf[..n]);
+            captured.extend_from_slice(&buf[..n]);
+            if captured.len() > MAX_CAPTURED_STDERR {
+                captured.drain(..captured.len() - MAX_CAPTURED_STDERR);
+            }
+        }
+        captured
+    });
+
+    let status = child.wait().context("Failed to wait for command")?;
+    let captured = tee.join().unwrap_or_default();
+
+    Ok(CapturedRun {
+        // 🦨 Killed by a signal: no code, report a generic failure
+        exit_code: status.code().unwrap_or(1),
+        stderr: String::from_utf8_lossy(&captured).to_string(),
+    })
+}
+
+/// The tool a command line runs, skipping `sudo`/`env`-style wrappers and `VAR=value` prefixes
+pub fn tool_name(command_line: &str) -> String {
+    command_line.split_whitespace()
+        .find(|word| !COMMAND_WRAPPERS.contains(word) && !word.starts_with('-') && !word.contains('='))
+        .map(|word| word.rsplit('/').next().unwrap_or(word).to_string())
+        .unwrap_or_else(|| "shell".to_string())
+}
+
+/// The most telling line of stderr: the first that reads like an error, else the last one
+pub fn error_pattern(stderr: &str) -> Option<String> {
+    let clean = ansi_regex().replace_all(stderr, "");
+    let lines: Vec<&str> = clean.lines().map(str::trim).filter(|l| !l.is_empty()).collect();
+    let line = lines.iter()
+        .find(|line| error_line_regex().is_match(line))
+        .or(lines.last())?;
+
+    let collapsed = line.split_whitespace().collect::<Vec<_>>().join(" ");
+    Some(collapsed.chars().take(MAX_PATTERN_CHARS).collect())
+}
+
+/// Short, path-free topic for an error pattern
+pub fn lesson_topic(error_pattern: &str) -> String {
+    let pattern = error_prefix_regex().replace(error_pattern.trim(), |caps: &regex::Captures| {
+        caps.get(1).map(|code| format!("{} ", code.as_str())).unwrap_or_default()
+    });
+
+    pattern.split_whitespace()
+        .filter(|word| !word.contains('/') && !word.contains('\\'))
+        .map(|word| word.trim_matches(|c: char| "`'\":,;".contains(c)))
+        .filter(|word| !word.is_empty())
+        .take(8)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tool_name() {
+        assert_eq!(tool_name("cargo build --release"), "cargo");
+        assert_eq!(tool_name("sudo -E /usr/bin/docker compose up"), "docker");
+        assert_eq!(tool_name("RUST_LOG=debug env just test"), "just");
+        assert_eq!(tool_name(""), "shell");
+    }
+
+    #[test]
+    fn test_error_pattern_and_topic() {
+        let stderr = "   Compiling b00t v0.1.0\n\x1b[1m\x1b[31merror[E0382]\x1b[0m: borrow of moved value: `config`\n  --> src/main.rs:42:9\n\nerror: could not compile `b00t`\n";
+        let pattern = error_pattern(stderr).unwrap();
+        assert_eq!(pattern, "error[E0382]: borrow of moved value: `config`");
+        assert_eq!(lesson_topic(&pattern), "E0382 borrow of moved value config");
+
+        assert_eq!(error_pattern("just: command not found\n").unwrap(), "just: command not found");
+        assert_eq!(error_pattern("warming up\nexit status 2\n").unwrap(), "exit status 2");
+        assert_eq!(error_pattern("\n  \n"), None);
+        assert_eq!(lesson_topic("fatal: not a git repository (or any of the parent directories): .git"),
+            "not a git repository (or any of the");
+    }
+
+    #[test]
+    fn test_pending_failure_scoped_to_session() {
+        let mut memory = SessionMemory::default();
+        let pending = PendingFailure {
+            session_id: memory.metadata.session_id.clone(),
+            tool: "cargo".to_string(),
+            command: "cargo build".to_string(),
+            error_pattern: "error: linker `cc` not found".to_string(),
+            exit_code: 101,
+            failed_at: Utc::now(),
+        };
+        memory.strings.insert(PENDING_FAILURE_KEY.to_string(), serde_json::to_string(&pending).unwrap());
+        assert_eq!(PendingFailure::load(&memory), Some(pending));
+        assert_eq!(PendingFailure::load(&memory).unwrap().lesson_topic(), "linker cc not found");
+
+        memory.metadata.session_id = "another-session".to_string();
+        assert_eq!(PendingFailure::load(&memory), None);
+    }
+
+    #[test]
+    fn test_run_captured() {
+        let run = run_captured(&["sh".to_string(), "-c".to_string(), "echo boom >&2; exit 3".to_string()]).unwrap();
+        assert_eq!(run.exit_code, 3);
+        assert_eq!(run.stderr.trim(), "boom");
+        assert!(run_captured(&[]).is_err());
+    }
+}
@@ -10,11 +10,13 @@ pub mod datum_docker;
 pub mod datum_k8s;
 pub mod datum_vscode;
 pub mod k8s;
+pub mod session_budget;
 pub mod session_memory;
 pub mod traits;
 pub mod utils;
 pub mod whoami;
 pub mod cloud_sync;
+pub mod failure_capture;
 pub use traits::*;
 
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
@@ -160,8 +162,18 @@ pub struct SessionState {
     pub agent_info: Option<AgentInfo>,
     pub hints: Vec<String>,
     pub last_activity: DateTime<Utc>,
+    #[serde(default)]
+    pub input_tokens: u64,
+    #[serde(default)]
+    pub output_tokens: u64,
+    // Thresholds already announced ("cost:80", "time:50"), so each warning fires once
+    #[serde(default)]
+    pub budget_warnings: Vec<String>,
 }
 
+/// Fractions of the budget/time limit at which a session warns the agent
+pub const SESSION_WARNING_THRESHOLDS: [u8; 3] = [50, 80, 95];
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct AgentInfo {
     pub name: String,
@@ -1307,6 +1319,9 @@ impl SessionState {
             agent_info,
             hints: vec![],
             last_activity: Utc::now(),
+            input_tokens: 0,
+            output_tokens: 0,
+            budget_warnings: vec![],
         }
     }
 
@@ -1339,12 +1354,104 @@ impl SessionState {
         self.last_activity = Utc::now();
     }
 
+    /// Fold an AI call's token usage into the session, priced when the model datum has pricing.
+    /// Returns any newly crossed threshold warnings.
+    pub fn record_usage(
+        &mut self,
+        usage: &b00t_c0re_lib::TokenUsage,
+        pricing: Option<&b00t_c0re_lib::datum_ai_model::ModelPricing>,
+    ) -> Vec<String> {
+        self.input_tokens += usage.input_tokens;
+        self.output_tokens += usage.output_tokens;
+        self.estimated_cost += pricing.map(|p| p.cost(usage)).unwrap_or(0.0);
+        self.last_activity = Utc::now();
+        self.take_limit_warnings()
+    }
+
+    pub fn elapsed_minutes(&self) -> i64 {
+        Utc::now().signed_duration_since(self.start_time).num_minutes()
+    }
+
+    /// Refuse further AI calls once the budget or time limit is exhausted
+    pub fn check_limits(&self) -> Result<()> {
+        if let Some(limit) = self.budget_limit {
+            if self.estimated_cost >= limit {
+                anyhow::bail!(
+                    "💸 Session budget exhausted: ${:.3} spent of ${:.2}. Start a new session with `b00t-cli session init --budget <usd>` to continue.",
+                    self.estimated_cost,
+                    limit
+                );
+            }
+        }
+
+        if let Some(limit) = self.time_limit_minutes {
+            let elapsed = self.elapsed_minutes();
+            if elapsed >= i64::from(limit) {
+                anyhow::bail!(
+                    "⏱️  Session time limit reached: {}m elapsed of {}m. Start a new session with `b00t-cli session init --time-limit <mins>` to continue.",
+                    elapsed,
+                    limit
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Warnings for thresholds crossed since the last call; each fires once per session
+    pub fn take_limit_warnings(&mut self) -> Vec<String> {
+        let mut warnings = Vec::new();
+
+        if let Some(limit) = self.budget_limit.filter(|l| *l > 0.0) {
+            let pct = self.estimated_cost / limit * 100.0;
+            if let Some(threshold) = self.crossed_threshold("cost", pct) {
+                warnings.push(format!(
+                    "💰 Session cost ${:.3} has passed {}% of the ${:.2} budget",
+                    self.estimated_cost, threshold, limit
+                ));
+            }
+        }
+
+        if let Some(limit) = self.time_limit_minutes.filter(|l| *l > 0) {
+            let elapsed = self.elapsed_minutes();
+            let pct = elapsed as f64 / f64::from(limit) * 100.0;
+            if let Some(threshold) = self.crossed_threshold("time", pct) {
+                warnings.push(format!(
+                    "⏱️  Session has used {}m, past {}% of the {}m time limit",
+                    elapsed, threshold, limit
+                ));
+            }
+        }
+
+        warnings
+    }
+
+    // Mark every threshold at or below `pct` as announced, returning the highest new one
+    fn crossed_threshold(&mut self, kind: &str, pct: f64) -> Option<u8> {
+        let mut crossed = None;
+        for threshold in SESSION_WARNING_THRESHOLDS {
+            let key = format!("{}:{}", kind, threshold);
+            if pct >= f64::from(threshold) && !self.budget_warnings.contains(&key) {
+                self.budget_warnings.push(key);
+                crossed = Some(threshold);
+            }
+        }
+        crossed
+    }
+
     pub fn get_status_line(&self) -> String {
         let duration = Utc::now().signed_duration_since(self.start_time);
         let elapsed_mins = duration.num_minutes();
 
-        let cost_info = if self.estimated_cost > 0.0 {
-            format!(" ${:.3}", self.estimated_cost)
+        let cost_info = match self.budget_limit {
+            Some(limit) => format!(" ${:.3}/${:.2}", self.estimated_cost, limit),
+            None if self.estimated_cost > 0.0 => format!(" ${:.3}", self.estimated_cost),
+            None => String::new(),
+        };
+
+        let total_tokens = self.input_tokens + self.output_tokens;
+        let token_info = if total_tokens > 0 {
+            format!(" {}tok", total_tokens)
         } else {
             String::new()
         };
@@ -1362,8 +1469,8 @@ impl SessionState {
             .unwrap_or_default();
 
         format!(
-            "🥾 {} cmds{}{}{}",
-            self.commands_run, cost_info, time_info, agent_info
+            "🥾 {} cmds{}{}{}{}",
+            self.commands_run, cost_info, token_info, time_info, agent_info
         )
     }
 }
@@ -39,7 +39,7 @@ use datum_mcp::McpDatum;
 use datum_vscode::VscodeDatum;
 use traits::*;
 
-use crate::commands::{AcpCommands, AiCommands, AppCommands, CliCommands, GrokCommands, InitCommands, K8sCommands, McpCommands, SessionCommands, WhatismyCommands};
+use crate::commands::{AcpCommands, AgentCommands, AiCommands, AppCommands, CliCommands, GrokCommands, InitCommands, K8sCommands, McpCommands, SessionCommands, WhatismyCommands};
 use crate::commands::learn::handle_learn;
 
 // Re-export commonly used functions for datum modules
@@ -108,6 +108,9 @@ Tips:
 - Body: <250 tokens, actionable, never repo-specific.
 - Affirmative: 'Do X for Y benefit', not 'Don't do X'.
 - Suitable tools: any with a b00t datum (TOML, learn/ dir, etc).
+
+Lessons that break these rules are rejected with a hint on how to fix them;
+--ai-review adds a model check, --force records anyway.
 "#
     )]
     Lfmf {
@@ -119,6 +122,10 @@ Tips:
         repo: bool,
         #[clap(long, group = "scope", help = "Record lesson globally (mutually exclusive with --repo)")]
         global: bool,
+        #[clap(long, help = "Record even if the lesson breaks the authoring rules")]
+        force: bool,
+        #[clap(long, conflicts_with = "force", help = "Also have an AI model review the lesson against the rules")]
+        ai_review: bool,
     },
     #[clap(
         about = "Get advice for syntax errors and debugging",
@@ -131,6 +138,8 @@ Usage:
   b00t-cli advice <tool> "<error_pattern>"
   b00t-cli advice <tool> list  # List all lessons for a tool
   b00t-cli advice <tool> search "<query>"  # Semantic search for lessons
+  b00t-cli advice <tool> upvote <id>  # Confirm a lesson that helped
+  b00t-cli advice <tool> downvote <id>  # Demote a lesson that was wrong
 
 Examples:
   b00t-cli advice just "Unknown start of token '.'"
@@ -141,7 +150,7 @@ Examples:
 
 The system will:
 1. Search for similar error patterns in the vector database
-2. Return relevant lessons with confidence scores
+2. Rank lessons by relevance and confidence (votes; unconfirmed lessons decay over time)
 3. Provide conversational debugging guidance
 4. Suggest specific solutions based on hive experience
 "#
@@ -149,11 +158,39 @@ The system will:
     Advice {
         #[clap(help = "Tool name")]
         tool: String,
-        #[clap(help = "Error pattern to get advice for, 'list' to show all lessons, or 'search <query>'")]
+        #[clap(help = "Error pattern to get advice for, 'list', 'search <query>', or 'upvote|downvote <id>'")]
         query: String,
         #[clap(long, help = "Maximum number of results to return (default: 5)")]
         count: Option<usize>,
     },
+    #[clap(
+        about = "Run a command and learn from its failures",
+        long_about = r#"
+Runs a command, passing its output through. When it fails, the error is distilled from
+stderr and matching lfmf lessons for the tool are shown. When the same tool later succeeds
+in this session, you're offered to record what fixed it as a lesson.
+
+Usage:
+  b00t-cli run -- <cmd> [args...]
+  b00t-cli run --exit-code <N> -- <cmd line>   # Report a command the shell already ran
+
+Examples:
+  b00t-cli run -- cargo build
+  b00t-cli run --no-prompt -- just test
+
+Shell hook (bash): export B00T_RUN_HOOK=1 before sourcing _b00t_.bashrc to report every
+interactive command via --exit-code.
+"#
+    )]
+    // 🦨 Not exposed via b00t-mcp: running arbitrary commands is the agent's own shell's job
+    Run {
+        #[clap(long, help = "Exit code of a command that already ran (shell hook mode; nothing is executed)")]
+        exit_code: Option<i32>,
+        #[clap(long, help = "Print the lfmf hint instead of asking what fixed the failure")]
+        no_prompt: bool,
+        #[clap(trailing_var_arg = true, allow_hyphen_values = true, required = true, help = "Command and arguments")]
+        command: Vec<String>,
+    },
     #[clap(about = "MCP (Model Context Protocol) server management")]
     Mcp {
         #[clap(subcommand)]
@@ -232,9 +269,22 @@ The system will:
     },
     #[clap(about = "Agent Coordination Protocol (ACP) - send messages to agents")]
     Acp {
+        #[clap(long, global = true, env = "B00T_TRANSPORT", help = "Transport URL: nats://, redis:// or memory:// (default: NATS_URL, else redis://localhost:6379)")]
+        transport: Option<String>,
         #[clap(subcommand)]
         acp_command: AcpCommands,
     },
+    #[clap(about = "Agent coordination (discovery, messaging, delegation, voting) over any ACP transport")]
+    Agent {
+        #[clap(long, global = true, env = "B00T_TRANSPORT", help = "Transport URL: nats://, redis:// or memory:// (default: NATS_URL, else redis://localhost:6379)")]
+        transport: Option<String>,
+        #[clap(long, global = true, help = "Hive namespace (default: account.<user>)")]
+        namespace: Option<String>,
+        #[clap(long, global = true, env = "B00T_AGENT_ID", help = "Agent id to act as (default: _B00T_Agent, else operator.<user>)")]
+        agent_id: Option<String>,
+        #[clap(subcommand)]
+        agent_command: AgentCommands,
+    },
     #[clap(about = "Learn about topics with guided documentation")]
     // 🤓 ENTANGLED: b00t-mcp/src/mcp_tools.rs LearnCommand
     // When this changes, update b00t-mcp LearnCommand structure
@@ -1015,6 +1065,10 @@ pub fn handle_session_status() -> Result<()> {
     let session = SessionState::load()?;
     println!("{}", session.get_status_line());
 
+    if let Err(e) = session.check_limits() {
+        println!("🚫 AI calls refused: {}", e);
+    }
+
     if !session.hints.is_empty() {
         println!("💡 Hints:");
         for hint in &session.hints {
@@ -1038,6 +1092,10 @@ pub fn handle_session_update(cost: &Option<f64>, hint: Option<&str>) -> Result<(
         session.hints.push(hint.to_string());
     }
 
+    for warning in session.take_limit_warnings() {
+        eprintln!("{}", warning);
+    }
+
     session.save()?;
     Ok(())
 }
@@ -1170,12 +1228,18 @@ async fn main() {
                 std::process::exit(1);
             }
         }
-        Some(Commands::Acp { acp_command }) => {
-            if let Err(e) = acp_command.execute().await {
+        Some(Commands::Acp { transport, acp_command }) => {
+            if let Err(e) = acp_command.execute(transport.as_deref()).await {
                 eprintln!("ACP Error: {}", e);
                 std::process::exit(1);
             }
         }
+        Some(Commands::Agent { transport, namespace, agent_id, agent_command }) => {
+            if let Err(e) = agent_command.execute(agent_id.as_deref(), transport.as_deref(), namespace.as_deref()).await {
+                eprintln!("Agent Error: {}", e);
+                std::process::exit(1);
+            }
+        }
         Some(Commands::Learn { topic, topic_flag }) => {
             // 🦨 MCP compatibility: merge positional and flag arguments
             let effective_topic = topic.as_ref().or(topic_flag.as_ref());
@@ -1196,12 +1260,12 @@ async fn main() {
                 }
             };
 
-            if let Err(e) = rt.block_on(handle_grok_command(grok_command.clone())) {
+            if let Err(e) = rt.block_on(handle_grok_command(grok_command.clone(), &cli.path)) {
                 eprintln!("Error: {}", e);
                 std::process::exit(1);
             }
         }
-        Some(Commands::Lfmf { tool, lesson, repo, global }) => {
+        Some(Commands::Lfmf { tool, lesson, repo, global, force, ai_review }) => {
             // Validate required fields
             let tool = match tool {
                 Some(t) => t,
@@ -1219,11 +1283,20 @@ async fn main() {
             };
             // Determine scope
             let scope = if *global { "global" } else { "repo" };
-            if let Err(e) = commands::lfmf::handle_lfmf(&cli.path, &tool, &lesson, scope) {
+            if let Err(e) = commands::lfmf::handle_lfmf(&cli.path, &tool, &lesson, scope, *force, *ai_review).await {
                 eprintln!("Error: {}", e);
                 std::process::exit(1);
             }
         }
+        Some(Commands::Run { exit_code, no_prompt, command }) => {
+            match commands::run::handle_run(&cli.path, command, *exit_code, *no_prompt).await {
+                Ok(code) => std::process::exit(code),
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
         Some(Commands::Advice { tool, query, count }) => {
             use crate::commands::advice::handle_advice;
 
@@ -0,0 +1,152 @@
+//! Session budget enforcement for AI calls
+//!
+//! Bridges `B00tAiClient` to the on-disk `SessionState`: every call is refused once the
+//! session budget or time limit is exhausted, and the tokens it consumed are priced from
+//! the `*.ai_model.toml` datums and folded back into the session.
+
+use anyhow::Result;
+use b00t_c0re_lib::datum_ai_model::{ModelPricing, ModelRegistry};
+use b00t_c0re_lib::{AiClientConfig, B00tAiClient, B00tResult, TokenUsage, UsageGuard};
+use std::sync::{Arc, Mutex};
+
+use crate::cloud_sync::CloudSyncClient;
+use crate::session_memory::SessionMemory;
+use crate::{SessionState, get_expanded_path};
+
+/// `UsageGuard` backed by the current `SessionState` file
+pub struct SessionBudgetGuard {
+    registry: ModelRegistry,
+    // 🤓 Serialize load/modify/save of the session file across concurrent calls
+    lock: Mutex<()>,
+}
+
+impl SessionBudgetGuard {
+    pub fn new(registry: ModelRegistry) -> Self {
+        Self {
+            registry,
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Build a guard using the model datums found under the b00t config path
+    pub fn from_path(path: &str) -> Result<Self> {
+        let registry = ModelRegistry::load_from_dir(&get_expanded_path(path)?)?;
+        Ok(Self::new(registry))
+    }
+
+    /// Pricing for a provider model id, if a datum declares it
+    pub fn pricing_for(&self, model: &str) -> Option<ModelPricing> {
+        self.registry.find_model(model).and_then(|datum| datum.pricing())
+    }
+}
+
+impl UsageGuard for SessionBudgetGuard {
+    fn check(&self) -> B00tResult<()> {
+        let _lock = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        SessionState::load()?.check_limits()
+    }
+
+    fn record(&self, _provider: &str, model: &str, usage: &TokenUsage) -> B00tResult<()> {
+        let _lock = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let pricing = self.pricing_for(model);
+        if pricing.is_none() {
+            eprintln!(
+                "⚠️ No pricing for model '{}'; add input/output_cost_per_1k_tokens to its ai_model datum",
+                model
+            );
+        }
+
+        let mut session = SessionState::load()?;
+        for warning in session.record_usage(usage, pricing.as_ref()) {
+            eprintln!("{}", warning);
+        }
+        session.save()
+    }
+}
+
+/// Create an AI client whose calls are metered against the current session
+pub fn budgeted_ai_client(config: AiClientConfig, path: &str) -> Result<B00tAiClient> {
+    let guard = SessionBudgetGuard::from_path(path)?;
+    Ok(B00tAiClient::new(config)?.with_usage_guard(Arc::new(guard)))
+}
+
+/// AI client from the cloud-synced config (or defaults) plus `<PROVIDER>_API_KEY`, metered by the session budget
+///
+/// 🤓 Every CLI command that talks to a model goes through here, so none bypasses the budget.
+pub fn session_ai_client(path: &str) -> Result<B00tAiClient> {
+    let config = SessionMemory::load().ok()
+        .and_then(|memory| CloudSyncClient::get_cached_ai_config(&memory))
+        .unwrap_or_default()
+        .with_env_keys();
+    budgeted_ai_client(config, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session_with(budget: Option<f64>, time_limit: Option<u32>) -> SessionState {
+        let mut session = SessionState::new(None);
+        session.budget_limit = budget;
+        session.time_limit_minutes = time_limit;
+        session
+    }
+
+    #[test]
+    fn test_record_usage_prices_tokens_and_warns_once() {
+        let mut session = session_with(Some(1.0), None);
+        let pricing = ModelPricing {
+            input_cost_per_1k: 0.5,
+            output_cost_per_1k: 0.5,
+        };
+        let usage = TokenUsage {
+            input_tokens: 600,
+            output_tokens: 400,
+            estimated: false,
+        };
+
+        let warnings = session.record_usage(&usage, Some(&pricing));
+        assert_eq!(session.input_tokens, 600);
+        assert_eq!(session.output_tokens, 400);
+        assert!((session.estimated_cost - 0.5).abs() < 1e-9);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("50%"));
+
+        // Same threshold does not fire twice
+        let warnings = session.record_usage(&TokenUsage::default(), Some(&pricing));
+        assert!(warnings.is_empty());
+        assert!(session.check_limits().is_ok());
+    }
+
+    #[test]
+    fn test_budget_exhausted_refuses() {
+        let mut session = session_with(Some(0.10), None);
+        session.increment_command(0.25);
+        let warnings = session.take_limit_warnings();
+        assert!(warnings[0].contains("95%"));
+        assert_eq!(session.budget_warnings.len(), 3);
+
+        let err = session.check_limits().unwrap_err();
+        assert!(err.to_string().contains("budget exhausted"));
+    }
+
+    #[test]
+    fn test_time_limit_refuses() {
+        let mut session = session_with(None, Some(10));
+        session.start_time = chrono::Utc::now() - chrono::Duration::minutes(11);
+        let err = session.check_limits().unwrap_err();
+        assert!(err.to_string().contains("time limit"));
+
+        session.time_limit_minutes = Some(30);
+        assert!(session.check_limits().is_ok());
+    }
+
+    #[test]
+    fn test_unpriced_usage_counts_tokens_only() {
+        let mut session = session_with(None, None);
+        session.record_usage(&TokenUsage::estimate("hello there", "general kenobi"), None);
+        assert!(session.input_tokens > 0);
+        assert_eq!(session.estimated_cost, 0.0);
+        assert!(session.get_status_line().contains("tok"));
+    }
+}
@@ -0,0 +1,531 @@
+// 🤓 Native structure-aware chunking: Markdown headings and fenced code, Rust/Python/TS
+//    item boundaries, packed to a token budget with overlap. No Python required.
+use crate::Chunker;
+use crate::errors::Result;
+use regex::Regex;
+use std::sync::OnceLock;
+use tiktoken_rs::{CoreBPE, o200k_base};
+
+pub const DEFAULT_MAX_TOKENS: usize = 512;
+pub const DEFAULT_OVERLAP_TOKENS: usize = 64;
+
+/// A chunk of source text and where it came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextChunk {
+    pub text: String,
+    /// Enclosing Markdown headings or code items, outermost first
+    pub heading_path: Vec<String>,
+    /// 1-based inclusive line range in the source, when known
+    pub line_range: Option<(usize, usize)>,
+}
+
+impl TextChunk {
+    /// Chunk with no structural metadata, as produced by plain text chunkers
+    pub fn plain(text: String) -> Self {
+        Self {
+            text,
+            heading_path: Vec::new(),
+            line_range: None,
+        }
+    }
+}
+
+/// The process-wide o200k tokenizer, `None` if it failed to load
+///
+/// 🤓 b00t-c0re-lib::tiktoken counts with this same instance, so chunk budgets and
+///    session token accounting agree (and the BPE ranks load once).
+pub fn tokenizer() -> Option<&'static CoreBPE> {
+    static BPE: OnceLock<Option<CoreBPE>> = OnceLock::new();
+    BPE.get_or_init(|| o200k_base().ok()).as_ref()
+}
+
+/// Count tokens with tiktoken's o200k encoding, ~4 chars/token if it can't load
+pub fn count_tokens(text: &str) -> usize {
+    match tokenizer() {
+        Some(bpe) => bpe.encode_with_special_tokens(text).len(),
+        None => text.len().div_ceil(4),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceKind {
+    /// Markdown, and text with no source (digests) or from an extension-less URL
+    Markdown,
+    /// Anything unrecognised: paragraphs only, so `#` comments in shell/TOML/YAML aren't headings
+    PlainText,
+    Rust,
+    Python,
+    TypeScript,
+}
+
+impl SourceKind {
+    /// Guess from a file name or URL extension
+    pub fn detect(source: Option<&str>) -> Self {
+        let Some(source) = source else {
+            return Self::Markdown;
+        };
+        let path = source.split(['?', '#']).next().unwrap_or(source);
+        let file_name = path.rsplit('/').next().unwrap_or(path);
+        let Some((_, extension)) = file_name.rsplit_once('.') else {
+            // 🤓 Web pages arrive converted to Markdown; a bare file name is just text
+            let is_url = source.starts_with("http://") || source.starts_with("https://");
+            return if is_url { Self::Markdown } else { Self::PlainText };
+        };
+        match extension.to_lowercase().as_str() {
+            "md" | "markdown" | "mdx" => Self::Markdown,
+            "rs" => Self::Rust,
+            "py" | "pyi" => Self::Python,
+            "ts" | "tsx" | "js" | "jsx" | "mjs" | "cjs" => Self::TypeScript,
+            _ => Self::PlainText,
+        }
+    }
+
+    fn item_pattern(self) -> Option<&'static Regex> {
+        static RUST: OnceLock<Regex> = OnceLock::new();
+        static PYTHON: OnceLock<Regex> = OnceLock::new();
+        static TYPESCRIPT: OnceLock<Regex> = OnceLock::new();
+        let pattern = match self {
+            Self::Markdown | Self::PlainText => return None,
+            Self::Rust => RUST.get_or_init(|| Regex::new(
+                r#"^\s*(pub(\([^)]*\))?\s+)?((async|const|unsafe|default|extern\s+"[^"]*")\s+)*(fn|struct|enum|trait|impl|mod|union|macro_rules!)[\s<{(]"#
+            ).expect("valid rust item regex")),
+            Self::Python => PYTHON.get_or_init(|| Regex::new(
+                r"^\s*((async\s+)?def|class)\s+\w+"
+            ).expect("valid python item regex")),
+            Self::TypeScript => TYPESCRIPT.get_or_init(|| Regex::new(
+                r"^\s*((export\s+)?(default\s+)?(declare\s+)?(abstract\s+)?(async\s+)?(function\*?|class|interface|enum|namespace)\s|(export\s+)?(const|let|var)\s+\w+\s*(:[^=]+)?=\s*(async\s+)?(\([^)]*\)|\w+)\s*(:[^=]+)?=>|((public|private|protected|static|readonly|async|get|set)\s+)*\w+\s*(<[^>]*>)?\([^)]*\)\s*(:\s*[^{]+)?\{\s*$)"
+            ).expect("valid typescript item regex")),
+        };
+        Some(pattern)
+    }
+
+    /// Comment/attribute/decorator lines that belong to the item below them
+    fn is_prelude(self, trimmed: &str) -> bool {
+        match self {
+            Self::Markdown | Self::PlainText => false,
+            Self::Rust => trimmed.starts_with("#[") || trimmed.starts_with("//"),
+            Self::Python => trimmed.starts_with('@') || trimmed.starts_with('#'),
+            Self::TypeScript => ["@", "//", "/*", "*"].iter().any(|p| trimmed.starts_with(p)),
+        }
+    }
+}
+
+/// A contiguous run of lines that should stay together where possible
+#[derive(Debug)]
+struct Block {
+    start: usize, // 0-based, inclusive
+    end: usize,   // 0-based, exclusive
+    path: Vec<String>,
+    /// Starts a new chunk even if it would fit in the current one (Markdown headings)
+    hard_break: bool,
+}
+
+/// Markdown- and code-aware chunker
+///
+/// Splits on Markdown headings (never inside fenced code) and on Rust/Python/TypeScript
+/// item boundaries, then packs neighbouring blocks from the same section up to
+/// `max_tokens`. Blocks that are still too large are split on lines, repeating the last
+/// `overlap_tokens` worth of lines at the start of the next chunk.
+pub struct StructuredChunker {
+    max_tokens: usize,
+    overlap_tokens: usize,
+    count: fn(&str) -> usize,
+}
+
+impl Default for StructuredChunker {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_TOKENS)
+    }
+}
+
+impl StructuredChunker {
+    pub fn new(max_tokens: usize) -> Self {
+        Self {
+            max_tokens: max_tokens.max(1),
+            overlap_tokens: DEFAULT_OVERLAP_TOKENS.min(max_tokens / 2),
+            count: count_tokens,
+        }
+    }
+
+    pub fn with_overlap(mut self, overlap_tokens: usize) -> Self {
+        self.overlap_tokens = overlap_tokens.min(self.max_tokens / 2);
+        self
+    }
+
+    /// Replace the tiktoken counter (e.g. to match a different embedding model)
+    pub fn with_token_counter(mut self, count: fn(&str) -> usize) -> Self {
+        self.count = count;
+        self
+    }
+
+    pub fn chunk_source(&self, content: &str, kind: SourceKind) -> Vec<TextChunk> {
+        let lines: Vec<&str> = content.lines().collect();
+        // 🤓 Count per line once; +1 approximates the newline joining them
+        let line_tokens: Vec<usize> = lines.iter().map(|l| (self.count)(l) + 1).collect();
+
+        let blocks = match (kind, kind.item_pattern()) {
+            (SourceKind::PlainText, _) => paragraph_blocks(&lines),
+            (_, None) => markdown_blocks(&lines),
+            (_, Some(pattern)) => code_blocks(&lines, kind, pattern),
+        };
+        self.pack(&lines, &line_tokens, blocks)
+    }
+
+    fn pack(&self, lines: &[&str], line_tokens: &[usize], blocks: Vec<Block>) -> Vec<TextChunk> {
+        let tokens = |start: usize, end: usize| line_tokens[start..end].iter().sum::<usize>();
+        let mut chunks = Vec::new();
+        let mut current: Option<Block> = None;
+
+        for block in blocks {
+            let block_tokens = tokens(block.start, block.end);
+            let same_section = current.as_ref()
+                .is_some_and(|c| !block.hard_break && c.path.first() == block.path.first());
+
+            if let Some(cur) = current.as_mut() {
+                if same_section && tokens(cur.start, block.end) <= self.max_tokens {
+                    cur.end = block.end;
+                    cur.path = common_prefix(&cur.path, &block.path);
+                    continue;
+                }
+            }
+
+            let previous_end = current.as_ref().map(|c| c.end);
+            if let Some(done) = current.take() {
+                push_chunk(&mut chunks, lines, done.start, done.end, done.path);
+            }
+
+            if block_tokens > self.max_tokens {
+                self.split_block(&mut chunks, lines, line_tokens, block);
+                continue;
+            }
+
+            // Continue the section with a little context from the chunk before
+            let mut start = block.start;
+            if let (true, Some(prev_end)) = (same_section, previous_end) {
+                let overlap = self.overlap_start(line_tokens, prev_end);
+                if tokens(overlap, block.end) <= self.max_tokens {
+                    start = overlap;
+                }
+            }
+            current = Some(Block { start, ..block });
+        }
+
+        if let Some(done) = current {
+            push_chunk(&mut chunks, lines, done.start, done.end, done.path);
+        }
+        chunks
+    }
+
+    /// Earliest line such that `[line, end)` fits in the overlap budget
+    fn overlap_start(&self, line_tokens: &[usize], end: usize) -> usize {
+        let mut start = end;
+        let mut total = 0;
+        while start > 0 && total + line_tokens[start - 1] <= self.overlap_tokens {
+            start -= 1;
+            total += line_tokens[start];
+        }
+        start
+    }
+
+    fn split_block(&self, chunks: &mut Vec<TextChunk>, lines: &[&str], line_tokens: &[usize], block: Block) {
+        let mut start = block.start;
+        while start < block.end {
+            if line_tokens[start] > self.max_tokens {
+                // 🦨 A single line over budget (minified code, giant table row): cut by chars
+                let chars: Vec<char> = lines[start].chars().collect();
+                let pieces = line_tokens[start].div_ceil(self.max_tokens);
+                for piece in chars.chunks(chars.len().div_ceil(pieces).max(1)) {
+                    chunks.push(TextChunk {
+                        text: piece.iter().collect(),
+                        heading_path: block.path.clone(),
+                        line_range: Some((start + 1, start + 1)),
+                    });
+                }
+                start += 1;
+                continue;
+            }
+
+            let mut end = start;
+            let mut total = 0;
+            while end < block.end && total + line_tokens[end] <= self.max_tokens {
+                total += line_tokens[end];
+                end += 1;
+            }
+            push_chunk(chunks, lines, start, end, block.path.clone());
+
+            if end >= block.end {
+                break;
+            }
+            start = self.overlap_start(line_tokens, end).max(start + 1);
+        }
+    }
+}
+
+impl Chunker for StructuredChunker {
+    fn chunk(&self, content: &str) -> Result<Vec<String>> {
+        Ok(self.chunk_sections(content, None)?.into_iter().map(|c| c.text).collect())
+    }
+
+    fn chunk_sections(&self, content: &str, source: Option<&str>) -> Result<Vec<TextChunk>> {
+        Ok(self.chunk_source(content, SourceKind::detect(source)))
+    }
+}
+
+fn push_chunk(chunks: &mut Vec<TextChunk>, lines: &[&str], mut start: usize, mut end: usize, path: Vec<String>) {
+    while start < end && lines[start].trim().is_empty() {
+        start += 1;
+    }
+    while end > start && lines[end - 1].trim().is_empty() {
+        end -= 1;
+    }
+    if start == end {
+        return;
+    }
+    chunks.push(TextChunk {
+        text: lines[start..end].join("\n"),
+        heading_path: path,
+        line_range: Some((start + 1, end)),
+    });
+}
+
+fn common_prefix(a: &[String], b: &[String]) -> Vec<String> {
+    a.iter().zip(b).take_while(|(x, y)| x == y).map(|(x, _)| x.clone()).collect()
+}
+
+/// ATX heading level and title: `## Install ##` -> (2, "Install")
+fn markdown_heading(line: &str) -> Option<(usize, String)> {
+    let indent = line.len() - line.trim_start().len();
+    if indent > 3 {
+        return None;
+    }
+    let trimmed = line.trim_start();
+    let level = trimmed.chars().take_while(|&c| c == '#').count();
+    let rest = &trimmed[level..];
+    if !(1..=6).contains(&level) || !(rest.is_empty() || rest.starts_with([' ', '\t'])) {
+        return None;
+    }
+    Some((level, rest.trim().trim_end_matches('#').trim_end().to_string()))
+}
+
+fn markdown_blocks(lines: &[&str]) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut headings: Vec<(usize, String)> = Vec::new();
+    let mut fence: Option<&str> = None;
+    let mut open: Option<usize> = None;
+
+    let path = |headings: &[(usize, String)]| headings.iter().map(|(_, t)| t.clone()).collect::<Vec<_>>();
+
+    for (i, line) in lines.iter().enumerate() {
+        let trimmed = line.trim_start();
+
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+                let start = open.take().unwrap_or(i);
+                blocks.push(Block { start, end: i + 1, path: path(&headings), hard_break: false });
+            }
+            continue;
+        }
+
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            if let Some(start) = open.take() {
+                blocks.push(Block { start, end: i, path: path(&headings), hard_break: false });
+            }
+            fence = Some(&trimmed[..3]);
+            open = Some(i);
+            continue;
+        }
+
+        if let Some((level, title)) = markdown_heading(line) {
+            if let Some(start) = open.take() {
+                blocks.push(Block { start, end: i, path: path(&headings), hard_break: false });
+            }
+            while headings.last().is_some_and(|(l, _)| *l >= level) {
+                headings.pop();
+            }
+            headings.push((level, title));
+            blocks.push(Block { start: i, end: i + 1, path: path(&headings), hard_break: true });
+            continue;
+        }
+
+        if line.trim().is_empty() {
+            if let Some(start) = open.take() {
+                blocks.push(Block { start, end: i, path: path(&headings), hard_break: false });
+            }
+        } else if open.is_none() {
+            open = Some(i);
+        }
+    }
+
+    // Trailing paragraph, or an unterminated fence
+    if let Some(start) = open {
+        blocks.push(Block { start, end: lines.len(), path: path(&headings), hard_break: false });
+    }
+    blocks
+}
+
+/// Runs of non-blank lines
+fn paragraph_blocks(lines: &[&str]) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut open: Option<usize> = None;
+    for (i, line) in lines.iter().enumerate() {
+        match (line.trim().is_empty(), open) {
+            (true, Some(start)) => {
+                blocks.push(Block { start, end: i, path: Vec::new(), hard_break: false });
+                open = None;
+            }
+            (false, None) => open = Some(i),
+            _ => {}
+        }
+    }
+    if let Some(start) = open {
+        blocks.push(Block { start, end: lines.len(), path: Vec::new(), hard_break: false });
+    }
+    blocks
+}
+
+fn item_label(line: &str) -> String {
+    let label = line.trim().trim_end_matches(['{', ':']).trim_end();
+    label.chars().take(80).collect()
+}
+
+fn code_blocks(lines: &[&str], kind: SourceKind, pattern: &Regex) -> Vec<Block> {
+    const TS_KEYWORDS: [&str; 7] = ["if", "for", "while", "switch", "catch", "return", "else"];
+    let mut blocks = Vec::new();
+    let mut start = 0;
+    let mut path: Vec<String> = Vec::new();
+    let mut top_level: Option<String> = None;
+
+    for (i, line) in lines.iter().enumerate() {
+        let indent = line.len() - line.trim_start().len();
+        // 🤓 Top-level items and one level of nesting (impl/class methods)
+        if indent > 4 || !pattern.is_match(line) {
+            continue;
+        }
+        if kind == SourceKind::TypeScript {
+            let first_word = line.trim_start().split(|c: char| !c.is_alphanumeric()).next().unwrap_or("");
+            if TS_KEYWORDS.contains(&first_word) {
+                continue;
+            }
+        }
+
+        // Pull attached doc comments/attributes/decorators into this item
+        let mut item_start = i;
+        while item_start > start && kind.is_prelude(lines[item_start - 1].trim_start()) {
+            item_start -= 1;
+        }
+
+        if item_start > start {
+            blocks.push(Block { start, end: item_start, path: path.clone(), hard_break: false });
+        }
+
+        let label = item_label(line);
+        path = if indent == 0 {
+            top_level = Some(label.clone());
+            vec![label]
+        } else {
+            top_level.iter().cloned().chain(std::iter::once(label)).collect()
+        };
+        start = item_start;
+    }
+
+    if start < lines.len() {
+        blocks.push(Block { start, end: lines.len(), path, hard_break: false });
+    }
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Deterministic budgets for tests: one token per word
+    fn words(text: &str) -> usize {
+        text.split_whitespace().count()
+    }
+
+    fn chunker(max_tokens: usize) -> StructuredChunker {
+        StructuredChunker::new(max_tokens).with_token_counter(words).with_overlap(0)
+    }
+
+    #[test]
+    fn test_markdown_headings_and_fences() {
+        let content = "# Guide\n\nIntro text.\n\n## Install\n\nRun this:\n\n```sh\n# not a heading\ncargo install b00t\n```\n\n## Usage\n\nUse it.";
+        let chunks = chunker(100).chunk_source(content, SourceKind::Markdown);
+
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0].heading_path, vec!["Guide"]);
+        assert_eq!(chunks[0].line_range, Some((1, 3)));
+        assert_eq!(chunks[1].heading_path, vec!["Guide", "Install"]);
+        assert!(chunks[1].text.contains("# not a heading"));
+        assert!(chunks[1].text.ends_with("```"));
+        assert_eq!(chunks[1].line_range, Some((5, 12)));
+        assert_eq!(chunks[2].heading_path, vec!["Guide", "Usage"]);
+    }
+
+    #[test]
+    fn test_rust_item_boundaries() {
+        let content = "use std::fmt;\n\n/// Adds\n#[inline]\npub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n\nimpl Foo {\n    fn bar(&self) {}\n}\n";
+        let chunks = chunker(30).chunk_source(content, SourceKind::Rust);
+
+        let add = chunks.iter().find(|c| c.text.contains("pub fn add")).unwrap();
+        assert!(add.text.starts_with("/// Adds"));
+        assert_eq!(add.heading_path, vec!["pub fn add(a: i32, b: i32) -> i32"]);
+        assert_eq!(add.line_range, Some((3, 7)));
+
+        let imp = chunks.iter().find(|c| c.text.contains("fn bar")).unwrap();
+        assert_eq!(imp.heading_path[0], "impl Foo");
+    }
+
+    #[test]
+    fn test_python_and_typescript_boundaries() {
+        let py = "import os\n\nclass Foo:\n    @property\n    def bar(self):\n        return 1\n\ndef baz():\n    pass\n";
+        let chunks = chunker(10).chunk_source(py, SourceKind::Python);
+        assert!(chunks.iter().any(|c| c.heading_path == vec!["class Foo", "def bar(self)"] && c.text.trim_start().starts_with("@property")));
+        assert!(chunks.iter().any(|c| c.heading_path == vec!["def baz()"]));
+
+        let ts = "export function a() {\n  if (x) {\n    return 1;\n  }\n}\nexport const b = (y: number) => y * 2;\n";
+        let chunks = chunker(40).chunk_source(ts, SourceKind::TypeScript);
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].heading_path, vec!["export function a()"]);
+    }
+
+    #[test]
+    fn test_oversized_block_splits_with_overlap() {
+        let content = (1..=10).map(|i| format!("line {}", i)).collect::<Vec<_>>().join("\n");
+        // 3 tokens per line (2 words + newline): 3 lines per chunk, 1 line of overlap
+        let chunks = StructuredChunker::new(9).with_token_counter(words).with_overlap(3)
+            .chunk_source(&content, SourceKind::Markdown);
+
+        assert_eq!(chunks[0].line_range, Some((1, 3)));
+        assert_eq!(chunks[1].line_range, Some((3, 5)));
+        assert!(chunks[1].text.starts_with("line 3"));
+        assert_eq!(chunks.last().unwrap().line_range.unwrap().1, 10);
+        assert!(chunks.iter().all(|c| words(&c.text) + c.text.lines().count() <= 9));
+    }
+
+    #[test]
+    fn test_source_kind_detection() {
+        assert_eq!(SourceKind::detect(Some("src/lib.rs")), SourceKind::Rust);
+        assert_eq!(SourceKind::detect(Some("https://x.dev/a.py?raw=1")), SourceKind::Python);
+        assert_eq!(SourceKind::detect(Some("app.tsx")), SourceKind::TypeScript);
+        assert_eq!(SourceKind::detect(Some("README.md")), SourceKind::Markdown);
+        assert_eq!(SourceKind::detect(None), SourceKind::Markdown);
+        assert_eq!(SourceKind::detect(Some("https://docs.rs/tokio/latest")), SourceKind::Markdown);
+        assert_eq!(SourceKind::detect(Some("install.sh")), SourceKind::PlainText);
+        assert_eq!(SourceKind::detect(Some("Cargo.toml")), SourceKind::PlainText);
+        assert_eq!(SourceKind::detect(Some("Dockerfile")), SourceKind::PlainText);
+
+        // Shell comments stay with their code instead of becoming headings
+        let script = "# install deps\napt-get update\n# build\ncargo build";
+        let chunks = chunker(100).chunk_source(script, SourceKind::PlainText);
+        assert_eq!(chunks.len(), 1);
+        assert!(chunks[0].heading_path.is_empty());
+    }
+
+    #[test]
+    fn test_count_tokens() {
+        assert_eq!(count_tokens(""), 0);
+        assert!(count_tokens("hello world") >= 2);
+    }
+}
@@ -0,0 +1,341 @@
+// 🤓 Pluggable embedding backends: any OpenAI-compatible /v1/embeddings endpoint
+//    (Ollama, OpenAI, LiteLLM, vLLM...) or an offline hashing embedder for tests/CI
+use crate::errors::{GrokError, Result};
+use async_openai::{Client, config::OpenAIConfig};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+/// Default Ollama embedding model and its vector size
+pub const DEFAULT_EMBEDDING_MODEL: &str = "nomic-embed-text";
+pub const DEFAULT_EMBEDDING_DIMENSION: usize = 768;
+
+#[async_trait]
+pub trait Embedder: Send + Sync {
+    /// Model identifier, recorded so collections can be traced back to their embedder
+    fn model_name(&self) -> &str;
+
+    /// Vector size produced by `embed`; drives the Qdrant collection's vector size
+    fn dimension(&self) -> usize;
+
+    async fn embed(&self, text: &str) -> Result<Vec<f32>>;
+}
+
+fn validate_text(text: &str) -> Result<()> {
+    if text.trim().is_empty() {
+        return Err(GrokError::InvalidQuery {
+            message: "Empty text cannot be encoded".to_string()
+        });
+    }
+    Ok(())
+}
+
+/// Embedder for any OpenAI-compatible embeddings endpoint
+#[derive(Debug)]
+pub struct EmbeddingModel {
+    model_name: String,
+    dimension: usize,
+    client: Client<OpenAIConfig>,
+}
+
+impl EmbeddingModel {
+    /// Ollama at `OLLAMA_API_URL` with `nomic-embed-text` (legacy default)
+    pub async fn new() -> Result<Self> {
+        let base_url = std::env::var("OLLAMA_API_URL")
+            .map_err(|_| GrokError::EnvironmentVariable { variable: "OLLAMA_API_URL".to_string() })?;
+
+        Ok(Self::openai_compatible(
+            &format!("{}/v1", base_url.trim_end_matches('/')),
+            None,
+            DEFAULT_EMBEDDING_MODEL,
+            DEFAULT_EMBEDDING_DIMENSION,
+        ))
+    }
+
+    /// Any endpoint serving `POST {api_base}/embeddings`
+    pub fn openai_compatible(api_base: &str, api_key: Option<&str>, model_name: &str, dimension: usize) -> Self {
+        let config = OpenAIConfig::default()
+            .with_api_base(api_base)
+            .with_api_key(api_key.unwrap_or("ollama")); // 🤓 Ollama doesn't require real API key
+
+        Self {
+            model_name: model_name.to_string(),
+            dimension,
+            client: Client::with_config(config),
+        }
+    }
+
+    pub async fn encode(&self, text: &str) -> Result<Vec<f32>> {
+        validate_text(text)?;
+
+        let request = async_openai::types::CreateEmbeddingRequestArgs::default()
+            .model(&self.model_name)
+            .input([text])
+            .build()
+            .map_err(|e| GrokError::EmbeddingGeneration { source: e })?;
+
+        let response = self.client.embeddings().create(request).await
+            .map_err(|e| GrokError::EmbeddingGeneration { source: e })?;
+
+        let embedding = response.data
+            .into_iter()
+            .next()
+            .ok_or_else(|| GrokError::InvalidQuery {
+                message: "No embeddings returned from API".to_string()
+            })?
+            .embedding;
+
+        if embedding.len() != self.dimension {
+            return Err(GrokError::VectorDimensions {
+                expected: self.dimension,
+                actual: embedding.len(),
+            });
+        }
+
+        tracing::debug!("✅ Generated {} dimensional embedding", embedding.len());
+        Ok(embedding)
+    }
+}
+
+#[async_trait]
+impl Embedder for EmbeddingModel {
+    fn model_name(&self) -> &str {
+        &self.model_name
+    }
+
+    fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        self.encode(text).await
+    }
+}
+
+/// Offline, deterministic feature-hashing embedder
+///
+/// Hashes lowercased words and word bigrams into signed buckets and L2-normalizes,
+/// so texts sharing vocabulary land close together. No network, no model weights.
+#[derive(Debug, Clone)]
+pub struct HashingEmbedder {
+    dimension: usize,
+}
+
+impl HashingEmbedder {
+    pub fn new(dimension: usize) -> Self {
+        Self { dimension: dimension.max(1) }
+    }
+
+    // 🤓 FNV-1a: stable across runs and platforms, unlike std's RandomState
+    fn hash(feature: &str) -> u64 {
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        for byte in feature.bytes() {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+        hash
+    }
+
+    pub fn encode(&self, text: &str) -> Vec<f32> {
+        let mut vector = vec![0.0f32; self.dimension];
+        let words: Vec<String> = text
+            .split(|c: char| !c.is_alphanumeric() && c != '_')
+            .filter(|w| !w.is_empty())
+            .map(str::to_lowercase)
+            .collect();
+
+        let bigrams = words.windows(2).map(|pair| format!("{} {}", pair[0], pair[1]));
+        for feature in words.iter().cloned().chain(bigrams) {
+            let hash = Self::hash(&feature);
+            let index = (hash % self.dimension as u64) as usize;
+            let sign = if hash >> 63 == 0 { 1.0 } else { -1.0 };
+            vector[index] += sign;
+        }
+
+        let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            vector.iter_mut().for_each(|v| *v /= norm);
+        }
+        vector
+    }
+}
+
+#[async_trait]
+impl Embedder for HashingEmbedder {
+    fn model_name(&self) -> &str {
+        "hashing"
+    }
+
+    fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        validate_text(text)?;
+        Ok(self.encode(text))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmbedderBackend {
+    /// Any OpenAI-compatible embeddings endpoint (Ollama, OpenAI, LiteLLM...)
+    #[serde(alias = "openai", alias = "ollama")]
+    OpenaiCompatible,
+    /// Offline deterministic hashing, for tests and air-gapped CI
+    Hashing,
+}
+
+/// Embedder selection, usually read from the environment
+///
+/// | variable | default |
+/// | --- | --- |
+/// | `B00T_EMBEDDING_BACKEND` | `openai_compatible` (`ollama`/`openai` aliases), or `hashing` |
+/// | `B00T_EMBEDDING_API_BASE` | `$OLLAMA_API_URL/v1` |
+/// | `B00T_EMBEDDING_API_KEY` | none |
+/// | `B00T_EMBEDDING_MODEL` | `nomic-embed-text` |
+/// | `B00T_EMBEDDING_DIM` | `768` |
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmbedderConfig {
+    pub backend: EmbedderBackend,
+    pub api_base: Option<String>,
+    pub api_key: Option<String>,
+    pub model: String,
+    pub dimension: usize,
+}
+
+impl Default for EmbedderConfig {
+    fn default() -> Self {
+        Self {
+            backend: EmbedderBackend::OpenaiCompatible,
+            api_base: None,
+            api_key: None,
+            model: DEFAULT_EMBEDDING_MODEL.to_string(),
+            dimension: DEFAULT_EMBEDDING_DIMENSION,
+        }
+    }
+}
+
+impl EmbedderConfig {
+    pub fn hashing(dimension: usize) -> Self {
+        Self {
+            backend: EmbedderBackend::Hashing,
+            model: "hashing".to_string(),
+            dimension,
+            ..Default::default()
+        }
+    }
+
+    pub fn from_env() -> Result<Self> {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    fn from_lookup(get: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let mut config = Self::default();
+
+        if let Some(backend) = get("B00T_EMBEDDING_BACKEND") {
+            config.backend = serde_json::from_value(serde_json::Value::String(backend.to_lowercase()))
+                .map_err(|_| GrokError::InvalidQuery {
+                    message: format!("Unknown B00T_EMBEDDING_BACKEND '{}': use openai_compatible, ollama, openai or hashing", backend)
+                })?;
+        }
+        if let Some(dimension) = get("B00T_EMBEDDING_DIM") {
+            config.dimension = dimension.parse().map_err(|_| GrokError::EnvironmentVariable {
+                variable: "B00T_EMBEDDING_DIM".to_string()
+            })?;
+        }
+        if let Some(model) = get("B00T_EMBEDDING_MODEL") {
+            config.model = model;
+        }
+
+        if config.backend == EmbedderBackend::Hashing {
+            config.model = "hashing".to_string();
+            return Ok(config);
+        }
+
+        config.api_key = get("B00T_EMBEDDING_API_KEY");
+        config.api_base = get("B00T_EMBEDDING_API_BASE")
+            .or_else(|| get("OLLAMA_API_URL").map(|url| format!("{}/v1", url.trim_end_matches('/'))));
+        if config.api_base.is_none() {
+            return Err(GrokError::EnvironmentVariable {
+                variable: "B00T_EMBEDDING_API_BASE (or OLLAMA_API_URL)".to_string()
+            });
+        }
+        Ok(config)
+    }
+
+    pub fn build(&self) -> Result<Box<dyn Embedder>> {
+        if self.dimension == 0 {
+            return Err(GrokError::InvalidQuery {
+                message: "Embedding dimension must be greater than zero".to_string()
+            });
+        }
+
+        match self.backend {
+            EmbedderBackend::Hashing => Ok(Box::new(HashingEmbedder::new(self.dimension))),
+            EmbedderBackend::OpenaiCompatible => {
+                let api_base = self.api_base.as_deref().ok_or_else(|| GrokError::EnvironmentVariable {
+                    variable: "B00T_EMBEDDING_API_BASE".to_string()
+                })?;
+                Ok(Box::new(EmbeddingModel::openai_compatible(
+                    api_base,
+                    self.api_key.as_deref(),
+                    &self.model,
+                    self.dimension,
+                )))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn cosine(a: &[f32], b: &[f32]) -> f32 {
+        a.iter().zip(b).map(|(x, y)| x * y).sum()
+    }
+
+    #[tokio::test]
+    async fn test_hashing_embedder_is_deterministic() {
+        let embedder = HashingEmbedder::new(64);
+        let a = embedder.embed("cargo build failed").await.unwrap();
+        let b = embedder.embed("cargo build failed").await.unwrap();
+        assert_eq!(a, b);
+        assert_eq!(a.len(), 64);
+        assert!((cosine(&a, &a) - 1.0).abs() < 1e-5);
+        assert!(embedder.embed("   ").await.is_err());
+    }
+
+    #[test]
+    fn test_hashing_embedder_similarity() {
+        let embedder = HashingEmbedder::new(256);
+        let query = embedder.encode("rust borrow checker error");
+        let close = embedder.encode("the rust borrow checker rejects this");
+        let far = embedder.encode("docker compose networking");
+        assert!(cosine(&query, &close) > cosine(&query, &far));
+    }
+
+    #[test]
+    fn test_config_from_lookup() {
+        let env: HashMap<&str, &str> = [
+            ("OLLAMA_API_URL", "http://localhost:11434/"),
+        ].into_iter().collect();
+        let config = EmbedderConfig::from_lookup(|k| env.get(k).map(|v| v.to_string())).unwrap();
+        assert_eq!(config.backend, EmbedderBackend::OpenaiCompatible);
+        assert_eq!(config.api_base.as_deref(), Some("http://localhost:11434/v1"));
+        assert_eq!(config.model, DEFAULT_EMBEDDING_MODEL);
+        assert_eq!(config.dimension, DEFAULT_EMBEDDING_DIMENSION);
+
+        let env: HashMap<&str, &str> = [
+            ("B00T_EMBEDDING_BACKEND", "hashing"),
+            ("B00T_EMBEDDING_DIM", "128"),
+        ].into_iter().collect();
+        let config = EmbedderConfig::from_lookup(|k| env.get(k).map(|v| v.to_string())).unwrap();
+        assert_eq!(config, EmbedderConfig::hashing(128));
+        assert_eq!(config.build().unwrap().dimension(), 128);
+
+        let err = EmbedderConfig::from_lookup(|_| None).unwrap_err();
+        assert!(matches!(err, GrokError::EnvironmentVariable { .. }));
+    }
+}
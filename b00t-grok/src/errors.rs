@@ -63,6 +63,14 @@ pub enum GrokError {
         actual: usize,
     },
 
+    #[snafu(display("Collection '{collection}' stores {existing}-dimensional vectors but embedder '{model}' produces {expected}; pick a matching model or use a new collection"))]
+    CollectionDimensionMismatch {
+        collection: String,
+        model: String,
+        expected: usize,
+        existing: usize,
+    },
+
     #[snafu(display("Content too large: {size} bytes exceeds limit of {limit} bytes"))]
     ContentTooLarge {
         size: usize,
@@ -124,6 +132,7 @@ impl GrokError {
             GrokError::VectorDimensions { .. } | GrokError::InvalidQuery { .. } => "validation",
             GrokError::ContentTooLarge { .. } => "limits",
             GrokError::CollectionOperation { .. } => "database",
+            GrokError::CollectionDimensionMismatch { .. } => "configuration",
             GrokError::Io { .. } | GrokError::Http { .. } => "network",
             GrokError::Timeout { .. } => "timeout",
             GrokError::UuidParsing { .. } | GrokError::DateTimeParsing { .. } => "parsing",
@@ -149,6 +158,8 @@ impl GrokError {
                 "Service not ready. Please wait for initialization to complete.".to_string(),
             GrokError::VectorDimensions { .. } => 
                 "Vector dimension mismatch. This may indicate a model configuration issue.".to_string(),
+            GrokError::CollectionDimensionMismatch { collection, existing, expected, .. } =>
+                format!("Collection '{}' was built with {}-dim embeddings but the configured embedder produces {}. Set B00T_EMBEDDING_MODEL/B00T_EMBEDDING_DIM to match or use a fresh collection.", collection, existing, expected),
             GrokError::ContentTooLarge { limit, .. } => 
                 format!("Content is too large. Maximum size is {} bytes.", limit),
             GrokError::InvalidQuery { .. } => 
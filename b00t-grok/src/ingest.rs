@@ -0,0 +1,335 @@
+// 🤓 Incremental ingestion: walk a directory, glob or git repo, chunk every text file and
+//    only embed chunks whose stable id (source + chunk hash) isn't already stored
+use crate::errors::{GrokError, Result};
+use crate::store::PayloadFilter;
+use crate::{payload_str, Attribution, GrokClient};
+use ignore::WalkBuilder;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// Files larger than this are skipped (generated code, lockfiles, data dumps)
+pub const MAX_INGEST_FILE_SIZE: u64 = 1_000_000;
+
+/// What `GrokClient::ingest` walks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IngestTarget {
+    /// A file or directory; `.gitignore`, `.ignore` and hidden files are respected
+    Path(PathBuf),
+    /// A glob such as `docs/**/*.md`, matched against files under its literal prefix
+    Glob(String),
+    /// A remote repository, shallow-cloned (or pulled) under `~/.b00t/grok_repos`
+    GitRepo(String),
+}
+
+impl IngestTarget {
+    pub fn parse(target: &str) -> Self {
+        let is_remote = ["http://", "https://", "ssh://", "git@"].iter().any(|p| target.starts_with(p));
+        if is_remote || (target.ends_with(".git") && !Path::new(target).exists()) {
+            IngestTarget::GitRepo(target.to_string())
+        } else if target.contains(['*', '?', '[', '{']) {
+            IngestTarget::Glob(target.to_string())
+        } else {
+            IngestTarget::Path(PathBuf::from(target))
+        }
+    }
+}
+
+/// Outcome of one `ingest` run
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IngestReport {
+    /// Identifies the ingested tree; re-ingesting the same root replaces its chunks
+    pub root: String,
+    pub files_scanned: usize,
+    /// Binary, non-UTF-8 or oversized files
+    pub files_skipped: usize,
+    pub chunks_added: usize,
+    pub chunks_unchanged: usize,
+    pub chunks_removed: usize,
+    /// Files seen by a previous run that no longer exist
+    pub files_removed: usize,
+}
+
+/// A file to ingest and how it is attributed
+#[derive(Debug, Clone)]
+struct IngestFile {
+    path: PathBuf,
+    /// Stable identity: absolute path, or `<repo>/blob/HEAD/<path>` for remote repos
+    source: String,
+    filename: String,
+    url: Option<String>,
+}
+
+/// Stable point id: the same text at the same source always maps to the same point
+pub fn chunk_point_id(source: &str, content_hash: &str) -> Uuid {
+    Uuid::new_v5(&Uuid::NAMESPACE_URL, format!("b00t-grok://{}#{}", source, content_hash).as_bytes())
+}
+
+pub fn content_hash(text: &str) -> String {
+    format!("{:x}", Sha256::digest(text.as_bytes()))
+}
+
+fn walk(root: &Path) -> Vec<PathBuf> {
+    // 🤓 require_git(false): honour .gitignore even in trees that aren't git checkouts
+    let mut files: Vec<PathBuf> = WalkBuilder::new(root)
+        .require_git(false)
+        .build()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_some_and(|t| t.is_file()))
+        .map(|entry| entry.into_path())
+        .collect();
+    files.sort();
+    files
+}
+
+fn absolute(path: &Path) -> Result<PathBuf> {
+    Ok(std::path::absolute(path)?)
+}
+
+fn local_file(path: PathBuf) -> IngestFile {
+    let source = path.to_string_lossy().to_string();
+    IngestFile { path, filename: source.clone(), source, url: None }
+}
+
+fn resolve_path(path: &Path) -> Result<(String, Vec<IngestFile>)> {
+    if !path.exists() {
+        return Err(GrokError::InvalidQuery {
+            message: format!("Ingest target '{}' does not exist", path.display())
+        });
+    }
+
+    let root = absolute(path)?;
+    let files = walk(&root).into_iter().map(local_file).collect();
+    Ok((root.to_string_lossy().to_string(), files))
+}
+
+fn resolve_glob(pattern: &str) -> Result<(String, Vec<IngestFile>)> {
+    let pattern = absolute(Path::new(pattern))?.to_string_lossy().to_string();
+    let matcher = globset::GlobBuilder::new(&pattern)
+        .literal_separator(true)
+        .build()
+        .map_err(|e| GrokError::InvalidQuery { message: format!("Invalid glob '{}': {}", pattern, e) })?
+        .compile_matcher();
+
+    // Walk from the longest prefix without glob metacharacters
+    let base: PathBuf = Path::new(&pattern)
+        .components()
+        .take_while(|c| !c.as_os_str().to_string_lossy().contains(['*', '?', '[', '{']))
+        .collect();
+
+    let files = walk(&base).into_iter()
+        .filter(|path| matcher.is_match(path))
+        .map(local_file)
+        .collect();
+    Ok((pattern, files))
+}
+
+fn repo_cache_dir(url: &str) -> PathBuf {
+    let name: String = url
+        .trim_end_matches('/')
+        .trim_end_matches(".git")
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' })
+        .collect();
+    let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    Path::new(&home).join(".b00t").join("grok_repos").join(name)
+}
+
+async fn git(args: &[&str]) -> Result<()> {
+    let output = tokio::process::Command::new("git").args(args).output().await?;
+    if !output.status.success() {
+        return Err(GrokError::InvalidQuery {
+            message: format!("git {} failed: {}", args.join(" "), String::from_utf8_lossy(&output.stderr).trim())
+        });
+    }
+    Ok(())
+}
+
+async fn resolve_repo(url: &str) -> Result<(String, Vec<IngestFile>)> {
+    let checkout = repo_cache_dir(url);
+    let checkout_str = checkout.to_string_lossy().to_string();
+    if checkout.join(".git").exists() {
+        git(&["-C", &checkout_str, "pull", "--ff-only", "--quiet"]).await?;
+    } else {
+        if let Some(parent) = checkout.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        git(&["clone", "--depth", "1", "--quiet", url, &checkout_str]).await?;
+    }
+
+    // 🤓 GitHub/GitLab/Gitea all serve <repo>/blob/<ref>/<path>
+    let web_base = url.trim_end_matches('/').trim_end_matches(".git");
+    let is_web = web_base.starts_with("http://") || web_base.starts_with("https://");
+
+    let files = walk(&checkout).into_iter().filter_map(|path| {
+        let relative = path.strip_prefix(&checkout).ok()?.to_string_lossy().replace('\\', "/");
+        let source = format!("{}/blob/HEAD/{}", web_base, relative);
+        Some(IngestFile {
+            path,
+            url: is_web.then(|| source.clone()),
+            source,
+            filename: relative,
+        })
+    }).collect();
+    Ok((url.to_string(), files))
+}
+
+fn read_text(path: &Path) -> Option<String> {
+    let metadata = std::fs::metadata(path).ok()?;
+    if metadata.len() > MAX_INGEST_FILE_SIZE {
+        return None;
+    }
+    let content = std::fs::read_to_string(path).ok()?; // non-UTF-8 → not text
+    if content.contains('\0') || content.trim().is_empty() {
+        return None;
+    }
+    Some(content)
+}
+
+impl GrokClient {
+    /// Ingest a file, directory, glob or git repository
+    ///
+    /// Point ids derive from source + chunk hash, so re-running only embeds new or
+    /// changed chunks, and drops chunks of edited or deleted files. Unchanged chunks keep
+    /// the line range recorded when they were first embedded.
+    pub async fn ingest(&self, target: &str) -> Result<IngestReport> {
+        let store = self.vector_store()?;
+
+        let (root, files) = match IngestTarget::parse(target) {
+            IngestTarget::Path(path) => resolve_path(&path)?,
+            IngestTarget::Glob(pattern) => resolve_glob(&pattern)?,
+            IngestTarget::GitRepo(url) => resolve_repo(&url).await?,
+        };
+
+        // Everything a previous run stored for this root, grouped by file
+        let mut existing: HashMap<String, HashSet<Uuid>> = HashMap::new();
+        let filter = PayloadFilter::new().with("ingest_root", root.clone());
+        for point in store.scroll(&filter, usize::MAX).await? {
+            // 🦨 Qdrant text match is a substring match; require the exact root
+            if payload_str(&point.payload, "ingest_root").as_deref() != Some(root.as_str()) {
+                continue;
+            }
+            if let Some(source) = payload_str(&point.payload, "source") {
+                existing.entry(source).or_default().insert(point.id);
+            }
+        }
+
+        let mut report = IngestReport { root: root.clone(), ..Default::default() };
+        let mut stale = Vec::new();
+
+        for file in files {
+            report.files_scanned += 1;
+            let Some(content) = read_text(&file.path) else {
+                report.files_skipped += 1;
+                continue;
+            };
+
+            let known = existing.remove(&file.source).unwrap_or_default();
+            let topic = self.infer_topic_from_source(&file.filename);
+            let attribution = Attribution {
+                url: file.url.clone(),
+                filename: Some(file.filename.clone()),
+                date: chrono::Utc::now().to_rfc3339(),
+            };
+
+            let sections = self.chunker.chunk_sections(&content, Some(&file.path.to_string_lossy()))?;
+            let mut current = HashSet::new();
+            let mut points = Vec::new();
+
+            for (i, section) in sections.into_iter().enumerate() {
+                if section.text.len() < 10 { continue; } // Skip very short chunks
+
+                let hash = content_hash(&section.text);
+                let id = chunk_point_id(&file.source, &hash);
+                if !current.insert(id) {
+                    continue; // identical chunk repeated within the file
+                }
+                if known.contains(&id) {
+                    report.chunks_unchanged += 1;
+                    continue;
+                }
+
+                let vector = self.generate_embedding(&section.text).await?;
+                let (_, mut point) = self.section_chunk(id, &file.source, &topic, attribution.clone(), i, section, vector);
+                point.payload.insert("ingest_root".to_string(), root.clone().into());
+                point.payload.insert("content_hash".to_string(), hash.into());
+                points.push(point);
+            }
+
+            report.chunks_added += points.len();
+            store.upsert(points).await?;
+            stale.extend(known.difference(&current).copied());
+        }
+
+        // Files ingested before that are gone now
+        report.files_removed = existing.len();
+        stale.extend(existing.into_values().flatten());
+
+        report.chunks_removed = stale.len();
+        store.delete(stale).await?;
+
+        tracing::info!("Ingested '{}': {} files, {} chunks added, {} unchanged, {} removed",
+            root, report.files_scanned, report.chunks_added, report.chunks_unchanged, report.chunks_removed);
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HashingEmbedder, LocalStore, VectorStore};
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_target() {
+        assert_eq!(IngestTarget::parse("https://github.com/elasticdotventures/dotfiles"),
+            IngestTarget::GitRepo("https://github.com/elasticdotventures/dotfiles".to_string()));
+        assert_eq!(IngestTarget::parse("git@github.com:org/repo.git"),
+            IngestTarget::GitRepo("git@github.com:org/repo.git".to_string()));
+        assert_eq!(IngestTarget::parse("docs/**/*.md"), IngestTarget::Glob("docs/**/*.md".to_string()));
+        assert_eq!(IngestTarget::parse("./src"), IngestTarget::Path(PathBuf::from("./src")));
+    }
+
+    #[tokio::test]
+    async fn test_ingest_is_incremental() {
+        let repo = TempDir::new().unwrap();
+        let dir = repo.path();
+        std::fs::write(dir.join(".gitignore"), "target/\n").unwrap();
+        std::fs::create_dir(dir.join("target")).unwrap();
+        std::fs::write(dir.join("target/build.md"), "# Build\n\nGenerated output that must not be ingested.\n").unwrap();
+        std::fs::write(dir.join("guide.md"), "# Guide\n\nInstall b00t with the bootstrap script.\n\n# Usage\n\nRun b00t-cli grok ask to search.\n").unwrap();
+        std::fs::write(dir.join("notes.md"), "# Notes\n\nQdrant stores vectors on port 6334.\n").unwrap();
+
+        let store_dir = TempDir::new().unwrap();
+        let store = Arc::new(LocalStore::open(store_dir.path().join("vectors.json"), "ingest").unwrap());
+        let mut client = GrokClient::new(String::new(), String::new())
+            .with_embedder(Arc::new(HashingEmbedder::new(64)))
+            .with_store(store.clone());
+        client.initialize().await.unwrap();
+
+        let target = dir.to_string_lossy().to_string();
+        let first = client.ingest(&target).await.unwrap();
+        assert_eq!(first.files_scanned, 2); // target/ is ignored, .gitignore is hidden
+        assert_eq!(first.chunks_added, 3);
+
+        // Unchanged tree: nothing re-embedded
+        let second = client.ingest(&target).await.unwrap();
+        assert_eq!((second.chunks_added, second.chunks_unchanged, second.chunks_removed), (0, 3, 0));
+
+        // Edit one section, delete a file
+        std::fs::write(dir.join("guide.md"), "# Guide\n\nInstall b00t with the bootstrap script.\n\n# Usage\n\nRun b00t-cli grok ingest to index a repo.\n").unwrap();
+        std::fs::remove_file(dir.join("notes.md")).unwrap();
+        let third = client.ingest(&target).await.unwrap();
+        assert_eq!((third.chunks_added, third.chunks_unchanged), (1, 1));
+        assert_eq!((third.chunks_removed, third.files_removed), (2, 1));
+        assert_eq!(store.count().await.unwrap(), 2);
+
+        let results = client.ask("grok ingest repo", None).await.unwrap();
+        let guide = dir.join("guide.md").to_string_lossy().to_string();
+        assert_eq!(results[0].attribution.filename.as_deref(), Some(guide.as_str()));
+        assert!(results[0].content.contains("grok ingest"));
+    }
+}
@@ -1,9 +1,24 @@
 // Import our structured error types
 pub mod errors;
 pub use errors::{GrokError, Result};
-use async_openai::{Client, config::OpenAIConfig};
+pub mod embedder;
+pub use embedder::{Embedder, EmbedderBackend, EmbedderConfig, EmbeddingModel, HashingEmbedder};
+pub mod store;
+pub mod chunker;
+pub use chunker::{SourceKind, StructuredChunker, TextChunk};
+pub mod ingest;
+pub use ingest::{IngestReport, IngestTarget};
+pub mod lifecycle;
+pub use lifecycle::{ForgetTarget, ImportReport, SnapshotRecord, TopicSummary};
+pub mod search;
+pub use search::{HttpReranker, Reranker, SearchMode, SearchOptions};
+pub use store::{
+    LocalStore, Payload, PayloadFilter, QdrantStore, StoreBackend, StoreConfig, StoredPoint,
+    VectorPoint, VectorStore,
+};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 use uuid::Uuid;
 
 #[cfg(feature = "pyo3")]
@@ -12,6 +27,11 @@ use pyo3::prelude::*;
 // 🤓 Clean abstraction: Minimal Python-Rust interface for semantic chunking
 pub trait Chunker {
     fn chunk(&self, content: &str) -> Result<Vec<String>>;
+
+    /// Chunks with heading path and line ranges; `source` (path or URL) hints the content type
+    fn chunk_sections(&self, content: &str, _source: Option<&str>) -> Result<Vec<TextChunk>> {
+        Ok(self.chunk(content)?.into_iter().map(TextChunk::plain).collect())
+    }
 }
 
 // PyO3-based semantic chunker
@@ -86,27 +106,6 @@ impl Chunker for BasicChunker {
     }
 }
 
-// 🤓 CRITICAL TRIBAL KNOWLEDGE: qdrant-client v1.15.0 Port Configuration
-//    
-//    IMPORTANT: Qdrant has two different ports:
-//    - Port 6333: Web UI and HTTP REST API (for manual testing)  
-//    - Port 6334: Client API (gRPC/HTTP for qdrant-client library)
-//    
-//    The qdrant-client crate needs port 6334, NOT 6333!
-//    Use: http://host:6334 (client API)
-//    NOT: http://host:6333 (web UI only)
-//    
-//    This was the root cause of "protocol error: invalid compression flag"
-//    Future agents: Always use port 6334 for qdrant-client connections!
-
-use qdrant_client::{
-    Qdrant,
-    qdrant::{
-        CreateCollection, Distance, PointStruct, SearchPoints, VectorParams,
-        WithPayloadSelector, Filter, Condition, CollectionOperationResponse,
-    },
-};
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Datum {
     pub name: String,
@@ -123,6 +122,12 @@ pub struct Chunk {
     pub attribution: Attribution,
     pub metadata: ChunkMetadata,
     pub vector: Option<Vec<f32>>,
+    /// Retrieval score from `search`/`ask` (see `SearchOptions`), or lfmf similarity
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<f32>,
+    /// Raw cosine similarity to the query; `None` for keyword-only hits
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub similarity: Option<f32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -141,74 +146,37 @@ pub struct ChunkMetadata {
     pub lesson_type: Option<String>,    // "fail", "advice", "pattern"
     pub error_pattern: Option<String>,  // Original error text for matching
     pub solution: Option<String>,       // Solution or advice content
-    pub confidence: Option<f32>,        // 0.0-1.0 confidence in lesson
+    pub confidence: Option<f32>,        // 0.0-1.0 confidence in lesson, or search relevance
+    // Source structure, filled in by structure-aware chunkers
+    #[serde(default)]
+    pub heading_path: Vec<String>,      // Enclosing Markdown headings or code items
+    #[serde(default)]
+    pub line_range: Option<(usize, usize)>, // 1-based inclusive lines in the source
+}
+
+fn payload_str(payload: &Payload, key: &str) -> Option<String> {
+    payload.get(key).and_then(|v| v.as_str()).map(str::to_string)
+}
+
+fn payload_line_range(payload: &Payload) -> Option<(usize, usize)> {
+    let start = payload.get("line_start")?.as_u64()?;
+    let end = payload.get("line_end")?.as_u64()?;
+    Some((start as usize, end as usize))
 }
 
 pub struct GrokClient {
     qdrant_url: String,
     api_key: String,
     collection_name: String,
-    embedding_model: Option<EmbeddingModel>,
-    qdrant_client: Option<Qdrant>,
+    embedder: Option<Arc<dyn Embedder>>, // 🤓 Abstraction: pluggable embedding backend
+    store: Option<Arc<dyn VectorStore>>, // 🤓 Abstraction: Qdrant or local single-file store
     chunker: Box<dyn Chunker + Send + Sync>, // 🤓 Abstraction: pluggable chunking strategy
-}
-
-#[derive(Debug)]
-pub struct EmbeddingModel {
-    model_name: String,
-    client: Client<OpenAIConfig>,
-}
-
-impl EmbeddingModel {
-    pub async fn new() -> Result<Self> {
-        let base_url = std::env::var("OLLAMA_API_URL")
-            .map_err(|_| GrokError::EnvironmentVariable { variable: "OLLAMA_API_URL".to_string() })?;
-        
-        let config = OpenAIConfig::default()
-            .with_api_base(format!("{}/v1", base_url))
-            .with_api_key("ollama"); // 🤓 Ollama doesn't require real API key
-        
-        let client = Client::with_config(config);
-        
-        Ok(Self {
-            model_name: "nomic-embed-text".to_string(),
-            client,
-        })
-    }
-    
-    pub async fn encode(&self, text: &str) -> Result<Vec<f32>> {
-        // Validate input
-        if text.trim().is_empty() {
-            return Err(GrokError::InvalidQuery { 
-                message: "Empty text cannot be encoded".to_string() 
-            });
-        }
-
-        let request = async_openai::types::CreateEmbeddingRequestArgs::default()
-            .model(&self.model_name)
-            .input([text])
-            .build()
-            .map_err(|e| GrokError::EmbeddingGeneration { source: e })?;
-        
-        let response = self.client.embeddings().create(request).await
-            .map_err(|e| GrokError::EmbeddingGeneration { source: e })?;
-        
-        let embedding = response.data
-            .into_iter()
-            .next()
-            .ok_or_else(|| GrokError::InvalidQuery { 
-                message: "No embeddings returned from API".to_string()
-            })?
-            .embedding;
-        
-        tracing::debug!("✅ Generated {} dimensional embedding", embedding.len());
-        Ok(embedding)
-    }
+    reranker: Option<Arc<dyn Reranker>>,     // 🤓 Optional cross-encoder for search
 }
 
 impl GrokClient {
     pub fn new(qdrant_url: String, api_key: String) -> Self {
-        // 🤓 Smart chunker selection: Use semantic if PyO3 available, fallback to basic
+        // 🤓 Smart chunker selection: Use semantic if PyO3 available, fallback to native structured
         let chunker: Box<dyn Chunker + Send + Sync> = {
             #[cfg(feature = "pyo3")]
             {
@@ -216,7 +184,7 @@ impl GrokClient {
             }
             #[cfg(not(feature = "pyo3"))]
             {
-                Box::new(BasicChunker)
+                Box::new(StructuredChunker::default())
             }
         };
         
@@ -224,108 +192,117 @@ impl GrokClient {
             qdrant_url,
             api_key,
             collection_name: "b00t_chunks".to_string(),
-            embedding_model: None,
-            qdrant_client: None,
+            embedder: None,
+            store: None,
             chunker,
+            reranker: None,
         }
     }
 
+    /// Use a specific embedder instead of the one configured by `EmbedderConfig::from_env`
+    pub fn with_embedder(mut self, embedder: Arc<dyn Embedder>) -> Self {
+        self.embedder = Some(embedder);
+        self
+    }
+
+    /// Store chunks in a collection other than `b00t_chunks`
+    pub fn with_collection(mut self, collection_name: impl Into<String>) -> Self {
+        self.collection_name = collection_name.into();
+        self
+    }
+
+    /// Use a specific vector store instead of the one selected by `StoreConfig::from_env`
+    ///
+    /// The store is bound to its own collection; `with_collection` does not change it.
+    pub fn with_store(mut self, store: Arc<dyn VectorStore>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Use a specific chunker for `learn`
+    pub fn with_chunker(mut self, chunker: Box<dyn Chunker + Send + Sync>) -> Self {
+        self.chunker = chunker;
+        self
+    }
+
+    /// Rerank search results with a cross-encoder (skipped per query with `with_rerank(false)`)
+    pub fn with_reranker(mut self, reranker: Arc<dyn Reranker>) -> Self {
+        self.reranker = Some(reranker);
+        self
+    }
+
+    pub fn embedder(&self) -> Option<&Arc<dyn Embedder>> {
+        self.embedder.as_ref()
+    }
+
+    pub fn store(&self) -> Option<&Arc<dyn VectorStore>> {
+        self.store.as_ref()
+    }
+
     pub async fn initialize(&mut self) -> Result<()> {
-        tracing::info!("Connecting to Qdrant: {}", self.qdrant_url);
-        
-        let client = self.build_qdrant_client(&self.qdrant_url, 
-            if self.api_key.is_empty() { None } else { Some(&self.api_key) })?;
-        
-        // Initialize embedding model
-        let embedding_model = EmbeddingModel::new().await?;
-        
-        self.qdrant_client = Some(client);
-        self.embedding_model = Some(embedding_model);
+        // Initialize embedder unless one was supplied
+        if self.embedder.is_none() {
+            self.embedder = Some(Arc::from(EmbedderConfig::from_env()?.build()?));
+        }
+
+        if self.store.is_none() {
+            self.store = Some(self.open_store(&StoreConfig::from_env()?).await?);
+        }
 
         // Ensure collection exists
         self.ensure_collection_exists().await?;
 
-        tracing::info!("GrokClient initialized for {}", self.qdrant_url);
+        tracing::info!("GrokClient initialized with {} store, collection '{}'",
+            self.store.as_ref().map_or("no", |s| s.backend_name()), self.collection_name);
         Ok(())
     }
 
-    // 🚀 HTTP-only client builder  
-    fn build_qdrant_client(&self, url: &str, api_key: Option<&str>) -> Result<Qdrant> {
-        // Convert grpc:// URLs to http:// since we're HTTP-only now
-        let clean_url = if url.starts_with("grpc://") {
-            tracing::info!("Converting grpc:// to http:// (HTTP-only mode)");
-            url.replacen("grpc://", "http://", 1)
-        } else if url.starts_with("grpcs://") {
-            tracing::info!("Converting grpcs:// to https:// (HTTP-only mode)");
-            url.replacen("grpcs://", "https://", 1)
-        } else {
-            url.to_string()
+    async fn open_store(&self, config: &StoreConfig) -> Result<Arc<dyn VectorStore>> {
+        let open_local = || -> Result<Arc<dyn VectorStore>> {
+            tracing::info!("Using local vector store: {}", config.local_path.display());
+            Ok(Arc::new(LocalStore::open(&config.local_path, self.collection_name.clone())?))
         };
 
-        tracing::info!("Using HTTP REST protocol: {}", clean_url);
-        
-        let mut builder = Qdrant::from_url(&clean_url);
-
-        if let Some(key) = api_key {
-            builder = builder.api_key(key.to_string());
+        if config.backend == StoreBackend::Local {
+            return open_local();
         }
 
-        // 🤓 NOTE: qdrant-client v1.15.0 requires gRPC for initialization even with HTTP URLs
-        //    This is a known limitation - server must have both ports 6333 (HTTP) and 6334 (gRPC) enabled
-
-        Ok(builder.build()?)
+        tracing::info!("Connecting to Qdrant: {}", self.qdrant_url);
+        let qdrant = QdrantStore::connect(&self.qdrant_url,
+            if self.api_key.is_empty() { None } else { Some(&self.api_key) },
+            self.collection_name.clone())?;
+
+        if config.backend == StoreBackend::Auto {
+            if let Err(e) = qdrant.health_check().await {
+                tracing::warn!("Qdrant unavailable at {} ({}), falling back to local vector store", self.qdrant_url, e);
+                return open_local();
+            }
+        }
+        Ok(Arc::new(qdrant))
     }
 
     async fn ensure_collection_exists(&self) -> Result<()> {
-        let client = self.qdrant_client.as_ref()
+        let store = self.store.as_ref()
             .ok_or(GrokError::ClientNotInitialized)?;
 
-        // Check if collection exists
-        match client.collection_info(&self.collection_name).await {
-            Ok(_) => {
-                tracing::info!("Collection '{}' already exists", self.collection_name);
-                Ok(())
-            }
-            Err(_) => {
-                // Collection doesn't exist, create it
-                tracing::info!("Creating collection '{}'", self.collection_name);
-                
-                let create_collection = CreateCollection {
-                    collection_name: self.collection_name.clone(),
-                    vectors_config: Some(VectorParams {
-                        size: 768, // 🤓 nomic-embed-text produces 768-dimensional vectors
-                        distance: Distance::Cosine.into(),
-                        ..Default::default()
-                    }.into()),
-                    ..Default::default()
-                };
-
-                let response: CollectionOperationResponse = client
-                    .create_collection(create_collection)
-                    .await?;
-
-                if response.result {
-                    tracing::info!("Successfully created collection '{}'", self.collection_name);
-                } else {
-                    return Err(GrokError::CollectionOperation {
-                        collection: self.collection_name.clone(),
-                        message: "Creation failed".to_string(),
-                    });
-                }
-                Ok(())
-            }
-        }
+        let embedder = self.embedder.as_ref()
+            .ok_or(GrokError::ClientNotInitialized)?;
+
+        store.ensure_collection(embedder.dimension(), embedder.model_name()).await
+    }
+
+    fn vector_store(&self) -> Result<&Arc<dyn VectorStore>> {
+        self.store.as_ref().ok_or(GrokError::ClientNotInitialized)
     }
 
     async fn generate_embedding(&self, text: &str) -> Result<Vec<f32>> {
-        let model = self.embedding_model.as_ref()
+        let embedder = self.embedder.as_ref()
             .ok_or(GrokError::ClientNotInitialized)?;
-        model.encode(text).await
+        embedder.embed(text).await
     }
 
     pub async fn digest(&self, topic: &str, content: &str) -> Result<Chunk> {
-        let client = self.qdrant_client.as_ref()
-            .ok_or(GrokError::ClientNotInitialized)?;
+        let store = self.vector_store()?;
 
         // Validate inputs
         if topic.trim().is_empty() {
@@ -368,156 +345,71 @@ impl GrokClient {
                 error_pattern: None,
                 solution: None,
                 confidence: None,
+                heading_path: vec![],
+                line_range: None,
             },
             vector: Some(vector.clone()),
+            score: None,
+            similarity: None,
         };
 
-        // Store in Qdrant
-        let mut payload = HashMap::new();
+        let mut payload = Payload::new();
         payload.insert("content".to_string(), content.into());
         payload.insert("datum".to_string(), topic.into());
         payload.insert("topic".to_string(), topic.into());
         payload.insert("created_at".to_string(), chunk.metadata.created_at.clone().into());
 
-        let point = PointStruct::new(
-            chunk_id.to_string(),
-            vector,
-            payload,
-        );
-
-        let upsert_request = qdrant_client::qdrant::UpsertPoints {
-            collection_name: self.collection_name.clone(),
-            points: vec![point],
-            ..Default::default()
-        };
-
-        client
-            .upsert_points(upsert_request)
-            .await?;
+        store.upsert(vec![VectorPoint { id: chunk_id, vector, payload }]).await?;
 
-        tracing::info!("Stored chunk {} for topic '{}' in Qdrant", chunk_id, topic);
+        tracing::info!("Stored chunk {} for topic '{}' in {} store", chunk_id, topic, store.backend_name());
 
         Ok(chunk)
     }
 
+    /// Hybrid search with default options (limit 10); see `search`
     pub async fn ask(&self, query: &str, topic: Option<&str>) -> Result<Vec<Chunk>> {
-        let client = self.qdrant_client.as_ref()
-            .ok_or(GrokError::ClientNotInitialized)?;
-
-        // Validate query
-        if query.trim().is_empty() {
-            return Err(GrokError::InvalidQuery { 
-                message: "Query cannot be empty".to_string() 
-            });
-        }
-
-        // Generate query embedding
-        let query_vector = self.generate_embedding(query).await?;
-
-        // Build search request
-        let mut search_request = SearchPoints {
-            collection_name: self.collection_name.clone(),
-            vector: query_vector,
-            limit: 10,
-            with_payload: Some(WithPayloadSelector {
-                selector_options: Some(qdrant_client::qdrant::with_payload_selector::SelectorOptions::Enable(true)),
-            }),
-            ..Default::default()
-        };
-
-        // Add topic filter if specified
-        if let Some(topic_filter) = topic {
-            let filter = Filter {
-                must: vec![Condition {
-                    condition_one_of: Some(qdrant_client::qdrant::condition::ConditionOneOf::Field(
-                        qdrant_client::qdrant::FieldCondition {
-                            key: "topic".to_string(),
-                            r#match: Some(qdrant_client::qdrant::Match {
-                                match_value: Some(qdrant_client::qdrant::r#match::MatchValue::Text(topic_filter.to_string())),
-                            }),
-                            ..Default::default()
-                        }
-                    )),
-                }],
-                ..Default::default()
-            };
-            search_request.filter = Some(filter);
-        }
+        self.search(query, &SearchOptions::default().with_topic(topic)).await
+    }
 
-        let search_result = client.search_points(search_request).await?;
+    /// Rebuild a learned/digested chunk from its stored payload
+    fn stored_chunk(point: StoredPoint, score: f32) -> Chunk {
+        let payload = &point.payload;
+        let created_at = payload_str(payload, "created_at").unwrap_or_default();
+        let source = payload_str(payload, "source");
 
-        let mut chunks = Vec::new();
-        for scored_point in search_result.result {
-            let payload = scored_point.payload;
-            let content = payload.get("content")
-                .and_then(|v| v.as_str())
-                .map_or("", |v| v)
-                .to_string();
-            
-            let datum = payload.get("datum")
-                .and_then(|v| v.as_str())
-                .map_or("unknown", |v| v)
-                .to_string();
-            
-            let topic_str = payload.get("topic")
-                .and_then(|v| v.as_str())
-                .map_or("unknown", |v| v)
-                .to_string();
-            
-            let created_at = payload.get("created_at")
-                .and_then(|v| v.as_str())
-                .map_or("", |v| v)
-                .to_string();
-
-            let point_id = match &scored_point.id {
-                Some(id) => match id.point_id_options.as_ref() {
-                    Some(qdrant_client::qdrant::point_id::PointIdOptions::Uuid(uuid_str)) => {
-                        Uuid::parse_str(&uuid_str).unwrap_or_else(|_| Uuid::new_v4())
-                    }
-                    Some(qdrant_client::qdrant::point_id::PointIdOptions::Num(_)) => Uuid::new_v4(),
-                    None => Uuid::new_v4(),
-                }
-                None => Uuid::new_v4(),
-            };
-
-            let chunk = Chunk {
-                id: point_id,
-                content,
-                datum: datum.clone(),
-                attribution: Attribution {
-                    url: None,
-                    filename: None,
-                    date: created_at.clone(),
-                },
-                metadata: ChunkMetadata {
-                    topic: topic_str,
-                    tags: vec![format!("score_{:.3}", scored_point.score)],
-                    created_at,
-                    lesson_type: None,
-                    error_pattern: None,
-                    solution: None,
-                    confidence: None,
-                },
-                vector: scored_point.vectors.and_then(|v| match v.vectors_options? {
-                    qdrant_client::qdrant::vectors_output::VectorsOptions::Vector(vector_struct) => {
-                        Some(vector_struct.data)
-                    }
-                    _ => None,
-                }),
-            };
-            
-            chunks.push(chunk);
+        Chunk {
+            id: point.id,
+            content: payload_str(payload, "content").unwrap_or_default(),
+            datum: payload_str(payload, "datum").unwrap_or_else(|| "unknown".to_string()),
+            attribution: Attribution {
+                url: payload_str(payload, "url")
+                    .or_else(|| source.clone().filter(|s| s.starts_with("http"))),
+                filename: payload_str(payload, "filename")
+                    .or_else(|| source.filter(|s| !s.starts_with("http") && s != "direct_input")),
+                date: created_at.clone(),
+            },
+            metadata: ChunkMetadata {
+                topic: payload_str(payload, "topic").unwrap_or_else(|| "unknown".to_string()),
+                tags: vec![],
+                created_at,
+                lesson_type: None,
+                error_pattern: None,
+                solution: None,
+                confidence: None,
+                heading_path: payload.get("heading_path")
+                    .and_then(|v| v.as_array())
+                    .map(|items| items.iter().filter_map(|v| v.as_str().map(str::to_string)).collect())
+                    .unwrap_or_default(),
+                line_range: payload_line_range(payload),
+            },
+            vector: None,
+            score: Some(score),
+            similarity: point.score,
         }
-
-        tracing::info!("Found {} chunks for query '{}' with topic filter {:?}", 
-                      chunks.len(), query, topic);
-
-        Ok(chunks)
     }
 
     pub async fn learn(&self, source: &str, content: &str) -> Result<Vec<Chunk>> {
-        let client = self.qdrant_client.as_ref()
-            .ok_or(GrokError::ClientNotInitialized)?;
+        let store = self.vector_store()?;
 
         // Validate inputs
         if source.trim().is_empty() {
@@ -540,80 +432,91 @@ impl GrokClient {
         }
 
         // 🤓 Semantic chunking via abstraction layer
-        let chunks = self.chunker.chunk(content)?;
+        let chunks = self.chunker.chunk_sections(content, Some(source))?;
         
         let mut result_chunks = Vec::new();
         let mut points = Vec::new();
         
-        for (i, chunk_text) in chunks.into_iter().enumerate() {
-            if chunk_text.len() < 10 { continue; } // Skip very short chunks
-            
-            // Infer topic from source or use "general"
-            let inferred_topic = self.infer_topic_from_source(source);
-            let chunk_id = Uuid::new_v4();
-            let vector = self.generate_embedding(&chunk_text).await?;
-            
-            let chunk = Chunk {
-                id: chunk_id,
-                content: chunk_text.clone(),
-                datum: inferred_topic.clone(),
-                attribution: Attribution {
-                    url: if source.starts_with("http") { Some(source.to_string()) } else { None },
-                    filename: if !source.starts_with("http") && source != "direct_input" { 
-                        Some(source.to_string()) 
-                    } else { None },
-                    date: chrono::Utc::now().to_rfc3339(),
-                },
-                metadata: ChunkMetadata {
-                    topic: inferred_topic.clone(),
-                    tags: vec![format!("chunk_{}", i)],
-                    created_at: chrono::Utc::now().to_rfc3339(),
-                    lesson_type: None,
-                    error_pattern: None,
-                    solution: None,
-                    confidence: None,
-                },
-                vector: Some(vector.clone()),
-            };
-
-            // Prepare point for batch insertion
-            let mut payload = HashMap::new();
-            payload.insert("content".to_string(), chunk_text.clone().into());
-            payload.insert("datum".to_string(), inferred_topic.clone().into());
-            payload.insert("topic".to_string(), inferred_topic.into());
-            payload.insert("source".to_string(), source.into());
-            payload.insert("created_at".to_string(), chunk.metadata.created_at.clone().into());
-            payload.insert("chunk_index".to_string(), (i as i64).into());
-
-            let point = PointStruct::new(
-                chunk_id.to_string(),
-                vector,
-                payload,
-            );
+        let attribution = Attribution {
+            url: if source.starts_with("http") { Some(source.to_string()) } else { None },
+            filename: if !source.starts_with("http") && source != "direct_input" {
+                Some(source.to_string())
+            } else { None },
+            date: chrono::Utc::now().to_rfc3339(),
+        };
+        // Infer topic from source or use "general"
+        let inferred_topic = self.infer_topic_from_source(source);
 
+        for (i, section) in chunks.into_iter().enumerate() {
+            if section.text.len() < 10 { continue; } // Skip very short chunks
+
+            let vector = self.generate_embedding(&section.text).await?;
+            let (chunk, point) = self.section_chunk(Uuid::new_v4(), source, &inferred_topic, attribution.clone(), i, section, vector);
             points.push(point);
             result_chunks.push(chunk);
         }
         
         // Batch insert all points
         if !points.is_empty() {
-            let upsert_request = qdrant_client::qdrant::UpsertPoints {
-                collection_name: self.collection_name.clone(),
-                points,
-                ..Default::default()
-            };
-
-            client
-                .upsert_points(upsert_request)
-                .await?;
-
-            tracing::info!("Stored {} chunks from source '{}' in Qdrant", 
-                          result_chunks.len(), source);
+            store.upsert(points).await?;
+
+            tracing::info!("Stored {} chunks from source '{}' in {} store", 
+                          result_chunks.len(), source, store.backend_name());
         }
 
         Ok(result_chunks)
     }
     
+    /// Build the chunk and store point for one section of a learned/ingested source
+    #[allow(clippy::too_many_arguments)]
+    fn section_chunk(&self, id: Uuid, source: &str, topic: &str, attribution: Attribution, index: usize, section: TextChunk, vector: Vec<f32>) -> (Chunk, VectorPoint) {
+        let created_at = chrono::Utc::now().to_rfc3339();
+
+        let mut payload = Payload::new();
+        payload.insert("content".to_string(), section.text.clone().into());
+        payload.insert("datum".to_string(), topic.into());
+        payload.insert("topic".to_string(), topic.into());
+        payload.insert("source".to_string(), source.into());
+        payload.insert("created_at".to_string(), created_at.clone().into());
+        payload.insert("chunk_index".to_string(), (index as i64).into());
+        if let Some(filename) = &attribution.filename {
+            payload.insert("filename".to_string(), filename.clone().into());
+        }
+        if let Some(url) = &attribution.url {
+            payload.insert("url".to_string(), url.clone().into());
+        }
+        if !section.heading_path.is_empty() {
+            payload.insert("heading_path".to_string(), section.heading_path.clone().into());
+        }
+        if let Some((start, end)) = section.line_range {
+            payload.insert("line_start".to_string(), start.into());
+            payload.insert("line_end".to_string(), end.into());
+        }
+
+        let chunk = Chunk {
+            id,
+            content: section.text,
+            datum: topic.to_string(),
+            attribution,
+            metadata: ChunkMetadata {
+                topic: topic.to_string(),
+                tags: vec![format!("chunk_{}", index)],
+                created_at,
+                lesson_type: None,
+                error_pattern: None,
+                solution: None,
+                confidence: None,
+                heading_path: section.heading_path,
+                line_range: section.line_range,
+            },
+            vector: Some(vector.clone()),
+            score: None,
+            similarity: None,
+        };
+
+        (chunk, VectorPoint { id, vector, payload })
+    }
+
     fn infer_topic_from_source(&self, source: &str) -> String {
         if source.contains("rust") || source.contains(".rs") {
             "rust".to_string()
@@ -632,8 +535,7 @@ impl GrokClient {
     
     /// Record a lesson learned from a failure
     pub async fn record_lfmf(&self, tool: &str, error: &str, lesson: &str) -> Result<Chunk> {
-        let client = self.qdrant_client.as_ref()
-            .ok_or(GrokError::ClientNotInitialized)?;
+        let store = self.vector_store()?;
 
         // Validate inputs
         if tool.trim().is_empty() {
@@ -690,12 +592,15 @@ impl GrokClient {
                 error_pattern: Some(error.to_string()),
                 solution: Some(lesson.to_string()),
                 confidence: Some(confidence),
+                heading_path: vec![],
+                line_range: None,
             },
             vector: Some(vector.clone()),
+            score: None,
+            similarity: None,
         };
 
-        // Store in Qdrant
-        let mut payload = HashMap::new();
+        let mut payload = Payload::new();
         payload.insert("content".to_string(), content.into());
         payload.insert("datum".to_string(), topic.clone().into());
         payload.insert("topic".to_string(), topic.into());
@@ -706,21 +611,7 @@ impl GrokClient {
         payload.insert("confidence".to_string(), confidence.into());
         payload.insert("created_at".to_string(), chunk.metadata.created_at.clone().into());
 
-        let point = PointStruct::new(
-            chunk_id.to_string(),
-            vector,
-            payload,
-        );
-
-        let upsert_request = qdrant_client::qdrant::UpsertPoints {
-            collection_name: self.collection_name.clone(),
-            points: vec![point],
-            ..Default::default()
-        };
-
-        client
-            .upsert_points(upsert_request)
-            .await?;
+        store.upsert(vec![VectorPoint { id: chunk_id, vector, payload }]).await?;
 
         tracing::info!("Recorded lfmf lesson {} for tool '{}': {}", chunk_id, tool, error);
 
@@ -729,8 +620,7 @@ impl GrokClient {
 
     /// Get advice for a specific error pattern
     pub async fn get_advice(&self, tool: &str, error: &str) -> Result<Vec<Chunk>> {
-        let client = self.qdrant_client.as_ref()
-            .ok_or(GrokError::ClientNotInitialized)?;
+        let store = self.vector_store()?;
 
         // Validate inputs
         if tool.trim().is_empty() {
@@ -750,123 +640,14 @@ impl GrokClient {
         // Generate query embedding
         let query_vector = self.generate_embedding(&query).await?;
 
-        // Build search request with lfmf-specific filters
-        use qdrant_client::qdrant::{Filter, Condition, FieldCondition, Match, r#match::MatchValue};
-        
-        let filter = Filter {
-            must: vec![
-                // Filter for lfmf lessons only
-                Condition {
-                    condition_one_of: Some(qdrant_client::qdrant::condition::ConditionOneOf::Field(
-                        FieldCondition {
-                            key: "lesson_type".to_string(),
-                            r#match: Some(Match {
-                                match_value: Some(MatchValue::Text("fail".to_string())),
-                            }),
-                            ..Default::default()
-                        }
-                    )),
-                },
-                // Filter for same tool
-                Condition {
-                    condition_one_of: Some(qdrant_client::qdrant::condition::ConditionOneOf::Field(
-                        FieldCondition {
-                            key: "tool".to_string(),
-                            r#match: Some(Match {
-                                match_value: Some(MatchValue::Text(tool.to_lowercase())),
-                            }),
-                            ..Default::default()
-                        }
-                    )),
-                },
-            ],
-            ..Default::default()
-        };
+        let results = store.search(&query_vector, &Self::lesson_filter(tool), 5).await?; // Top 5 most similar lessons
 
-        let search_request = SearchPoints {
-            collection_name: self.collection_name.clone(),
-            vector: query_vector,
-            filter: Some(filter),
-            limit: 5, // Top 5 most similar lessons
-            with_payload: Some(WithPayloadSelector {
-                selector_options: Some(qdrant_client::qdrant::with_payload_selector::SelectorOptions::Enable(true)),
-            }),
-            ..Default::default()
-        };
-
-        let search_result = client.search_points(search_request).await?;
-
-        let mut advice_chunks = Vec::new();
-        for scored_point in search_result.result {
-            let payload = scored_point.payload;
-            
-            let content = payload.get("content")
-                .and_then(|v| v.as_str())
-                .map_or("", |v| v).to_string();
-            
-            let lesson = payload.get("lesson")
-                .and_then(|v| v.as_str())
-                .map_or("", |v| v).to_string();
-                
-            let error_pattern = payload.get("error_pattern")
-                .and_then(|v| v.as_str())
-                .map_or("", |v| v).to_string();
-
-            let confidence = payload.get("confidence")
-                .and_then(|v| match v {
-                    qdrant_client::qdrant::Value { kind: Some(qdrant_client::qdrant::value::Kind::DoubleValue(f)) } => Some(*f as f32),
-                    qdrant_client::qdrant::Value { kind: Some(qdrant_client::qdrant::value::Kind::IntegerValue(i)) } => Some(*i as f32),
-                    _ => None,
-                })
-                .unwrap_or(0.0);
-
-            let created_at = payload.get("created_at")
-                .and_then(|v| v.as_str())
-                .map_or("", |v| v).to_string();
-
-            let point_id = match &scored_point.id {
-                Some(id) => match id.point_id_options.as_ref() {
-                    Some(qdrant_client::qdrant::point_id::PointIdOptions::Uuid(uuid_str)) => {
-                        Uuid::parse_str(&uuid_str).unwrap_or_else(|_| Uuid::new_v4())
-                    }
-                    Some(qdrant_client::qdrant::point_id::PointIdOptions::Num(_)) => Uuid::new_v4(),
-                    None => Uuid::new_v4(),
-                }
-                None => Uuid::new_v4(),
-            };
-
-            let chunk = Chunk {
-                id: point_id,
-                content,
-                datum: tool.to_string(),
-                attribution: Attribution {
-                    url: None,
-                    filename: None,
-                    date: created_at.clone(),
-                },
-                metadata: ChunkMetadata {
-                    topic: tool.to_string(),
-                    tags: vec![
-                        "lfmf:lesson".to_string(),
-                        format!("tool:{}", tool),
-                        format!("similarity:{:.3}", scored_point.score),
-                    ],
-                    created_at,
-                    lesson_type: Some("fail".to_string()),
-                    error_pattern: Some(error_pattern),
-                    solution: Some(lesson),
-                    confidence: Some(confidence),
-                },
-                vector: scored_point.vectors.and_then(|v| match v.vectors_options? {
-                    qdrant_client::qdrant::vectors_output::VectorsOptions::Vector(vector_struct) => {
-                        Some(vector_struct.data)
-                    }
-                    _ => None,
-                }),
-            };
-            
-            advice_chunks.push(chunk);
-        }
+        let advice_chunks: Vec<Chunk> = results.into_iter().map(|point| {
+            let similarity = format!("similarity:{:.3}", point.score.unwrap_or_default());
+            let mut chunk = Self::lesson_chunk(tool, point);
+            chunk.metadata.tags.push(similarity);
+            chunk
+        }).collect();
 
         tracing::info!("Found {} advice chunks for tool '{}' error '{}'", 
                       advice_chunks.len(), tool, error);
@@ -876,8 +657,7 @@ impl GrokClient {
 
     /// List all lessons for a specific tool
     pub async fn list_lessons(&self, tool: &str) -> Result<Vec<Chunk>> {
-        let client = self.qdrant_client.as_ref()
-            .ok_or(GrokError::ClientNotInitialized)?;
+        let store = self.vector_store()?;
 
         if tool.trim().is_empty() {
             return Err(GrokError::InvalidQuery { 
@@ -885,119 +665,11 @@ impl GrokClient {
             });
         }
 
-        // Build filter for tool-specific lfmf lessons
-        use qdrant_client::qdrant::{Filter, Condition, FieldCondition, Match, r#match::MatchValue};
-        
-        let filter = Filter {
-            must: vec![
-                Condition {
-                    condition_one_of: Some(qdrant_client::qdrant::condition::ConditionOneOf::Field(
-                        FieldCondition {
-                            key: "lesson_type".to_string(),
-                            r#match: Some(Match {
-                                match_value: Some(MatchValue::Text("fail".to_string())),
-                            }),
-                            ..Default::default()
-                        }
-                    )),
-                },
-                Condition {
-                    condition_one_of: Some(qdrant_client::qdrant::condition::ConditionOneOf::Field(
-                        FieldCondition {
-                            key: "tool".to_string(),
-                            r#match: Some(Match {
-                                match_value: Some(MatchValue::Text(tool.to_lowercase())),
-                            }),
-                            ..Default::default()
-                        }
-                    )),
-                },
-            ],
-            ..Default::default()
-        };
-
-        let scroll_request = qdrant_client::qdrant::ScrollPoints {
-            collection_name: self.collection_name.clone(),
-            filter: Some(filter),
-            limit: Some(50), // Reasonable limit for lesson listing
-            with_payload: Some(WithPayloadSelector {
-                selector_options: Some(qdrant_client::qdrant::with_payload_selector::SelectorOptions::Enable(true)),
-            }),
-            ..Default::default()
-        };
+        let points = store.scroll(&Self::lesson_filter(tool), 50).await?; // Reasonable limit for lesson listing
 
-        let scroll_result = client.scroll(scroll_request).await?;
-        
-        let mut lessons = Vec::new();
-        for point in scroll_result.result {
-            let payload = point.payload;
-            
-            let content = payload.get("content")
-                .and_then(|v| v.as_str())
-                .map_or("", |v| v).to_string();
-                
-            let lesson = payload.get("lesson")
-                .and_then(|v| v.as_str())
-                .map_or("", |v| v).to_string();
-                
-            let error_pattern = payload.get("error_pattern")
-                .and_then(|v| v.as_str())
-                .map_or("", |v| v).to_string();
-
-            let confidence = payload.get("confidence")
-                .and_then(|v| match v {
-                    qdrant_client::qdrant::Value { kind: Some(qdrant_client::qdrant::value::Kind::DoubleValue(f)) } => Some(*f as f32),
-                    qdrant_client::qdrant::Value { kind: Some(qdrant_client::qdrant::value::Kind::IntegerValue(i)) } => Some(*i as f32),
-                    _ => None,
-                })
-                .unwrap_or(0.0);
-
-            let created_at = payload.get("created_at")
-                .and_then(|v| v.as_str())
-                .map_or("", |v| v).to_string();
-
-            let point_id = match &point.id {
-                Some(id) => match id.point_id_options.as_ref() {
-                    Some(qdrant_client::qdrant::point_id::PointIdOptions::Uuid(uuid_str)) => {
-                        Uuid::parse_str(&uuid_str).unwrap_or_else(|_| Uuid::new_v4())
-                    }
-                    Some(qdrant_client::qdrant::point_id::PointIdOptions::Num(_)) => Uuid::new_v4(),
-                    None => Uuid::new_v4(),
-                }
-                None => Uuid::new_v4(),
-            };
-
-            let chunk = Chunk {
-                id: point_id,
-                content,
-                datum: tool.to_string(),
-                attribution: Attribution {
-                    url: None,
-                    filename: None,
-                    date: created_at.clone(),
-                },
-                metadata: ChunkMetadata {
-                    topic: tool.to_string(),
-                    tags: vec![
-                        "lfmf:lesson".to_string(),
-                        format!("tool:{}", tool),
-                    ],
-                    created_at,
-                    lesson_type: Some("fail".to_string()),
-                    error_pattern: Some(error_pattern),
-                    solution: Some(lesson),
-                    confidence: Some(confidence),
-                },
-                vector: point.vectors.and_then(|v| match v.vectors_options? {
-                    qdrant_client::qdrant::vectors_output::VectorsOptions::Vector(vector_struct) => {
-                        Some(vector_struct.data)
-                    }
-                    _ => None,
-                }),
-            };
-            
-            lessons.push(chunk);
-        }
+        let lessons: Vec<Chunk> = points.into_iter()
+            .map(|point| Self::lesson_chunk(tool, point))
+            .collect();
 
         tracing::info!("Listed {} lessons for tool '{}'", lessons.len(), tool);
         Ok(lessons)
@@ -1005,6 +677,49 @@ impl GrokClient {
 
     // Helper methods for lfmf functionality
 
+    /// lfmf lessons recorded for `tool`
+    fn lesson_filter(tool: &str) -> PayloadFilter {
+        PayloadFilter::new()
+            .with("lesson_type", "fail")
+            .with("tool", tool.to_lowercase())
+    }
+
+    fn lesson_chunk(tool: &str, point: StoredPoint) -> Chunk {
+        let payload = &point.payload;
+        let created_at = payload_str(payload, "created_at").unwrap_or_default();
+        let confidence = payload.get("confidence")
+            .and_then(|v| v.as_f64())
+            .map_or(0.0, |f| f as f32);
+
+        Chunk {
+            id: point.id,
+            content: payload_str(payload, "content").unwrap_or_default(),
+            datum: tool.to_string(),
+            attribution: Attribution {
+                url: None,
+                filename: None,
+                date: created_at.clone(),
+            },
+            metadata: ChunkMetadata {
+                topic: tool.to_string(),
+                tags: vec![
+                    "lfmf:lesson".to_string(),
+                    format!("tool:{}", tool),
+                ],
+                created_at,
+                lesson_type: Some("fail".to_string()),
+                error_pattern: Some(payload_str(payload, "error_pattern").unwrap_or_default()),
+                solution: Some(payload_str(payload, "lesson").unwrap_or_default()),
+                confidence: Some(confidence),
+                heading_path: vec![],
+                line_range: None,
+            },
+            vector: None,
+            score: point.score,
+            similarity: point.score,
+        }
+    }
+
     fn infer_lfmf_topic(&self, tool: &str, error: &str, _content: &str) -> String {
         let base_topic = match tool.to_lowercase().as_str() {
             "just" | "justfile" => "just",
@@ -1176,6 +891,50 @@ mod tests {
         assert_eq!(client.api_key, "test_key");
     }
 
+    #[test]
+    fn test_with_embedder_and_collection() {
+        let client = GrokClient::new(
+            "https://example.com".to_string(),
+            "test_key".to_string()
+        )
+        .with_embedder(Arc::new(HashingEmbedder::new(32)))
+        .with_collection("test_chunks");
+
+        assert_eq!(client.collection_name, "test_chunks");
+        assert_eq!(client.embedder().map(|e| e.dimension()), Some(32));
+    }
+
+    #[tokio::test]
+    async fn test_local_store_roundtrip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let store = LocalStore::open(dir.path().join("vectors.json"), "b00t_chunks").unwrap();
+        let mut client = GrokClient::new(String::new(), String::new())
+            .with_embedder(Arc::new(HashingEmbedder::new(128)))
+            .with_store(Arc::new(store));
+        client.initialize().await.unwrap();
+
+        client.digest("rust", "cargo build needs a linker such as cc").await.unwrap();
+        client.digest("docker", "docker compose publishes ports with -p").await.unwrap();
+        let results = client.ask("cargo linker", None).await.unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].metadata.topic, "rust");
+        assert_eq!(client.ask("cargo linker", Some("docker")).await.unwrap().len(), 1);
+
+        client.record_lfmf("just", "Unknown start of token", "quote recipe args").await.unwrap();
+        let advice = client.get_advice("Just", "Unknown start of token '.'").await.unwrap();
+        assert_eq!(advice.len(), 1);
+        assert_eq!(advice[0].metadata.solution.as_deref(), Some("quote recipe args"));
+        assert_eq!(client.list_lessons("just").await.unwrap().len(), 1);
+        assert!(client.list_lessons("git").await.unwrap().is_empty());
+
+        let learned = client.learn("guide.md", "# Guide\n\n## Qdrant\n\nUse port 6334 for the client API.").await.unwrap();
+        assert_eq!(learned[0].metadata.heading_path, vec!["Guide", "Qdrant"]);
+        let found = client.ask("qdrant client port", Some("general")).await.unwrap();
+        assert_eq!(found[0].metadata.heading_path, vec!["Guide", "Qdrant"]);
+        assert_eq!(found[0].metadata.line_range, Some((3, 5)));
+        assert_eq!(found[0].attribution.filename.as_deref(), Some("guide.md"));
+    }
+
     #[tokio::test]
     async fn test_embeddings_integration() {
         // Skip if no OLLAMA_API_URL is set
@@ -1309,8 +1068,12 @@ This is the fourth paragraph with more detailed information. It explains complex
                 error_pattern: None,
                 solution: None,
                 confidence: None,
+                heading_path: vec![],
+                line_range: None,
             },
             vector: Some(vec![0.1, 0.2, 0.3]),
+            score: Some(0.5),
+            similarity: None,
         };
         
         let json = serde_json::to_string(&chunk).unwrap();
@@ -1319,6 +1082,7 @@ This is the fourth paragraph with more detailed information. It explains complex
         assert_eq!(chunk.content, deserialized.content);
         assert_eq!(chunk.datum, deserialized.datum);
         assert_eq!(chunk.vector, deserialized.vector);
+        assert_eq!(deserialized.score, Some(0.5));
     }
 
     #[tokio::test]
@@ -1338,7 +1102,7 @@ This is the fourth paragraph with more detailed information. It explains complex
         let mut client = GrokClient::new(
             "https://example.com".to_string(),
             "test_key".to_string()
-        );
+        ).with_chunker(Box::new(BasicChunker));
         client.initialize().await.unwrap();
         
         let content = "First paragraph about Rust.\n\nSecond paragraph about Cargo.\n\nThird paragraph about testing.";
@@ -0,0 +1,316 @@
+// 🤓 Knowledge-base lifecycle: list topics, forget bad chunks, and move a curated knowledge
+//    base between machines as portable JSONL that can be versioned in git
+use crate::errors::{GrokError, Result};
+use crate::store::{Payload, PayloadFilter, VectorPoint};
+use crate::{payload_str, Attribution, GrokClient};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{BufRead, Write};
+use uuid::Uuid;
+
+/// Points upserted per batch by `import`
+const IMPORT_BATCH_SIZE: usize = 64;
+
+/// A topic and how many chunks it holds
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TopicSummary {
+    pub topic: String,
+    pub chunks: usize,
+    /// Newest `created_at` among the topic's chunks
+    pub last_updated: Option<String>,
+}
+
+/// What `GrokClient::forget` removes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ForgetTarget {
+    Id(Uuid),
+    Topic(String),
+}
+
+impl ForgetTarget {
+    /// A UUID names one chunk, anything else a topic
+    pub fn parse(target: &str) -> Self {
+        match Uuid::parse_str(target.trim()) {
+            Ok(id) => ForgetTarget::Id(id),
+            Err(_) => ForgetTarget::Topic(target.trim().to_string()),
+        }
+    }
+}
+
+/// One line of a `grok export` snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotRecord {
+    pub id: Uuid,
+    pub topic: String,
+    pub content: String,
+    pub attribution: Attribution,
+    /// Remaining payload: datum, tool, heading_path, ingest_root, ...
+    #[serde(default)]
+    pub metadata: Payload,
+    /// Stored embedding, only when exported with vectors
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vector: Option<Vec<f32>>,
+    /// Embedder that produced `vector`; import re-embeds when it differs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+}
+
+impl SnapshotRecord {
+    fn from_point(point: VectorPoint, model: Option<&str>) -> Self {
+        let mut payload = point.payload;
+        let mut take = |key: &str| payload.remove(key).and_then(|v| v.as_str().map(str::to_string));
+
+        let content = take("content").unwrap_or_default();
+        let topic = take("topic").unwrap_or_else(|| "unknown".to_string());
+        let attribution = Attribution {
+            url: take("url"),
+            filename: take("filename"),
+            date: take("created_at").unwrap_or_default(),
+        };
+
+        let with_vector = model.is_some() && !point.vector.is_empty();
+        SnapshotRecord {
+            id: point.id,
+            topic,
+            content,
+            attribution,
+            metadata: payload,
+            vector: with_vector.then_some(point.vector),
+            model: model.filter(|_| with_vector).map(str::to_string),
+        }
+    }
+
+    /// Payload as it was stored, the inverse of `from_point`
+    fn payload(&self) -> Payload {
+        let mut payload = self.metadata.clone();
+        payload.insert("content".to_string(), self.content.clone().into());
+        payload.insert("topic".to_string(), self.topic.clone().into());
+        if let Some(url) = &self.attribution.url {
+            payload.insert("url".to_string(), url.clone().into());
+        }
+        if let Some(filename) = &self.attribution.filename {
+            payload.insert("filename".to_string(), filename.clone().into());
+        }
+        if !self.attribution.date.is_empty() {
+            payload.insert("created_at".to_string(), self.attribution.date.clone().into());
+        }
+        payload
+    }
+}
+
+/// Outcome of one `import` run
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportReport {
+    pub records: usize,
+    /// Records without a usable vector for the current embedder
+    pub reembedded: usize,
+}
+
+impl GrokClient {
+    /// Topics in the collection, largest first
+    pub async fn topics(&self) -> Result<Vec<TopicSummary>> {
+        let store = self.vector_store()?;
+
+        let mut topics: BTreeMap<String, TopicSummary> = BTreeMap::new();
+        for point in store.scroll(&PayloadFilter::new(), usize::MAX).await? {
+            let topic = payload_str(&point.payload, "topic").unwrap_or_else(|| "unknown".to_string());
+            let created_at = payload_str(&point.payload, "created_at");
+            let summary = topics.entry(topic.clone()).or_insert(TopicSummary {
+                topic,
+                chunks: 0,
+                last_updated: None,
+            });
+            summary.chunks += 1;
+            if created_at > summary.last_updated {
+                summary.last_updated = created_at;
+            }
+        }
+
+        let mut topics: Vec<TopicSummary> = topics.into_values().collect();
+        topics.sort_by(|a, b| b.chunks.cmp(&a.chunks));
+        Ok(topics)
+    }
+
+    /// Delete one chunk by id, or every chunk of a topic; returns how many were removed
+    pub async fn forget(&self, target: &str) -> Result<usize> {
+        let store = self.vector_store()?;
+
+        if target.trim().is_empty() {
+            return Err(GrokError::InvalidQuery {
+                message: "Forget target cannot be empty".to_string()
+            });
+        }
+
+        let ids: Vec<Uuid> = match ForgetTarget::parse(target) {
+            // 🤓 Look the id up first so an unknown id reports 0 instead of a silent no-op
+            ForgetTarget::Id(id) => store.get(&[id]).await?
+                .into_iter()
+                .map(|point| point.id)
+                .collect(),
+            ForgetTarget::Topic(topic) => store.scroll(&PayloadFilter::new().with("topic", topic.clone()), usize::MAX).await?
+                .into_iter()
+                // 🦨 Qdrant text match is a substring match; require the exact topic
+                .filter(|point| payload_str(&point.payload, "topic").is_some_and(|t| t.eq_ignore_ascii_case(&topic)))
+                .map(|point| point.id)
+                .collect(),
+        };
+
+        let removed = ids.len();
+        store.delete(ids).await?;
+
+        tracing::info!("Forgot {} chunks matching '{}' in {} store", removed, target, store.backend_name());
+        Ok(removed)
+    }
+
+    /// Write the collection (or one topic) as JSONL, one `SnapshotRecord` per line
+    ///
+    /// Records are sorted by topic and id so re-exports of an unchanged knowledge base diff cleanly.
+    pub async fn export<W: Write>(&self, mut writer: W, topic: Option<&str>, include_vectors: bool) -> Result<usize> {
+        let store = self.vector_store()?;
+        let model = include_vectors
+            .then(|| self.embedder.as_ref().map(|e| e.model_name().to_string()))
+            .flatten();
+
+        let filter = topic.map_or_else(PayloadFilter::new, |t| PayloadFilter::new().with("topic", t));
+        let mut records: Vec<SnapshotRecord> = store.scroll_vectors(&filter, usize::MAX).await?
+            .into_iter()
+            .map(|point| SnapshotRecord::from_point(point, model.as_deref()))
+            .filter(|record| topic.is_none_or(|t| record.topic.eq_ignore_ascii_case(t)))
+            .collect();
+        records.sort_by(|a, b| (&a.topic, a.id).cmp(&(&b.topic, b.id)));
+
+        for record in &records {
+            serde_json::to_writer(&mut writer, record)?;
+            writer.write_all(b"\n")?;
+        }
+        writer.flush()?;
+
+        tracing::info!("Exported {} chunks from {} store", records.len(), store.backend_name());
+        Ok(records.len())
+    }
+
+    /// Restore a JSONL snapshot, keeping chunk ids so re-importing is idempotent
+    ///
+    /// Vectors are reused when they come from the current embedder, otherwise content is re-embedded.
+    pub async fn import<R: BufRead>(&self, reader: R) -> Result<ImportReport> {
+        let store = self.vector_store()?;
+        let embedder = self.embedder.as_ref().ok_or(GrokError::ClientNotInitialized)?;
+
+        let mut report = ImportReport::default();
+        let mut batch = Vec::new();
+
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record: SnapshotRecord = serde_json::from_str(&line).map_err(|e| GrokError::InvalidQuery {
+                message: format!("Snapshot line {}: {}", i + 1, e)
+            })?;
+
+            let reusable = record.model.as_deref() == Some(embedder.model_name())
+                && record.vector.as_ref().is_some_and(|v| v.len() == embedder.dimension());
+            let payload = record.payload();
+            let vector = match record.vector {
+                Some(vector) if reusable => vector,
+                _ => {
+                    report.reembedded += 1;
+                    embedder.embed(&record.content).await?
+                }
+            };
+
+            batch.push(VectorPoint { id: record.id, vector, payload });
+            report.records += 1;
+            if batch.len() >= IMPORT_BATCH_SIZE {
+                store.upsert(std::mem::take(&mut batch)).await?;
+            }
+        }
+        store.upsert(batch).await?;
+
+        tracing::info!("Imported {} chunks ({} re-embedded) into {} store",
+            report.records, report.reembedded, store.backend_name());
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HashingEmbedder, LocalStore, VectorStore};
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    async fn client(dir: &TempDir, dimension: usize) -> (GrokClient, Arc<LocalStore>) {
+        let store = Arc::new(LocalStore::open(dir.path().join("vectors.json"), "kb").unwrap());
+        let mut client = GrokClient::new(String::new(), String::new())
+            .with_embedder(Arc::new(HashingEmbedder::new(dimension)))
+            .with_store(store.clone());
+        client.initialize().await.unwrap();
+        (client, store)
+    }
+
+    #[test]
+    fn test_parse_forget_target() {
+        let id = Uuid::new_v4();
+        assert_eq!(ForgetTarget::parse(&id.to_string()), ForgetTarget::Id(id));
+        assert_eq!(ForgetTarget::parse(" rust "), ForgetTarget::Topic("rust".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_topics_and_forget() {
+        let dir = TempDir::new().unwrap();
+        let (client, store) = client(&dir, 64).await;
+
+        let first = client.digest("rust", "Cargo workspaces share one target directory").await.unwrap();
+        client.digest("rust", "Clippy lints are configured in the workspace manifest").await.unwrap();
+        client.digest("docker", "Multi-stage builds keep images small").await.unwrap();
+
+        let topics = client.topics().await.unwrap();
+        assert_eq!(topics.iter().map(|t| (t.topic.as_str(), t.chunks)).collect::<Vec<_>>(),
+            vec![("rust", 2), ("docker", 1)]);
+        assert!(topics[0].last_updated.is_some());
+
+        assert_eq!(client.forget(&first.id.to_string()).await.unwrap(), 1);
+        assert_eq!(client.forget(&Uuid::new_v4().to_string()).await.unwrap(), 0);
+        assert_eq!(client.forget("Docker").await.unwrap(), 1);
+        assert_eq!(store.count().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_export_import_roundtrip() {
+        let source_dir = TempDir::new().unwrap();
+        let (source, source_store) = client(&source_dir, 64).await;
+        source.learn("docs/guide.md", "# Install\n\nRun the bootstrap script to install b00t.\n").await.unwrap();
+        source.digest("rust", "Cargo workspaces share one target directory").await.unwrap();
+
+        let mut snapshot = Vec::new();
+        assert_eq!(source.export(&mut snapshot, None, true).await.unwrap(), 2);
+        let mut rust_only = Vec::new();
+        assert_eq!(source.export(&mut rust_only, Some("rust"), false).await.unwrap(), 1);
+        assert!(!String::from_utf8(rust_only).unwrap().contains("\"vector\""));
+
+        // Same embedder: vectors are reused and payloads restored exactly
+        let same_dir = TempDir::new().unwrap();
+        let (same, same_store) = client(&same_dir, 64).await;
+        let report = same.import(snapshot.as_slice()).await.unwrap();
+        assert_eq!(report, ImportReport { records: 2, reembedded: 0 });
+
+        let original = source_store.scroll_vectors(&PayloadFilter::new(), usize::MAX).await.unwrap();
+        for point in original {
+            let restored = same_store.scroll_vectors(&PayloadFilter::new(), usize::MAX).await.unwrap()
+                .into_iter().find(|p| p.id == point.id).unwrap();
+            assert_eq!(restored.payload, point.payload);
+            assert_eq!(restored.vector, point.vector);
+        }
+
+        // Different embedder: content is re-embedded, re-import doesn't duplicate
+        let other_dir = TempDir::new().unwrap();
+        let (other, other_store) = client(&other_dir, 32).await;
+        assert_eq!(other.import(snapshot.as_slice()).await.unwrap().reembedded, 2);
+        other.import(snapshot.as_slice()).await.unwrap();
+        assert_eq!(other_store.count().await.unwrap(), 2);
+        assert!(other.ask("bootstrap install", None).await.unwrap()[0].content.contains("bootstrap"));
+
+        assert!(other.import("not json\n".as_bytes()).await.is_err());
+    }
+}
@@ -0,0 +1,406 @@
+// 🤓 Hybrid retrieval: BM25 keyword ranking + vector similarity, fused with reciprocal
+//    rank fusion and optionally reranked by a cross-encoder behind an HTTP /rerank endpoint
+use crate::errors::{GrokError, Result};
+use crate::store::{PayloadFilter, StoredPoint};
+use crate::{payload_str, Chunk, GrokClient};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// RRF damping constant from the original paper (Cormack et al. 2009)
+pub const RRF_K: f32 = 60.0;
+
+/// Points scanned for keyword ranking; BM25 runs client-side over scrolled payloads
+pub const KEYWORD_CANDIDATES: usize = 2_000;
+
+/// Candidates handed to the reranker, as a multiple of the requested limit
+const RERANK_POOL_FACTOR: usize = 3;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchMode {
+    /// Vector similarity only
+    Vector,
+    /// BM25 over chunk content only, no embedding call
+    Keyword,
+    /// Vector + BM25 fused with reciprocal rank fusion
+    #[default]
+    Hybrid,
+}
+
+/// Options for `GrokClient::search`
+///
+/// Every mode reports scores in roughly 0..1 (cosine similarity, BM25 relative to the best
+/// hit, RRF relative to a first place in every ranking, or the reranker's relevance score),
+/// but only cosine similarity is absolute, so `min_score` is always checked against it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchOptions {
+    pub topic: Option<String>,
+    pub limit: usize,
+    /// Minimum cosine similarity from the vector leg; hits without one are dropped
+    pub min_score: Option<f32>,
+    pub mode: SearchMode,
+    /// Rerank with the client's reranker, if one is configured
+    pub rerank: bool,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            topic: None,
+            limit: 10,
+            min_score: None,
+            mode: SearchMode::Hybrid,
+            rerank: true,
+        }
+    }
+}
+
+impl SearchOptions {
+    pub fn with_topic(mut self, topic: Option<&str>) -> Self {
+        self.topic = topic.map(str::to_string);
+        self
+    }
+
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    pub fn with_min_score(mut self, min_score: Option<f32>) -> Self {
+        self.min_score = min_score;
+        self
+    }
+
+    pub fn with_mode(mut self, mode: SearchMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn with_rerank(mut self, rerank: bool) -> Self {
+        self.rerank = rerank;
+        self
+    }
+}
+
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Okapi BM25 (k1 = 1.2, b = 0.75) of `query` against each document, best first
+///
+/// Documents without any query term are omitted.
+pub fn bm25_scores(query: &str, documents: &[(Uuid, &str)]) -> Vec<(Uuid, f32)> {
+    const K1: f32 = 1.2;
+    const B: f32 = 0.75;
+
+    let mut terms = tokenize(query);
+    terms.sort();
+    terms.dedup();
+    if terms.is_empty() || documents.is_empty() {
+        return Vec::new();
+    }
+
+    let docs: Vec<Vec<String>> = documents.iter().map(|(_, text)| tokenize(text)).collect();
+    let n = docs.len() as f32;
+    let avg_len = (docs.iter().map(Vec::len).sum::<usize>() as f32 / n).max(1.0);
+
+    let idf: HashMap<&str, f32> = terms.iter().map(|term| {
+        let df = docs.iter().filter(|doc| doc.contains(term)).count() as f32;
+        (term.as_str(), ((n - df + 0.5) / (df + 0.5) + 1.0).ln())
+    }).collect();
+
+    let mut scored: Vec<(Uuid, f32)> = documents.iter().zip(&docs).filter_map(|((id, _), doc)| {
+        let len = doc.len() as f32;
+        let score: f32 = terms.iter().map(|term| {
+            let tf = doc.iter().filter(|w| *w == term).count() as f32;
+            idf[term.as_str()] * tf * (K1 + 1.0) / (tf + K1 * (1.0 - B + B * len / avg_len))
+        }).sum();
+        (score > 0.0).then_some((*id, score))
+    }).collect();
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+    scored
+}
+
+/// Reciprocal rank fusion of several best-first rankings
+///
+/// Scores are normalized so an item ranked first in every list scores 1.0.
+pub fn reciprocal_rank_fusion(rankings: &[Vec<Uuid>], k: f32) -> Vec<(Uuid, f32)> {
+    let mut fused: HashMap<Uuid, f32> = HashMap::new();
+    for ranking in rankings {
+        for (rank, id) in ranking.iter().enumerate() {
+            *fused.entry(*id).or_default() += 1.0 / (k + rank as f32 + 1.0);
+        }
+    }
+
+    let best = rankings.len() as f32 / (k + 1.0);
+    let mut scored: Vec<(Uuid, f32)> = fused.into_iter().map(|(id, s)| (id, s / best)).collect();
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+    scored
+}
+
+#[async_trait]
+pub trait Reranker: Send + Sync {
+    fn model_name(&self) -> &str;
+
+    /// Relevance of each document to `query`, in document order
+    async fn rerank(&self, query: &str, documents: &[String]) -> Result<Vec<f32>>;
+}
+
+/// Reranker for the Cohere-style `POST {api_base}/rerank` API
+///
+/// Served by Cohere, Jina, Voyage, LiteLLM and HuggingFace TEI.
+#[derive(Debug, Clone)]
+pub struct HttpReranker {
+    api_base: String,
+    api_key: Option<String>,
+    model: String,
+    client: reqwest::Client,
+}
+
+#[derive(Serialize)]
+struct RerankRequest<'a> {
+    model: &'a str,
+    query: &'a str,
+    documents: &'a [String],
+}
+
+#[derive(Deserialize)]
+struct RerankResponse {
+    results: Vec<RerankResult>,
+}
+
+#[derive(Deserialize)]
+struct RerankResult {
+    index: usize,
+    relevance_score: f32,
+}
+
+impl HttpReranker {
+    pub fn new(api_base: &str, api_key: Option<&str>, model: &str) -> Self {
+        Self {
+            api_base: api_base.trim_end_matches('/').to_string(),
+            api_key: api_key.map(str::to_string),
+            model: model.to_string(),
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl Reranker for HttpReranker {
+    fn model_name(&self) -> &str {
+        &self.model
+    }
+
+    async fn rerank(&self, query: &str, documents: &[String]) -> Result<Vec<f32>> {
+        let body = serde_json::to_vec(&RerankRequest { model: &self.model, query, documents })?;
+        let mut request = self.client.post(format!("{}/rerank", self.api_base))
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body);
+        if let Some(key) = &self.api_key {
+            request = request.bearer_auth(key);
+        }
+
+        let response = request.send().await?.error_for_status()?;
+        let parsed: RerankResponse = serde_json::from_slice(&response.bytes().await?)?;
+
+        let mut scores = vec![f32::MIN; documents.len()];
+        for result in parsed.results {
+            if let Some(score) = scores.get_mut(result.index) {
+                *score = result.relevance_score;
+            }
+        }
+        Ok(scores)
+    }
+}
+
+impl GrokClient {
+    /// Search the knowledgebase with keyword, vector or hybrid ranking
+    pub async fn search(&self, query: &str, options: &SearchOptions) -> Result<Vec<Chunk>> {
+        let store = self.vector_store()?;
+
+        if query.trim().is_empty() {
+            return Err(GrokError::InvalidQuery {
+                message: "Query cannot be empty".to_string()
+            });
+        }
+
+        if options.mode == SearchMode::Keyword && options.min_score.is_some() {
+            return Err(GrokError::InvalidQuery {
+                message: "min_score needs vector similarity; use vector or hybrid mode".to_string()
+            });
+        }
+
+        let mut filter = PayloadFilter::new();
+        if let Some(topic) = &options.topic {
+            filter = filter.with("topic", topic.clone());
+        }
+
+        let reranker = self.reranker.as_ref().filter(|_| options.rerank);
+        let pool = if reranker.is_some() { options.limit * RERANK_POOL_FACTOR } else { options.limit };
+        let mut points: HashMap<Uuid, StoredPoint> = HashMap::new();
+        let mut rankings = Vec::new();
+
+        if options.mode != SearchMode::Keyword {
+            let query_vector = self.generate_embedding(query).await?;
+            let hits = store.search(&query_vector, &filter, pool).await?;
+            rankings.push(hits.iter().map(|p| p.id).collect::<Vec<_>>());
+            points.extend(hits.into_iter().map(|p| (p.id, p)));
+        }
+
+        let mut keyword_scores = Vec::new();
+        if options.mode != SearchMode::Vector {
+            let candidates = store.scroll(&filter, KEYWORD_CANDIDATES).await?;
+            if candidates.len() >= KEYWORD_CANDIDATES {
+                tracing::warn!("Keyword ranking only scanned the first {} chunks; narrow the search with a topic",
+                    KEYWORD_CANDIDATES);
+            }
+            let contents: Vec<(Uuid, String)> = candidates.iter()
+                .map(|p| (p.id, payload_str(&p.payload, "content").unwrap_or_default()))
+                .collect();
+            let documents: Vec<(Uuid, &str)> = contents.iter().map(|(id, c)| (*id, c.as_str())).collect();
+            keyword_scores = bm25_scores(query, &documents);
+            keyword_scores.truncate(pool);
+
+            rankings.push(keyword_scores.iter().map(|(id, _)| *id).collect());
+            let wanted: std::collections::HashSet<Uuid> = keyword_scores.iter().map(|(id, _)| *id).collect();
+            for point in candidates.into_iter().filter(|p| wanted.contains(&p.id)) {
+                points.entry(point.id).or_insert(point);
+            }
+        }
+
+        let mut ranked: Vec<(Uuid, f32)> = match options.mode {
+            SearchMode::Vector => rankings[0].iter()
+                .map(|id| (*id, points[id].score.unwrap_or_default()))
+                .collect(),
+            SearchMode::Keyword => {
+                let best = keyword_scores.first().map_or(1.0, |(_, s)| *s);
+                keyword_scores.iter().map(|(id, s)| (*id, s / best)).collect()
+            }
+            SearchMode::Hybrid => reciprocal_rank_fusion(&rankings, RRF_K),
+        };
+        ranked.truncate(pool);
+
+        if let Some(reranker) = reranker {
+            if !ranked.is_empty() {
+                let documents: Vec<String> = ranked.iter()
+                    .map(|(id, _)| payload_str(&points[id].payload, "content").unwrap_or_default())
+                    .collect();
+                let scores = reranker.rerank(query, &documents).await?;
+                ranked = ranked.iter().zip(scores).map(|((id, _), score)| (*id, score)).collect();
+                ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+            }
+        }
+
+        // 🤓 Rank-based scores (RRF, relative BM25) say nothing about absolute relevance, so the
+        //    cutoff uses the raw cosine similarity; metadata.confidence carries the reranker
+        //    score or cosine similarity
+        let reranked = reranker.is_some() && !ranked.is_empty();
+        let kept: Vec<(Uuid, f32)> = ranked.into_iter()
+            .filter(|(id, _)| options.min_score.is_none_or(|min| {
+                points.get(id).and_then(|p| p.score).is_some_and(|similarity| similarity >= min)
+            }))
+            .take(options.limit)
+            .collect();
+        let chunks: Vec<Chunk> = kept.into_iter()
+            .filter_map(|(id, score)| points.remove(&id).map(|p| {
+                let confidence = if reranked { Some(score) } else { p.score };
+                let mut chunk = Self::stored_chunk(p, score);
+                chunk.metadata.confidence = confidence;
+                chunk
+            }))
+            .collect();
+
+        tracing::info!("Found {} chunks for query '{}' ({:?}, topic {:?}, reranked: {})",
+            chunks.len(), query, options.mode, options.topic, reranker.is_some());
+        Ok(chunks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HashingEmbedder, LocalStore};
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    /// Scores documents by length, so the shortest one always wins
+    struct ShortestFirst;
+
+    #[async_trait]
+    impl Reranker for ShortestFirst {
+        fn model_name(&self) -> &str {
+            "shortest-first"
+        }
+
+        async fn rerank(&self, _query: &str, documents: &[String]) -> Result<Vec<f32>> {
+            Ok(documents.iter().map(|d| 1.0 / d.len() as f32).collect())
+        }
+    }
+
+    #[test]
+    fn test_bm25_prefers_rare_matching_terms() {
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let docs = [
+            (a, "qdrant listens on port 6334 for the grpc client"),
+            (b, "the client and the server and the port"),
+            (c, "docker compose networking"),
+        ];
+        let scores = bm25_scores("qdrant grpc port", &docs);
+        assert_eq!(scores.len(), 2);
+        assert_eq!(scores[0].0, a);
+        assert!(bm25_scores("   ", &docs).is_empty());
+    }
+
+    #[test]
+    fn test_rrf_rewards_agreement() {
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let fused = reciprocal_rank_fusion(&[vec![a, b], vec![b, c]], RRF_K);
+        assert_eq!(fused[0].0, b);
+        assert_eq!(fused.len(), 3);
+
+        let unanimous = reciprocal_rank_fusion(&[vec![a], vec![a]], RRF_K);
+        assert!((unanimous[0].1 - 1.0).abs() < 1e-6);
+    }
+
+    #[tokio::test]
+    async fn test_search_modes_and_cutoff() {
+        let dir = TempDir::new().unwrap();
+        let store = Arc::new(LocalStore::open(dir.path().join("vectors.json"), "search").unwrap());
+        let mut client = GrokClient::new(String::new(), String::new())
+            .with_embedder(Arc::new(HashingEmbedder::new(128)))
+            .with_store(store)
+            .with_reranker(Arc::new(ShortestFirst));
+        client.initialize().await.unwrap();
+
+        client.digest("rust", "cargo build fails when the linker lld is missing on the host system").await.unwrap();
+        client.digest("rust", "cargo linker errors").await.unwrap();
+        client.digest("docker", "docker compose networking between containers").await.unwrap();
+
+        let plain = SearchOptions::default().with_rerank(false);
+        let hybrid = client.search("cargo linker lld", &plain).await.unwrap();
+        assert_eq!(hybrid.len(), 3);
+        assert!(hybrid[0].content.contains("lld"));
+        assert!(hybrid.windows(2).all(|w| w[0].score >= w[1].score));
+
+        let keyword = client.search("lld", &plain.clone().with_mode(SearchMode::Keyword)).await.unwrap();
+        assert_eq!(keyword.len(), 1);
+        assert_eq!(keyword[0].score, Some(1.0));
+        assert_eq!(keyword[0].metadata.confidence, None); // no vector similarity for keyword-only hits
+
+        // min_score compares cosine similarity, not the RRF rank score
+        let best = hybrid.iter().filter_map(|c| c.metadata.confidence).fold(f32::MIN, f32::max);
+        let cut = client.search("cargo linker lld", &plain.clone().with_min_score(Some(best)).with_limit(5)).await.unwrap();
+        assert!(!cut.is_empty());
+        assert!(cut.iter().all(|c| c.metadata.confidence.is_some_and(|s| s >= best)));
+        assert!(client.search("cargo", &plain.clone().with_mode(SearchMode::Keyword).with_min_score(Some(0.1))).await.is_err());
+
+        let reranked = client.search("cargo linker", &SearchOptions::default().with_topic(Some("rust"))).await.unwrap();
+        assert_eq!(reranked[0].content, "cargo linker errors");
+    }
+}
@@ -0,0 +1,1043 @@
+// 🤓 Pluggable vector storage: Qdrant when a server is reachable, or a single-file
+//    brute-force store under ~/.b00t so grok/lfmf search works on laptops and offline CI
+use crate::errors::{GrokError, Result};
+use async_trait::async_trait;
+use qdrant_client::{
+    Qdrant,
+    qdrant::{
+        Condition, CountPoints, CreateCollection, CreateFieldIndexCollection, DeletePoints, Distance,
+        FieldCondition, FieldType, Filter, GetPoints, Match, PointId, PointStruct, PointsSelector, RetrievedPoint,
+        ScrollPoints, SearchPoints, UpsertPoints,
+        VectorParams, VectorsOutput, WithPayloadSelector, r#match::MatchValue,
+    },
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// Point payload, stored as plain JSON by every backend
+pub type Payload = Map<String, Value>;
+
+/// A vector and its payload, as written to a store
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VectorPoint {
+    pub id: Uuid,
+    pub vector: Vec<f32>,
+    pub payload: Payload,
+}
+
+/// A point read back from a store; `score` is set by similarity search only
+#[derive(Debug, Clone)]
+pub struct StoredPoint {
+    pub id: Uuid,
+    pub payload: Payload,
+    pub score: Option<f32>,
+}
+
+/// Payload fields Qdrant filters on, through a keyword-indexed normalized copy
+pub const KEYWORD_FIELDS: &[&str] = &["topic", "tool", "lesson_type", "ingest_root"];
+
+/// Payload key holding those normalized copies on Qdrant (stripped again on read)
+const KEYWORDS_KEY: &str = "_keywords";
+
+/// How payload filters compare values: lowercased, scalars as their JSON text
+pub fn normalize_keyword(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.to_lowercase()),
+        Value::Number(_) | Value::Bool(_) => Some(value.to_string().to_lowercase()),
+        _ => None,
+    }
+}
+
+/// Payload conditions that must all hold
+///
+/// Values compare as whole, case-insensitive strings, so `tool = "Just"` matches a stored
+/// `"just"` but not `"justfile"`, on every backend. On Qdrant that holds for `KEYWORD_FIELDS`;
+/// other keys match exactly as given.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PayloadFilter {
+    pub must: Vec<(String, String)>,
+}
+
+impl PayloadFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.must.push((key.into(), value.into()));
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.must.is_empty()
+    }
+
+    pub fn matches(&self, payload: &Payload) -> bool {
+        self.must.iter().all(|(key, expected)| {
+            payload.get(key).and_then(normalize_keyword).is_some_and(|value| value == expected.to_lowercase())
+        })
+    }
+}
+
+#[async_trait]
+pub trait VectorStore: Send + Sync {
+    /// Short backend identifier for status output ("qdrant", "local")
+    fn backend_name(&self) -> &str;
+
+    /// Collection this store reads and writes
+    fn collection(&self) -> &str;
+
+    /// Create the collection if needed, refusing one built with a different vector size
+    async fn ensure_collection(&self, dimension: usize, model: &str) -> Result<()>;
+
+    /// Insert points, replacing any with the same id
+    async fn upsert(&self, points: Vec<VectorPoint>) -> Result<()>;
+
+    /// Nearest points by cosine similarity, best first
+    async fn search(&self, vector: &[f32], filter: &PayloadFilter, limit: usize) -> Result<Vec<StoredPoint>>;
+
+    /// Points matching `filter`, in storage order
+    async fn scroll(&self, filter: &PayloadFilter, limit: usize) -> Result<Vec<StoredPoint>>;
+
+    /// Like `scroll`, but with stored vectors (for snapshots)
+    async fn scroll_vectors(&self, filter: &PayloadFilter, limit: usize) -> Result<Vec<VectorPoint>>;
+
+    async fn count(&self) -> Result<usize>;
+
+    /// Points with these ids, without scanning the collection; unknown ids are skipped
+    async fn get(&self, ids: &[Uuid]) -> Result<Vec<StoredPoint>>;
+
+    /// Remove points by id; unknown ids are ignored
+    async fn delete(&self, ids: Vec<Uuid>) -> Result<()>;
+}
+
+// ---------------------------------------------------------------------------
+// Qdrant
+// ---------------------------------------------------------------------------
+
+// 🤓 CRITICAL TRIBAL KNOWLEDGE: qdrant-client v1.15.0 Port Configuration
+//    
+//    IMPORTANT: Qdrant has two different ports:
+//    - Port 6333: Web UI and HTTP REST API (for manual testing)  
+//    - Port 6334: Client API (gRPC/HTTP for qdrant-client library)
+//    
+//    The qdrant-client crate needs port 6334, NOT 6333!
+//    Use: http://host:6334 (client API)
+//    NOT: http://host:6333 (web UI only)
+//    
+//    This was the root cause of "protocol error: invalid compression flag"
+//    Future agents: Always use port 6334 for qdrant-client connections!
+
+/// `VectorStore` backed by a Qdrant collection
+pub struct QdrantStore {
+    client: Qdrant,
+    collection: String,
+}
+
+impl QdrantStore {
+    pub fn new(client: Qdrant, collection: impl Into<String>) -> Self {
+        Self {
+            client,
+            collection: collection.into(),
+        }
+    }
+
+    // 🚀 HTTP-only client builder
+    pub fn connect(url: &str, api_key: Option<&str>, collection: impl Into<String>) -> Result<Self> {
+        // Convert grpc:// URLs to http:// since we're HTTP-only now
+        let clean_url = if url.starts_with("grpc://") {
+            tracing::info!("Converting grpc:// to http:// (HTTP-only mode)");
+            url.replacen("grpc://", "http://", 1)
+        } else if url.starts_with("grpcs://") {
+            tracing::info!("Converting grpcs:// to https:// (HTTP-only mode)");
+            url.replacen("grpcs://", "https://", 1)
+        } else {
+            url.to_string()
+        };
+
+        tracing::info!("Using HTTP REST protocol: {}", clean_url);
+
+        let mut builder = Qdrant::from_url(&clean_url);
+
+        if let Some(key) = api_key {
+            builder = builder.api_key(key.to_string());
+        }
+
+        // 🤓 NOTE: qdrant-client v1.15.0 requires gRPC for initialization even with HTTP URLs
+        //    This is a known limitation - server must have both ports 6333 (HTTP) and 6334 (gRPC) enabled
+
+        Ok(Self::new(builder.build()?, collection))
+    }
+
+    /// Cheap reachability probe, used to decide whether to fall back to the local store
+    pub async fn health_check(&self) -> Result<()> {
+        self.client.health_check().await?;
+        Ok(())
+    }
+
+    fn to_qdrant_filter(filter: &PayloadFilter) -> Option<Filter> {
+        if filter.is_empty() {
+            return None;
+        }
+        Some(Filter {
+            must: filter.must.iter().map(|(key, value)| {
+                if !KEYWORD_FIELDS.contains(&key.as_str()) {
+                    return Self::keyword_condition(key.clone(), value.clone());
+                }
+                // 🤓 MatchValue::Text is a full-text partial match; keywords match whole values.
+                //    Points written before `_keywords` existed only have the raw field
+                Condition {
+                    condition_one_of: Some(qdrant_client::qdrant::condition::ConditionOneOf::Filter(Filter {
+                        should: vec![
+                            Self::keyword_condition(format!("{}.{}", KEYWORDS_KEY, key), value.to_lowercase()),
+                            Self::keyword_condition(key.clone(), value.clone()),
+                        ],
+                        ..Default::default()
+                    })),
+                }
+            }).collect(),
+            ..Default::default()
+        })
+    }
+
+    fn keyword_condition(key: String, value: String) -> Condition {
+        Condition {
+            condition_one_of: Some(qdrant_client::qdrant::condition::ConditionOneOf::Field(
+                FieldCondition {
+                    key,
+                    r#match: Some(Match {
+                        match_value: Some(MatchValue::Keyword(value)),
+                    }),
+                    ..Default::default()
+                }
+            )),
+        }
+    }
+
+    fn with_payload() -> Option<WithPayloadSelector> {
+        Some(WithPayloadSelector {
+            selector_options: Some(qdrant_client::qdrant::with_payload_selector::SelectorOptions::Enable(true)),
+        })
+    }
+
+    fn point_uuid(id: Option<PointId>) -> Uuid {
+        match id.and_then(|id| id.point_id_options) {
+            Some(qdrant_client::qdrant::point_id::PointIdOptions::Uuid(uuid_str)) => {
+                Uuid::parse_str(&uuid_str).unwrap_or_else(|_| Uuid::new_v4())
+            }
+            _ => Uuid::new_v4(),
+        }
+    }
+
+    fn to_payload(payload: HashMap<String, qdrant_client::qdrant::Value>) -> Payload {
+        payload.into_iter()
+            .filter(|(k, _)| k != KEYWORDS_KEY)
+            .map(|(k, v)| (k, Value::from(v)))
+            .collect()
+    }
+
+    /// `payload` plus the normalized copies `to_qdrant_filter` matches against
+    fn with_keywords(mut payload: Payload) -> Payload {
+        let keywords: Payload = KEYWORD_FIELDS.iter()
+            .filter_map(|field| Some((field.to_string(), Value::String(normalize_keyword(payload.get(*field)?)?))))
+            .collect();
+        if !keywords.is_empty() {
+            payload.insert(KEYWORDS_KEY.to_string(), Value::Object(keywords));
+        }
+        payload
+    }
+
+    /// Keyword index on each normalized filter field (re-creating an existing one is a no-op)
+    async fn ensure_keyword_indexes(&self) -> Result<()> {
+        for field in KEYWORD_FIELDS {
+            self.client.create_field_index(CreateFieldIndexCollection {
+                collection_name: self.collection.clone(),
+                wait: Some(true),
+                field_name: format!("{}.{}", KEYWORDS_KEY, field),
+                field_type: Some(FieldType::Keyword.into()),
+                ..Default::default()
+            }).await?;
+        }
+        Ok(())
+    }
+
+    /// Unnamed dense vector of a retrieved point; empty if vectors weren't requested
+    fn dense_vector(vectors: Option<VectorsOutput>) -> Vec<f32> {
+        use qdrant_client::qdrant::{vector_output, vectors_output::VectorsOptions};
+        match vectors.and_then(|v| v.vectors_options) {
+            Some(VectorsOptions::Vector(output)) => match output.vector {
+                Some(vector_output::Vector::Dense(dense)) => dense.data,
+                _ => output.data,
+            },
+            _ => Vec::new(),
+        }
+    }
+
+    async fn ensure_vectors(&self, dimension: usize, model: &str) -> Result<()> {
+        // Check if collection exists
+        match self.client.collection_info(&self.collection).await {
+            Ok(info) => {
+                tracing::info!("Collection '{}' already exists", self.collection);
+                // 🤓 Refuse to mix vectors from a different embedder into an existing collection
+                let existing = info.result
+                    .and_then(|i| i.config)
+                    .and_then(|c| c.params)
+                    .and_then(|p| p.vectors_config)
+                    .and_then(|v| v.config)
+                    .and_then(|c| match c {
+                        qdrant_client::qdrant::vectors_config::Config::Params(params) => Some(params.size as usize),
+                        qdrant_client::qdrant::vectors_config::Config::ParamsMap(_) => None,
+                    });
+                if let Some(existing) = existing {
+                    if existing != dimension {
+                        return Err(GrokError::CollectionDimensionMismatch {
+                            collection: self.collection.clone(),
+                            model: model.to_string(),
+                            expected: dimension,
+                            existing,
+                        });
+                    }
+                }
+                Ok(())
+            }
+            Err(_) => {
+                // Collection doesn't exist, create it
+                tracing::info!("Creating collection '{}'", self.collection);
+
+                let create_collection = CreateCollection {
+                    collection_name: self.collection.clone(),
+                    vectors_config: Some(VectorParams {
+                        size: dimension as u64, // 🤓 Derived from the configured embedder
+                        distance: Distance::Cosine.into(),
+                        ..Default::default()
+                    }.into()),
+                    ..Default::default()
+                };
+
+                let response = self.client.create_collection(create_collection).await?;
+
+                if response.result {
+                    tracing::info!("Successfully created collection '{}'", self.collection);
+                } else {
+                    return Err(GrokError::CollectionOperation {
+                        collection: self.collection.clone(),
+                        message: "Creation failed".to_string(),
+                    });
+                }
+                Ok(())
+            }
+        }
+    }
+
+    async fn scroll_points(&self, filter: &PayloadFilter, limit: usize, with_vectors: bool) -> Result<Vec<RetrievedPoint>> {
+        // 🤓 Qdrant pages scroll results; follow next_page_offset until `limit` is reached
+        const PAGE_SIZE: usize = 256;
+        let mut points = Vec::new();
+        let mut offset = None;
+
+        while points.len() < limit {
+            let scroll_request = ScrollPoints {
+                collection_name: self.collection.clone(),
+                filter: Self::to_qdrant_filter(filter),
+                offset,
+                limit: Some((limit - points.len()).min(PAGE_SIZE) as u32),
+                with_payload: Self::with_payload(),
+                with_vectors: Some(with_vectors.into()),
+                ..Default::default()
+            };
+
+            let scroll_result = self.client.scroll(scroll_request).await?;
+            points.extend(scroll_result.result);
+
+            match scroll_result.next_page_offset {
+                Some(next) => offset = Some(next),
+                None => break,
+            }
+        }
+
+        Ok(points)
+    }
+}
+
+#[async_trait]
+impl VectorStore for QdrantStore {
+    fn backend_name(&self) -> &str {
+        "qdrant"
+    }
+
+    fn collection(&self) -> &str {
+        &self.collection
+    }
+
+    async fn ensure_collection(&self, dimension: usize, model: &str) -> Result<()> {
+        self.ensure_vectors(dimension, model).await?;
+        self.ensure_keyword_indexes().await
+    }
+
+    async fn upsert(&self, points: Vec<VectorPoint>) -> Result<()> {
+        if points.is_empty() {
+            return Ok(());
+        }
+
+        let points = points.into_iter()
+            .map(|p| PointStruct::new(p.id.to_string(), p.vector, Self::with_keywords(p.payload)))
+            .collect();
+
+        let upsert_request = UpsertPoints {
+            collection_name: self.collection.clone(),
+            points,
+            ..Default::default()
+        };
+
+        self.client.upsert_points(upsert_request).await?;
+        Ok(())
+    }
+
+    async fn search(&self, vector: &[f32], filter: &PayloadFilter, limit: usize) -> Result<Vec<StoredPoint>> {
+        let search_request = SearchPoints {
+            collection_name: self.collection.clone(),
+            vector: vector.to_vec(),
+            filter: Self::to_qdrant_filter(filter),
+            limit: limit as u64,
+            with_payload: Self::with_payload(),
+            ..Default::default()
+        };
+
+        let search_result = self.client.search_points(search_request).await?;
+
+        Ok(search_result.result.into_iter().map(|point| StoredPoint {
+            id: Self::point_uuid(point.id),
+            payload: Self::to_payload(point.payload),
+            score: Some(point.score),
+        }).collect())
+    }
+
+    async fn scroll(&self, filter: &PayloadFilter, limit: usize) -> Result<Vec<StoredPoint>> {
+        Ok(self.scroll_points(filter, limit, false).await?.into_iter().map(|point| StoredPoint {
+            id: Self::point_uuid(point.id),
+            payload: Self::to_payload(point.payload),
+            score: None,
+        }).collect())
+    }
+
+    async fn scroll_vectors(&self, filter: &PayloadFilter, limit: usize) -> Result<Vec<VectorPoint>> {
+        Ok(self.scroll_points(filter, limit, true).await?.into_iter().map(|point| VectorPoint {
+            id: Self::point_uuid(point.id),
+            vector: Self::dense_vector(point.vectors),
+            payload: Self::to_payload(point.payload),
+        }).collect())
+    }
+
+    async fn count(&self) -> Result<usize> {
+        let response = self.client.count(CountPoints {
+            collection_name: self.collection.clone(),
+            exact: Some(true),
+            ..Default::default()
+        }).await?;
+        Ok(response.result.map_or(0, |r| r.count as usize))
+    }
+
+    async fn get(&self, ids: &[Uuid]) -> Result<Vec<StoredPoint>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let response = self.client.get_points(GetPoints {
+            collection_name: self.collection.clone(),
+            ids: ids.iter().map(|id| PointId::from(id.to_string())).collect(),
+            with_payload: Self::with_payload(),
+            with_vectors: Some(false.into()),
+            ..Default::default()
+        }).await?;
+        Ok(response.result.into_iter().map(|point| StoredPoint {
+            id: Self::point_uuid(point.id),
+            payload: Self::to_payload(point.payload),
+            score: None,
+        }).collect())
+    }
+
+    async fn delete(&self, ids: Vec<Uuid>) -> Result<()> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+
+        let ids: Vec<PointId> = ids.into_iter().map(|id| PointId::from(id.to_string())).collect();
+        self.client.delete_points(DeletePoints {
+            collection_name: self.collection.clone(),
+            points: Some(PointsSelector::from(ids)),
+            wait: Some(true),
+            ..Default::default()
+        }).await?;
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Local single-file store
+// ---------------------------------------------------------------------------
+
+const LOCAL_STORE_VERSION: u32 = 1;
+
+/// Journal entries after which the snapshot is rewritten and the journal emptied
+const COMPACT_AFTER: usize = 64;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct LocalStoreFile {
+    version: u32,
+    collections: BTreeMap<String, LocalCollection>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct LocalCollection {
+    dimension: usize,
+    model: String,
+    points: Vec<VectorPoint>,
+}
+
+/// One change, as appended to the journal
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum JournalEntry {
+    Collection { collection: String, dimension: usize, model: String },
+    Upsert { collection: String, points: Vec<VectorPoint> },
+    Delete { collection: String, ids: Vec<Uuid> },
+}
+
+impl LocalStoreFile {
+    fn empty() -> Self {
+        Self {
+            version: LOCAL_STORE_VERSION,
+            ..Default::default()
+        }
+    }
+
+    /// Snapshot plus journal, as read from disk; returns the number of journal entries replayed
+    fn parse(snapshot: Option<&str>, journal: Option<&str>, path: &Path) -> Result<(Self, usize)> {
+        let mut file = match snapshot {
+            Some(content) => serde_json::from_str(content)?,
+            None => Self::empty(),
+        };
+        let mut replayed = 0;
+        for line in journal.unwrap_or_default().lines().filter(|l| !l.trim().is_empty()) {
+            match serde_json::from_str(line) {
+                Ok(entry) => {
+                    file.apply(entry);
+                    replayed += 1;
+                }
+                Err(e) => {
+                    // 🤓 A crash mid-append leaves a torn last line; everything before it is intact
+                    tracing::warn!("Ignoring unreadable journal entry in {}: {}", path.display(), e);
+                    break;
+                }
+            }
+        }
+        Ok((file, replayed))
+    }
+
+    fn apply(&mut self, entry: JournalEntry) {
+        match entry {
+            JournalEntry::Collection { collection, dimension, model } => {
+                self.collections.entry(collection).or_insert(LocalCollection { dimension, model, points: Vec::new() });
+            }
+            JournalEntry::Upsert { collection, points } => {
+                let Some(collection) = self.collections.get_mut(&collection) else { return };
+                let mut index: HashMap<Uuid, usize> = collection.points.iter()
+                    .enumerate()
+                    .map(|(i, p)| (p.id, i))
+                    .collect();
+                for point in points {
+                    match index.get(&point.id) {
+                        Some(&i) => collection.points[i] = point,
+                        None => {
+                            index.insert(point.id, collection.points.len());
+                            collection.points.push(point);
+                        }
+                    }
+                }
+            }
+            JournalEntry::Delete { collection, ids } => {
+                let Some(collection) = self.collections.get_mut(&collection) else { return };
+                let ids: std::collections::HashSet<Uuid> = ids.into_iter().collect();
+                collection.points.retain(|p| !ids.contains(&p.id));
+            }
+        }
+    }
+}
+
+struct LocalState {
+    file: LocalStoreFile,
+    /// Entries in the journal since the last compaction
+    journaled: usize,
+}
+
+/// `VectorStore` kept in one JSON file, searched by brute-force cosine similarity
+///
+/// Every collection lives in the same file, so grok chunks and lfmf lessons share
+/// `~/.b00t/grok_vectors.json` by default. Changes are appended to a `.log` journal next to
+/// it and folded into the snapshot (written atomically) every `COMPACT_AFTER` entries.
+pub struct LocalStore {
+    path: PathBuf,
+    collection: String,
+    state: Mutex<LocalState>,
+}
+
+impl LocalStore {
+    /// Open (or lazily create) the store at `path`
+    pub fn open(path: impl Into<PathBuf>, collection: impl Into<String>) -> Result<Self> {
+        let path = path.into();
+        // 🤓 Not halfway through another process's compaction (nothing to read without a directory)
+        let _lock = path.parent().is_none_or(Path::exists).then(|| Self::lock(&path)).transpose()?;
+        let snapshot = path.exists().then(|| std::fs::read_to_string(&path)).transpose()?;
+        let journal = std::fs::read_to_string(Self::journal_for(&path)).ok();
+        let (file, journaled) = LocalStoreFile::parse(snapshot.as_deref(), journal.as_deref(), &path)?;
+
+        Ok(Self {
+            path,
+            collection: collection.into(),
+            state: Mutex::new(LocalState { file, journaled }),
+        })
+    }
+
+    /// `$B00T_GROK_STORE_PATH`, else `~/.b00t/grok_vectors.json`
+    pub fn default_path() -> PathBuf {
+        let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+        if let Ok(path) = std::env::var("B00T_GROK_STORE_PATH") {
+            // 🤓 .env files don't expand ~
+            return match path.strip_prefix("~/") {
+                Some(rest) => Path::new(&home).join(rest),
+                None => PathBuf::from(path),
+            };
+        }
+        Path::new(&home).join(".b00t").join("grok_vectors.json")
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn journal_for(path: &Path) -> PathBuf {
+        let mut journal = path.as_os_str().to_owned();
+        journal.push(".log");
+        PathBuf::from(journal)
+    }
+
+    /// Exclusive lock on `{path}.lock` across processes, released when the file is dropped
+    fn lock(path: &Path) -> std::io::Result<std::fs::File> {
+        let mut lock_path = path.as_os_str().to_owned();
+        lock_path.push(".lock");
+        let file = std::fs::OpenOptions::new().create(true).truncate(false).write(true).open(lock_path)?;
+        file.lock()?;
+        Ok(file)
+    }
+
+    /// Append `entry` to the journal, then apply it; compacts once the journal is long enough
+    ///
+    /// 🦨 Holds the file lock throughout: an append landing between compaction's read and its
+    /// truncate would be lost
+    async fn commit(&self, state: &mut LocalState, entry: JournalEntry) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let path = self.path.clone();
+        let _lock = tokio::task::spawn_blocking(move || Self::lock(&path))
+            .await
+            .map_err(std::io::Error::other)??;
+        let mut line = serde_json::to_vec(&entry)?;
+        line.push(b'\n');
+        let mut journal = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(Self::journal_for(&self.path))
+            .await?;
+        journal.write_all(&line).await?;
+        journal.flush().await?;
+
+        state.file.apply(entry);
+        state.journaled += 1;
+        if state.journaled >= COMPACT_AFTER {
+            self.compact(state).await?;
+        }
+        Ok(())
+    }
+
+    /// Rewrite the snapshot from snapshot + journal and empty the journal; call with the lock held
+    async fn compact(&self, state: &mut LocalState) -> Result<()> {
+        // 🤓 Re-read rather than dump memory: other stores on the same file (lfmf lessons
+        //    beside grok chunks) append to the same journal
+        let journal_path = Self::journal_for(&self.path);
+        let snapshot = match tokio::fs::read_to_string(&self.path).await {
+            Ok(content) => Some(content),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e.into()),
+        };
+        let journal = tokio::fs::read_to_string(&journal_path).await.ok();
+        let (file, _) = LocalStoreFile::parse(snapshot.as_deref(), journal.as_deref(), &self.path)?;
+
+        // Write-then-rename so a crash never leaves a truncated store behind
+        let tmp = self.path.with_extension("json.tmp");
+        tokio::fs::write(&tmp, serde_json::to_vec(&file)?).await?;
+        tokio::fs::rename(&tmp, &self.path).await?;
+        tokio::fs::write(&journal_path, b"").await?;
+
+        state.file = file;
+        state.journaled = 0;
+        Ok(())
+    }
+
+    fn missing_collection(&self) -> GrokError {
+        GrokError::CollectionOperation {
+            collection: self.collection.clone(),
+            message: format!("not found in {}", self.path.display()),
+        }
+    }
+}
+
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+#[async_trait]
+impl VectorStore for LocalStore {
+    fn backend_name(&self) -> &str {
+        "local"
+    }
+
+    fn collection(&self) -> &str {
+        &self.collection
+    }
+
+    async fn ensure_collection(&self, dimension: usize, model: &str) -> Result<()> {
+        let mut state = self.state.lock().await;
+        if let Some(existing) = state.file.collections.get(&self.collection) {
+            if existing.dimension != dimension {
+                return Err(GrokError::CollectionDimensionMismatch {
+                    collection: self.collection.clone(),
+                    model: model.to_string(),
+                    expected: dimension,
+                    existing: existing.dimension,
+                });
+            }
+            if existing.model != model {
+                tracing::warn!("Collection '{}' was built with embedder '{}', now using '{}'; similarity scores may be meaningless",
+                    self.collection, existing.model, model);
+            }
+            return Ok(());
+        }
+
+        tracing::info!("Creating local collection '{}' in {}", self.collection, self.path.display());
+        self.commit(&mut state, JournalEntry::Collection {
+            collection: self.collection.clone(),
+            dimension,
+            model: model.to_string(),
+        }).await
+    }
+
+    async fn upsert(&self, points: Vec<VectorPoint>) -> Result<()> {
+        if points.is_empty() {
+            return Ok(());
+        }
+
+        let mut state = self.state.lock().await;
+        let collection = state.file.collections.get(&self.collection)
+            .ok_or_else(|| self.missing_collection())?;
+
+        for point in &points {
+            if point.vector.len() != collection.dimension {
+                return Err(GrokError::VectorDimensions {
+                    expected: collection.dimension,
+                    actual: point.vector.len(),
+                });
+            }
+        }
+
+        self.commit(&mut state, JournalEntry::Upsert { collection: self.collection.clone(), points }).await
+    }
+
+    async fn search(&self, vector: &[f32], filter: &PayloadFilter, limit: usize) -> Result<Vec<StoredPoint>> {
+        let state = self.state.lock().await;
+        let Some(collection) = state.file.collections.get(&self.collection) else {
+            return Ok(Vec::new());
+        };
+
+        let mut scored: Vec<(f32, &VectorPoint)> = collection.points.iter()
+            .filter(|p| filter.matches(&p.payload))
+            .map(|p| (cosine_similarity(vector, &p.vector), p))
+            .collect();
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        Ok(scored.into_iter().take(limit).map(|(score, p)| StoredPoint {
+            id: p.id,
+            payload: p.payload.clone(),
+            score: Some(score),
+        }).collect())
+    }
+
+    async fn scroll(&self, filter: &PayloadFilter, limit: usize) -> Result<Vec<StoredPoint>> {
+        let state = self.state.lock().await;
+        let Some(collection) = state.file.collections.get(&self.collection) else {
+            return Ok(Vec::new());
+        };
+
+        Ok(collection.points.iter()
+            .filter(|p| filter.matches(&p.payload))
+            .take(limit)
+            .map(|p| StoredPoint {
+                id: p.id,
+                payload: p.payload.clone(),
+                score: None,
+            })
+            .collect())
+    }
+
+    async fn scroll_vectors(&self, filter: &PayloadFilter, limit: usize) -> Result<Vec<VectorPoint>> {
+        let state = self.state.lock().await;
+        let Some(collection) = state.file.collections.get(&self.collection) else {
+            return Ok(Vec::new());
+        };
+
+        Ok(collection.points.iter()
+            .filter(|p| filter.matches(&p.payload))
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+
+    async fn count(&self) -> Result<usize> {
+        Ok(self.state.lock().await.file.collections.get(&self.collection).map_or(0, |c| c.points.len()))
+    }
+
+    async fn get(&self, ids: &[Uuid]) -> Result<Vec<StoredPoint>> {
+        let state = self.state.lock().await;
+        let Some(collection) = state.file.collections.get(&self.collection) else {
+            return Ok(Vec::new());
+        };
+
+        Ok(collection.points.iter()
+            .filter(|p| ids.contains(&p.id))
+            .map(|p| StoredPoint {
+                id: p.id,
+                payload: p.payload.clone(),
+                score: None,
+            })
+            .collect())
+    }
+
+    async fn delete(&self, ids: Vec<Uuid>) -> Result<()> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+
+        let mut state = self.state.lock().await;
+        let Some(collection) = state.file.collections.get(&self.collection) else {
+            return Ok(());
+        };
+        let present: std::collections::HashSet<Uuid> = collection.points.iter().map(|p| p.id).collect();
+        let ids: Vec<Uuid> = ids.into_iter().filter(|id| present.contains(id)).collect();
+        if ids.is_empty() {
+            return Ok(());
+        }
+        self.commit(&mut state, JournalEntry::Delete { collection: self.collection.clone(), ids }).await
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Backend selection
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StoreBackend {
+    /// Qdrant if it answers a health check, otherwise the local file
+    Auto,
+    Qdrant,
+    Local,
+}
+
+/// Vector store selection, usually read from the environment
+///
+/// | variable | default |
+/// | --- | --- |
+/// | `B00T_GROK_STORE` | `auto` (`qdrant` or `local` to force one) |
+/// | `B00T_GROK_STORE_PATH` | `~/.b00t/grok_vectors.json` |
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoreConfig {
+    pub backend: StoreBackend,
+    pub local_path: PathBuf,
+}
+
+impl Default for StoreConfig {
+    fn default() -> Self {
+        Self {
+            backend: StoreBackend::Auto,
+            local_path: LocalStore::default_path(),
+        }
+    }
+}
+
+impl StoreConfig {
+    pub fn local(path: impl Into<PathBuf>) -> Self {
+        Self {
+            backend: StoreBackend::Local,
+            local_path: path.into(),
+        }
+    }
+
+    pub fn from_env() -> Result<Self> {
+        let mut config = Self::default();
+        if let Ok(backend) = std::env::var("B00T_GROK_STORE") {
+            config.backend = serde_json::from_value(Value::String(backend.to_lowercase()))
+                .map_err(|_| GrokError::InvalidQuery {
+                    message: format!("Unknown B00T_GROK_STORE '{}': use auto, qdrant or local", backend)
+                })?;
+        }
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn point(vector: Vec<f32>, tool: &str) -> VectorPoint {
+        let mut payload = Payload::new();
+        payload.insert("tool".to_string(), json!(tool));
+        VectorPoint { id: Uuid::new_v4(), vector, payload }
+    }
+
+    #[tokio::test]
+    async fn test_local_store_search_and_persist() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("vectors.json");
+
+        let store = LocalStore::open(&path, "test").unwrap();
+        store.ensure_collection(2, "hashing").await.unwrap();
+        let near = point(vec![1.0, 0.1], "just");
+        let far = point(vec![0.0, 1.0], "git");
+        store.upsert(vec![near.clone(), far]).await.unwrap();
+
+        let results = store.search(&[1.0, 0.0], &PayloadFilter::new(), 10).await.unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].id, near.id);
+        assert!(results[0].score.unwrap() > results[1].score.unwrap());
+
+        // Upsert replaces by id
+        store.upsert(vec![near.clone()]).await.unwrap();
+        assert_eq!(store.count().await.unwrap(), 2);
+
+        // Reopen from disk
+        let reopened = LocalStore::open(&path, "test").unwrap();
+        let filtered = reopened.scroll(&PayloadFilter::new().with("tool", "JUST"), 10).await.unwrap();
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].id, near.id);
+        assert!(filtered[0].score.is_none());
+
+        let got = reopened.get(&[near.id, Uuid::new_v4()]).await.unwrap();
+        assert_eq!(got.len(), 1);
+        assert_eq!(got[0].id, near.id);
+
+        reopened.delete(vec![near.id, Uuid::new_v4()]).await.unwrap();
+        assert_eq!(reopened.count().await.unwrap(), 1);
+        assert_eq!(LocalStore::open(&path, "test").unwrap().count().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_local_store_rejects_dimension_mismatch() {
+        let dir = TempDir::new().unwrap();
+        let store = LocalStore::open(dir.path().join("vectors.json"), "test").unwrap();
+        store.ensure_collection(4, "hashing").await.unwrap();
+
+        let err = store.ensure_collection(8, "other").await.unwrap_err();
+        assert!(matches!(err, GrokError::CollectionDimensionMismatch { existing: 4, expected: 8, .. }));
+
+        let err = store.upsert(vec![point(vec![1.0], "just")]).await.unwrap_err();
+        assert!(matches!(err, GrokError::VectorDimensions { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_local_store_journal_compacts_without_losing_other_collections() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("vectors.json");
+        let chunks = LocalStore::open(&path, "chunks").unwrap();
+        let lessons = LocalStore::open(&path, "lessons").unwrap();
+        chunks.ensure_collection(2, "hashing").await.unwrap();
+        lessons.ensure_collection(2, "hashing").await.unwrap();
+        lessons.upsert(vec![point(vec![0.0, 1.0], "git")]).await.unwrap();
+
+        for _ in 0..COMPACT_AFTER {
+            chunks.upsert(vec![point(vec![1.0, 0.0], "just")]).await.unwrap();
+        }
+        assert!(path.exists(), "snapshot written on compaction");
+        assert!(std::fs::read_to_string(LocalStore::journal_for(&path)).unwrap().lines().count() < COMPACT_AFTER);
+
+        assert_eq!(LocalStore::open(&path, "chunks").unwrap().count().await.unwrap(), COMPACT_AFTER);
+        assert_eq!(LocalStore::open(&path, "lessons").unwrap().count().await.unwrap(), 1);
+
+        // Two writers compacting the same file concurrently keep each other's appends
+        let other = LocalStore::open(&path, "chunks").unwrap();
+        async fn writes(store: &LocalStore) {
+            for _ in 0..COMPACT_AFTER {
+                store.upsert(vec![point(vec![1.0, 0.0], "just")]).await.unwrap();
+            }
+        }
+        tokio::join!(writes(&chunks), writes(&other));
+        assert_eq!(LocalStore::open(&path, "chunks").unwrap().count().await.unwrap(), 3 * COMPACT_AFTER);
+
+        // A torn last line (crash mid-append) loses only that entry
+        std::fs::write(LocalStore::journal_for(&path), "{\"op\":\"delete\",\"coll").unwrap();
+        assert_eq!(LocalStore::open(&path, "lessons").unwrap().count().await.unwrap(), 1);
+    }
+
+    #[test]
+    fn test_qdrant_filters_match_normalized_keywords() {
+        let mut payload = Payload::new();
+        payload.insert("topic".to_string(), json!("Rust Async"));
+        payload.insert("content".to_string(), json!("long text"));
+        let stored = QdrantStore::with_keywords(payload);
+        assert_eq!(stored[KEYWORDS_KEY], json!({"topic": "rust async"}));
+
+        let filter = QdrantStore::to_qdrant_filter(&PayloadFilter::new().with("topic", "RUST Async")).unwrap();
+        use qdrant_client::qdrant::condition::ConditionOneOf;
+        let Some(ConditionOneOf::Filter(either)) = &filter.must[0].condition_one_of else {
+            panic!("expected a nested filter");
+        };
+        let fields: Vec<(&str, MatchValue)> = either.should.iter().map(|condition| match &condition.condition_one_of {
+            Some(ConditionOneOf::Field(field)) => (field.key.as_str(), field.r#match.clone().unwrap().match_value.unwrap()),
+            _ => panic!("expected a field condition"),
+        }).collect();
+        // Points stored before `_keywords` existed still match on the raw field
+        assert_eq!(fields, vec![
+            ("_keywords.topic", MatchValue::Keyword("rust async".to_string())),
+            ("topic", MatchValue::Keyword("RUST Async".to_string())),
+        ]);
+
+        // Whole values only, as on Qdrant
+        let mut payload = Payload::new();
+        payload.insert("tool".to_string(), json!("justfile"));
+        assert!(!PayloadFilter::new().with("tool", "just").matches(&payload));
+    }
+
+    #[test]
+    fn test_payload_filter_matches() {
+        let mut payload = Payload::new();
+        payload.insert("lesson_type".to_string(), json!("fail"));
+        payload.insert("confidence".to_string(), json!(1));
+
+        assert!(PayloadFilter::new().matches(&payload));
+        assert!(PayloadFilter::new().with("lesson_type", "FAIL").with("confidence", "1").matches(&payload));
+        assert!(!PayloadFilter::new().with("tool", "just").matches(&payload));
+    }
+}
@@ -1,14 +1,17 @@
 //! High-level agent implementation for ACP coordination
 
 use crate::{
-    ACPMessage, MessageType, StepBarrier, NatsTransport,
-    ACPError, Result, JsonValue, AcpJwtValidator, AcpSecurityContext, NamespaceEnforcer
+    ACPMessage, MessageType, StepBarrier, NatsTransport, Transport,
+    ACPError, Result, JsonValue, AcpJwtValidator, AcpSecurityContext,
+    BarrierEvent, StragglerPolicy
 };
+use crate::security::{EnforcedTransport, JwtKey, QuarantinedMessage};
+use crate::signing::{MessageVerifier, SigningKey, UnsignedPolicy, Verification, VerifyingKey};
 use crate::transport::NatsConfig;
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::Mutex;
-use tokio::time::{timeout, Duration, sleep};
+use tokio::sync::{broadcast, Mutex};
+use tokio::time::{Duration, Instant, sleep};
 use tracing::{debug, info, warn, error};
 use uuid::Uuid;
 
@@ -23,10 +26,22 @@ pub struct AgentConfig {
     pub namespace: String,
     /// JWT token for NATS authentication
     pub jwt_token: Option<String>,
+    /// Key that verifies `jwt_token`; an agent with a token but no key refuses to start
+    pub jwt_key: Option<JwtKey>,
     /// Agent role (ai-assistant, ci-cd, monitoring, etc.)
     pub role: String,
     /// Default timeout for operations in milliseconds
     pub timeout_ms: u64,
+    /// Signs every outbound message
+    pub signing_key: Option<SigningKey>,
+    /// Keys inbound signatures are checked against; empty skips verification
+    pub verifier: MessageVerifier,
+    /// What to do with unsigned inbound messages when verifying
+    pub unsigned_policy: UnsignedPolicy,
+    /// What the step barrier does once a step outlives `timeout_ms`
+    pub straggler_policy: StragglerPolicy,
+    /// Heartbeat interval; agents silent for `HEARTBEAT_MISSES` intervals are dropped
+    pub heartbeat_ms: Option<u64>,
 }
 
 impl AgentConfig {
@@ -40,14 +55,31 @@ impl AgentConfig {
         };
 
         let jwt_token = std::env::var("B00T_HIVE_JWT").ok();
+        // 🤓 One HMAC secret per namespace: everyone holding it can sign and verify
+        let signing_key = std::env::var("B00T_ACP_SIGNING_SECRET").ok()
+            .map(|secret| SigningKey::hmac(namespace.clone(), secret.as_bytes()));
+        let mut verifier = MessageVerifier::new();
+        if let Some(key) = &signing_key {
+            verifier.trust(key.key_id(), key.verifying_key());
+        }
 
         Self {
             agent_id,
             nats_url,
             namespace,
             jwt_token,
+            jwt_key: JwtKey::from_env(),
             role: "ai-assistant".to_string(),
             timeout_ms: 30000,
+            signing_key,
+            verifier,
+            unsigned_policy: std::env::var("B00T_ACP_UNSIGNED").ok()
+                .and_then(|policy| policy.parse().ok())
+                .unwrap_or_default(),
+            straggler_policy: std::env::var("B00T_ACP_STRAGGLER").ok()
+                .and_then(|policy| policy.parse().ok())
+                .unwrap_or_default(),
+            heartbeat_ms: None,
         }
     }
 
@@ -57,6 +89,50 @@ impl AgentConfig {
         self
     }
 
+    /// Set the key that verifies the JWT
+    pub fn with_jwt_key(mut self, jwt_key: JwtKey) -> Self {
+        self.jwt_key = Some(jwt_key);
+        self
+    }
+
+    /// Sign outbound messages with `key` (and trust it for inbound ones)
+    pub fn with_signing_key(mut self, key: SigningKey) -> Self {
+        self.verifier.trust(key.key_id(), key.verifying_key());
+        self.verifier.allow(key.key_id(), self.agent_id.clone());
+        self.signing_key = Some(key);
+        self
+    }
+
+    /// Accept signatures from another agent's (or namespace's) key
+    pub fn with_trusted_key(mut self, key_id: impl Into<String>, key: VerifyingKey) -> Self {
+        self.verifier.trust(key_id, key);
+        self
+    }
+
+    /// Accept Ed25519 key `key_id` as signing for `agent_id` (it otherwise only signs for itself)
+    pub fn with_key_agent(mut self, key_id: impl Into<String>, agent_id: impl Into<String>) -> Self {
+        self.verifier.allow(key_id, agent_id);
+        self
+    }
+
+    /// Accept, flag or drop unsigned inbound messages
+    pub fn with_unsigned_policy(mut self, policy: UnsignedPolicy) -> Self {
+        self.unsigned_policy = policy;
+        self
+    }
+
+    /// Wait, drop stragglers or abort the mission when a step times out
+    pub fn with_straggler_policy(mut self, policy: StragglerPolicy) -> Self {
+        self.straggler_policy = policy;
+        self
+    }
+
+    /// Send a heartbeat every `interval_ms` and drop peers that stop sending theirs
+    pub fn with_heartbeat(mut self, interval_ms: u64) -> Self {
+        self.heartbeat_ms = Some(interval_ms);
+        self
+    }
+
     /// Set agent role
     pub fn with_role(mut self, role: String) -> Self {
         self.role = role;
@@ -79,6 +155,11 @@ impl AgentConfig {
         if self.agent_id.is_empty() {
             return Err(ACPError::invalid_config("agent_id cannot be empty"));
         }
+        if !ACPMessage::is_valid_agent_id(&self.agent_id) {
+            return Err(ACPError::invalid_config(format!(
+                "agent_id '{}' must be '.'-separated tokens without spaces, '*' or '>'", self.agent_id
+            )));
+        }
         if self.nats_url.is_empty() {
             return Err(ACPError::invalid_config("nats_url cannot be empty"));
         }
@@ -88,6 +169,9 @@ impl AgentConfig {
         if self.timeout_ms == 0 {
             return Err(ACPError::invalid_config("timeout_ms must be greater than 0"));
         }
+        if self.heartbeat_ms == Some(0) {
+            return Err(ACPError::invalid_config("heartbeat_ms must be greater than 0"));
+        }
         Ok(())
     }
 }
@@ -95,18 +179,99 @@ impl AgentConfig {
 /// High-level agent for ACP coordination
 pub struct Agent {
     config: AgentConfig,
-    transport: NatsTransport,
+    transport: Arc<dyn Transport>,
     step_barrier: Arc<Mutex<StepBarrier>>,
     message_handlers: Arc<Mutex<HashMap<MessageType, Box<dyn Fn(&ACPMessage) + Send + Sync>>>>,
     running: Arc<Mutex<bool>>,
-    /// Security context for JWT-based namespace enforcement
-    security_context: Option<AcpSecurityContext>,
-    /// Namespace enforcer for validating operations
-    namespace_enforcer: Option<NamespaceEnforcer>,
+    /// JWT enforcement wrapped around the transport, when the agent has a token
+    enforced: Option<Arc<EnforcedTransport>>,
+    /// Inbound messages dropped or flagged by signature checks
+    screened: Arc<std::sync::Mutex<Screened>>,
+    /// Every decision the step barrier takes
+    barrier_events: broadcast::Sender<BarrierEvent>,
+}
+
+/// Messages the receive loop didn't simply trust
+#[derive(Default)]
+struct Screened {
+    dropped: Vec<QuarantinedMessage>,
+    flagged: Vec<QuarantinedMessage>,
+}
+
+/// How many screened messages of each kind are kept
+const SCREENED_LIMIT: usize = 100;
+
+/// Missed heartbeat intervals before a peer is dropped
+const HEARTBEAT_MISSES: u64 = 3;
+
+/// How often the receive loop applies barrier timeouts and heartbeats
+const BARRIER_TICK: Duration = Duration::from_millis(100);
+
+/// Log barrier decisions and pass them on to `Agent::barrier_events` subscribers
+fn announce(agent_id: &str, events: Vec<BarrierEvent>, sender: &broadcast::Sender<BarrierEvent>) {
+    for event in events {
+        match &event {
+            BarrierEvent::StepAdvanced { to, forced: false, .. } => info!("Advanced to step {}", to),
+            BarrierEvent::StepAdvanced { from, to, forced: true } => warn!("'{}' forced step {} -> {}", agent_id, from, to),
+            BarrierEvent::StepTimedOut { step, pending } => warn!("Step {} timed out waiting for {:?}", step, pending),
+            BarrierEvent::AgentJoined { agent_id: joined, from_step } => info!("Agent '{}' joined from step {}", joined, from_step),
+            BarrierEvent::AgentDropped { agent_id: dropped, step, reason } => warn!("Dropped agent '{}' at step {} ({:?})", dropped, step, reason),
+            BarrierEvent::MissionAborted { step, pending } => error!("Mission aborted at step {} waiting for {:?}", step, pending),
+            BarrierEvent::CaughtUp { from, to } => info!("'{}' caught up from step {} to {}", agent_id, from, to),
+        }
+        // 🤓 No subscribers is fine
+        let _ = sender.send(event);
+    }
+}
+
+/// Stamp the signer and sign, as the message will go on the wire
+fn prepare(config: &AgentConfig, signer: Option<&str>, message: &ACPMessage) -> Result<ACPMessage> {
+    let mut message = message.clone();
+    if let Some(signer) = signer {
+        message.signer = Some(signer.to_string());
+    }
+    if let Some(key) = &config.signing_key {
+        message.sign(key)?;
+    }
+    Ok(message)
+}
+
+fn is_heartbeat(message: &ACPMessage) -> bool {
+    message.message_type == MessageType::Status && message.payload.get("heartbeat") == Some(&JsonValue::Bool(true))
+}
+
+/// Does the message carry a signature from a trusted key?
+fn verified(config: &AgentConfig, message: &ACPMessage) -> bool {
+    !config.verifier.is_empty() && config.verifier.verify(message) == Verification::Verified
+}
+
+/// Check an inbound message's signature; false if it must not be delivered
+fn admit(config: &AgentConfig, screened: &std::sync::Mutex<Screened>, pattern: &str, message: &ACPMessage) -> bool {
+    if config.verifier.is_empty() {
+        return true;
+    }
+    let (keep, reason) = match config.verifier.verify(message) {
+        Verification::Verified => return true,
+        Verification::Unsigned => match config.unsigned_policy {
+            UnsignedPolicy::Accept => return true,
+            UnsignedPolicy::Flag => (true, "unsigned".to_string()),
+            UnsignedPolicy::Drop => (false, "unsigned".to_string()),
+        },
+        Verification::Invalid(reason) => (false, reason),
+    };
+
+    warn!("{} message from '{}' on {}: {}", if keep { "Flagged" } else { "Dropped" }, message.agent_id, pattern, reason);
+    let mut screened = screened.lock().unwrap_or_else(|e| e.into_inner());
+    let list = if keep { &mut screened.flagged } else { &mut screened.dropped };
+    if list.len() >= SCREENED_LIMIT {
+        list.remove(0);
+    }
+    list.push(QuarantinedMessage { pattern: pattern.to_string(), message: message.clone(), reason });
+    keep
 }
 
 impl Agent {
-    /// Create new agent
+    /// Create new agent connected to NATS at `config.nats_url`
     pub async fn new(config: AgentConfig) -> Result<Self> {
         config.validate()?;
 
@@ -119,42 +284,59 @@ impl Agent {
         };
 
         let transport = NatsTransport::new(nats_config).await?;
-        let step_barrier = Arc::new(Mutex::new(
-            StepBarrier::new(vec![config.agent_id.clone()], config.timeout_ms)
-        ));
+        Self::with_transport(config, Arc::new(transport)).await
+    }
 
-        // Initialize security context if JWT is provided
-        let (security_context, namespace_enforcer) = if let Some(jwt_token) = &config.jwt_token {
+    /// Create new agent on any transport (e.g. `InMemoryTransport` in tests)
+    pub async fn with_transport(config: AgentConfig, transport: Arc<dyn Transport>) -> Result<Self> {
+        config.validate()?;
+
+        let mut barrier = StepBarrier::new(vec![config.agent_id.clone()], config.timeout_ms)
+            .with_policy(config.straggler_policy)
+            .with_local_agent(config.agent_id.clone());
+        if let Some(heartbeat_ms) = config.heartbeat_ms {
+            barrier = barrier.with_heartbeat_timeout(heartbeat_ms * HEARTBEAT_MISSES);
+        }
+        let step_barrier = Arc::new(Mutex::new(barrier));
+
+        // Validate our own token up front, then hold every publish/subscribe to its claims
+        let (transport, enforced) = if let Some(jwt_token) = &config.jwt_token {
             info!("Validating JWT token for agent '{}'", config.agent_id);
-            
-            // For development, we'll use a placeholder validator
-            // In production, this would use the actual operator JWT
-            let validator = AcpJwtValidator::new("placeholder_secret".to_string());
-            
-            match validator.validate_jwt(jwt_token) {
-                Ok(security_ctx) => {
-                    // Verify namespace matches config
-                    if security_ctx.namespace != config.namespace {
-                        return Err(ACPError::authentication_failed(format!(
-                            "JWT namespace '{}' does not match config namespace '{}'",
-                            security_ctx.namespace, config.namespace
-                        )));
-                    }
-                    
-                    let enforcer = NamespaceEnforcer::new(security_ctx.clone());
-                    info!("Agent '{}' authenticated for namespace '{}'", config.agent_id, security_ctx.namespace);
-                    (Some(security_ctx), Some(enforcer))
-                },
-                Err(e) => {
-                    warn!("JWT validation failed for agent '{}': {}", config.agent_id, e);
-                    // For development, continue without security context
-                    // In production, this should be an error
-                    (None, None)
-                }
+            let validator = config.jwt_key.as_ref().map(AcpJwtValidator::from_key).ok_or_else(|| {
+                ACPError::authentication_failed(format!(
+                    "agent '{}' has a JWT but no key to verify it: use with_jwt_key or set B00T_HIVE_JWT_SECRET / B00T_HIVE_JWT_PUBLIC_KEY",
+                    config.agent_id
+                ))
+            })?;
+            // 🤓 Peers only trust our signer once it's signed over, and we only trust theirs likewise
+            if config.signing_key.is_none() {
+                return Err(ACPError::authentication_failed(format!(
+                    "agent '{}' has a JWT but no signing key: use with_signing_key or set B00T_ACP_SIGNING_SECRET",
+                    config.agent_id
+                )));
+            }
+            let security_ctx = validator.validate_jwt(jwt_token)
+                .map_err(|e| ACPError::authentication_failed(format!("{:#}", e)))?;
+
+            // Verify namespace matches config
+            if security_ctx.namespace != config.namespace {
+                return Err(ACPError::authentication_failed(format!(
+                    "JWT namespace '{}' does not match config namespace '{}'",
+                    security_ctx.namespace, config.namespace
+                )));
             }
+            if !security_ctx.owns_agent_id(&config.agent_id) {
+                return Err(ACPError::authentication_failed(format!(
+                    "JWT subject '{}' does not cover agent '{}'", security_ctx.subject, config.agent_id
+                )));
+            }
+
+            info!("Agent '{}' authenticated for namespace '{}'", config.agent_id, security_ctx.namespace);
+            let enforced = Arc::new(EnforcedTransport::new(transport, security_ctx).with_verifier(config.verifier.clone()));
+            (Arc::clone(&enforced) as Arc<dyn Transport>, Some(enforced))
         } else {
             info!("No JWT provided for agent '{}' - running in development mode", config.agent_id);
-            (None, None)
+            (transport, None)
         };
 
         info!("Agent '{}' initialized", config.agent_id);
@@ -165,8 +347,9 @@ impl Agent {
             step_barrier,
             message_handlers: Arc::new(Mutex::new(HashMap::new())),
             running: Arc::new(Mutex::new(false)),
-            security_context,
-            namespace_enforcer,
+            enforced,
+            screened: Arc::new(std::sync::Mutex::new(Screened::default())),
+            barrier_events: broadcast::channel(64).0,
         })
     }
 
@@ -181,14 +364,12 @@ impl Agent {
 
         info!("Starting agent '{}'", self.config.agent_id);
 
-        // Subscribe to agent-specific messages
-        let agent_subject = format!("{}.agents.{}.{}.>", 
-            self.config.namespace, 
-            self.config.role,
-            self.config.agent_id
-        );
-        
+        // Subscribe to messages addressed to this agent and to coordination broadcasts
+        // (task delegations, votes, capability requests from `AgentCoordinator`s)
+        let agent_subject = ACPMessage::inbox_subject(&self.config.namespace, &self.config.agent_id);
         let mut subscriber = self.transport.subscribe(&agent_subject).await?;
+        let broadcast_subject = ACPMessage::channel_subject(&self.config.namespace, "*");
+        let mut broadcast_subscriber = self.transport.subscribe(&broadcast_subject).await?;
 
         // Subscribe to step coordination messages
         let step_subject = format!("{}.acp.>", self.config.namespace);
@@ -199,17 +380,52 @@ impl Agent {
             let handlers = Arc::clone(&self.message_handlers);
             let running = Arc::clone(&self.running);
             let step_barrier = Arc::clone(&self.step_barrier);
+            let config = self.config.clone();
+            let screened = Arc::clone(&self.screened);
+            let events = self.barrier_events.clone();
+            let transport = Arc::clone(&self.transport);
+            let signer = self.security_context().map(|context| context.subject.clone());
+            let heartbeat_every = config.heartbeat_ms.map(Duration::from_millis);
             
             async move {
+                let mut ticker = tokio::time::interval(BARRIER_TICK);
+                let mut last_heartbeat: Option<Instant> = None;
                 while *running.lock().await {
                     tokio::select! {
-                        // Handle regular messages
+                        // Apply barrier timeouts and heartbeat expiry, and keep our own heartbeat going
+                        _ = ticker.tick() => {
+                            let now = Instant::now();
+                            let (step, decided) = {
+                                let mut barrier = step_barrier.lock().await;
+                                barrier.heartbeat(&config.agent_id, now.into_std());
+                                (barrier.current_step(), barrier.tick(now.into_std()))
+                            };
+                            announce(&config.agent_id, decided, &events);
+
+                            let due = heartbeat_every.is_some_and(|every| last_heartbeat.is_none_or(|sent| now - sent >= every));
+                            if due {
+                                last_heartbeat = Some(now);
+                                let heartbeat = ACPMessage::status(config.agent_id.clone(), step, serde_json::json!({"heartbeat": true}));
+                                let subject = format!("{}.acp.{}.{}.status", config.namespace, step, config.agent_id);
+                                let sent = match prepare(&config, signer.as_deref(), &heartbeat) {
+                                    Ok(heartbeat) => transport.publish(&subject, &heartbeat).await,
+                                    Err(e) => Err(e),
+                                };
+                                if let Err(e) = sent {
+                                    warn!("Failed to send heartbeat: {}", e);
+                                }
+                            }
+                        }
+
+                        // Handle regular messages: our inbox, then coordination broadcasts
                         msg_result = subscriber.next_message(1000) => {
                             match msg_result {
                                 Ok(Some(msg)) => {
                                     debug!("Received message: {:?}", msg.message_type);
-                                    if let Some(handler) = handlers.lock().await.get(&msg.message_type) {
-                                        handler(&msg);
+                                    if admit(&config, &screened, &agent_subject, &msg) {
+                                        if let Some(handler) = handlers.lock().await.get(&msg.message_type) {
+                                            handler(&msg);
+                                        }
                                     }
                                 }
                                 Ok(None) => break,
@@ -220,19 +436,62 @@ impl Agent {
                                 }
                             }
                         }
+                        broadcast_result = broadcast_subscriber.next_message(1000) => {
+                            match broadcast_result {
+                                Ok(Some(msg)) => {
+                                    if admit(&config, &screened, &broadcast_subject, &msg) {
+                                        if let Some(handler) = handlers.lock().await.get(&msg.message_type) {
+                                            handler(&msg);
+                                        }
+                                    }
+                                }
+                                Ok(None) => break,
+                                Err(ACPError::ReceiveTimeout) => continue,
+                                Err(e) => {
+                                    error!("Error receiving broadcast: {}", e);
+                                    break;
+                                }
+                            }
+                        }
                         
                         // Handle step coordination messages
                         step_result = step_subscriber.next_message(1000) => {
                             match step_result {
                                 Ok(Some(msg)) => {
-                                    if msg.message_type == MessageType::Step {
+                                    // 🤓 Coordination shares the namespace but not the barrier; the
+                                    // inbox and broadcast subscriptions above deliver it
+                                    if msg.message_type == MessageType::Coordination {
+                                        continue;
+                                    }
+                                    if !admit(&config, &screened, &step_subject, &msg) {
+                                        continue;
+                                    }
+                                    if !is_heartbeat(&msg) {
+                                        if let Some(handler) = handlers.lock().await.get(&msg.message_type) {
+                                            handler(&msg);
+                                        }
+                                    }
+
+                                    let now = Instant::now().into_std();
+                                    let mut decided = Vec::new();
+                                    {
                                         let mut barrier = step_barrier.lock().await;
-                                        barrier.record_step_completion(msg.step, msg.agent_id);
-                                        
-                                        if barrier.try_advance_step() {
-                                            info!("Advanced to step {}", barrier.current_step());
+                                        let known = barrier.known_agents().contains(&msg.agent_id);
+                                        // 🤓 Joined mid-mission: start where the others are rather than at step 0,
+                                        // but only on a peer's word; anyone can publish `acp step --step N`
+                                        if !barrier.has_completed_any(&config.agent_id) && (known || verified(&config, &msg)) {
+                                            decided.extend(barrier.catch_up(msg.step, now));
+                                        }
+                                        if !known {
+                                            decided.extend(barrier.join(msg.agent_id.clone(), now));
+                                        }
+                                        barrier.heartbeat(&msg.agent_id, now);
+                                        if msg.message_type == MessageType::Step {
+                                            barrier.record_step_completion(msg.step, msg.agent_id);
+                                            decided.extend(barrier.advance_at(now));
                                         }
                                     }
+                                    announce(&config.agent_id, decided, &events);
                                 }
                                 Ok(None) => break,
                                 Err(ACPError::ReceiveTimeout) => continue,
@@ -276,7 +535,7 @@ impl Agent {
             self.config.agent_id
         );
 
-        self.transport.publish_to_subject(&subject, &message).await?;
+        self.publish(&subject, &message).await?;
         debug!("Sent STATUS: {}", description);
         Ok(())
     }
@@ -299,7 +558,7 @@ impl Agent {
             self.config.agent_id
         );
 
-        self.transport.publish_to_subject(&subject, &message).await?;
+        self.publish(&subject, &message).await?;
         debug!("Sent PROPOSE: {}", action);
         Ok(())
     }
@@ -319,7 +578,7 @@ impl Agent {
             self.config.agent_id
         );
 
-        self.transport.publish_to_subject(&subject, &message).await?;
+        self.publish(&subject, &message).await?;
         
         // Record our own step completion
         {
@@ -332,41 +591,41 @@ impl Agent {
     }
 
     /// Wait for step to complete (all agents)
+    ///
+    /// Past `timeout_ms` the straggler policy decides: `Wait` gives up with `StepTimeout`
+    /// (the barrier stays put), `DropStraggler` returns once the step is forced through,
+    /// and `AbortMission` fails with `MissionAborted`.
     pub async fn wait_for_step_complete(&self, step: u64) -> Result<()> {
-        let timeout_duration = Duration::from_millis(self.config.timeout_ms);
-        
-        let result = timeout(timeout_duration, async {
-            loop {
-                {
-                    let barrier = self.step_barrier.lock().await;
-                    if barrier.is_step_complete(step) {
-                        return Ok::<(), ACPError>(());
-                    }
-                    
-                    let pending = barrier.pending_agents(step);
-                    if !pending.is_empty() {
-                        debug!("Waiting for agents to complete step {}: {:?}", step, pending);
-                    }
-                }
-                
-                sleep(Duration::from_millis(100)).await;
-            }
-        }).await;
+        let deadline = Instant::now() + Duration::from_millis(self.config.timeout_ms);
 
-        match result {
-            Ok(_) => {
-                info!("Step {} completed by all agents", step);
-                Ok(())
-            }
-            Err(_) => {
-                warn!("Step {} timed out, forcing advancement", step);
+        loop {
+            {
                 let mut barrier = self.step_barrier.lock().await;
-                barrier.force_advance_step();
-                Err(ACPError::StepTimeout { 
-                    step, 
-                    timeout_ms: self.config.timeout_ms 
-                })
+                let decided = barrier.tick(Instant::now().into_std());
+                announce(&self.config.agent_id, decided, &self.barrier_events);
+
+                if barrier.current_step() > step || barrier.is_step_complete(step) {
+                    info!("Step {} completed", step);
+                    return Ok(());
+                }
+                if barrier.is_aborted() {
+                    return Err(ACPError::MissionAborted { step: barrier.current_step() });
+                }
+                if barrier.policy() == StragglerPolicy::Wait && Instant::now() >= deadline {
+                    warn!("Step {} timed out waiting for {:?}", step, barrier.pending_agents(step));
+                    return Err(ACPError::StepTimeout {
+                        step,
+                        timeout_ms: self.config.timeout_ms
+                    });
+                }
+
+                let pending = barrier.pending_agents(step);
+                if !pending.is_empty() {
+                    debug!("Waiting for agents to complete step {}: {:?}", step, pending);
+                }
             }
+
+            sleep(Duration::from_millis(100)).await;
         }
     }
 
@@ -376,6 +635,12 @@ impl Agent {
         barrier.current_step()
     }
 
+    /// Resume at `step`, e.g. after replaying mission history; never moves backwards
+    pub async fn catch_up(&self, step: u64) {
+        let caught_up = self.step_barrier.lock().await.catch_up(step, Instant::now().into_std());
+        announce(&self.config.agent_id, caught_up.into_iter().collect(), &self.barrier_events);
+    }
+
     /// Add agent to coordination group
     pub async fn add_agent(&self, agent_id: String) -> Result<()> {
         let mut barrier = self.step_barrier.lock().await;
@@ -384,8 +649,9 @@ impl Agent {
             return Err(ACPError::AgentAlreadyExists { agent_id });
         }
         
-        barrier.add_agent(agent_id.clone());
-        info!("Added agent '{}' to coordination group", agent_id);
+        let joined = barrier.join(agent_id.clone(), Instant::now().into_std());
+        drop(barrier);
+        announce(&self.config.agent_id, joined.into_iter().collect(), &self.barrier_events);
         Ok(())
     }
 
@@ -404,6 +670,11 @@ impl Agent {
         Ok(())
     }
 
+    /// Subscribe to the step barrier's decisions: advances, timeouts, joins, drops, aborts
+    pub fn barrier_events(&self) -> broadcast::Receiver<BarrierEvent> {
+        self.barrier_events.subscribe()
+    }
+
     /// Get list of known agents
     pub async fn known_agents(&self) -> Vec<String> {
         let barrier = self.step_barrier.lock().await;
@@ -426,18 +697,64 @@ impl Agent {
 
     /// Send custom message to specific subject
     pub async fn send_message(&self, subject: &str, message: &ACPMessage) -> Result<()> {
-        self.transport.publish_to_subject(subject, message).await
+        self.publish(subject, message).await
     }
 
     /// Request-response pattern
     pub async fn request(&self, subject: &str, message: &ACPMessage) -> Result<ACPMessage> {
-        self.transport.request(subject, message, self.config.timeout_ms).await
+        let mut message = message.clone();
+        // 🤓 Fixed before signing: the transport would otherwise add it after the signature
+        message.correlation_id.get_or_insert_with(Uuid::new_v4);
+        let message = self.prepare(&message)?;
+        let reply = self.transport.request(subject, &message, self.config.timeout_ms).await?;
+        if !admit(&self.config, &self.screened, subject, &reply) {
+            return Err(ACPError::authentication_failed(format!("reply from '{}' on {} failed verification", reply.agent_id, subject)));
+        }
+        Ok(reply)
+    }
+
+    /// Answer a `request`, signed like everything else this agent sends
+    pub async fn reply(&self, request: &ACPMessage, mut response: ACPMessage) -> Result<()> {
+        let correlation_id = request.correlation_id
+            .ok_or_else(|| ACPError::invalid_message("request has no correlation_id to reply to"))?;
+        response.correlation_id = Some(correlation_id);
+        self.publish(&ACPMessage::reply_subject(correlation_id), &response).await
+    }
+
+    fn prepare(&self, message: &ACPMessage) -> Result<ACPMessage> {
+        prepare(&self.config, self.security_context().map(|context| context.subject.as_str()), message)
+    }
+
+    async fn publish(&self, subject: &str, message: &ACPMessage) -> Result<()> {
+        self.transport.publish(subject, &self.prepare(message)?).await
+    }
+
+    /// Inbound messages delivered despite failing the unsigned-message policy (`Flag`)
+    pub fn flagged(&self) -> Vec<QuarantinedMessage> {
+        self.screened.lock().unwrap_or_else(|e| e.into_inner()).flagged.clone()
     }
 
     /// Get agent configuration
     pub fn config(&self) -> &AgentConfig {
         &self.config
     }
+
+    /// Claims from the agent's JWT, if it has one
+    pub fn security_context(&self) -> Option<&AcpSecurityContext> {
+        self.enforced.as_deref().map(EnforcedTransport::security_context)
+    }
+
+    /// Inbound messages rejected: sender doesn't match signer, bad signature, or unsigned under `Drop`
+    pub fn quarantined(&self) -> Vec<QuarantinedMessage> {
+        let mut quarantined = self.enforced.as_deref().map(EnforcedTransport::quarantined).unwrap_or_default();
+        quarantined.extend(self.screened.lock().unwrap_or_else(|e| e.into_inner()).dropped.iter().cloned());
+        quarantined
+    }
+
+    /// Transport this agent talks through (e.g. to `reply` to a request)
+    pub fn transport(&self) -> Arc<dyn Transport> {
+        Arc::clone(&self.transport)
+    }
 }
 
 #[cfg(test)]
@@ -479,6 +796,185 @@ mod tests {
         assert_eq!(config.timeout_ms, 60000);
     }
     
+    #[tokio::test]
+    async fn test_step_barrier_over_in_memory_transport() {
+        let bus = crate::InMemoryTransport::new();
+        let mut agents = Vec::new();
+        for id in ["a1", "a2"] {
+            let config = AgentConfig::new(id.to_string(), "memory://".to_string(), "account.test".to_string())
+                .with_timeout(2000);
+            let agent = Agent::with_transport(config, Arc::new(bus.clone())).await.unwrap();
+            agent.add_agent(if id == "a1" { "a2" } else { "a1" }.to_string()).await.unwrap();
+            agent.start().await.unwrap();
+            agents.push(agent);
+        }
+
+        agents[0].complete_step().await.unwrap();
+        sleep(Duration::from_millis(50)).await;
+        assert_eq!(agents[1].current_step().await, 0, "a2 hasn't completed step 0 yet");
+
+        agents[1].complete_step().await.unwrap();
+        for agent in &agents {
+            agent.wait_for_step_complete(0).await.unwrap();
+        }
+        // The barrier advances once the STEP messages come back over the bus
+        sleep(Duration::from_millis(50)).await;
+        for agent in &agents {
+            assert_eq!(agent.current_step().await, 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_agent_enforces_its_jwt() {
+        let issuer = crate::LocalKeyPair::generate().unwrap();
+        let jwt = issuer.issue_agent_jwt("alice", "worker", "7", Duration::from_secs(600)).unwrap();
+        let config = |agent_id: &str| AgentConfig::new(agent_id.to_string(), "memory://".to_string(), "account.alice.worker".to_string())
+            .with_jwt(jwt.clone())
+            .with_jwt_key(JwtKey::Ed25519(issuer.public_key().to_vec()))
+            .with_signing_key(SigningKey::hmac("account.alice.worker", b"hive secret"));
+        let bus: Arc<dyn Transport> = Arc::new(crate::InMemoryTransport::new());
+
+        let impostor = Agent::with_transport(config("claude.8"), Arc::clone(&bus)).await;
+        assert!(matches!(impostor, Err(ACPError::AuthenticationFailed { .. })));
+        let forged = Agent::with_transport(config("claude.7").with_jwt_key(JwtKey::Secret("guess".to_string())), Arc::clone(&bus)).await;
+        assert!(matches!(forged, Err(ACPError::AuthenticationFailed { .. })));
+        let keyless = AgentConfig { jwt_key: None, ..config("claude.7") };
+        let unverified = Agent::with_transport(keyless, Arc::clone(&bus)).await;
+        assert!(matches!(unverified, Err(ACPError::AuthenticationFailed { .. })));
+        let signless = AgentConfig { signing_key: None, ..config("claude.7") };
+        let unsigned = Agent::with_transport(signless, Arc::clone(&bus)).await;
+        assert!(matches!(unsigned, Err(ACPError::AuthenticationFailed { .. })));
+
+        let agent = Agent::with_transport(config("claude.7"), Arc::clone(&bus)).await.unwrap();
+        assert_eq!(agent.security_context().unwrap().hive, "alice");
+        agent.start().await.unwrap();
+        agent.send_status("ready", serde_json::json!({})).await.unwrap();
+
+        let elsewhere = ACPMessage::status("claude.7".to_string(), 0, serde_json::json!({}));
+        let denied = agent.send_message("account.bob.worker.acp.0.claude.7.status", &elsewhere).await;
+        assert!(matches!(denied, Err(ACPError::PermissionDenied { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_forged_and_unsigned_steps_are_dropped() {
+        let key = SigningKey::hmac("account.test", b"hive secret");
+        let bus = crate::InMemoryTransport::new();
+        let config = |id: &str| AgentConfig::new(id.to_string(), "memory://".to_string(), "account.test".to_string())
+            .with_timeout(2000)
+            .with_signing_key(key.clone())
+            .with_unsigned_policy(UnsignedPolicy::Drop);
+        let a1 = Agent::with_transport(config("a1"), Arc::new(bus.clone())).await.unwrap();
+        let a2 = Agent::with_transport(config("a2"), Arc::new(bus.clone())).await.unwrap();
+        a1.add_agent("a2".to_string()).await.unwrap();
+        a1.start().await.unwrap();
+
+        // A rogue process completes step 0 for a2: once unsigned, once with the wrong secret
+        let forged = ACPMessage::step_complete("a2".to_string(), 0);
+        bus.publish("account.test.acp.0.a2.step", &forged).await.unwrap();
+        let mut wrong_secret = forged.clone();
+        wrong_secret.sign(&SigningKey::hmac("account.test", b"guess")).unwrap();
+        bus.publish("account.test.acp.0.a2.step", &wrong_secret).await.unwrap();
+
+        a1.complete_step().await.unwrap();
+        sleep(Duration::from_millis(50)).await;
+        assert_eq!(a1.current_step().await, 0, "forged STEPs must not move the barrier");
+        assert_eq!(a1.quarantined().len(), 2);
+
+        a2.complete_step().await.unwrap();
+        a1.wait_for_step_complete(0).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_request_verifies_the_reply() {
+        let key = SigningKey::hmac("account.test", b"hive secret");
+        let bus = crate::InMemoryTransport::new();
+        let config = |id: &str| AgentConfig::new(id.to_string(), "memory://".to_string(), "account.test".to_string())
+            .with_timeout(1000)
+            .with_signing_key(key.clone())
+            .with_unsigned_policy(UnsignedPolicy::Drop);
+        let a1 = Agent::with_transport(config("a1"), Arc::new(bus.clone())).await.unwrap();
+        let a2 = Agent::with_transport(config("a2"), Arc::new(bus.clone())).await.unwrap();
+        let mut requests = bus.subscribe("svc.echo").await.unwrap();
+
+        // A rogue process answers first, unsigned; then a2 answers properly
+        let rogue = bus.clone();
+        tokio::spawn(async move {
+            let request = requests.next_message_blocking().await.unwrap().unwrap();
+            rogue.reply(&request, ACPMessage::status("a2".to_string(), 0, serde_json::json!({"ok": false}))).await.unwrap();
+            let request = requests.next_message_blocking().await.unwrap().unwrap();
+            a2.reply(&request, ACPMessage::status("a2".to_string(), 0, serde_json::json!({"ok": true}))).await.unwrap();
+        });
+
+        let request = ACPMessage::propose("a1".to_string(), 0, serde_json::json!({}));
+        let forged = a1.request("svc.echo", &request).await;
+        assert!(matches!(forged, Err(ACPError::AuthenticationFailed { .. })));
+        assert_eq!(a1.quarantined().len(), 1);
+        let reply = a1.request("svc.echo", &request).await.unwrap();
+        assert_eq!(reply.payload["ok"], true);
+    }
+
+    #[tokio::test]
+    async fn test_silent_agent_is_dropped_and_late_joiner_catches_up() {
+        let bus = crate::InMemoryTransport::new();
+        let config = |id: &str| AgentConfig::new(id.to_string(), "memory://".to_string(), "account.test".to_string())
+            .with_timeout(300)
+            .with_straggler_policy(StragglerPolicy::DropStraggler);
+        let a1 = Agent::with_transport(config("a1"), Arc::new(bus.clone())).await.unwrap();
+        let a2 = Agent::with_transport(config("a2"), Arc::new(bus.clone())).await.unwrap();
+        let mut events = a1.barrier_events();
+        for (agent, peers) in [(&a1, ["a2", "ghost"]), (&a2, ["a1", "ghost"])] {
+            for peer in peers {
+                agent.add_agent(peer.to_string()).await.unwrap();
+            }
+            agent.start().await.unwrap();
+        }
+
+        // "ghost" never shows up: both agents drop it and move on together
+        a1.complete_step().await.unwrap();
+        a2.complete_step().await.unwrap();
+        a1.wait_for_step_complete(0).await.unwrap();
+        a2.wait_for_step_complete(0).await.unwrap();
+        sleep(Duration::from_millis(50)).await;
+        assert_eq!(a1.current_step().await, 1);
+        assert_eq!(a1.known_agents().await, vec!["a1", "a2"]);
+
+        let mut seen = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            seen.push(event);
+        }
+        assert!(seen.contains(&BarrierEvent::AgentDropped { agent_id: "ghost".to_string(), step: 0, reason: crate::DropReason::Straggler }));
+        assert!(seen.iter().any(|e| matches!(e, BarrierEvent::StepAdvanced { from: 0, to: 1, .. })));
+
+        // A stranger claiming a later step doesn't move a latecomer
+        let a3 = Agent::with_transport(config("a3"), Arc::new(bus.clone())).await.unwrap();
+        for peer in ["a1", "a2"] {
+            a3.add_agent(peer.to_string()).await.unwrap();
+        }
+        a3.start().await.unwrap();
+        bus.publish("account.test.acp.7.cli.step", &ACPMessage::step_complete("cli".to_string(), 7)).await.unwrap();
+        sleep(Duration::from_millis(50)).await;
+        assert_eq!(a3.current_step().await, 0);
+
+        // It adopts the mission's step once a peer speaks, and the members take it in
+        a1.send_status("working", serde_json::json!({})).await.unwrap();
+        a3.send_status("joining", serde_json::json!({})).await.unwrap();
+        sleep(Duration::from_millis(50)).await;
+        assert_eq!(a3.current_step().await, 1);
+        assert!(a1.known_agents().await.contains(&"a3".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_abort_mission_policy() {
+        let config = AgentConfig::new("a1".to_string(), "memory://".to_string(), "account.test".to_string())
+            .with_timeout(200)
+            .with_straggler_policy(StragglerPolicy::AbortMission);
+        let agent = Agent::with_transport(config, Arc::new(crate::InMemoryTransport::new())).await.unwrap();
+        agent.add_agent("ghost".to_string()).await.unwrap();
+        agent.start().await.unwrap();
+        agent.complete_step().await.unwrap();
+        assert!(matches!(agent.wait_for_step_complete(0).await, Err(ACPError::MissionAborted { step: 0 })));
+    }
+
     #[test]
     fn test_agent_config_from_env() {
         // Test environment variable fallback
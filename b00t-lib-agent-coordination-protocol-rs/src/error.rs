@@ -9,6 +9,10 @@ pub enum ACPError {
     #[error("NATS error: {0}")]
     NatsError(#[from] async_nats::Error),
 
+    /// Redis connection or transport error
+    #[error("Redis error: {0}")]
+    RedisError(#[from] redis::RedisError),
+
     /// Message serialization failed
     #[error("Serialization error: {0}")]
     SerializationError(#[from] serde_json::Error),
@@ -33,6 +37,10 @@ pub enum ACPError {
     #[error("Step timeout - not all agents completed step {step} within {timeout_ms}ms")]
     StepTimeout { step: u64, timeout_ms: u64 },
 
+    /// The barrier's straggler policy gave up on the mission
+    #[error("Mission aborted at step {step}")]
+    MissionAborted { step: u64 },
+
     /// Invalid agent configuration
     #[error("Invalid agent configuration: {0}")]
     InvalidConfig(String),
@@ -115,7 +123,8 @@ impl ACPError {
             Self::RequestTimeout | 
             Self::ReceiveTimeout |
             Self::NotConnected |
-            Self::NatsError(_)
+            Self::NatsError(_) |
+            Self::RedisError(_)
         )
     }
 
@@ -125,7 +134,8 @@ impl ACPError {
             Self::ConnectionTimeout |
             Self::RequestTimeout |
             Self::ReceiveTimeout |
-            Self::StepTimeout { .. }
+            Self::StepTimeout { .. } |
+            Self::MissionAborted { .. }
         )
     }
 
@@ -0,0 +1,422 @@
+//! Durable, replayable mission history
+//!
+//! Pub/sub forgets: an agent that joins a mission late sees nothing that happened before.
+//! `MessageHistory` records every message per mission and replays it from a step. Backends:
+//!
+//! - `JetStreamHistory`: one NATS JetStream stream per mission
+//! - `RedisStreamHistory`: one Redis Stream (`XADD`/`XRANGE`) per mission
+//! - `InMemoryHistory`: in-process, for tests
+//!
+//! `record_mission` captures a mission's whole namespace (`{namespace}.acp.>`): JetStream
+//! stores it at the broker, whoever publishes, in one capture stream per namespace that
+//! mission streams source from; other backends record through a `RecordingTransport` in
+//! every participant. Each history keeps roughly the last `HISTORY_MAX_LEN` messages.
+
+use crate::transport::{MessageSubscriber, NatsConfig, NatsTransport, Transport};
+use crate::{ACPError, ACPMessage, Result};
+use async_nats::jetstream;
+use async_trait::async_trait;
+use futures::StreamExt;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use tracing::{debug, warn};
+
+/// Messages kept per history stream (older ones are trimmed)
+pub const HISTORY_MAX_LEN: usize = 10_000;
+
+/// Per-mission message log
+#[async_trait]
+pub trait MessageHistory: Send + Sync {
+    /// Record a message in the mission's history
+    async fn append(&self, mission_id: &str, message: &ACPMessage) -> Result<()>;
+
+    /// Messages at or after `from_step`, oldest first
+    async fn replay(&self, mission_id: &str, from_step: u64) -> Result<Vec<ACPMessage>>;
+
+    /// Have the broker record everything published under `{namespace}.acp.`; false if this
+    /// backend can't, and senders must record through a `RecordingTransport`
+    async fn capture(&self, _mission_id: &str, _namespace: &str) -> Result<bool> {
+        Ok(false)
+    }
+}
+
+/// Record a mission's traffic: `transport` as is when the history captures at the broker,
+/// otherwise wrapped so every message sent through it is recorded
+pub async fn record_mission(
+    history: Arc<dyn MessageHistory>,
+    mission_id: &str,
+    namespace: &str,
+    transport: Arc<dyn Transport>,
+) -> Result<Arc<dyn Transport>> {
+    if history.capture(mission_id, namespace).await? {
+        return Ok(transport);
+    }
+    Ok(Arc::new(RecordingTransport::new(transport, history, mission_id, namespace)))
+}
+
+/// Transport that records each message it publishes under `{namespace}.acp.` in a mission's history
+pub struct RecordingTransport {
+    inner: Arc<dyn Transport>,
+    history: Arc<dyn MessageHistory>,
+    mission_id: String,
+    prefix: String,
+}
+
+impl RecordingTransport {
+    pub fn new(inner: Arc<dyn Transport>, history: Arc<dyn MessageHistory>, mission_id: &str, namespace: &str) -> Self {
+        Self { inner, history, mission_id: mission_id.to_string(), prefix: format!("{}.acp.", namespace) }
+    }
+}
+
+#[async_trait]
+impl Transport for RecordingTransport {
+    async fn publish(&self, subject: &str, message: &ACPMessage) -> Result<()> {
+        self.inner.publish(subject, message).await?;
+        if subject.starts_with(&self.prefix) {
+            // 🦨 The message is already out; a gap in the record beats failing the send
+            if let Err(e) = self.history.append(&self.mission_id, message).await {
+                warn!("⚠️ Failed to record {} in mission {}: {}", subject, self.mission_id, e);
+            }
+        }
+        Ok(())
+    }
+
+    async fn subscribe(&self, pattern: &str) -> Result<MessageSubscriber> {
+        self.inner.subscribe(pattern).await
+    }
+
+    async fn is_connected(&self) -> bool {
+        self.inner.is_connected().await
+    }
+
+    async fn flush(&self) -> Result<()> {
+        self.inner.flush().await
+    }
+}
+
+/// Build a history store from a URL: `nats://` (JetStream), `redis://` (Streams) or `memory://`
+pub async fn history_from_url(url: &str) -> Result<Arc<dyn MessageHistory>> {
+    let scheme = url.split("://").next().unwrap_or_default();
+    match scheme {
+        "nats" | "tls" => {
+            let config = NatsConfig { url: url.to_string(), ..NatsConfig::default() };
+            let transport = NatsTransport::new(config).await?;
+            Ok(Arc::new(JetStreamHistory::from_client(transport.client().clone())))
+        }
+        "redis" | "rediss" => Ok(Arc::new(RedisStreamHistory::new(url)?.with_max_len(HISTORY_MAX_LEN))),
+        "memory" => Ok(Arc::new(InMemoryHistory::new())),
+        _ => Err(ACPError::InvalidConfig(format!("Unsupported history URL: {}", url))),
+    }
+}
+
+/// Mission id as a single NATS token / stream name
+fn mission_token(mission_id: &str) -> String {
+    mission_id.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect()
+}
+
+fn decode(payload: &[u8], from_step: u64) -> Option<ACPMessage> {
+    match serde_json::from_slice::<ACPMessage>(payload) {
+        Ok(message) if message.is_supported_version() && message.step >= from_step => Some(message),
+        Ok(_) => None,
+        Err(e) => {
+            debug!("Skipping history entry that isn't an ACP message: {}", e);
+            None
+        }
+    }
+}
+
+/// NATS JetStream history: stream `ACP_HISTORY_{mission}` on subject `b00t.history.{mission}`,
+/// plus, once captured, the namespace's traffic sourced from `ACP_CAPTURE_{namespace}`
+///
+/// 🦨 JetStream won't let two streams bind overlapping subjects, so only the per-namespace
+/// capture stream binds `{namespace}.acp.>`; a mission sources it from when it started capturing
+pub struct JetStreamHistory {
+    context: jetstream::Context,
+    /// Streams (and captures) already set up by this process
+    streams: Mutex<HashSet<String>>,
+}
+
+impl JetStreamHistory {
+    pub fn from_client(client: async_nats::Client) -> Self {
+        Self { context: jetstream::new(client), streams: Mutex::new(HashSet::new()) }
+    }
+
+    fn stream_name(mission_id: &str) -> String {
+        format!("ACP_HISTORY_{}", mission_token(mission_id))
+    }
+
+    fn capture_stream_name(namespace: &str) -> String {
+        format!("ACP_CAPTURE_{}", mission_token(namespace))
+    }
+
+    fn subject(mission_id: &str) -> String {
+        format!("b00t.history.{}", mission_token(mission_id))
+    }
+
+    fn is_set_up(&self, key: &str) -> bool {
+        self.streams.lock().unwrap_or_else(|e| e.into_inner()).contains(key)
+    }
+
+    fn set_up(&self, key: String) {
+        self.streams.lock().unwrap_or_else(|e| e.into_inner()).insert(key);
+    }
+
+    async fn stream(&self, name: String, subject: String) -> Result<jetstream::stream::Stream> {
+        self.context.get_or_create_stream(jetstream::stream::Config {
+            name,
+            subjects: vec![subject],
+            max_messages: HISTORY_MAX_LEN as i64,
+            ..Default::default()
+        })
+        .await
+        .map_err(|e| ACPError::NatsError(Box::new(e)))
+    }
+}
+
+#[async_trait]
+impl MessageHistory for JetStreamHistory {
+    async fn append(&self, mission_id: &str, message: &ACPMessage) -> Result<()> {
+        let name = Self::stream_name(mission_id);
+        if !self.is_set_up(&name) {
+            self.stream(name.clone(), Self::subject(mission_id)).await?;
+            self.set_up(name);
+        }
+
+        let payload = serde_json::to_vec(message)?;
+        // 🤓 The second await is the server's ack: the message is stored, not just sent
+        self.context.publish(Self::subject(mission_id), payload.into())
+            .await
+            .map_err(|e| ACPError::NatsError(Box::new(e)))?
+            .await
+            .map_err(|e| ACPError::NatsError(Box::new(e)))?;
+        Ok(())
+    }
+
+    async fn replay(&self, mission_id: &str, from_step: u64) -> Result<Vec<ACPMessage>> {
+        let Ok(stream) = self.context.get_stream(Self::stream_name(mission_id)).await else {
+            return Ok(Vec::new());
+        };
+        let state = stream.get_info().await.map_err(|e| ACPError::NatsError(Box::new(e)))?.state;
+        if state.messages == 0 {
+            return Ok(Vec::new());
+        }
+
+        // 🤓 An ordered consumer streams the log in batches instead of one round trip per message
+        let consumer = stream.create_consumer(jetstream::consumer::pull::OrderedConfig {
+            deliver_policy: jetstream::consumer::DeliverPolicy::All,
+            ..Default::default()
+        })
+        .await
+        .map_err(|e| ACPError::NatsError(Box::new(e)))?;
+        let mut entries = consumer.messages().await.map_err(|e| ACPError::NatsError(Box::new(e)))?;
+
+        let mut messages = Vec::new();
+        while let Some(entry) = entries.next().await {
+            let entry = entry.map_err(|e| ACPError::NatsError(Box::new(e)))?;
+            messages.extend(decode(&entry.payload, from_step));
+            let info = entry.info().map_err(|e| ACPError::other(e.to_string()))?;
+            if info.pending == 0 {
+                break;
+            }
+        }
+        Ok(messages)
+    }
+
+    /// The mission stream sources the namespace's capture stream from here on: every
+    /// sender's traffic, nobody appends
+    async fn capture(&self, mission_id: &str, namespace: &str) -> Result<bool> {
+        let name = Self::stream_name(mission_id);
+        let capture_name = Self::capture_stream_name(namespace);
+        let key = format!("{} <- {}", name, capture_name);
+        if self.is_set_up(&key) {
+            return Ok(true);
+        }
+
+        let capture = self.stream(capture_name.clone(), format!("{}.acp.>", namespace)).await?;
+        let next = capture.get_info().await.map_err(|e| ACPError::NatsError(Box::new(e)))?.state.last_sequence + 1;
+        let stream = self.stream(name, Self::subject(mission_id)).await?;
+        let mut config = stream.cached_info().config.clone();
+        let sources = config.sources.get_or_insert_with(Vec::new);
+        if !sources.iter().any(|source| source.name == capture_name) {
+            sources.push(jetstream::stream::Source {
+                name: capture_name,
+                start_sequence: Some(next),
+                ..Default::default()
+            });
+            self.context.update_stream(config).await.map_err(|e| ACPError::NatsError(Box::new(e)))?;
+        }
+        self.set_up(key);
+        Ok(true)
+    }
+}
+
+/// Redis Streams history: stream `b00t:acp:history:{mission}`, one entry per message
+pub struct RedisStreamHistory {
+    client: redis::Client,
+    max_len: Option<usize>,
+}
+
+impl RedisStreamHistory {
+    /// History on e.g. `redis://localhost:6379`; connects per call
+    pub fn new(url: &str) -> Result<Self> {
+        Ok(Self::from_client(redis::Client::open(url)?))
+    }
+
+    pub fn from_client(client: redis::Client) -> Self {
+        Self { client, max_len: None }
+    }
+
+    /// Keep roughly the last `max_len` messages per mission (`XADD MAXLEN ~`)
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = Some(max_len);
+        self
+    }
+
+    fn key(mission_id: &str) -> String {
+        format!("b00t:acp:history:{}", mission_id)
+    }
+
+    /// Decode an `XRANGE` reply: entries of (id, [field, value, ...])
+    fn entries(reply: &redis::Value, from_step: u64) -> Result<Vec<ACPMessage>> {
+        let entries: Vec<(String, Vec<String>)> = redis::from_redis_value(reply)?;
+        Ok(entries.into_iter()
+            .filter_map(|(_, fields)| {
+                let message = fields.chunks(2).find(|pair| pair[0] == "message")?.get(1)?;
+                decode(message.as_bytes(), from_step)
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl MessageHistory for RedisStreamHistory {
+    async fn append(&self, mission_id: &str, message: &ACPMessage) -> Result<()> {
+        let client = self.client.clone();
+        let key = Self::key(mission_id);
+        let max_len = self.max_len;
+        let step = message.step;
+        let payload = serde_json::to_string(message)?;
+
+        // 🤓 The redis crate is blocking; keep it off the async workers
+        tokio::task::spawn_blocking(move || {
+            let mut conn = client.get_connection()?;
+            let mut cmd = redis::cmd("XADD");
+            cmd.arg(&key);
+            if let Some(max_len) = max_len {
+                cmd.arg("MAXLEN").arg("~").arg(max_len);
+            }
+            cmd.arg("*").arg("step").arg(step).arg("message").arg(payload)
+                .query::<String>(&mut conn)
+        })
+        .await
+        .map_err(|e| ACPError::other(e.to_string()))??;
+        Ok(())
+    }
+
+    async fn replay(&self, mission_id: &str, from_step: u64) -> Result<Vec<ACPMessage>> {
+        let client = self.client.clone();
+        let key = Self::key(mission_id);
+
+        let reply = tokio::task::spawn_blocking(move || {
+            let mut conn = client.get_connection()?;
+            redis::cmd("XRANGE").arg(&key).arg("-").arg("+").query::<redis::Value>(&mut conn)
+        })
+        .await
+        .map_err(|e| ACPError::other(e.to_string()))??;
+
+        Self::entries(&reply, from_step)
+    }
+}
+
+/// In-process history; clones share one log
+#[derive(Clone, Default)]
+pub struct InMemoryHistory {
+    missions: Arc<Mutex<HashMap<String, Vec<ACPMessage>>>>,
+}
+
+impl InMemoryHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl MessageHistory for InMemoryHistory {
+    async fn append(&self, mission_id: &str, message: &ACPMessage) -> Result<()> {
+        let mut missions = self.missions.lock().unwrap_or_else(|e| e.into_inner());
+        missions.entry(mission_id.to_string()).or_default().push(message.clone());
+        Ok(())
+    }
+
+    async fn replay(&self, mission_id: &str, from_step: u64) -> Result<Vec<ACPMessage>> {
+        let missions = self.missions.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(missions.get(mission_id)
+            .map(|log| log.iter().filter(|m| m.step >= from_step).cloned().collect())
+            .unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_in_memory_replay_from_step() {
+        let history = InMemoryHistory::new();
+        for step in 0..3 {
+            history.append("m1", &ACPMessage::step_complete("a1".to_string(), step)).await.unwrap();
+        }
+        history.append("m2", &ACPMessage::step_complete("a2".to_string(), 5)).await.unwrap();
+
+        let replayed = history.replay("m1", 1).await.unwrap();
+        assert_eq!(replayed.iter().map(|m| m.step).collect::<Vec<_>>(), vec![1, 2]);
+        assert!(history.replay("nobody", 0).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_recording_transport_records_the_namespace() {
+        let history = Arc::new(InMemoryHistory::new());
+        let bus: Arc<dyn Transport> = Arc::new(crate::InMemoryTransport::new());
+        let transport = record_mission(history.clone(), "m1", "account.test", bus).await.unwrap();
+
+        // Barrier STEPs and coordination are recorded; other namespaces aren't
+        let agent = crate::Agent::with_transport(
+            crate::AgentConfig::new("a1".to_string(), String::new(), "account.test".to_string()),
+            Arc::clone(&transport),
+        ).await.unwrap();
+        agent.complete_step().await.unwrap();
+        let vote = ACPMessage::coordination("a1".to_string(), 0, serde_json::json!({"msg_type": "vote"}));
+        transport.publish(&ACPMessage::channel_subject("account.test", "votes"), &vote).await.unwrap();
+        transport.publish("account.other.acp.0.a1.step", &ACPMessage::step_complete("a1".to_string(), 0)).await.unwrap();
+
+        let recorded = history.replay("m1", 0).await.unwrap();
+        let kinds: Vec<_> = recorded.iter().map(|m| m.message_type.clone()).collect();
+        assert_eq!(kinds, vec![crate::MessageType::Step, crate::MessageType::Coordination]);
+    }
+
+    #[test]
+    fn test_redis_xrange_reply() {
+        use redis::Value;
+        let bulk = |s: &str| Value::BulkString(s.as_bytes().to_vec());
+        let entry = |id: &str, step: u64| Value::Array(vec![bulk(id), Value::Array(vec![
+            bulk("step"), bulk(&step.to_string()),
+            bulk("message"), bulk(&serde_json::to_string(&ACPMessage::step_complete("a1".to_string(), step)).unwrap()),
+        ])]);
+        let reply = Value::Array(vec![entry("1-0", 0), entry("2-0", 4)]);
+
+        let replayed = RedisStreamHistory::entries(&reply, 1).unwrap();
+        assert_eq!(replayed.len(), 1);
+        assert_eq!(replayed[0].step, 4);
+    }
+
+    #[tokio::test]
+    async fn test_history_from_url() {
+        assert!(history_from_url("memory://").await.is_ok());
+        assert!(history_from_url("redis://127.0.0.1:1").await.is_ok());
+        assert!(matches!(history_from_url("ftp://nope").await, Err(ACPError::InvalidConfig(_))));
+        assert_eq!(JetStreamHistory::stream_name("deploy.v2 prod"), "ACP_HISTORY_deploy_v2_prod");
+        assert_eq!(JetStreamHistory::capture_stream_name("account.alice"), "ACP_CAPTURE_account_alice");
+    }
+}
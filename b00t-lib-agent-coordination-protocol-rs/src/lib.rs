@@ -6,8 +6,10 @@
 //! ## Features
 //!
 //! - **Step-based coordination**: Discrete rounds with barrier synchronization
-//! - **NATS transport**: Built on async-nats for reliable messaging
+//! - **Pluggable transport**: NATS (async-nats), Redis pub/sub, or in-memory for tests
+//! - **Mission history**: optional replayable log on NATS JetStream or Redis Streams
 //! - **Role-based permissions**: Isolated namespaces per GitHub user
+//! - **Message signing**: optional Ed25519 or per-namespace HMAC signatures
 //! - **Language bindings**: Python and TypeScript/JavaScript support
 //!
 //! ## Message Types
@@ -18,7 +20,7 @@
 //!
 //! ## Example
 //!
-//! ```rust
+//! ```rust,no_run
 //! use b00t_acp::{Agent, AgentConfig, MessageType};
 //!
 //! #[tokio::main]
@@ -53,8 +55,10 @@
 pub mod agent;
 pub mod protocol;
 pub mod transport;
+pub mod history;
 pub mod error;
 pub mod security;
+pub mod signing;
 
 #[cfg(feature = "python")]
 pub mod python;
@@ -63,12 +67,22 @@ pub mod python;
 pub mod wasm;
 
 pub use agent::{Agent, AgentConfig};
-pub use protocol::{ACPMessage, MessageType, StepBarrier};
-pub use transport::{NatsTransport, NatsConfig};
+pub use protocol::{ACP_PROTOCOL_VERSION, ACPMessage, COORDINATION_CHANNELS, MessageType, StepBarrier, StragglerPolicy, BarrierEvent, DropReason};
+pub use transport::{
+    InMemoryTransport, MessageSubscriber, NatsConfig, NatsTransport, RedisTransport, Transport,
+    default_transport_url, subject_matches, transport_from_url
+};
+pub use history::{
+    HISTORY_MAX_LEN, InMemoryHistory, JetStreamHistory, MessageHistory, RecordingTransport, RedisStreamHistory,
+    history_from_url, record_mission
+};
 pub use error::{ACPError, Result};
 pub use security::{
-    AcpJwtValidator, AcpSecurityContext, NamespaceEnforcer, 
-    SubjectOperation, fetch_jwt_from_website
+    AcpJwtValidator, AcpSecurityContext, EnforcedTransport, JwtKey, LocalKeyPair, NamespaceEnforcer,
+    QuarantinedMessage, SubjectOperation, fetch_jwt_from_website
+};
+pub use signing::{
+    MessageSignature, MessageVerifier, SigningKey, UnsignedPolicy, Verification, VerifyingKey
 };
 
 // Re-export commonly used types
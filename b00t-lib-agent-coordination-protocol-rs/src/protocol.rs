@@ -1,10 +1,22 @@
 //! Core ACP protocol types and step synchronization logic
 
+use crate::signing::MessageSignature;
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 use std::collections::HashMap;
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 
+/// Envelope version written by this library
+///
+/// - 1: STATUS/PROPOSE/STEP only (messages without a `version` field)
+/// - 2: adds COORDINATION (task, vote, capability, presence, event payloads)
+pub const ACP_PROTOCOL_VERSION: u32 = 2;
+
+fn legacy_version() -> u32 {
+    1
+}
+
 /// ACP Message Types as defined in the protocol specification
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "UPPERCASE")]
@@ -15,13 +27,18 @@ pub enum MessageType {
     Propose, 
     /// Mark the completion of a step
     Step,
+    /// Task, vote, capability, presence or event payload; `payload.msg_type` names the kind
+    Coordination,
 }
 
-/// Core ACP message structure
+/// Core ACP message structure - the versioned envelope every transport carries
 /// 
 /// All ACP messages MUST include: step, agent_id, type, payload
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ACPMessage {
+    /// Envelope version (see `ACP_PROTOCOL_VERSION`)
+    #[serde(default = "legacy_version")]
+    pub version: u32,
     /// Step number (monotonically increasing)
     pub step: u64,
     /// Agent unique identifier
@@ -39,12 +56,20 @@ pub struct ACPMessage {
     /// Optional correlation ID for request/response patterns
     #[serde(skip_serializing_if = "Option::is_none")]
     pub correlation_id: Option<Uuid>,
+    /// JWT subject of the publisher, stamped by `EnforcedTransport`.
+    /// 🦨 Anyone on the raw broker can write this; trust it only once `signature` verifies
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signer: Option<String>,
+    /// Detached signature over everything else (see `signing`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<MessageSignature>,
 }
 
 impl ACPMessage {
     /// Create a new STATUS message
     pub fn status(agent_id: String, step: u64, payload: serde_json::Value) -> Self {
         Self {
+            version: ACP_PROTOCOL_VERSION,
             step,
             agent_id,
             message_type: MessageType::Status,
@@ -52,12 +77,15 @@ impl ACPMessage {
             timestamp: Utc::now(),
             message_id: Some(Uuid::new_v4()),
             correlation_id: None,
+            signer: None,
+            signature: None,
         }
     }
 
     /// Create a new PROPOSE message
     pub fn propose(agent_id: String, step: u64, payload: serde_json::Value) -> Self {
         Self {
+            version: ACP_PROTOCOL_VERSION,
             step,
             agent_id,
             message_type: MessageType::Propose,
@@ -65,12 +93,15 @@ impl ACPMessage {
             timestamp: Utc::now(),
             message_id: Some(Uuid::new_v4()),
             correlation_id: None,
+            signer: None,
+            signature: None,
         }
     }
 
     /// Create a new STEP message
     pub fn step_complete(agent_id: String, step: u64) -> Self {
         Self {
+            version: ACP_PROTOCOL_VERSION,
             step,
             agent_id,
             message_type: MessageType::Step,
@@ -78,9 +109,40 @@ impl ACPMessage {
             timestamp: Utc::now(),
             message_id: Some(Uuid::new_v4()),
             correlation_id: None,
+            signer: None,
+            signature: None,
+        }
+    }
+
+    /// Create a new COORDINATION message; `payload` is a `{"msg_type", "data"}` object
+    pub fn coordination(agent_id: String, step: u64, payload: serde_json::Value) -> Self {
+        Self {
+            version: ACP_PROTOCOL_VERSION,
+            step,
+            agent_id,
+            message_type: MessageType::Coordination,
+            payload,
+            timestamp: Utc::now(),
+            message_id: Some(Uuid::new_v4()),
+            correlation_id: None,
+            signer: None,
+            signature: None,
         }
     }
 
+    /// Kind of a COORDINATION message (e.g. "TaskDelegation")
+    pub fn coordination_kind(&self) -> Option<&str> {
+        match self.message_type {
+            MessageType::Coordination => self.payload.get("msg_type").and_then(|kind| kind.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Can this library understand the message?
+    pub fn is_supported_version(&self) -> bool {
+        (1..=ACP_PROTOCOL_VERSION).contains(&self.version)
+    }
+
     /// Get NATS subject for this message
     /// Format: acp.{step}.{agent_id}.{type}
     pub fn subject(&self) -> String {
@@ -98,39 +160,170 @@ impl ACPMessage {
     }
 
     /// Get wildcard subject for listening to all messages from an agent
-    /// Format: acp.>.{agent_id}.>
+    /// Format: acp.*.{agent_id}.>
     pub fn agent_wildcard(agent_id: &str) -> String {
-        format!("acp.>.{}.>", agent_id)
+        format!("acp.*.{}.>", agent_id)
+    }
+
+    /// Subject a reply to a request with this correlation ID is published on
+    /// Format: _INBOX.{correlation_id}
+    pub fn reply_subject(correlation_id: Uuid) -> String {
+        format!("_INBOX.{}", correlation_id)
+    }
+
+    /// Whether `agent_id` can stand in a subject: non-empty '.'-separated tokens without
+    /// whitespace or the `*`/`>` wildcards
+    pub fn is_valid_agent_id(agent_id: &str) -> bool {
+        agent_id.split('.').all(|token| {
+            !token.is_empty() && token.chars().all(|c| c.is_ascii_graphic() && c != '*' && c != '>')
+        })
+    }
+
+    /// Subject for COORDINATION messages addressed to one agent
+    /// Format: {namespace}.acp.agents.{agent_id}
+    pub fn inbox_subject(namespace: &str, agent_id: &str) -> String {
+        format!("{}.acp.agents.{}", namespace, agent_id)
+    }
+
+    /// Subject for COORDINATION broadcasts on one of `COORDINATION_CHANNELS`
+    /// Format: {namespace}.acp.coord.{channel}
+    pub fn channel_subject(namespace: &str, channel: &str) -> String {
+        format!("{}.acp.coord.{}", namespace, channel)
     }
 }
 
+/// Broadcast channels for COORDINATION messages (see `ACPMessage::channel_subject`)
+pub const COORDINATION_CHANNELS: &[&str] = &["presence", "progress", "votes", "events", "capabilities"];
+
+/// What a `StepBarrier` does when a step outlives its timeout
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StragglerPolicy {
+    /// Keep waiting (the timeout is only reported)
+    #[default]
+    Wait,
+    /// Drop the agents that haven't completed the step and advance without them
+    DropStraggler,
+    /// Give up on the mission
+    AbortMission,
+}
+
+impl std::str::FromStr for StragglerPolicy {
+    type Err = crate::ACPError;
+
+    fn from_str(s: &str) -> crate::Result<Self> {
+        match s.to_lowercase().replace('_', "-").as_str() {
+            "wait" => Ok(Self::Wait),
+            "drop" | "drop-straggler" => Ok(Self::DropStraggler),
+            "abort" | "abort-mission" => Ok(Self::AbortMission),
+            _ => Err(crate::ACPError::invalid_config(format!("Unknown straggler policy '{}' (wait, drop, abort)", s))),
+        }
+    }
+}
+
+/// Why an agent left the barrier without asking
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DropReason {
+    /// Didn't complete a step in time (`StragglerPolicy::DropStraggler`)
+    Straggler,
+    /// Went quiet for longer than the heartbeat timeout
+    MissedHeartbeat,
+}
+
+/// Every decision a `StepBarrier` takes, for logging and subscribers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum BarrierEvent {
+    /// `forced` when some agents were left behind
+    StepAdvanced { from: u64, to: u64, forced: bool },
+    StepTimedOut { step: u64, pending: Vec<String> },
+    /// Required to complete steps from `from_step` on
+    AgentJoined { agent_id: String, from_step: u64 },
+    AgentDropped { agent_id: String, step: u64, reason: DropReason },
+    MissionAborted { step: u64, pending: Vec<String> },
+    /// A late joiner jumped to the mission's current step
+    CaughtUp { from: u64, to: u64 },
+}
+
 /// Step barrier synchronization manager
 /// 
-/// Ensures all agents complete a step before advancing to the next
+/// Ensures all agents complete a step before advancing to the next. Time is passed in
+/// (`tick`, `heartbeat`, `join`) so policies are deterministic; `Agent` drives them.
 #[derive(Debug)]
 pub struct StepBarrier {
     current_step: u64,
     known_agents: Vec<String>,
     step_completions: HashMap<u64, Vec<String>>,
     timeout_ms: u64,
+    policy: StragglerPolicy,
+    /// Agents silent for longer than this are dropped; `None` never drops
+    heartbeat_timeout_ms: Option<u64>,
+    step_started: Instant,
+    last_seen: HashMap<String, Instant>,
+    /// First step each agent must complete (later than 0 for mid-mission joiners)
+    required_from: HashMap<String, u64>,
+    /// The current step's timeout has been reported
+    timed_out: bool,
+    aborted: bool,
+    /// The agent this barrier belongs to; it is never dropped
+    local_agent: Option<String>,
 }
 
 impl StepBarrier {
     /// Create new step barrier
     pub fn new(known_agents: Vec<String>, timeout_ms: u64) -> Self {
+        let now = Instant::now();
         Self {
             current_step: 0,
+            last_seen: known_agents.iter().map(|agent| (agent.clone(), now)).collect(),
             known_agents,
             step_completions: HashMap::new(),
             timeout_ms,
+            policy: StragglerPolicy::default(),
+            heartbeat_timeout_ms: None,
+            step_started: now,
+            required_from: HashMap::new(),
+            timed_out: false,
+            aborted: false,
+            local_agent: None,
         }
     }
 
+    /// Mark `agent_id` as the barrier's owner, which timeouts never drop
+    pub fn with_local_agent(mut self, agent_id: impl Into<String>) -> Self {
+        self.local_agent = Some(agent_id.into());
+        self
+    }
+
+    fn is_local(&self, agent_id: &str) -> bool {
+        self.local_agent.as_deref() == Some(agent_id)
+    }
+
+    pub fn with_policy(mut self, policy: StragglerPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Drop agents not heard from (`heartbeat`) for `timeout_ms`
+    pub fn with_heartbeat_timeout(mut self, timeout_ms: u64) -> Self {
+        self.heartbeat_timeout_ms = Some(timeout_ms);
+        self
+    }
+
     /// Get current step number
     pub fn current_step(&self) -> u64 {
         self.current_step
     }
 
+    pub fn policy(&self) -> StragglerPolicy {
+        self.policy
+    }
+
+    pub fn is_aborted(&self) -> bool {
+        self.aborted
+    }
+
     /// Record that an agent completed a step
     pub fn record_step_completion(&mut self, step: u64, agent_id: String) {
         let completions = self.step_completions.entry(step).or_insert_with(Vec::new);
@@ -139,36 +332,54 @@ impl StepBarrier {
         }
     }
 
+    /// Has `agent_id` completed any step yet?
+    pub fn has_completed_any(&self, agent_id: &str) -> bool {
+        self.step_completions.values().any(|completions| completions.iter().any(|id| id == agent_id))
+    }
+
+    /// Agents that must complete `step`
+    fn required_for(&self, step: u64) -> impl Iterator<Item = &String> {
+        self.known_agents.iter()
+            .filter(move |agent| self.required_from.get(*agent).is_none_or(|from| *from <= step))
+    }
+
     /// Check if step is complete (all known agents have sent STEP message)
     pub fn is_step_complete(&self, step: u64) -> bool {
         if let Some(completions) = self.step_completions.get(&step) {
-            completions.len() >= self.known_agents.len() && 
-            self.known_agents.iter().all(|agent| completions.contains(agent))
+            self.required_for(step).all(|agent| completions.contains(agent))
         } else {
             false
         }
     }
 
+    fn advance(&mut self, now: Instant, forced: bool) -> BarrierEvent {
+        let from = self.current_step;
+        self.current_step += 1;
+        self.step_started = now;
+        self.timed_out = false;
+        BarrierEvent::StepAdvanced { from, to: self.current_step, forced }
+    }
+
     /// Advance to next step if current step is complete
     pub fn try_advance_step(&mut self) -> bool {
-        if self.is_step_complete(self.current_step) {
-            self.current_step += 1;
-            true
-        } else {
-            false
-        }
+        self.advance_at(Instant::now()).is_some()
+    }
+
+    /// `try_advance_step` with an explicit clock
+    pub fn advance_at(&mut self, now: Instant) -> Option<BarrierEvent> {
+        (!self.aborted && self.is_step_complete(self.current_step)).then(|| self.advance(now, false))
     }
 
     /// Force advance to next step (timeout scenario)
     pub fn force_advance_step(&mut self) {
-        self.current_step += 1;
+        self.advance(Instant::now(), true);
     }
 
     /// Get agents that haven't completed the current step
     pub fn pending_agents(&self, step: u64) -> Vec<String> {
         let empty_vec = Vec::new();
         let completed = self.step_completions.get(&step).unwrap_or(&empty_vec);
-        self.known_agents.iter()
+        self.required_for(step)
             .filter(|agent| !completed.contains(agent))
             .cloned()
             .collect()
@@ -176,20 +387,118 @@ impl StepBarrier {
 
     /// Add new agent to known agents list
     pub fn add_agent(&mut self, agent_id: String) {
-        if !self.known_agents.contains(&agent_id) {
-            self.known_agents.push(agent_id);
+        self.join(agent_id, Instant::now());
+    }
+
+    /// Add an agent, possibly mid-mission
+    ///
+    /// 🤓 It must complete the current step only if nobody has completed it yet; otherwise
+    /// it would hold up a step already underway, so it starts with the next one.
+    pub fn join(&mut self, agent_id: String, now: Instant) -> Option<BarrierEvent> {
+        self.last_seen.insert(agent_id.clone(), now);
+        if self.known_agents.contains(&agent_id) {
+            return None;
+        }
+        let underway = self.step_completions.get(&self.current_step).is_some_and(|c| !c.is_empty());
+        let from_step = self.current_step + u64::from(underway);
+        self.required_from.insert(agent_id.clone(), from_step);
+        self.known_agents.push(agent_id.clone());
+        Some(BarrierEvent::AgentJoined { agent_id, from_step })
+    }
+
+    /// A late joiner adopts the mission's step instead of waiting at its own
+    pub fn catch_up(&mut self, step: u64, now: Instant) -> Option<BarrierEvent> {
+        if step <= self.current_step || self.aborted {
+            return None;
+        }
+        let from = self.current_step;
+        self.current_step = step;
+        self.step_started = now;
+        self.timed_out = false;
+        Some(BarrierEvent::CaughtUp { from, to: step })
+    }
+
+    /// Note that `agent_id` is alive
+    pub fn heartbeat(&mut self, agent_id: &str, now: Instant) {
+        if self.known_agents.iter().any(|id| id == agent_id) {
+            self.last_seen.insert(agent_id.to_string(), now);
         }
     }
 
     /// Remove agent from known agents list
     pub fn remove_agent(&mut self, agent_id: &str) {
         self.known_agents.retain(|id| id != agent_id);
+        self.last_seen.remove(agent_id);
+        self.required_from.remove(agent_id);
         // Remove from all step completions
         for completions in self.step_completions.values_mut() {
             completions.retain(|id| id != agent_id);
         }
     }
 
+    /// Apply heartbeat expiry and the timeout policy as of `now`; returns what was decided
+    pub fn tick(&mut self, now: Instant) -> Vec<BarrierEvent> {
+        let mut events = Vec::new();
+        if self.aborted {
+            return events;
+        }
+
+        if let Some(heartbeat_timeout) = self.heartbeat_timeout_ms.map(Duration::from_millis) {
+            let silent: Vec<String> = self.known_agents.iter()
+                .filter(|agent| !self.is_local(agent))
+                .filter(|agent| self.last_seen.get(*agent).is_none_or(|seen| now.saturating_duration_since(*seen) > heartbeat_timeout))
+                .cloned()
+                .collect();
+            for agent_id in silent {
+                self.remove_agent(&agent_id);
+                events.push(BarrierEvent::AgentDropped { agent_id, step: self.current_step, reason: DropReason::MissedHeartbeat });
+            }
+        }
+
+        let step = self.current_step;
+        let overdue = now.saturating_duration_since(self.step_started) >= Duration::from_millis(self.timeout_ms);
+        if overdue && !self.is_step_complete(step) {
+            let pending = self.pending_agents(step);
+            match self.policy {
+                StragglerPolicy::Wait => {
+                    if !self.timed_out {
+                        events.push(BarrierEvent::StepTimedOut { step, pending });
+                    }
+                }
+                // 🤓 Nobody is a straggler until someone has finished, and we never drop
+                // ourselves: without either the step just waits, as under `Wait`
+                StragglerPolicy::DropStraggler => {
+                    if !self.timed_out {
+                        events.push(BarrierEvent::StepTimedOut { step, pending: pending.clone() });
+                    }
+                    let started = self.step_completions.get(&step).is_some_and(|c| !c.is_empty());
+                    if started {
+                        let stragglers: Vec<String> = pending.into_iter().filter(|agent| !self.is_local(agent)).collect();
+                        for agent_id in stragglers {
+                            self.remove_agent(&agent_id);
+                            events.push(BarrierEvent::AgentDropped { agent_id, step, reason: DropReason::Straggler });
+                        }
+                        if self.is_step_complete(step) {
+                            events.push(self.advance(now, true));
+                            return events;
+                        }
+                    }
+                }
+                StragglerPolicy::AbortMission => {
+                    self.aborted = true;
+                    events.push(BarrierEvent::StepTimedOut { step, pending: pending.clone() });
+                    events.push(BarrierEvent::MissionAborted { step, pending });
+                    return events;
+                }
+            }
+            self.timed_out = true;
+        }
+
+        // 🤓 Dropping a silent agent may be all the current step was waiting for
+        events.extend(self.advance_at(now));
+        events
+    }
+
     /// Get list of known agents
     pub fn known_agents(&self) -> &[String] {
         &self.known_agents
@@ -208,6 +517,17 @@ impl StepBarrier {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_agent_id_validation() {
+        assert!(ACPMessage::is_valid_agent_id("agent-1"));
+        assert!(ACPMessage::is_valid_agent_id("operator.alice"));
+        assert!(!ACPMessage::is_valid_agent_id(""));
+        assert!(!ACPMessage::is_valid_agent_id("🤖 Custom Agent"));
+        assert!(!ACPMessage::is_valid_agent_id("agent."));
+        assert!(!ACPMessage::is_valid_agent_id("agent.*"));
+        assert!(!ACPMessage::is_valid_agent_id(">"));
+    }
+
     #[test]
     fn test_acp_message_creation() {
         let msg = ACPMessage::status(
@@ -233,6 +553,26 @@ mod tests {
         assert_eq!(msg.subject(), "acp.5.claude.124435.propose");
     }
 
+    #[test]
+    fn test_envelope_versions() {
+        let msg = ACPMessage::coordination(
+            "captain".to_string(),
+            2,
+            serde_json::json!({"msg_type": "TaskDelegation", "data": {"task_id": "t1"}})
+        );
+        assert_eq!(msg.version, ACP_PROTOCOL_VERSION);
+        assert_eq!(msg.subject(), "acp.2.captain.coordination");
+        assert_eq!(msg.coordination_kind(), Some("TaskDelegation"));
+
+        // v1 peers don't send a version
+        let mut legacy = serde_json::to_value(ACPMessage::step_complete("old".to_string(), 1)).unwrap();
+        legacy.as_object_mut().unwrap().remove("version");
+        let legacy: ACPMessage = serde_json::from_value(legacy).unwrap();
+        assert_eq!(legacy.version, 1);
+        assert!(legacy.is_supported_version());
+        assert_eq!(legacy.coordination_kind(), None);
+    }
+
     #[test]
     fn test_step_barrier() {
         let mut barrier = StepBarrier::new(
@@ -266,4 +606,70 @@ mod tests {
         let pending = barrier.pending_agents(0);
         assert_eq!(pending, vec!["a2"]);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_straggler_policies() {
+        let agents = || vec!["a1".to_string(), "a2".to_string(), "a3".to_string()];
+        let start = Instant::now();
+        let late = start + Duration::from_millis(1500);
+
+        let mut waiting = StepBarrier::new(agents(), 1000);
+        waiting.record_step_completion(0, "a1".to_string());
+        assert!(waiting.tick(start).is_empty());
+        assert!(matches!(waiting.tick(late).as_slice(), [BarrierEvent::StepTimedOut { step: 0, .. }]));
+        assert!(waiting.tick(late).is_empty(), "a timeout is reported once");
+        assert_eq!(waiting.current_step(), 0);
+
+        let mut dropping = StepBarrier::new(agents(), 1000).with_policy(StragglerPolicy::DropStraggler);
+        dropping.record_step_completion(0, "a1".to_string());
+        let events = dropping.tick(late);
+        assert_eq!(events.last(), Some(&BarrierEvent::StepAdvanced { from: 0, to: 1, forced: true }));
+        assert_eq!(events.iter().filter(|e| matches!(e, BarrierEvent::AgentDropped { reason: DropReason::Straggler, .. })).count(), 2);
+        assert_eq!(dropping.known_agents(), ["a1"]);
+
+        // Nobody finished: nothing to drop and nothing to advance on
+        let mut idle = StepBarrier::new(agents(), 1000).with_policy(StragglerPolicy::DropStraggler);
+        assert!(matches!(idle.tick(late).as_slice(), [BarrierEvent::StepTimedOut { step: 0, .. }]));
+        assert_eq!((idle.current_step(), idle.known_agents().len()), (0, 3));
+
+        // The local agent is never dropped; the step waits for it instead
+        let mut local = StepBarrier::new(agents(), 1000).with_policy(StragglerPolicy::DropStraggler).with_local_agent("a3");
+        local.record_step_completion(0, "a1".to_string());
+        local.tick(late);
+        assert_eq!((local.current_step(), local.known_agents()), (0, &["a1".to_string(), "a3".to_string()][..]));
+        local.record_step_completion(0, "a3".to_string());
+        assert!(matches!(local.tick(late).last(), Some(BarrierEvent::StepAdvanced { from: 0, to: 1, forced: false })));
+
+        let mut aborting = StepBarrier::new(agents(), 1000).with_policy(StragglerPolicy::AbortMission);
+        assert!(matches!(aborting.tick(late).last(), Some(BarrierEvent::MissionAborted { step: 0, .. })));
+        assert!(aborting.is_aborted());
+        assert_eq!("drop".parse::<StragglerPolicy>().unwrap(), StragglerPolicy::DropStraggler);
+    }
+
+    #[test]
+    fn test_heartbeats_and_mid_mission_joins() {
+        let start = Instant::now();
+        let mut barrier = StepBarrier::new(vec!["a1".to_string(), "a2".to_string()], 60_000)
+            .with_heartbeat_timeout(500);
+        barrier.record_step_completion(0, "a1".to_string());
+
+        // a3 joins while step 0 is underway: it starts with step 1
+        assert_eq!(barrier.join("a3".to_string(), start), Some(BarrierEvent::AgentJoined { agent_id: "a3".to_string(), from_step: 1 }));
+        assert_eq!(barrier.pending_agents(0), vec!["a2"]);
+
+        // a2 went quiet; dropping it completes step 0
+        let later = start + Duration::from_millis(800);
+        barrier.heartbeat("a1", later);
+        barrier.heartbeat("a3", later);
+        let events = barrier.tick(later);
+        assert_eq!(events, vec![
+            BarrierEvent::AgentDropped { agent_id: "a2".to_string(), step: 0, reason: DropReason::MissedHeartbeat },
+            BarrierEvent::StepAdvanced { from: 0, to: 1, forced: false },
+        ]);
+        assert_eq!(barrier.pending_agents(1), vec!["a1", "a3"]);
+
+        let mut joiner = StepBarrier::new(vec!["a4".to_string()], 60_000);
+        assert_eq!(joiner.catch_up(5, start), Some(BarrierEvent::CaughtUp { from: 0, to: 5 }));
+        assert_eq!(joiner.catch_up(3, start), None);
+    }
+}
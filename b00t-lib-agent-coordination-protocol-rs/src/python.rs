@@ -234,6 +234,7 @@ impl PyACPMessage {
             "status" => MessageType::Status,
             "propose" => MessageType::Propose,
             "step" => MessageType::Step,
+            "coordination" => MessageType::Coordination,
             _ => return Err(pyo3::exceptions::PyValueError::new_err("Invalid message type")),
         };
 
@@ -243,6 +244,7 @@ impl PyACPMessage {
             MessageType::Status => ACPMessage::status(agent_id, step, json_payload),
             MessageType::Propose => ACPMessage::propose(agent_id, step, json_payload),
             MessageType::Step => ACPMessage::step_complete(agent_id, step),
+            MessageType::Coordination => ACPMessage::coordination(agent_id, step, json_payload),
         };
 
         Ok(Self { inner })
@@ -1,13 +1,21 @@
 //! JWT Security and Namespace Enforcement for ACP
 //! 
 //! Integrates with b00t-website JWT provisioning to enforce namespace isolation
-//! based on GitHub user identity.
+//! based on GitHub user identity. `EnforcedTransport` applies the claims to every
+//! publish and subscription; `LocalKeyPair` issues tokens without the website.
 
 use anyhow::{Result, Context};
+use crate::signing::{MessageVerifier, Verification};
+use crate::transport::{MessageSubscriber, Transport};
+use crate::{ACPError, ACPMessage};
+use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use chrono::{DateTime, Utc};
-use jsonwebtoken::{decode, Algorithm, DecodingKey, Validation};
+use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use ring::signature::{Ed25519KeyPair, KeyPair};
+use tokio::sync::mpsc;
+use tracing::warn;
 
 /// JWT Claims structure matching b00t-website NATS provisioner
 #[derive(Debug, Serialize, Deserialize)]
@@ -74,6 +82,8 @@ pub struct SubjectPermissions {
 /// Security context for ACP operations
 #[derive(Debug, Clone)]
 pub struct AcpSecurityContext {
+    /// JWT subject: user.{hive}.{role}.{pid} or acp.{hive}.{role}
+    pub subject: String,
     /// GitHub user ID (hive identifier) extracted from JWT
     pub hive: String,
     /// Hive's allowed namespace (account.{hive}.{role})
@@ -90,16 +100,99 @@ pub struct AcpSecurityContext {
     pub subscribe_subjects: Vec<String>,
 }
 
+impl AcpSecurityContext {
+    /// May a message claiming `agent_id` come from this identity?
+    pub fn owns_agent_id(&self, agent_id: &str) -> bool {
+        signer_owns_agent_id(&self.subject, agent_id)
+    }
+}
+
+/// Hive of a JWT subject (`user.{hive}.{role}.{pid}` or `acp.{hive}.{role}`)
+fn signer_hive(signer: &str) -> Option<&str> {
+    signer.split('.').nth(1)
+}
+
+/// Agent ids carry the signer's pid as their last segment (`claude.124435` for
+/// `user.{hive}.{role}.124435`); hive tokens speak for every agent in the hive
+fn signer_owns_agent_id(signer: &str, agent_id: &str) -> bool {
+    let parts: Vec<&str> = signer.split('.').collect();
+    match parts.as_slice() {
+        ["acp", _, _] => true,
+        ["user", _, _, pid] => agent_id == *pid || agent_id.ends_with(&format!(".{}", pid)),
+        _ => false,
+    }
+}
+
+/// Key that verifies ACP JWTs
+#[derive(Debug, Clone)]
+pub enum JwtKey {
+    /// HS256 shared secret (what b00t-website derives from the operator JWT)
+    Secret(String),
+    /// EdDSA public key, raw 32 bytes (see `LocalKeyPair`)
+    Ed25519(Vec<u8>),
+}
+
+impl JwtKey {
+    /// `B00T_HIVE_JWT_SECRET` (HS256) or `B00T_HIVE_JWT_PUBLIC_KEY` (hex Ed25519)
+    pub fn from_env() -> Option<Self> {
+        if let Ok(secret) = std::env::var("B00T_HIVE_JWT_SECRET") {
+            return Some(Self::Secret(secret));
+        }
+        let public_key = std::env::var("B00T_HIVE_JWT_PUBLIC_KEY").ok()?;
+        match hex::decode(public_key.trim()) {
+            Ok(bytes) => Some(Self::Ed25519(bytes)),
+            Err(e) => {
+                warn!("Ignoring B00T_HIVE_JWT_PUBLIC_KEY: {}", e);
+                None
+            }
+        }
+    }
+}
+
 /// JWT validator for ACP operations
 pub struct AcpJwtValidator {
-    /// Signing secret derived from operator JWT (matches b00t-website)
-    signing_secret: String,
+    decoding_key: DecodingKey,
+    algorithm: Algorithm,
+    /// Only for tokens whose key we don't hold; expiry and claims are still checked
+    verify_signature: bool,
 }
 
 impl AcpJwtValidator {
     /// Create new JWT validator with signing secret
     pub fn new(signing_secret: String) -> Self {
-        Self { signing_secret }
+        Self {
+            decoding_key: DecodingKey::from_secret(signing_secret.as_bytes()),
+            algorithm: Algorithm::HS256,
+            verify_signature: true,
+        }
+    }
+
+    /// Validator for EdDSA tokens signed by the holder of this public key
+    pub fn from_ed25519_public_key(public_key: &[u8]) -> Self {
+        Self {
+            decoding_key: DecodingKey::from_ed_der(public_key),
+            algorithm: Algorithm::EdDSA,
+            verify_signature: true,
+        }
+    }
+
+    pub fn from_key(key: &JwtKey) -> Self {
+        match key {
+            JwtKey::Secret(secret) => Self::new(secret.clone()),
+            JwtKey::Ed25519(public_key) => Self::from_ed25519_public_key(public_key),
+        }
+    }
+
+    /// Read claims without checking the signature
+    ///
+    /// 🦨 For agents that only hold their own token: the NATS server authenticates it,
+    /// this just lets the agent apply the same permissions locally.
+    pub fn unverified() -> Self {
+        Self {
+            decoding_key: DecodingKey::from_secret(&[]),
+            algorithm: Algorithm::HS256,
+            verify_signature: false,
+        }
     }
 
     /// Create validator by deriving secret from operator JWT (matches b00t-website logic)
@@ -111,13 +204,15 @@ impl AcpJwtValidator {
     /// Validate JWT and extract security context
     pub fn validate_jwt(&self, jwt_token: &str) -> Result<AcpSecurityContext> {
         // Set up JWT validation parameters
-        let mut validation = Validation::new(Algorithm::HS256);
+        let mut validation = Validation::new(self.algorithm);
         validation.validate_exp = true;
         validation.validate_aud = false; // We'll validate audience manually
+        if !self.verify_signature {
+            validation.insecure_disable_signature_validation();
+        }
 
         // Decode and validate JWT
-        let decoding_key = DecodingKey::from_secret(self.signing_secret.as_bytes());
-        let token_data = decode::<AcpJwtClaims>(jwt_token, &decoding_key, &validation)
+        let token_data = decode::<AcpJwtClaims>(jwt_token, &self.decoding_key, &validation)
             .context("Failed to decode JWT")?;
 
         let claims = token_data.claims;
@@ -156,6 +251,7 @@ impl AcpJwtValidator {
         Self::validate_namespace_permissions(&namespace, &claims.nats.permissions)?;
 
         Ok(AcpSecurityContext {
+            subject: claims.sub,
             hive,
             namespace,
             role,
@@ -208,6 +304,79 @@ impl AcpJwtValidator {
     }
 }
 
+/// Ed25519 key pair that issues ACP JWTs locally (tests, air-gapped hives)
+pub struct LocalKeyPair {
+    pkcs8: Vec<u8>,
+    key_pair: Ed25519KeyPair,
+}
+
+impl LocalKeyPair {
+    pub fn generate() -> Result<Self> {
+        let rng = ring::rand::SystemRandom::new();
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&rng)
+            .map_err(|_| anyhow::anyhow!("Failed to generate Ed25519 key pair"))?;
+        Self::from_pkcs8(pkcs8.as_ref())
+    }
+
+    /// Load a key pair saved with `pkcs8()`
+    pub fn from_pkcs8(pkcs8: &[u8]) -> Result<Self> {
+        let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8)
+            .map_err(|e| anyhow::anyhow!("Invalid Ed25519 PKCS#8 key: {}", e))?;
+        Ok(Self { pkcs8: pkcs8.to_vec(), key_pair })
+    }
+
+    pub fn pkcs8(&self) -> &[u8] {
+        &self.pkcs8
+    }
+
+    /// Raw 32-byte public key, for `JwtKey::Ed25519` / `B00T_HIVE_JWT_PUBLIC_KEY` (hex)
+    pub fn public_key(&self) -> &[u8] {
+        self.key_pair.public_key().as_ref()
+    }
+
+    pub fn validator(&self) -> AcpJwtValidator {
+        AcpJwtValidator::from_ed25519_public_key(self.public_key())
+    }
+
+    /// Sign arbitrary claims
+    pub fn issue(&self, claims: &AcpJwtClaims) -> Result<String> {
+        encode(&Header::new(Algorithm::EdDSA), claims, &EncodingKey::from_ed_der(&self.pkcs8))
+            .context("Failed to sign JWT")
+    }
+
+    /// Token for `user.{hive}.{role}.{pid}`, allowed everything under `account.{hive}.{role}`
+    pub fn issue_agent_jwt(&self, hive: &str, role: &str, pid: &str, ttl: std::time::Duration) -> Result<String> {
+        let now = Utc::now().timestamp().max(0) as u64;
+        let namespace = format!("account.{}.{}", hive, role);
+        let claims = AcpJwtClaims {
+            sub: format!("user.{}.{}.{}", hive, role, pid),
+            aud: format!("github.{}", hive),
+            iat: now,
+            exp: now + ttl.as_secs(),
+            nats: NatsPermissions {
+                user_type: "user".to_string(),
+                version: 2,
+                subs: -1,
+                data: -1,
+                payload: -1,
+                connect_only: false,
+                permissions: SubjectPermissions {
+                    publish: vec![format!("{}.>", namespace)],
+                    subscribe: vec![format!("{}.>", namespace)],
+                },
+            },
+            acp: Some(AcpPermissions {
+                token_type: "user".to_string(),
+                namespace,
+                role: role.to_string(),
+                permissions: vec!["publish".to_string(), "subscribe".to_string()],
+                issued_by: "local".to_string(),
+            }),
+        };
+        self.issue(&claims)
+    }
+}
+
 /// Namespace enforcement for ACP hive operations
 pub struct NamespaceEnforcer {
     security_context: AcpSecurityContext,
@@ -240,6 +409,11 @@ impl NamespaceEnforcer {
 
     /// Validate that an ACP subject is allowed for this hive
     pub fn validate_subject_access(&self, subject: &str, operation: SubjectOperation) -> Result<()> {
+        // 🤓 Request/reply inboxes are per-request and always allowed, as NATS does for responses
+        if subject.starts_with("_INBOX.") {
+            return Ok(());
+        }
+
         let allowed_subjects = match operation {
             SubjectOperation::Publish => &self.security_context.publish_subjects,
             SubjectOperation::Subscribe => &self.security_context.subscribe_subjects,
@@ -296,6 +470,137 @@ impl NamespaceEnforcer {
     }
 }
 
+/// Message rejected by `EnforcedTransport` on the way in
+#[derive(Debug, Clone)]
+pub struct QuarantinedMessage {
+    /// Subscription pattern it arrived on
+    pub pattern: String,
+    pub message: ACPMessage,
+    pub reason: String,
+}
+
+/// How many rejected messages `EnforcedTransport` keeps for inspection
+const QUARANTINE_LIMIT: usize = 100;
+
+/// Transport that applies a hive JWT's claims to everything passing through
+///
+/// Publishes and subscriptions outside the token's permissions fail with `PermissionDenied`,
+/// as do outbound messages whose `agent_id` the token doesn't own. Outbound messages are
+/// stamped with the token's subject as `signer`. Inbound messages must carry a signature the
+/// verifier accepts; a `signer` they name must also be in our hive and own their `agent_id`.
+pub struct EnforcedTransport {
+    inner: Arc<dyn Transport>,
+    enforcer: NamespaceEnforcer,
+    /// Keys inbound signatures must verify against
+    verifier: MessageVerifier,
+    quarantine: Arc<Mutex<Vec<QuarantinedMessage>>>,
+}
+
+impl EnforcedTransport {
+    pub fn new(inner: Arc<dyn Transport>, security_context: AcpSecurityContext) -> Self {
+        Self {
+            inner,
+            enforcer: NamespaceEnforcer::new(security_context),
+            verifier: MessageVerifier::new(),
+            quarantine: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Check inbound signatures against `verifier`; without keys every inbound message is quarantined
+    pub fn with_verifier(mut self, verifier: MessageVerifier) -> Self {
+        self.verifier = verifier;
+        self
+    }
+
+    pub fn security_context(&self) -> &AcpSecurityContext {
+        self.enforcer.security_context()
+    }
+
+    /// Most recent inbound messages that failed the signer check, oldest first
+    pub fn quarantined(&self) -> Vec<QuarantinedMessage> {
+        self.quarantine.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Why an inbound message can't be trusted, if it can't
+    ///
+    /// 🦨 `signer` is written by the sender: it only counts once a trusted key (one that
+    /// `signs_for` the `agent_id`, or the hive's HMAC secret) has signed over it
+    fn inbound_violation(hive: &str, verifier: &MessageVerifier, message: &ACPMessage) -> Option<String> {
+        match verifier.verify(message) {
+            Verification::Verified => {}
+            Verification::Unsigned => return Some(match &message.signer {
+                Some(signer) => format!("signer '{}' is not backed by a signature", signer),
+                None => "message has no signature".to_string(),
+            }),
+            Verification::Invalid(reason) => return Some(reason),
+        }
+        // A signature without a JWT signer (e.g. b00t-c0re-lib's AgentCoordinator) vouches on its own
+        let Some(signer) = message.signer.as_deref() else {
+            return None;
+        };
+        if signer_hive(signer) != Some(hive) {
+            return Some(format!("signer '{}' is not in hive '{}'", signer, hive));
+        }
+        (!signer_owns_agent_id(signer, &message.agent_id))
+            .then(|| format!("agent_id '{}' does not belong to signer '{}'", message.agent_id, signer))
+    }
+}
+
+#[async_trait]
+impl Transport for EnforcedTransport {
+    async fn publish(&self, subject: &str, message: &ACPMessage) -> crate::Result<()> {
+        self.enforcer.validate_subject_access(subject, SubjectOperation::Publish)
+            .map_err(|e| ACPError::permission_denied(e.to_string()))?;
+        let context = self.enforcer.security_context();
+        if !context.owns_agent_id(&message.agent_id) {
+            return Err(ACPError::permission_denied(format!(
+                "'{}' may not send as agent '{}'", context.subject, message.agent_id
+            )));
+        }
+
+        let mut message = message.clone();
+        message.signer = Some(context.subject.clone());
+        self.inner.publish(subject, &message).await
+    }
+
+    async fn subscribe(&self, pattern: &str) -> crate::Result<MessageSubscriber> {
+        self.enforcer.validate_subject_access(pattern, SubjectOperation::Subscribe)
+            .map_err(|e| ACPError::permission_denied(e.to_string()))?;
+        let mut inner = self.inner.subscribe(pattern).await?;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        let hive = self.enforcer.security_context().hive.clone();
+        let verifier = self.verifier.clone();
+        let quarantine = Arc::clone(&self.quarantine);
+        let subscribed = pattern.to_string();
+        tokio::spawn(async move {
+            while let Ok(Some(message)) = inner.next_message_blocking().await {
+                if let Some(reason) = Self::inbound_violation(&hive, &verifier, &message) {
+                    warn!("Quarantined message from '{}' on {}: {}", message.agent_id, subscribed, reason);
+                    let mut quarantine = quarantine.lock().unwrap_or_else(|e| e.into_inner());
+                    if quarantine.len() >= QUARANTINE_LIMIT {
+                        quarantine.remove(0);
+                    }
+                    quarantine.push(QuarantinedMessage { pattern: subscribed.clone(), message, reason });
+                    continue;
+                }
+                if tx.send(message).is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(MessageSubscriber::new(pattern, rx))
+    }
+
+    async fn is_connected(&self) -> bool {
+        self.inner.is_connected().await
+    }
+
+    async fn flush(&self) -> crate::Result<()> {
+        self.inner.flush().await
+    }
+}
+
 /// Type of subject operation for permission checking
 #[derive(Debug, Clone, Copy)]
 pub enum SubjectOperation {
@@ -341,6 +646,83 @@ pub async fn fetch_jwt_from_website(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::InMemoryTransport;
+    use std::time::Duration;
+
+    #[test]
+    fn test_local_key_pair_issues_verifiable_jwts() {
+        let issuer = LocalKeyPair::generate().unwrap();
+        let jwt = issuer.issue_agent_jwt("alice", "worker", "4242", Duration::from_secs(600)).unwrap();
+
+        let context = issuer.validator().validate_jwt(&jwt).unwrap();
+        assert_eq!(context.namespace, "account.alice.worker");
+        assert_eq!(context.pid, "4242");
+        assert!(context.owns_agent_id("claude.4242"));
+        assert!(!context.owns_agent_id("claude.1"));
+
+        let restored = LocalKeyPair::from_pkcs8(issuer.pkcs8()).unwrap();
+        assert_eq!(restored.public_key(), issuer.public_key());
+
+        // Another key can't vouch for it; an unverified read still sees the claims
+        let stranger = LocalKeyPair::generate().unwrap();
+        assert!(stranger.validator().validate_jwt(&jwt).is_err());
+        assert_eq!(AcpJwtValidator::unverified().validate_jwt(&jwt).unwrap().hive, "alice");
+    }
+
+    #[tokio::test]
+    async fn test_enforced_transport_applies_claims() {
+        let issuer = LocalKeyPair::generate().unwrap();
+        let context = |pid: &str| {
+            let jwt = issuer.issue_agent_jwt("alice", "worker", pid, Duration::from_secs(600)).unwrap();
+            issuer.validator().validate_jwt(&jwt).unwrap()
+        };
+        let bus: Arc<dyn Transport> = Arc::new(InMemoryTransport::new());
+        let key = crate::SigningKey::ed25519("claude.1", LocalKeyPair::generate().unwrap().pkcs8()).unwrap();
+        let verifier = MessageVerifier::new().with_key("claude.1", key.verifying_key());
+        let alice = EnforcedTransport::new(Arc::clone(&bus), context("1")).with_verifier(verifier);
+        let signed = |signer: Option<&str>| {
+            let mut message = ACPMessage::status("claude.1".to_string(), 0, serde_json::json!({}));
+            message.signer = signer.map(str::to_string);
+            message.sign(&key).unwrap();
+            message
+        };
+        let message = signed(Some("user.alice.worker.1"));
+
+        let outside = alice.publish("account.bob.worker.acp", &message).await;
+        assert!(matches!(outside, Err(ACPError::PermissionDenied { .. })));
+        assert!(alice.subscribe("account.bob.>").await.is_err());
+        let spoofed = ACPMessage::status("claude.2".to_string(), 0, serde_json::json!({}));
+        assert!(alice.publish("account.alice.worker.acp", &spoofed).await.is_err());
+
+        let mut inbox = alice.subscribe("account.alice.worker.>").await.unwrap();
+        alice.publish("account.alice.worker.acp", &message).await.unwrap();
+        let received = inbox.next_message(1000).await.unwrap().unwrap();
+        assert_eq!(received.signer.as_deref(), Some("user.alice.worker.1"));
+
+        // A rogue process on the raw bus claims to be claude.1 but signs as pid 2
+        let mut forged = message.clone();
+        forged.signer = Some("user.alice.worker.2".to_string());
+        bus.publish("account.alice.worker.acp", &forged).await.unwrap();
+        assert!(matches!(inbox.next_message(100).await, Err(ACPError::ReceiveTimeout)));
+        assert_eq!(alice.quarantined().len(), 1);
+
+        // Nor can it drop the signer and signature and hope to pass as a peer without a JWT
+        let mut unsigned = message.clone();
+        unsigned.signer = None;
+        unsigned.signature = None;
+        bus.publish("account.alice.worker.acp", &unsigned).await.unwrap();
+        assert!(matches!(inbox.next_message(100).await, Err(ACPError::ReceiveTimeout)));
+        assert_eq!(alice.quarantined().len(), 2);
+
+        // A trusted signature still can't vouch for a signer from another hive...
+        bus.publish("account.alice.worker.acp", &signed(Some("user.bob.worker.1"))).await.unwrap();
+        assert!(matches!(inbox.next_message(100).await, Err(ACPError::ReceiveTimeout)));
+        assert_eq!(alice.quarantined().len(), 3);
+
+        // ...but vouches alone for a peer without a JWT, such as an AgentCoordinator
+        bus.publish("account.alice.worker.acp", &signed(None)).await.unwrap();
+        assert_eq!(inbox.next_message(1000).await.unwrap().unwrap().signer, None);
+    }
 
     #[test]
     fn test_subject_pattern_matching() {
@@ -0,0 +1,283 @@
+//! Detached message signatures
+//!
+//! `agent_id` is just a string; anyone on the bus can claim it. A signed message carries a
+//! `MessageSignature` over its canonical form (sorted-key JSON without the signature), made
+//! with an Ed25519 key pair or an HMAC-SHA256 secret shared per namespace. Receivers check it
+//! against a `MessageVerifier` and apply an `UnsignedPolicy` to messages that carry none.
+//!
+//! An Ed25519 key only vouches for the agent it's named after (plus any ids bound to it with
+//! `allow`); an HMAC secret vouches for anyone in its namespace, since every holder can sign.
+
+use crate::{ACPError, ACPMessage, Result};
+use ring::hmac;
+use ring::signature::{Ed25519KeyPair, KeyPair, UnparsedPublicKey, ED25519};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+/// Signature attached to an `ACPMessage`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MessageSignature {
+    /// "ed25519" or "hmac-sha256"
+    pub alg: String,
+    /// Which key signed: an agent's key name, or the namespace for HMAC
+    pub key_id: String,
+    /// Hex-encoded signature
+    pub value: String,
+}
+
+const ED25519_ALG: &str = "ed25519";
+const HMAC_ALG: &str = "hmac-sha256";
+
+/// Key that signs outbound messages
+#[derive(Debug, Clone)]
+pub enum SigningKey {
+    Ed25519 { key_id: String, key_pair: Arc<Ed25519KeyPair> },
+    Hmac { key_id: String, key: hmac::Key },
+}
+
+impl SigningKey {
+    /// Ed25519 signing from a PKCS#8 key (e.g. `LocalKeyPair::pkcs8()`)
+    pub fn ed25519(key_id: impl Into<String>, pkcs8: &[u8]) -> Result<Self> {
+        let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8)
+            .map_err(|e| ACPError::invalid_config(format!("Invalid Ed25519 PKCS#8 key: {}", e)))?;
+        Ok(Self::Ed25519 { key_id: key_id.into(), key_pair: Arc::new(key_pair) })
+    }
+
+    /// HMAC-SHA256 with a secret shared by everyone in `namespace`
+    pub fn hmac(namespace: impl Into<String>, secret: &[u8]) -> Self {
+        Self::Hmac { key_id: namespace.into(), key: hmac::Key::new(hmac::HMAC_SHA256, secret) }
+    }
+
+    pub fn key_id(&self) -> &str {
+        match self {
+            Self::Ed25519 { key_id, .. } | Self::Hmac { key_id, .. } => key_id,
+        }
+    }
+
+    /// What a receiver needs to check this key's signatures
+    pub fn verifying_key(&self) -> VerifyingKey {
+        match self {
+            Self::Ed25519 { key_pair, .. } => VerifyingKey::Ed25519(key_pair.public_key().as_ref().to_vec()),
+            Self::Hmac { key, .. } => VerifyingKey::Hmac(key.clone()),
+        }
+    }
+
+    fn sign(&self, bytes: &[u8]) -> MessageSignature {
+        let (alg, value) = match self {
+            Self::Ed25519 { key_pair, .. } => (ED25519_ALG, hex::encode(key_pair.sign(bytes))),
+            Self::Hmac { key, .. } => (HMAC_ALG, hex::encode(hmac::sign(key, bytes))),
+        };
+        MessageSignature { alg: alg.to_string(), key_id: self.key_id().to_string(), value }
+    }
+}
+
+/// Key that checks signatures made by a `SigningKey`
+#[derive(Debug, Clone)]
+pub enum VerifyingKey {
+    /// Raw 32-byte public key
+    Ed25519(Vec<u8>),
+    Hmac(hmac::Key),
+}
+
+/// Outcome of checking a message's signature
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verification {
+    Verified,
+    Unsigned,
+    Invalid(String),
+}
+
+/// Trusted keys by `key_id`
+#[derive(Debug, Clone, Default)]
+pub struct MessageVerifier {
+    keys: HashMap<String, VerifyingKey>,
+    /// Agent ids an Ed25519 key may sign for besides its own `key_id`
+    agents: HashMap<String, HashSet<String>>,
+}
+
+impl MessageVerifier {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_key(mut self, key_id: impl Into<String>, key: VerifyingKey) -> Self {
+        self.keys.insert(key_id.into(), key);
+        self
+    }
+
+    pub fn trust(&mut self, key_id: impl Into<String>, key: VerifyingKey) {
+        self.keys.insert(key_id.into(), key);
+    }
+
+    /// Let Ed25519 key `key_id` sign for `agent_id` too
+    pub fn allow(&mut self, key_id: impl Into<String>, agent_id: impl Into<String>) {
+        self.agents.entry(key_id.into()).or_default().insert(agent_id.into());
+    }
+
+    pub fn with_agent(mut self, key_id: impl Into<String>, agent_id: impl Into<String>) -> Self {
+        self.allow(key_id, agent_id);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    fn signs_for(&self, key_id: &str, agent_id: &str) -> bool {
+        key_id == agent_id || self.agents.get(key_id).is_some_and(|agents| agents.contains(agent_id))
+    }
+
+    pub fn verify(&self, message: &ACPMessage) -> Verification {
+        let Some(signature) = &message.signature else {
+            return Verification::Unsigned;
+        };
+        let Some(key) = self.keys.get(&signature.key_id) else {
+            return Verification::Invalid(format!("unknown key '{}'", signature.key_id));
+        };
+        let Ok(value) = hex::decode(&signature.value) else {
+            return Verification::Invalid("signature is not hex".to_string());
+        };
+        let bytes = match message.canonical_bytes() {
+            Ok(bytes) => bytes,
+            Err(e) => return Verification::Invalid(e.to_string()),
+        };
+
+        let valid = match (key, signature.alg.as_str()) {
+            (VerifyingKey::Ed25519(_), ED25519_ALG) if !self.signs_for(&signature.key_id, &message.agent_id) => {
+                return Verification::Invalid(format!("key '{}' does not sign for agent '{}'", signature.key_id, message.agent_id));
+            }
+            (VerifyingKey::Ed25519(public_key), ED25519_ALG) => {
+                UnparsedPublicKey::new(&ED25519, public_key).verify(&bytes, &value).is_ok()
+            }
+            (VerifyingKey::Hmac(key), HMAC_ALG) => hmac::verify(key, &bytes, &value).is_ok(),
+            _ => return Verification::Invalid(format!("key '{}' does not sign with {}", signature.key_id, signature.alg)),
+        };
+        if valid {
+            Verification::Verified
+        } else {
+            Verification::Invalid(format!("bad {} signature from key '{}'", signature.alg, signature.key_id))
+        }
+    }
+}
+
+/// What to do with messages that carry no signature (forged ones are always dropped)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnsignedPolicy {
+    /// Deliver them
+    Accept,
+    /// Deliver them, but warn and record them as flagged
+    #[default]
+    Flag,
+    /// Discard them
+    Drop,
+}
+
+impl std::str::FromStr for UnsignedPolicy {
+    type Err = ACPError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "accept" => Ok(Self::Accept),
+            "flag" => Ok(Self::Flag),
+            "drop" => Ok(Self::Drop),
+            _ => Err(ACPError::invalid_config(format!("Unknown unsigned-message policy '{}' (accept, flag, drop)", s))),
+        }
+    }
+}
+
+/// JSON with object keys sorted at every level
+fn canonical_json(value: &Value, out: &mut String) {
+    match value {
+        Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            out.push('{');
+            for (i, key) in keys.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                canonical_json(&map[key], out);
+            }
+            out.push('}');
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                canonical_json(item, out);
+            }
+            out.push(']');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
+
+impl ACPMessage {
+    /// Bytes a signature covers: the message without its signature, keys sorted
+    pub fn canonical_bytes(&self) -> Result<Vec<u8>> {
+        let mut value = serde_json::to_value(self)?;
+        if let Value::Object(map) = &mut value {
+            map.remove("signature");
+        }
+        let mut out = String::new();
+        canonical_json(&value, &mut out);
+        Ok(out.into_bytes())
+    }
+
+    /// Attach a signature over the message as it stands; changing it afterwards invalidates it
+    pub fn sign(&mut self, key: &SigningKey) -> Result<()> {
+        self.signature = None;
+        let bytes = self.canonical_bytes()?;
+        self.signature = Some(key.sign(&bytes));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ed25519_and_hmac_signatures() {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&ring::rand::SystemRandom::new()).unwrap();
+        let agent_key = SigningKey::ed25519("claude.1", pkcs8.as_ref()).unwrap();
+        let hive_key = SigningKey::hmac("account.alice.worker", b"hive secret");
+        let verifier = MessageVerifier::new()
+            .with_key("claude.1", agent_key.verifying_key())
+            .with_key("account.alice.worker", hive_key.verifying_key());
+
+        for key in [&agent_key, &hive_key] {
+            let mut message = ACPMessage::propose("claude.1".to_string(), 3, serde_json::json!({"b": 1, "a": [1, {"z": 2, "y": 1}]}));
+            assert_eq!(verifier.verify(&message), Verification::Unsigned);
+            message.sign(key).unwrap();
+
+            // Survives the wire, whatever order the keys come back in
+            let wire: ACPMessage = serde_json::from_str(&serde_json::to_string(&message).unwrap()).unwrap();
+            assert_eq!(verifier.verify(&wire), Verification::Verified);
+
+            let mut forged = wire.clone();
+            forged.agent_id = "claude.2".to_string();
+            assert!(matches!(verifier.verify(&forged), Verification::Invalid(_)));
+        }
+
+        let mut stranger = ACPMessage::status("x".to_string(), 0, serde_json::json!({}));
+        stranger.sign(&SigningKey::hmac("account.bob.worker", b"other")).unwrap();
+        assert!(matches!(verifier.verify(&stranger), Verification::Invalid(reason) if reason.contains("unknown key")));
+
+        // claude.1's key can't vouch for claude.2, even with a genuine signature, until bound to it
+        let mut borrowed = ACPMessage::status("claude.2".to_string(), 0, serde_json::json!({}));
+        borrowed.sign(&agent_key).unwrap();
+        assert!(matches!(verifier.verify(&borrowed), Verification::Invalid(reason) if reason.contains("does not sign for")));
+        assert_eq!(verifier.clone().with_agent("claude.1", "claude.2").verify(&borrowed), Verification::Verified);
+        // The namespace secret speaks for everyone who holds it
+        borrowed.sign(&hive_key).unwrap();
+        assert_eq!(verifier.verify(&borrowed), Verification::Verified);
+        assert_eq!("drop".parse::<UnsignedPolicy>().unwrap(), UnsignedPolicy::Drop);
+    }
+}
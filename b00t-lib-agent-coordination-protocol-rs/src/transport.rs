@@ -1,9 +1,171 @@
-//! Simplified NATS transport stub for ACP messages
+//! Pluggable transports for ACP messages
+//!
+//! `Transport` is what an `Agent` talks through: publish, subscribe with NATS-style
+//! wildcards (`*` matches one token, `>` the rest) and request/reply. Backends:
+//!
+//! - `NatsTransport`: async-nats
+//! - `RedisTransport`: Redis pub/sub (`PSUBSCRIBE`)
+//! - `InMemoryTransport`: in-process, for tests and demos with no services
+//!
+//! `transport_from_url` picks one from a URL scheme, so every stack (ACP agents,
+//! task/vote coordination, CLI, MCP) can be pointed at the same broker.
 
 use crate::{ACPMessage, ACPError, Result};
-use serde_json;
+use async_trait::async_trait;
+use futures::StreamExt;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
-use tracing::{debug, info};
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+/// Message transport used by `Agent`
+#[async_trait]
+pub trait Transport: Send + Sync {
+    /// Publish a message on `subject`
+    async fn publish(&self, subject: &str, message: &ACPMessage) -> Result<()>;
+
+    /// Subscribe to a subject pattern (`*` and `>` wildcards)
+    async fn subscribe(&self, pattern: &str) -> Result<MessageSubscriber>;
+
+    /// Check if connected
+    async fn is_connected(&self) -> bool;
+
+    /// Flush pending messages
+    async fn flush(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Publish on the message's own subject (`acp.{step}.{agent_id}.{type}`)
+    async fn publish_message(&self, message: &ACPMessage) -> Result<()> {
+        self.publish(&message.subject(), message).await
+    }
+
+    /// Subscribe to all messages for a specific step
+    async fn subscribe_step(&self, step: u64) -> Result<MessageSubscriber> {
+        self.subscribe(&ACPMessage::step_wildcard(step)).await
+    }
+
+    /// Subscribe to all messages from a specific agent
+    async fn subscribe_agent(&self, agent_id: &str) -> Result<MessageSubscriber> {
+        self.subscribe(&ACPMessage::agent_wildcard(agent_id)).await
+    }
+
+    /// Request-response: the reply arrives on `_INBOX.{correlation_id}` (see `reply`)
+    async fn request(&self, subject: &str, message: &ACPMessage, timeout_ms: u64) -> Result<ACPMessage> {
+        let mut message = message.clone();
+        let correlation_id = *message.correlation_id.get_or_insert_with(Uuid::new_v4);
+
+        // 🤓 Subscribe before publishing so a fast responder can't be missed
+        let mut inbox = self.subscribe(&ACPMessage::reply_subject(correlation_id)).await?;
+        self.publish(subject, &message).await?;
+
+        match inbox.next_message(timeout_ms).await {
+            Ok(Some(reply)) => Ok(reply),
+            Ok(None) => Err(ACPError::NotConnected),
+            Err(ACPError::ReceiveTimeout) => Err(ACPError::RequestTimeout),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Answer a `request`
+    async fn reply(&self, request: &ACPMessage, mut response: ACPMessage) -> Result<()> {
+        let correlation_id = request.correlation_id
+            .ok_or_else(|| ACPError::invalid_message("request has no correlation_id to reply to"))?;
+        response.correlation_id = Some(correlation_id);
+        self.publish(&ACPMessage::reply_subject(correlation_id), &response).await
+    }
+}
+
+/// Does `subject` match a NATS-style `pattern`?
+pub fn subject_matches(pattern: &str, subject: &str) -> bool {
+    let mut pattern = pattern.split('.');
+    let mut subject = subject.split('.');
+    loop {
+        match (pattern.next(), subject.next()) {
+            (Some(">"), Some(_)) => return true,
+            (Some("*"), Some(_)) => continue,
+            (Some(p), Some(s)) if p == s => continue,
+            (None, None) => return true,
+            _ => return false,
+        }
+    }
+}
+
+/// Messages arriving on a subscription
+pub struct MessageSubscriber {
+    pattern: String,
+    receiver: mpsc::UnboundedReceiver<ACPMessage>,
+}
+
+impl MessageSubscriber {
+    /// Wrap a channel fed by a transport backend
+    pub fn new(pattern: &str, receiver: mpsc::UnboundedReceiver<ACPMessage>) -> Self {
+        Self { pattern: pattern.to_string(), receiver }
+    }
+
+    /// Pattern this subscription was made with
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Receive next message; `ReceiveTimeout` if none arrives in time, `None` once closed
+    pub async fn next_message(&mut self, timeout_ms: u64) -> Result<Option<ACPMessage>> {
+        tokio::time::timeout(Duration::from_millis(timeout_ms), self.receiver.recv())
+            .await
+            .map_err(|_| ACPError::ReceiveTimeout)
+    }
+
+    /// Receive next message (blocking); `None` once closed
+    pub async fn next_message_blocking(&mut self) -> Result<Option<ACPMessage>> {
+        Ok(self.receiver.recv().await)
+    }
+
+    /// Unsubscribe from subject
+    pub async fn unsubscribe(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+fn decode(payload: &[u8]) -> Option<ACPMessage> {
+    match serde_json::from_slice::<ACPMessage>(payload) {
+        Ok(message) if !message.is_supported_version() => {
+            debug!("Skipping ACP v{} message from {}", message.version, message.agent_id);
+            None
+        }
+        Ok(message) => Some(message),
+        Err(e) => {
+            debug!("Skipping payload that isn't an ACP message: {}", e);
+            None
+        }
+    }
+}
+
+/// Transport every b00t stack uses when none is given: B00T_TRANSPORT, else NATS_URL, else local Redis
+///
+/// 🦨 Never a shared public broker: the local fallback is the Redis the agent and task
+/// registries already live in, so discovery and messaging see the same hive
+pub fn default_transport_url() -> String {
+    ["B00T_TRANSPORT", "NATS_URL"].iter()
+        .find_map(|var| std::env::var(var).ok().filter(|url| !url.is_empty()))
+        .unwrap_or_else(|| "redis://localhost:6379".to_string())
+}
+
+/// Build a transport from a URL: `nats://`, `redis://` (or `rediss://`) or `memory://`
+///
+/// 🦨 Each `memory://` call is its own bus; share one `InMemoryTransport` to talk in-process
+pub async fn transport_from_url(url: &str) -> Result<Arc<dyn Transport>> {
+    let scheme = url.split("://").next().unwrap_or_default();
+    match scheme {
+        "nats" | "tls" => {
+            let config = NatsConfig { url: url.to_string(), ..NatsConfig::default() };
+            Ok(Arc::new(NatsTransport::new(config).await?))
+        }
+        "redis" | "rediss" => Ok(Arc::new(RedisTransport::new(url)?)),
+        "memory" => Ok(Arc::new(InMemoryTransport::new())),
+        _ => Err(ACPError::InvalidConfig(format!("Unsupported transport URL: {}", url))),
+    }
+}
 
 /// NATS transport configuration
 #[derive(Debug, Clone)]
@@ -32,106 +194,244 @@ impl Default for NatsConfig {
     }
 }
 
-/// Simplified NATS transport for ACP messages (stub implementation)
+/// NATS transport for ACP messages
 pub struct NatsTransport {
     config: NatsConfig,
-    connected: bool,
+    client: async_nats::Client,
 }
 
 impl NatsTransport {
-    /// Create new NATS transport
+    /// Connect to the NATS server
     pub async fn new(config: NatsConfig) -> Result<Self> {
-        info!("Creating NATS transport for: {}", config.url);
-        
-        Ok(Self { 
-            config,
-            connected: false,
-        })
+        info!("Connecting NATS transport to: {}", config.url);
+
+        // 🦨 jwt_token is the hive's ACP JWT, not NATS user credentials; it isn't sent here
+        let reconnect_delay = Duration::from_millis(config.reconnect_delay_ms);
+        let client = async_nats::ConnectOptions::new()
+            .connection_timeout(Duration::from_millis(config.timeout_ms))
+            .max_reconnects(config.max_reconnect_attempts)
+            .reconnect_delay_callback(move |_| reconnect_delay)
+            .connect(config.url.as_str())
+            .await
+            .map_err(|e| ACPError::NatsError(Box::new(e)))?;
+
+        Ok(Self { config, client })
+    }
+
+    /// Server this transport is connected to
+    pub fn url(&self) -> &str {
+        &self.config.url
+    }
+
+    /// Underlying NATS client (e.g. for JetStream)
+    pub fn client(&self) -> &async_nats::Client {
+        &self.client
+    }
+
+    /// Close connection gracefully
+    pub async fn close(self) -> Result<()> {
+        self.client.flush().await.map_err(|e| ACPError::NatsError(Box::new(e)))
     }
+}
 
-    /// Publish ACP message (stub)
-    pub async fn publish(&self, message: &ACPMessage) -> Result<()> {
-        let subject = message.subject();
+#[async_trait]
+impl Transport for NatsTransport {
+    async fn publish(&self, subject: &str, message: &ACPMessage) -> Result<()> {
         debug!("Publishing message to subject: {}", subject);
-        Ok(())
+        let payload = serde_json::to_vec(message)?;
+        self.client.publish(subject.to_string(), payload.into()).await
+            .map_err(|e| ACPError::NatsError(Box::new(e)))
     }
 
-    /// Publish message with custom subject (stub)
-    pub async fn publish_to_subject(&self, subject: &str, message: &ACPMessage) -> Result<()> {
-        debug!("Publishing message to custom subject: {}", subject);
-        Ok(())
+    async fn subscribe(&self, pattern: &str) -> Result<MessageSubscriber> {
+        debug!("Subscribing to subject: {}", pattern);
+        let mut subscriber = self.client.subscribe(pattern.to_string()).await
+            .map_err(|e| ACPError::NatsError(Box::new(e)))?;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            while let Some(msg) = subscriber.next().await {
+                if let Some(message) = decode(&msg.payload) {
+                    if tx.send(message).is_err() {
+                        break;
+                    }
+                }
+            }
+            let _ = subscriber.unsubscribe().await;
+        });
+        Ok(MessageSubscriber::new(pattern, rx))
     }
 
-    /// Subscribe to subject pattern (stub)
-    pub async fn subscribe(&self, subject: &str) -> Result<MessageSubscriber> {
-        debug!("Subscribing to subject: {}", subject);
-        Ok(MessageSubscriber::new())
+    async fn is_connected(&self) -> bool {
+        self.client.connection_state() == async_nats::connection::State::Connected
     }
 
-    /// Subscribe to all messages for a specific step
-    pub async fn subscribe_step(&self, step: u64) -> Result<MessageSubscriber> {
-        let subject = ACPMessage::step_wildcard(step);
-        self.subscribe(&subject).await
+    async fn flush(&self) -> Result<()> {
+        self.client.flush().await.map_err(|e| ACPError::NatsError(Box::new(e)))
     }
+}
 
-    /// Subscribe to all messages from a specific agent
-    pub async fn subscribe_agent(&self, agent_id: &str) -> Result<MessageSubscriber> {
-        let subject = ACPMessage::agent_wildcard(agent_id);
-        self.subscribe(&subject).await
+/// Redis pub/sub transport for ACP messages
+pub struct RedisTransport {
+    client: redis::Client,
+    connection: Arc<Mutex<Option<redis::Connection>>>,
+}
+
+impl RedisTransport {
+    /// Redis transport for e.g. `redis://localhost:6379`; connects on first use
+    pub fn new(url: &str) -> Result<Self> {
+        Ok(Self::from_client(redis::Client::open(url)?))
     }
 
-    /// Request-response pattern with timeout (stub)
-    pub async fn request(&self, subject: &str, message: &ACPMessage, _timeout_ms: u64) -> Result<ACPMessage> {
-        debug!("Sending request to subject: {}", subject);
-        
-        // Return echo response for testing
-        Ok(ACPMessage::status(
-            "echo-agent".to_string(),
-            message.step,
-            serde_json::json!({"echo": true, "original_subject": subject})
-        ))
+    /// Wrap an existing client
+    pub fn from_client(client: redis::Client) -> Self {
+        Self { client, connection: Arc::new(Mutex::new(None)) }
     }
 
-    /// Check if connected
-    pub async fn is_connected(&self) -> bool {
-        self.connected
+    /// Run `f` on the shared connection, (re)connecting if needed
+    fn with_connection<T>(
+        client: &redis::Client,
+        connection: &Mutex<Option<redis::Connection>>,
+        f: impl FnOnce(&mut redis::Connection) -> redis::RedisResult<T>,
+    ) -> redis::RedisResult<T> {
+        let mut guard = connection.lock().unwrap_or_else(|e| e.into_inner());
+        if guard.is_none() {
+            *guard = Some(client.get_connection()?);
+        }
+        let result = f(guard.as_mut().expect("connection was just set"));
+        if result.as_ref().is_err_and(|e| e.is_connection_dropped() || e.is_io_error()) {
+            *guard = None;
+        }
+        result
     }
 
-    /// Flush pending messages (stub)
-    pub async fn flush(&self) -> Result<()> {
-        Ok(())
+    /// NATS wildcards as a Redis glob; `subject_matches` narrows it back down
+    fn glob(pattern: &str) -> String {
+        pattern.chars()
+            .flat_map(|c| match c {
+                '*' | '>' => vec!['*'],
+                '?' | '[' | ']' | '\\' => vec!['\\', c],
+                c => vec![c],
+            })
+            .collect()
     }
+}
 
-    /// Close connection gracefully
-    pub async fn close(self) -> Result<()> {
+#[async_trait]
+impl Transport for RedisTransport {
+    async fn publish(&self, subject: &str, message: &ACPMessage) -> Result<()> {
+        debug!("Publishing message to channel: {}", subject);
+        let payload = serde_json::to_string(message)?;
+        let client = self.client.clone();
+        let connection = Arc::clone(&self.connection);
+        let subject = subject.to_string();
+
+        // 🤓 The redis crate is blocking; keep it off the async workers
+        tokio::task::spawn_blocking(move || {
+            Self::with_connection(&client, &connection, |conn| {
+                redis::cmd("PUBLISH").arg(&subject).arg(&payload).query::<i64>(conn)
+            })
+        })
+        .await
+        .map_err(|e| ACPError::other(e.to_string()))??;
         Ok(())
     }
-}
 
-/// Message subscriber wrapper (stub)
-pub struct MessageSubscriber;
+    async fn subscribe(&self, pattern: &str) -> Result<MessageSubscriber> {
+        debug!("Subscribing to pattern: {}", pattern);
+        let client = self.client.clone();
+        let (tx, rx) = mpsc::unbounded_channel();
+        let (ready_tx, ready_rx) = tokio::sync::oneshot::channel();
+        let nats_pattern = pattern.to_string();
+        let glob = Self::glob(pattern);
 
-impl MessageSubscriber {
-    fn new() -> Self {
-        Self
+        // 🤓 Connect and PSUBSCRIBE on the reader thread, but only return once the
+        // subscription is live, so a publish right after `subscribe` can't be missed
+        std::thread::spawn(move || {
+            let mut conn = match client.get_connection() {
+                Ok(conn) => conn,
+                Err(e) => {
+                    let _ = ready_tx.send(Err(e));
+                    return;
+                }
+            };
+            let mut pubsub = conn.as_pubsub();
+            if let Err(e) = pubsub.psubscribe(&glob)
+                .and_then(|_| pubsub.set_read_timeout(Some(Duration::from_secs(1)))) {
+                let _ = ready_tx.send(Err(e));
+                return;
+            }
+            let _ = ready_tx.send(Ok(()));
+            // Runs until the subscriber is dropped
+            while !tx.is_closed() {
+                match pubsub.get_message() {
+                    Ok(msg) => {
+                        if !subject_matches(&nats_pattern, msg.get_channel_name()) {
+                            continue;
+                        }
+                        if let Some(message) = decode(msg.get_payload_bytes()) {
+                            let _ = tx.send(message);
+                        }
+                    }
+                    Err(e) if e.is_timeout() => continue,
+                    Err(e) => {
+                        warn!("Redis subscription to '{}' ended: {}", glob, e);
+                        break;
+                    }
+                }
+            }
+        });
+        ready_rx.await.map_err(|_| ACPError::other("Redis subscription thread exited"))??;
+        Ok(MessageSubscriber::new(pattern, rx))
     }
 
-    /// Receive next message with timeout (stub)
-    pub async fn next_message(&mut self, _timeout_ms: u64) -> Result<Option<ACPMessage>> {
-        // Return None to indicate no messages (stub)
-        Ok(None)
+    async fn is_connected(&self) -> bool {
+        let client = self.client.clone();
+        let connection = Arc::clone(&self.connection);
+        tokio::task::spawn_blocking(move || {
+            Self::with_connection(&client, &connection, |conn| redis::cmd("PING").query::<String>(conn)).is_ok()
+        })
+        .await
+        .unwrap_or(false)
     }
+}
 
-    /// Receive next message (blocking) (stub)
-    pub async fn next_message_blocking(&mut self) -> Result<Option<ACPMessage>> {
-        // Return None to indicate no messages (stub)
-        Ok(None)
+/// In-process transport; clones share one bus
+#[derive(Clone, Default)]
+pub struct InMemoryTransport {
+    subscribers: Arc<Mutex<Vec<(String, mpsc::UnboundedSender<ACPMessage>)>>>,
+}
+
+impl InMemoryTransport {
+    pub fn new() -> Self {
+        Self::default()
     }
+}
 
-    /// Unsubscribe from subject (stub)
-    pub async fn unsubscribe(self) -> Result<()> {
+#[async_trait]
+impl Transport for InMemoryTransport {
+    async fn publish(&self, subject: &str, message: &ACPMessage) -> Result<()> {
+        debug!("Publishing message to subject: {}", subject);
+        let mut subscribers = self.subscribers.lock().unwrap_or_else(|e| e.into_inner());
+        subscribers.retain(|(_, tx)| !tx.is_closed());
+        for (pattern, tx) in subscribers.iter() {
+            if subject_matches(pattern, subject) {
+                let _ = tx.send(message.clone());
+            }
+        }
         Ok(())
     }
+
+    async fn subscribe(&self, pattern: &str) -> Result<MessageSubscriber> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let mut subscribers = self.subscribers.lock().unwrap_or_else(|e| e.into_inner());
+        subscribers.push((pattern.to_string(), tx));
+        Ok(MessageSubscriber::new(pattern, rx))
+    }
+
+    async fn is_connected(&self) -> bool {
+        true
+    }
 }
 
 #[cfg(test)]
@@ -147,9 +447,78 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_transport_creation() {
-        let config = NatsConfig::default();
-        let transport = NatsTransport::new(config).await;
-        assert!(transport.is_ok());
+    async fn test_nats_transport_requires_server() {
+        let config = NatsConfig { url: "nats://127.0.0.1:1".to_string(), timeout_ms: 500, ..NatsConfig::default() };
+        assert!(NatsTransport::new(config).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_redis_subscribe_reports_connection_failure() {
+        let transport = RedisTransport::new("redis://127.0.0.1:1").unwrap();
+        assert!(transport.subscribe("acp.>").await.is_err());
+    }
+
+    #[test]
+    fn test_subject_matches() {
+        assert!(subject_matches("acp.1.>", "acp.1.agent.status"));
+        assert!(!subject_matches("acp.1.>", "acp.1"));
+        assert!(subject_matches("acp.*.agent.step", "acp.7.agent.step"));
+        assert!(!subject_matches("acp.*.agent.step", "acp.7.other.step"));
+        assert!(!subject_matches("acp.*", "acp.7.agent"));
+        assert!(subject_matches("acp.7", "acp.7"));
+        assert_eq!(RedisTransport::glob("ns.acp.*.a[1].>"), "ns.acp.*.a\\[1\\].*");
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_publish_subscribe() {
+        let bus = InMemoryTransport::new();
+        let mut step1 = bus.subscribe_step(1).await.unwrap();
+        let mut all = bus.clone().subscribe("acp.>").await.unwrap();
+
+        bus.publish_message(&ACPMessage::status("a1".to_string(), 2, serde_json::json!({}))).await.unwrap();
+        bus.publish_message(&ACPMessage::step_complete("a1".to_string(), 1)).await.unwrap();
+
+        assert_eq!(step1.next_message(100).await.unwrap().unwrap().step, 1);
+        assert!(matches!(step1.next_message(10).await, Err(ACPError::ReceiveTimeout)));
+        assert_eq!(all.next_message(100).await.unwrap().unwrap().step, 2);
+        assert_eq!(all.next_message(100).await.unwrap().unwrap().step, 1);
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_in_memory_request_reply() {
+        let bus = InMemoryTransport::new();
+        let mut requests = bus.subscribe("svc.echo").await.unwrap();
+
+        let responder = bus.clone();
+        tokio::spawn(async move {
+            let request = requests.next_message_blocking().await.unwrap().unwrap();
+            let response = ACPMessage::status("echo".to_string(), request.step, request.payload.clone());
+            responder.reply(&request, response).await.unwrap();
+        });
+
+        let request = ACPMessage::propose("a1".to_string(), 3, serde_json::json!({"ping": 1}));
+        let reply = bus.request("svc.echo", &request, 1000).await.unwrap();
+        assert_eq!(reply.agent_id, "echo");
+        assert_eq!(reply.payload["ping"], 1);
+
+        let unanswered = bus.request("svc.nobody", &request, 20).await;
+        assert!(matches!(unanswered, Err(ACPError::RequestTimeout)));
+    }
+
+    #[tokio::test]
+    async fn test_transport_from_url() {
+        let bus = transport_from_url("memory://").await.unwrap();
+        assert!(bus.is_connected().await);
+        // Redis connects lazily, so construction works without a server
+        assert!(transport_from_url("redis://127.0.0.1:1").await.is_ok());
+        assert!(matches!(transport_from_url("carrier-pigeon://loft").await, Err(ACPError::InvalidConfig(_))));
+    }
+
+    #[test]
+    fn test_decode_skips_future_versions() {
+        let mut message = ACPMessage::coordination("a1".to_string(), 0, serde_json::json!({"msg_type": "Event"}));
+        assert!(decode(&serde_json::to_vec(&message).unwrap()).is_some());
+        message.version = crate::ACP_PROTOCOL_VERSION + 1;
+        assert!(decode(&serde_json::to_vec(&message).unwrap()).is_none());
+    }
+}
@@ -254,6 +254,7 @@ impl JsACPMessage {
             "status" => MessageType::Status,
             "propose" => MessageType::Propose,
             "step" => MessageType::Step,
+            "coordination" => MessageType::Coordination,
             _ => return Err(JsValue::from_str("Invalid message type")),
         };
 
@@ -264,6 +265,7 @@ impl JsACPMessage {
             MessageType::Status => ACPMessage::status(agent_id, step, json_payload),
             MessageType::Propose => ACPMessage::propose(agent_id, step, json_payload),
             MessageType::Step => ACPMessage::step_complete(agent_id, step),
+            MessageType::Coordination => ACPMessage::coordination(agent_id, step, json_payload),
         };
 
         Ok(Self { inner })
@@ -2,16 +2,22 @@
 //! 
 //! Enables MCP agents to participate in coordinated hive missions using the 
 //! Agent Coordination Protocol (ACP) StepSync Protocol.
+//!
+//! Hive status is folded from the mission's live traffic on `{namespace}.acp.>`. With a
+//! `MessageHistory` attached (`with_history`) that traffic is recorded per mission (see
+//! `b00t_acp::record_mission`) and late joiners replay it before going live.
 
 use anyhow::{Result, Context};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
 use tokio::time::sleep;
 use tracing::{info, warn, debug, error};
 use uuid::Uuid;
 
-use b00t_acp::{Agent, AgentConfig, ACPMessage, MessageType};
+use b00t_acp::{Agent, AgentConfig, ACPMessage, MessageHistory, MessageType, NatsConfig, NatsTransport, Transport, record_mission};
 
 /// Hive mission configuration for ACP coordination
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -63,11 +69,19 @@ pub struct HiveStatus {
 /// ACP Hive Client for MCP agent coordination
 pub struct AcpHiveClient {
     agent: Agent,
+    /// Transport the agent was built on, before any history recording
+    transport: Arc<dyn Transport>,
+    /// Hive messages delivered by the agent, folded in by `check_hive_status`
+    inbox: mpsc::UnboundedReceiver<ACPMessage>,
+    /// Agents whose STEP is seen, per step
+    ready: HashMap<u64, Vec<String>>,
     mission: HiveMission,
     agent_status: AgentStatus,
     hive_status: HiveStatus,
     /// JWT token for NATS authentication and namespace enforcement
     jwt_token: Option<String>,
+    /// Persistent mission history, when enabled
+    history: Option<Arc<dyn MessageHistory>>,
 }
 
 impl AcpHiveClient {
@@ -77,19 +91,29 @@ impl AcpHiveClient {
         role: String,
         mission: HiveMission,
         nats_url: String,
+    ) -> Result<Self> {
+        let transport = NatsTransport::new(NatsConfig { url: nats_url, ..NatsConfig::default() }).await
+            .context("Failed to connect to NATS")?;
+        Self::with_transport(agent_id, role, mission, Arc::new(transport)).await
+    }
+
+    /// Create hive client on any ACP transport
+    pub async fn with_transport(
+        agent_id: String,
+        role: String,
+        mission: HiveMission,
+        transport: Arc<dyn Transport>,
     ) -> Result<Self> {
         // Create agent configuration for hive communication
         let config = AgentConfig::new(
             agent_id.clone(),
-            nats_url,
+            String::new(),
             mission.namespace.clone(),
         )
         .with_role(role.clone())
         .with_timeout(30000);
 
-        // Initialize ACP agent
-        let agent = Agent::new(config).await
-            .context("Failed to initialize ACP agent")?;
+        let (agent, inbox) = Self::start_agent(config, Arc::clone(&transport)).await?;
 
         // Initialize agent status
         let agent_status = AgentStatus {
@@ -117,13 +141,92 @@ impl AcpHiveClient {
 
         Ok(Self {
             agent,
+            transport,
+            inbox,
+            ready: HashMap::new(),
             mission,
             agent_status,
             hive_status,
             jwt_token: None,
+            history: None,
         })
     }
 
+    /// Start an ACP agent that hands every hive message to the returned inbox
+    async fn start_agent(config: AgentConfig, transport: Arc<dyn Transport>) -> Result<(Agent, mpsc::UnboundedReceiver<ACPMessage>)> {
+        let agent = Agent::with_transport(config, transport).await
+            .context("Failed to initialize ACP agent")?;
+        let (tx, inbox) = mpsc::unbounded_channel();
+        for message_type in [MessageType::Status, MessageType::Propose, MessageType::Step] {
+            let tx = tx.clone();
+            agent.on_message(message_type, move |message| {
+                let _ = tx.send(message.clone());
+            }).await;
+        }
+        agent.start().await.context("Failed to start ACP agent")?;
+        Ok((agent, inbox))
+    }
+
+    /// Record the mission's traffic in `history` so late joiners can replay it
+    pub async fn with_history(mut self, history: Arc<dyn MessageHistory>) -> Result<Self> {
+        let transport = record_mission(Arc::clone(&history), &self.mission.mission_id, &self.mission.namespace, Arc::clone(&self.transport)).await
+            .context("Failed to record mission history")?;
+        // 🤓 Swap the agent before anything is sent so nothing escapes the record
+        self.agent.stop().await?;
+        let (agent, inbox) = Self::start_agent(self.agent.config().clone(), transport).await?;
+        self.agent = agent;
+        self.inbox = inbox;
+        self.history = Some(history);
+        Ok(self)
+    }
+
+    /// Replay recorded messages from `from_step`, folding them into the hive status
+    pub async fn replay_from(&mut self, from_step: u64) -> Result<Vec<ACPMessage>> {
+        let Some(history) = &self.history else {
+            anyhow::bail!("Mission {} has no persistent history", self.mission.mission_id);
+        };
+        let messages = history.replay(&self.mission.mission_id, from_step).await
+            .context("Failed to replay mission history")?;
+        for message in &messages {
+            self.apply_message(message);
+        }
+        Ok(messages)
+    }
+
+    /// Publish under the mission namespace, where peers and the history see it
+    async fn publish(&mut self, message: &ACPMessage) -> Result<()> {
+        let subject = format!("{}.{}", self.mission.namespace, message.subject());
+        self.agent.send_message(&subject, message).await?;
+        self.apply_message(message);
+        Ok(())
+    }
+
+    /// Update what we know about the sender from one hive message
+    fn apply_message(&mut self, message: &ACPMessage) {
+        if message.message_type == MessageType::Step {
+            let ready = self.ready.entry(message.step).or_default();
+            if !ready.contains(&message.agent_id) {
+                ready.push(message.agent_id.clone());
+            }
+        }
+        let known_role = self.hive_status.agents.get(&message.agent_id).map(|agent| agent.role.clone());
+        let status = match message.message_type {
+            MessageType::Status => message.payload["description"].as_str().unwrap_or("status").to_string(),
+            MessageType::Propose => format!("proposed {}", message.payload["action"].as_str().unwrap_or("action")),
+            MessageType::Step => format!("ready for step {}", message.step),
+            MessageType::Coordination => message.coordination_kind().unwrap_or("coordination").to_string(),
+        };
+        self.hive_status.agents.insert(message.agent_id.clone(), AgentStatus {
+            agent_id: message.agent_id.clone(),
+            step: message.step,
+            status,
+            last_seen: message.timestamp,
+            role: message.payload["role"].as_str().map(str::to_string)
+                .or(known_role)
+                .unwrap_or_else(|| "unknown".to_string()),
+        });
+    }
+
     /// Send status update to the hive
     pub async fn send_status(&mut self, description: &str, payload: Option<serde_json::Value>) -> Result<()> {
         let payload = payload.unwrap_or_else(|| {
@@ -144,8 +247,7 @@ impl AcpHiveClient {
 
         info!("🐝 Sending hive status: {} (step {})", description, self.agent_status.step);
         
-        // Send via ACP agent (stub for now)
-        self.agent.send_message(&message.subject(), &message).await
+        self.publish(&message).await
             .context("Failed to send status to hive")?;
 
         // Update local status
@@ -171,7 +273,7 @@ impl AcpHiveClient {
 
         info!("🐝 Proposing action to hive: {} (step {})", action, self.agent_status.step);
 
-        self.agent.send_message(&message.subject(), &message).await
+        self.publish(&message).await
             .context("Failed to propose action to hive")?;
 
         Ok(())
@@ -226,7 +328,7 @@ impl AcpHiveClient {
 
         info!("🐝 Signaling ready for step {}", step);
 
-        self.agent.send_message(&message.subject(), &message).await
+        self.publish(&message).await
             .context("Failed to signal step readiness")?;
 
         self.agent_status.step = step;
@@ -238,46 +340,21 @@ impl AcpHiveClient {
         self.check_hive_status().await
     }
 
-    /// Check hive status by listening for recent messages
+    /// Fold in the hive messages received since the last check
     async fn check_hive_status(&mut self) -> Result<HiveStatus> {
-        // Listen for recent messages from all agents in the hive
-        let subject_pattern = format!("{}.acp.{}.>", self.mission.namespace, self.agent_status.step);
-        
-        debug!("🐝 Checking hive status with pattern: {}", subject_pattern);
-
-        // For now, simulate hive status (stub implementation)
-        // In production, this would query NATS for recent messages
-        let mut updated_status = self.hive_status.clone();
-
-        // Update our own agent status in the hive
-        updated_status.agents.insert(
-            self.agent_status.agent_id.clone(),
-            self.agent_status.clone(),
-        );
-
-        // Simulate other agents for demo purposes
-        if self.mission.expected_agents > 1 {
-            for i in 1..=self.mission.expected_agents {
-                let other_agent_id = format!("agent_{}", i);
-                if other_agent_id != self.agent_status.agent_id {
-                    let other_status = AgentStatus {
-                        agent_id: other_agent_id.clone(),
-                        step: self.agent_status.step,
-                        status: "active".to_string(),
-                        last_seen: chrono::Utc::now(),
-                        role: "worker".to_string(),
-                    };
-                    updated_status.agents.insert(other_agent_id.clone(), other_status);
-                    updated_status.agents_ready.push(other_agent_id);
-                }
-            }
+        while let Ok(message) = self.inbox.try_recv() {
+            self.apply_message(&message);
         }
 
-        // Check if step is complete (all expected agents are ready)
-        updated_status.step_complete = updated_status.agents_ready.len() >= self.mission.expected_agents;
-
-        self.hive_status = updated_status.clone();
-        Ok(updated_status)
+        // Agents ready for our step are the ones whose STEP for it we've seen
+        let step = self.agent_status.step;
+        let ready = self.ready.get(&step).cloned().unwrap_or_default();
+        let agent_id = self.agent_status.agent_id.clone();
+        self.hive_status.agents.insert(agent_id, self.agent_status.clone());
+        self.hive_status.current_step = step;
+        self.hive_status.step_complete = ready.len() >= self.mission.expected_agents;
+        self.hive_status.agents_ready = ready;
+        Ok(self.hive_status.clone())
     }
 
     /// Create a new hive mission
@@ -297,13 +374,14 @@ impl AcpHiveClient {
         }
     }
 
-    /// Join an existing hive mission
+    /// Join an existing hive mission; with `history`, catch up on everything recorded so far
     pub async fn join_mission(
         agent_id: String,
         role: String,
         mission_id: String,
         namespace: String,
         nats_url: String,
+        history: Option<Arc<dyn MessageHistory>>,
     ) -> Result<Self> {
         // Create a mission config for joining
         let mission = HiveMission {
@@ -316,6 +394,16 @@ impl AcpHiveClient {
         };
 
         let mut client = Self::new(agent_id, role, mission, nats_url).await?;
+        if let Some(history) = history {
+            client = client.with_history(history).await?;
+            let replayed = client.replay_from(0).await?;
+            // Resume at the step the hive has reached, barrier included
+            if let Some(latest) = replayed.iter().map(|m| m.step).max() {
+                client.agent_status.step = client.agent_status.step.max(latest);
+                client.agent.catch_up(latest).await;
+            }
+            info!("🐝 Replayed {} message(s) of mission {}", replayed.len(), client.mission.mission_id);
+        }
         
         // Announce joining the mission
         client.send_status("joined_mission", None).await?;
@@ -432,6 +520,39 @@ mod tests {
         assert_eq!(status.role, "worker");
     }
 
-    // Note: Integration tests would require actual NATS server
-    // For now, focusing on unit tests of data structures
+    #[tokio::test]
+    async fn test_history_rebuilds_hive_status() {
+        let bus: Arc<dyn Transport> = Arc::new(b00t_acp::InMemoryTransport::new());
+        let history = Arc::new(b00t_acp::InMemoryHistory::new());
+        let mission = AcpHiveClient::create_mission("m1".to_string(), "account.test".to_string(), 2, "Test".to_string());
+
+        let mut leader = AcpHiveClient::with_transport("leader".to_string(), "captain".to_string(), mission.clone(), Arc::clone(&bus))
+            .await.unwrap()
+            .with_history(history.clone()).await.unwrap();
+        leader.send_status("planning", None).await.unwrap();
+        leader.signal_step_ready(1).await.unwrap();
+
+        // A late joiner only learns about the leader from the record
+        let mut late = AcpHiveClient::with_transport("late".to_string(), "worker".to_string(), mission, bus)
+            .await.unwrap()
+            .with_history(history).await.unwrap();
+        let replayed = late.replay_from(0).await.unwrap();
+        assert_eq!(replayed.len(), 2);
+        assert_eq!(late.hive_status.agents["leader"].role, "captain");
+
+        let status = late.get_hive_status().await.unwrap();
+        assert_eq!(status.agents_ready, vec!["leader".to_string()]);
+        assert!(!status.step_complete);
+
+        late.signal_step_ready(1).await.unwrap();
+        assert!(late.get_hive_status().await.unwrap().step_complete);
+
+        // From here on the leader is heard live
+        leader.send_status("building", None).await.unwrap();
+        let deadline = Instant::now() + Duration::from_secs(2);
+        while late.get_hive_status().await.unwrap().agents["leader"].status != "building" {
+            assert!(Instant::now() < deadline, "live status never arrived");
+            sleep(Duration::from_millis(20)).await;
+        }
+    }
 }
\ No newline at end of file
@@ -1,6 +1,8 @@
-//! MCP Tools for ACP Hive Communication
+//! ACP Hive Communication helpers
 //! 
-//! Provides MCP tools that enable agents to participate in coordinated hive missions.
+//! In-process `AcpHiveClient`s, one per mission, for embedders that stay joined to a hive.
+//! The registered MCP tools shell out per call and can't hold membership, so they use the
+//! stateless `b00t_acp_status/propose/step` and `b00t_agent_*` commands instead.
 
 use anyhow::{Result, Context};
 use serde::{Deserialize, Serialize};
@@ -10,7 +12,7 @@ use tokio::sync::Mutex;
 use tracing::{info, warn, error};
 
 use crate::acp_hive::{AcpHiveClient, HiveMission};
-use b00t_acp::{fetch_jwt_from_website, AcpJwtValidator};
+use b00t_acp::{fetch_jwt_from_website, history_from_url, AcpJwtValidator, JwtKey, MessageHistory};
 
 /// Global hive client registry for MCP agents
 type HiveRegistry = Arc<Mutex<HashMap<String, AcpHiveClient>>>;
@@ -30,6 +32,9 @@ pub struct JoinHiveParams {
     pub namespace: Option<String>,
     /// NATS server URL (defaults to c010.promptexecution.com:4222)
     pub nats_url: Option<String>,
+    /// Persistent history URL, nats:// (JetStream) or redis:// (Streams); defaults to B00T_ACP_HISTORY
+    #[serde(default)]
+    pub history_url: Option<String>,
 }
 
 /// Parameters for creating a new hive mission
@@ -47,6 +52,9 @@ pub struct CreateHiveParams {
     pub namespace: Option<String>,
     /// NATS server URL (defaults to c010.promptexecution.com:4222)
     pub nats_url: Option<String>,
+    /// Persistent history URL, nats:// (JetStream) or redis:// (Streams); defaults to B00T_ACP_HISTORY
+    #[serde(default)]
+    pub history_url: Option<String>,
 }
 
 /// Parameters for sending status to hive
@@ -107,18 +115,22 @@ pub async fn acp_hive_join(params: JoinHiveParams) -> Result<String> {
     let jwt_token = fetch_jwt_for_hive_operation(&params.role, &namespace).await?;
 
     // Create hive client with JWT authentication
+    let history = get_hive_history(params.history_url.as_deref()).await?;
+    let persistent = history.is_some();
     let mut client = AcpHiveClient::join_mission(
         agent_id.clone(),
         params.role.clone(),
         params.mission_id.clone(),
         namespace.clone(),
         nats_url,
+        history,
     ).await.context("Failed to join hive mission")?;
     
     // Set JWT token for security
     if jwt_token != "development_mode_no_jwt" {
         client.set_jwt_token(jwt_token.clone());
     }
+    let client_step = client.current_step();
 
     // Register client globally
     {
@@ -133,6 +145,8 @@ pub async fn acp_hive_join(params: JoinHiveParams) -> Result<String> {
         "mission_id": params.mission_id,
         "role": params.role,
         "namespace": namespace,
+        "current_step": client_step,
+        "persistent_history": persistent,
         "security": {
             "jwt_authenticated": jwt_token != "development_mode_no_jwt",
             "namespace_enforced": jwt_token != "development_mode_no_jwt"
@@ -168,12 +182,17 @@ pub async fn acp_hive_create(params: CreateHiveParams) -> Result<String> {
     );
 
     // Create hive client with JWT authentication
+    let history = get_hive_history(params.history_url.as_deref()).await?;
+    let persistent = history.is_some();
     let mut client = AcpHiveClient::new(
         agent_id.clone(),
         params.role.clone(),
         mission,
         nats_url,
     ).await.context("Failed to create hive mission")?;
+    if let Some(history) = history {
+        client = client.with_history(history).await?;
+    }
     
     // Set JWT token for security
     if jwt_token != "development_mode_no_jwt" {
@@ -195,6 +214,7 @@ pub async fn acp_hive_create(params: CreateHiveParams) -> Result<String> {
         "role": params.role,
         "namespace": namespace,
         "description": params.description,
+        "persistent_history": persistent,
         "security": {
             "jwt_authenticated": jwt_token != "development_mode_no_jwt",
             "namespace_enforced": jwt_token != "development_mode_no_jwt"
@@ -377,10 +397,24 @@ pub async fn acp_hive_leave(params: HiveShowParams) -> Result<String> {
     }
 }
 
-/// Helper to get NATS URL from environment or default
+/// Helper to get the transport URL every b00t stack defaults to
 pub fn get_nats_url() -> String {
-    std::env::var("NATS_URL")
-        .unwrap_or_else(|_| "nats://c010.promptexecution.com:4222".to_string())
+    b00t_acp::default_transport_url()
+}
+
+/// Persistent mission history from `url` or B00T_ACP_HISTORY; `None` keeps the hive fire-and-forget
+pub async fn get_hive_history(url: Option<&str>) -> Result<Option<Arc<dyn MessageHistory>>> {
+    let url = url.map(str::to_string).or_else(|| std::env::var("B00T_ACP_HISTORY").ok());
+    match url.filter(|url| !url.is_empty()) {
+        Some(url) => Ok(Some(history_from_url(&url).await
+            .with_context(|| format!("Failed to open mission history at {}", url))?)),
+        None => Ok(None),
+    }
+}
+
+/// Helper to get the current user's namespace
+pub fn get_user_namespace() -> String {
+    format!("account.{}", whoami::username())
 }
 
 /// Helper to get current hive namespace for a given role
@@ -404,23 +438,19 @@ async fn fetch_jwt_for_hive_operation(role: &str, namespace: &str) -> Result<Str
         
         match fetch_jwt_from_website(&website_url, &session_token, role).await {
             Ok(jwt) => {
-                // Validate JWT and ensure namespace matches
-                if let Ok(validator) = AcpJwtValidator::from_operator_jwt("placeholder") {
-                    if let Ok(security_ctx) = validator.validate_jwt(&jwt) {
-                        if security_ctx.namespace == namespace {
-                            info!("✅ JWT validated for namespace: {}", namespace);
-                            return Ok(jwt);
-                        } else {
-                            return Err(anyhow::anyhow!(
-                                "JWT namespace '{}' does not match requested namespace '{}'",
-                                security_ctx.namespace, namespace
-                            ));
-                        }
-                    }
+                // Validate JWT and ensure namespace matches; without a key the claims are read unverified
+                let validator = JwtKey::from_env()
+                    .map(|key| AcpJwtValidator::from_key(&key))
+                    .unwrap_or_else(AcpJwtValidator::unverified);
+                let security_ctx = validator.validate_jwt(&jwt)
+                    .context("JWT from b00t-website failed validation")?;
+                if security_ctx.namespace != namespace {
+                    return Err(anyhow::anyhow!(
+                        "JWT namespace '{}' does not match requested namespace '{}'",
+                        security_ctx.namespace, namespace
+                    ));
                 }
-                
-                // Return JWT even if validation fails (for development)
-                warn!("JWT validation failed, continuing in development mode");
+                info!("✅ JWT validated for namespace: {}", namespace);
                 return Ok(jwt);
             }
             Err(e) => {
@@ -447,6 +477,7 @@ mod tests {
             role: "worker".to_string(),
             namespace: None,
             nats_url: None,
+            history_url: None,
         };
 
         assert_eq!(join_params.mission_id, "test_mission");
@@ -456,7 +487,7 @@ mod tests {
     #[test]
     fn test_nats_url_helper() {
         let url = get_nats_url();
-        assert!(url.starts_with("nats://"));
+        assert!(url.starts_with("nats://") || url.starts_with("redis://"));
     }
 
     #[test]
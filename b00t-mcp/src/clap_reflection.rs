@@ -12,6 +12,13 @@ pub trait McpReflection: CommandFactory {
     
     /// Get the full command path (e.g., ["mcp", "list"])
     fn command_path() -> Vec<String>;
+
+    /// Names of the positional arguments, in order (as they appear in the schema)
+    fn positional_args() -> Vec<String> {
+        Self::command().get_positionals()
+            .map(|arg| arg.get_id().as_str().replace('-', "_"))
+            .collect()
+    }
     
     /// Generate MCP tool from this command structure
     fn to_mcp_tool() -> Tool {
@@ -28,6 +35,23 @@ pub trait McpReflection: CommandFactory {
         }
     }
     
+    /// JSON type of an option's value: integer and float parsers map to "integer" / "number"
+    fn value_type(arg: &clap::Arg) -> &'static str {
+        use std::any::TypeId;
+        let id = arg.get_value_parser().type_id();
+        let integers = [
+            TypeId::of::<u8>(), TypeId::of::<u16>(), TypeId::of::<u32>(), TypeId::of::<u64>(), TypeId::of::<usize>(),
+            TypeId::of::<i8>(), TypeId::of::<i16>(), TypeId::of::<i32>(), TypeId::of::<i64>(), TypeId::of::<isize>(),
+        ];
+        if integers.iter().any(|t| id == *t) {
+            "integer"
+        } else if id == TypeId::of::<f32>() || id == TypeId::of::<f64>() {
+            "number"
+        } else {
+            "string"
+        }
+    }
+
     /// Generate JSON schema from CLAP command structure
     fn generate_json_schema() -> Map<String, Value> {
         let cmd = Self::command();
@@ -46,7 +70,7 @@ pub trait McpReflection: CommandFactory {
             let arg_type = if arg.is_positional() {
                 "string"
             } else if arg.get_action().takes_values() {
-                "string" 
+                Self::value_type(arg)
             } else {
                 "boolean"
             };
@@ -83,6 +107,24 @@ pub trait McpExecutor {
     /// Execute the command with the given parameters
     fn execute_mcp_call(params: &HashMap<String, Value>) -> Result<String>;
     
+    /// Convert MCP parameters to CLAP arguments: options first, then positionals in order
+    fn cli_args(params: &HashMap<String, Value>, positionals: &[String]) -> Vec<String> {
+        let options: HashMap<String, Value> = params.iter()
+            .filter(|(key, _)| !positionals.contains(key))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+
+        let mut args = Self::params_to_args(&options);
+        for name in positionals {
+            match params.get(name) {
+                Some(Value::String(s)) => args.push(s.clone()),
+                Some(Value::Null) | None => {}
+                Some(value) => args.push(value.to_string()),
+            }
+        }
+        args
+    }
+
     /// Convert MCP parameters back to CLAP arguments
     fn params_to_args(params: &HashMap<String, Value>) -> Vec<String> {
         let mut args = Vec::new();
@@ -125,6 +167,7 @@ pub trait McpExecutor {
 pub struct McpCommandRegistry {
     commands: Arc<Vec<Box<dyn Fn() -> Tool + Send + Sync>>>,
     executors: Arc<HashMap<String, Box<dyn Fn(&HashMap<String, Value>) -> Result<String> + Send + Sync>>>,
+    positionals: Arc<HashMap<String, Vec<String>>>,
 }
 
 impl McpCommandRegistry {
@@ -132,6 +175,7 @@ impl McpCommandRegistry {
         Self {
             commands: Arc::new(Vec::new()),
             executors: Arc::new(HashMap::new()),
+            positionals: Arc::new(HashMap::new()),
         }
     }
     
@@ -145,6 +189,16 @@ impl McpCommandRegistry {
         self.commands.iter().map(|f| f()).collect()
     }
     
+    /// Get a single tool by name
+    pub fn get_tool(&self, tool_name: &str) -> Option<Tool> {
+        self.get_tools().into_iter().find(|tool| tool.name == tool_name)
+    }
+
+    /// Positional arguments of a tool, in order
+    pub fn positional_args(&self, tool_name: &str) -> &[String] {
+        self.positionals.get(tool_name).map(Vec::as_slice).unwrap_or_default()
+    }
+    
     /// Execute a tool call
     pub fn execute(&self, tool_name: &str, params: &HashMap<String, Value>) -> Result<String> {
         if let Some(executor) = self.executors.get(tool_name) {
@@ -159,6 +213,7 @@ impl McpCommandRegistry {
 pub struct McpCommandRegistryBuilder {
     commands: Vec<Box<dyn Fn() -> Tool + Send + Sync>>,
     executors: HashMap<String, Box<dyn Fn(&HashMap<String, Value>) -> Result<String> + Send + Sync>>,
+    positionals: HashMap<String, Vec<String>>,
 }
 
 impl McpCommandRegistryBuilder {
@@ -166,6 +221,7 @@ impl McpCommandRegistryBuilder {
         Self {
             commands: Vec::new(),
             executors: HashMap::new(),
+            positionals: HashMap::new(),
         }
     }
     
@@ -181,9 +237,10 @@ impl McpCommandRegistryBuilder {
         
         // Register executor
         self.executors.insert(
-            tool_name,
+            tool_name.clone(),
             Box::new(|params| T::execute_mcp_call(params))
         );
+        self.positionals.insert(tool_name, T::positional_args());
         
         self
     }
@@ -193,6 +250,7 @@ impl McpCommandRegistryBuilder {
         McpCommandRegistry {
             commands: Arc::new(self.commands),
             executors: Arc::new(self.executors),
+            positionals: Arc::new(self.positionals),
         }
     }
 }
@@ -207,6 +265,7 @@ impl Default for McpCommandRegistry {
 mod tests {
     use super::*;
     use clap::Parser;
+    use serde_json::json;
     
     #[derive(Parser)]
     struct TestCommand {
@@ -255,6 +314,17 @@ mod tests {
         assert!(tool.description.is_some());
     }
     
+    #[test]
+    fn test_cli_args_positionals_last() {
+        let mut params = HashMap::new();
+        params.insert("name".to_string(), json!("filesystem"));
+        params.insert("verbose".to_string(), json!(true));
+
+        assert_eq!(TestCommand::positional_args(), vec!["name"]);
+        let args = TestCommand::cli_args(&params, &TestCommand::positional_args());
+        assert_eq!(args, vec!["--verbose", "filesystem"]);
+    }
+
     #[test] 
     fn test_registry() {
         let mut builder = McpCommandRegistry::builder();
@@ -264,5 +334,7 @@ mod tests {
         let tools = registry.get_tools();
         assert_eq!(tools.len(), 1);
         assert_eq!(tools[0].name.as_ref(), "b00t_test");
+        assert_eq!(registry.positional_args("b00t_test"), ["name"]);
+        assert!(registry.get_tool("b00t_test").is_some());
     }
 }
\ No newline at end of file
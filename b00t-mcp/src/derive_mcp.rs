@@ -29,7 +29,7 @@ macro_rules! impl_mcp_tool {
         impl crate::clap_reflection::McpExecutor for $struct_name {
             fn execute_mcp_call(params: &std::collections::HashMap<String, serde_json::Value>) -> anyhow::Result<String> {
                 let args = Self::command_path();
-                let param_args = Self::params_to_args(params);
+                let param_args = Self::cli_args(params, &Self::positional_args());
                 
                 // Combine command path with parameters
                 let mut all_args = args;
@@ -9,6 +9,7 @@ pub mod derive_mcp;
 pub mod mcp_tools;
 pub mod acp_hive;
 pub mod acp_tools;
+pub mod slash_dispatch;
 
 pub use acl::{AclConfig, AclFilter, Policy};
 pub use mcp_server_rusty::B00tMcpServerRusty;
@@ -17,4 +18,5 @@ pub use oauth_minimal::{MinimalOAuthConfig, MinimalOAuthState, minimal_oauth_rou
 pub use github_auth::{GitHubAuthConfig, GitHubAuthState, github_auth_router, GitHubUser};
 pub use params::*;
 pub use acp_hive::{AcpHiveClient, HiveMission, AgentStatus, HiveStatus};
-pub use acp_tools::*;
\ No newline at end of file
+pub use acp_tools::*;
+pub use slash_dispatch::{SlashCommand, SlashDispatcher, SLASH_TOOL_NAME};
\ No newline at end of file
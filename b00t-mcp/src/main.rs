@@ -79,6 +79,17 @@ async fn main() -> Result<()> {
     let port = matches.get_one::<String>("port").unwrap().parse::<u16>()
         .expect("Invalid port number");
 
+    // 🤓 Grok tools run b00t-cli grok; resolve its config now so a bad setting disables them
+    //    up front instead of failing every call (the rest of the server doesn't need grok)
+    let b00t_dir = std::env::var("_B00T_Path").unwrap_or_else(|_| "~/.dotfiles/_b00t_".to_string());
+    let grok = match b00t_c0re_lib::GrokConfig::load(&b00t_cli::get_expanded_path(&b00t_dir)?) {
+        Ok(_) => true,
+        Err(e) => {
+            eprintln!("⚠️  Grok config failed to load, grok tools disabled: {:#}", e);
+            false
+        }
+    };
+
     let is_stdio_mode = matches.get_flag("stdio") || matches.get_one::<String>("mode").map_or(false, |m| m == "stdio");
     let is_http_mode = matches.get_flag("http") || matches.get_one::<String>("mode").map_or(false, |m| m == "http");
     
@@ -91,7 +102,7 @@ async fn main() -> Result<()> {
         // );
 
         // No stderr output in stdio mode as it breaks the MCP protocol
-        let server = B00tMcpServerRusty::new(working_path, &config_path)?;
+        let server = B00tMcpServerRusty::new(working_path, &config_path)?.with_grok(grok);
         let running_service = server.serve(stdio()).await?;
 
         // Keep the server running
@@ -102,7 +113,7 @@ async fn main() -> Result<()> {
         
         eprintln!("🌐 Starting HTTP MCP server on http://{}", addr);
         eprintln!("🦀 Rusty MCP server with {} compile-time tools", 
-                 B00tMcpServerRusty::new(working_path, &config_path)?.tool_count());
+                 B00tMcpServerRusty::new(working_path, &config_path)?.with_grok(grok).tool_count());
         
         // Create HTTP service with CORS support  
         let http_config = StreamableHttpServerConfig::default();
@@ -112,7 +123,7 @@ async fn main() -> Result<()> {
         
         let service: StreamableHttpService<B00tMcpServerRusty, LocalSessionManager> = 
             StreamableHttpService::new(
-                move || B00tMcpServerRusty::new(&working_dir_clone, &config_path_clone).map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e)),
+                move || B00tMcpServerRusty::new(&working_dir_clone, &config_path_clone).map(|server| server.with_grok(grok)).map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e)),
                 Default::default(),
                 http_config,
             );
@@ -16,8 +16,9 @@ use std::path::Path;
 use std::collections::HashMap;
 use tracing::{info, error, debug};
 
-use crate::mcp_tools::create_mcp_registry;
+use crate::mcp_tools::{create_mcp_registry, create_mcp_registry_with};
 use crate::clap_reflection::McpCommandRegistry;
+use crate::slash_dispatch::{SlashDispatcher, SLASH_TOOL_NAME};
 use b00t_c0re_lib::{B00tContext, utils};
 
 /// Rusty b00t MCP server with compile-time generated tools
@@ -40,6 +41,12 @@ impl B00tMcpServerRusty {
         })
     }
     
+    /// Serve the grok tools only if `enabled` (grok's config may not load)
+    pub fn with_grok(mut self, enabled: bool) -> Self {
+        self.registry = create_mcp_registry_with(enabled);
+        self
+    }
+
     /// Get the number of available tools
     pub fn tool_count(&self) -> usize {
         self.registry.get_tools().len()
@@ -97,7 +104,8 @@ impl ServerHandler for B00tMcpServerRusty {
     ) -> Result<ListToolsResult, McpError> {
         debug!("🦀 list_tools called - using compile-time generated tools");
         
-        let tools = self.registry.get_tools();
+        let mut tools = self.registry.get_tools();
+        tools.push(SlashDispatcher::tool());
         
         info!("🦀 Generated {} compile-time tools from b00t-cli CLAP structures", tools.len());
         
@@ -126,8 +134,14 @@ impl ServerHandler for B00tMcpServerRusty {
 
         info!("🦀 Executing compile-time tool: {} with params: {:?}", tool_name, params);
 
-        // Execute the command using the registry
-        match self.registry.execute(tool_name, &params) {
+        // Execute the command using the registry; b00t_slash goes through the slash dispatcher
+        let result = if tool_name == SLASH_TOOL_NAME {
+            let line = params.get("line").and_then(|v| v.as_str()).unwrap_or_default();
+            SlashDispatcher::new(self.registry.clone()).dispatch(line)
+        } else {
+            self.registry.execute(tool_name, &params)
+        };
+        match result {
             Ok(output) => {
                 info!("✅ Successfully executed tool: {}", tool_name);
                 Ok(self.create_success_result(&output))
@@ -1,4 +1,4 @@
-use clap::Parser;
+use clap::{Args, Parser};
 use crate::clap_reflection::{McpReflection, McpCommandRegistry};
 use crate::impl_mcp_tool;
 // use b00t_c0re_lib::GrokClient;
@@ -107,6 +107,10 @@ pub struct LfmfCommand {
     pub repo: bool,
     #[arg(long, group = "scope", help = "Record lesson globally (mutually exclusive with --repo)")]
     pub global: bool,
+    #[arg(long, help = "Record even if the lesson breaks the authoring rules")]
+    pub force: bool,
+    #[arg(long, help = "Also have an AI model review the lesson against the rules")]
+    pub ai_review: bool,
 }
 
 impl_mcp_tool!(LfmfCommand, "b00t_lfmf", ["lfmf"]);
@@ -116,7 +120,7 @@ impl_mcp_tool!(LfmfCommand, "b00t_lfmf", ["lfmf"]);
 pub struct AdviceCommand {
     #[arg(help = "Tool name")]
     pub tool: String,
-    #[arg(help = "Error pattern to get advice for, 'list' to show all lessons, or 'search <query>'")]
+    #[arg(help = "Error pattern to get advice for, 'list', 'search <query>', or 'upvote|downvote <id>'")]
     pub query: String,
     #[arg(long, help = "Maximum number of results to return (default: 5)")]
     pub count: Option<usize>,
@@ -173,6 +177,16 @@ impl_mcp_tool!(AiOutputCommand, "b00t_ai_output", ["ai", "output"]);
 
 // Agent coordination MCP commands
 
+/// Transport and namespace every ACP/agent tool passes through to b00t-cli
+#[derive(Args, Clone)]
+pub struct TransportArgs {
+    #[arg(long, help = "Transport URL: nats://, redis:// or memory:// (default: B00T_TRANSPORT, else NATS_URL, else redis://localhost:6379)")]
+    pub transport: Option<String>,
+
+    #[arg(long, help = "Hive namespace (defaults to account.username)")]
+    pub namespace: Option<String>,
+}
+
 /// MCP command for agent discovery
 #[derive(Parser, Clone)]
 pub struct AgentDiscoverCommand {
@@ -187,6 +201,9 @@ pub struct AgentDiscoverCommand {
 
     #[arg(long, help = "Output in JSON format")]
     pub json: bool,
+
+    #[command(flatten)]
+    pub hive: TransportArgs,
 }
 
 impl_mcp_tool!(AgentDiscoverCommand, "b00t_agent_discover", ["agent", "discover"]);
@@ -205,6 +222,9 @@ pub struct AgentMessageCommand {
 
     #[arg(long, help = "Require acknowledgment")]
     pub ack: bool,
+
+    #[command(flatten)]
+    pub hive: TransportArgs,
 }
 
 impl_mcp_tool!(AgentMessageCommand, "b00t_agent_message", ["agent", "message"]);
@@ -232,10 +252,28 @@ pub struct AgentDelegateCommand {
 
     #[arg(long, help = "Block until completion")]
     pub blocking: bool,
+
+    #[command(flatten)]
+    pub hive: TransportArgs,
 }
 
 impl_mcp_tool!(AgentDelegateCommand, "b00t_agent_delegate", ["agent", "delegate"]);
 
+/// MCP command for enforcing leases and deadlines on delegated tasks (captain only)
+#[derive(Parser, Clone)]
+pub struct AgentSuperviseCommand {
+    #[arg(long, help = "Captain agent ID whose tasks to supervise")]
+    pub captain: Option<String>,
+
+    #[arg(long, help = "Stop after this many seconds")]
+    pub duration: Option<u64>,
+
+    #[command(flatten)]
+    pub hive: TransportArgs,
+}
+
+impl_mcp_tool!(AgentSuperviseCommand, "b00t_agent_supervise", ["agent", "supervise"]);
+
 /// MCP command for completing tasks (worker response)
 #[derive(Parser, Clone)]
 pub struct AgentCompleteCommand {
@@ -253,6 +291,9 @@ pub struct AgentCompleteCommand {
 
     #[arg(long, help = "Output artifacts (comma-separated paths)")]
     pub artifacts: Option<String>,
+
+    #[command(flatten)]
+    pub hive: TransportArgs,
 }
 
 impl_mcp_tool!(AgentCompleteCommand, "b00t_agent_complete", ["agent", "complete"]);
@@ -271,6 +312,9 @@ pub struct AgentProgressCommand {
 
     #[arg(long, help = "Estimated completion in minutes")]
     pub eta: Option<u64>,
+
+    #[command(flatten)]
+    pub hive: TransportArgs,
 }
 
 impl_mcp_tool!(AgentProgressCommand, "b00t_agent_progress", ["agent", "progress"]);
@@ -295,6 +339,18 @@ pub struct AgentVoteCreateCommand {
 
     #[arg(help = "Eligible voters (comma-separated agent IDs)")]
     pub voters: String,
+
+    #[arg(long, help = "Fraction of eligible voters that must vote (default 0.5)")]
+    pub quorum: Option<f32>,
+
+    #[arg(long, help = "Support the winner needs: plurality, majority or unanimous")]
+    pub threshold: Option<String>,
+
+    #[arg(long, help = "Tie-break: option-order or undecided")]
+    pub tie_break: Option<String>,
+
+    #[command(flatten)]
+    pub hive: TransportArgs,
 }
 
 impl_mcp_tool!(AgentVoteCreateCommand, "b00t_agent_vote_create", ["agent", "vote", "create"]);
@@ -310,6 +366,9 @@ pub struct AgentVoteSubmitCommand {
 
     #[arg(long, help = "Vote reasoning")]
     pub reasoning: Option<String>,
+
+    #[command(flatten)]
+    pub hive: TransportArgs,
 }
 
 impl_mcp_tool!(AgentVoteSubmitCommand, "b00t_agent_vote_submit", ["agent", "vote", "submit"]);
@@ -331,6 +390,9 @@ pub struct AgentWaitCommand {
 
     #[arg(long, help = "Filter by subject")]
     pub subject: Option<String>,
+
+    #[command(flatten)]
+    pub hive: TransportArgs,
 }
 
 impl_mcp_tool!(AgentWaitCommand, "b00t_agent_wait", ["agent", "wait"]);
@@ -349,6 +411,9 @@ pub struct AgentNotifyCommand {
 
     #[arg(long, help = "Target specific agents (comma-separated)")]
     pub agents: Option<String>,
+
+    #[command(flatten)]
+    pub hive: TransportArgs,
 }
 
 impl_mcp_tool!(AgentNotifyCommand, "b00t_agent_notify", ["agent", "notify"]);
@@ -364,6 +429,24 @@ pub struct AgentCapabilityCommand {
 
     #[arg(long, help = "Request urgency", value_enum)]
     pub urgency: Option<String>, // "low", "normal", "high", "emergency"
+
+    #[arg(long, help = "Prefer agents running this model size: small or large")]
+    pub model_size: Option<String>,
+
+    #[arg(long, help = "Seconds to collect responses (default 2)")]
+    pub window: Option<u64>,
+
+    #[arg(long, help = "Only agents holding every capability")]
+    pub require_all: bool,
+
+    #[arg(long, help = "Delegate the task with this id to the best agent")]
+    pub delegate: Option<String>,
+
+    #[arg(long, help = "Print the ranking as JSON")]
+    pub json: bool,
+
+    #[command(flatten)]
+    pub hive: TransportArgs,
 }
 
 impl_mcp_tool!(AgentCapabilityCommand, "b00t_agent_capability", ["agent", "capability"]);
@@ -493,6 +576,21 @@ pub struct GrokAskCommand {
 
     #[arg(long, help = "Maximum number of results to return", default_value = "10")]
     pub limit: Option<usize>,
+
+    #[arg(long, help = "Drop results whose vector similarity is below this (0..1)")]
+    pub min_score: Option<f32>,
+
+    #[arg(long, help = "Ranking: hybrid (BM25 + vector), vector or keyword")]
+    pub mode: Option<String>,
+
+    #[arg(long, help = "Skip the reranker even if a rerank model is configured")]
+    pub no_rerank: bool,
+
+    #[arg(long, help = "Synthesize an answer with inline citations instead of listing chunks")]
+    pub answer: bool,
+
+    #[arg(long, help = "Refuse to answer when no source reaches this vector similarity (0..1)")]
+    pub min_confidence: Option<f32>,
 }
 
 impl_mcp_tool!(GrokAskCommand, "b00t_grok_ask", ["grok", "ask"]);
@@ -510,136 +608,170 @@ pub struct GrokLearnCommand {
 
 impl_mcp_tool!(GrokLearnCommand, "b00t_grok_learn", ["grok", "learn"]);
 
-/// MCP command for getting grok system status
+/// MCP command for incrementally indexing a directory, glob or git repository
+/// 🤓 ENTANGLED: b00t-cli/src/commands/grok.rs GrokCommands::Ingest
 #[derive(Parser, Clone)]
-pub struct GrokStatusCommand;
-
-impl_mcp_tool!(GrokStatusCommand, "b00t_grok_status", ["grok", "status"]);
+pub struct GrokIngestCommand {
+    #[arg(help = "Path, glob or git repository URL to ingest")]
+    pub target: String,
+}
 
-// ACP Hive coordination MCP tools
+impl_mcp_tool!(GrokIngestCommand, "b00t_grok_ingest", ["grok", "ingest"]);
 
-/// MCP command for joining a hive mission
+/// MCP command for listing knowledgebase topics
+/// 🤓 ENTANGLED: b00t-cli/src/commands/grok.rs GrokCommands::Topics
 #[derive(Parser, Clone)]
-pub struct AcpHiveJoinCommand {
-    #[arg(help = "Mission identifier")]
-    pub mission_id: String,
+pub struct GrokTopicsCommand;
 
-    #[arg(help = "Agent role in the mission")]
-    pub role: String,
+impl_mcp_tool!(GrokTopicsCommand, "b00t_grok_topics", ["grok", "topics"]);
 
-    #[arg(long, help = "Agent namespace (defaults to account.username)")]
-    pub namespace: Option<String>,
-
-    #[arg(long, help = "NATS server URL (defaults to c010.promptexecution.com:4222)")]
-    pub nats_url: Option<String>,
+/// MCP command for deleting a chunk or a whole topic
+/// 🤓 ENTANGLED: b00t-cli/src/commands/grok.rs GrokCommands::Forget
+#[derive(Parser, Clone)]
+pub struct GrokForgetCommand {
+    #[arg(help = "Chunk UUID or topic name")]
+    pub target: String,
 }
 
-impl_mcp_tool!(AcpHiveJoinCommand, "b00t_acp_hive_join", ["acp", "hive", "join"]);
+impl_mcp_tool!(GrokForgetCommand, "b00t_grok_forget", ["grok", "forget"]);
 
-/// MCP command for creating a hive mission
+/// MCP command for exporting the knowledgebase as JSONL
+/// 🤓 ENTANGLED: b00t-cli/src/commands/grok.rs GrokCommands::Export
 #[derive(Parser, Clone)]
-pub struct AcpHiveCreateCommand {
-    #[arg(help = "Mission identifier")]
-    pub mission_id: String,
+pub struct GrokExportCommand {
+    #[arg(short, long, help = "Output file (stdout if omitted)")]
+    pub output: Option<String>,
 
-    #[arg(help = "Expected number of agents")]
-    pub expected_agents: usize,
+    #[arg(short, long, help = "Only export this topic")]
+    pub topic: Option<String>,
 
-    #[arg(help = "Mission description")]
-    pub description: String,
+    #[arg(long, help = "Include embeddings so import can skip re-embedding")]
+    pub vectors: bool,
+}
 
-    #[arg(help = "Agent role in the mission")]
-    pub role: String,
+impl_mcp_tool!(GrokExportCommand, "b00t_grok_export", ["grok", "export"]);
 
-    #[arg(long, help = "Agent namespace (defaults to account.username)")]
-    pub namespace: Option<String>,
-
-    #[arg(long, help = "NATS server URL (defaults to c010.promptexecution.com:4222)")]
-    pub nats_url: Option<String>,
+/// MCP command for importing a JSONL knowledgebase snapshot
+/// 🤓 ENTANGLED: b00t-cli/src/commands/grok.rs GrokCommands::Import
+#[derive(Parser, Clone)]
+pub struct GrokImportCommand {
+    #[arg(help = "Snapshot file written by grok export")]
+    pub file: String,
 }
 
-impl_mcp_tool!(AcpHiveCreateCommand, "b00t_acp_hive_create", ["acp", "hive", "create"]);
+impl_mcp_tool!(GrokImportCommand, "b00t_grok_import", ["grok", "import"]);
 
-/// MCP command for sending status to hive
+/// MCP command for getting grok system status
 #[derive(Parser, Clone)]
-pub struct AcpHiveStatusCommand {
-    #[arg(help = "Mission identifier")]
-    pub mission_id: String,
+pub struct GrokStatusCommand;
 
-    #[arg(help = "Status description")]
+impl_mcp_tool!(GrokStatusCommand, "b00t_grok_status", ["grok", "status"]);
+
+// ACP MCP tools: step-sync, history and tasks (coordination is the b00t_agent_* tools)
+
+/// MCP command for announcing state to the hive
+#[derive(Parser, Clone)]
+pub struct AcpStatusCommand {
+    #[arg(long, help = "Status description")]
     pub description: String,
 
-    #[arg(long, help = "Optional payload data (JSON)")]
+    #[arg(long, help = "Additional payload (JSON)")]
     pub payload: Option<String>,
+
+    #[arg(long, help = "Step number (default 1)")]
+    pub step: Option<u64>,
+
+    #[command(flatten)]
+    pub hive: TransportArgs,
 }
 
-impl_mcp_tool!(AcpHiveStatusCommand, "b00t_acp_hive_status", ["acp", "hive", "status"]);
+impl_mcp_tool!(AcpStatusCommand, "b00t_acp_status", ["acp", "status"]);
 
-/// MCP command for proposing actions to hive
+/// MCP command for proposing an action to the hive
 #[derive(Parser, Clone)]
-pub struct AcpHiveProposeCommand {
-    #[arg(help = "Mission identifier")]
-    pub mission_id: String,
-
+pub struct AcpProposeCommand {
     #[arg(help = "Action to propose")]
     pub action: String,
 
-    #[arg(long, help = "Optional action payload (JSON)")]
+    #[arg(long, help = "Action payload (JSON)")]
     pub payload: Option<String>,
+
+    #[arg(long, help = "Step number (default 1)")]
+    pub step: Option<u64>,
+
+    #[command(flatten)]
+    pub hive: TransportArgs,
 }
 
-impl_mcp_tool!(AcpHiveProposeCommand, "b00t_acp_hive_propose", ["acp", "hive", "propose"]);
+impl_mcp_tool!(AcpProposeCommand, "b00t_acp_propose", ["acp", "propose"]);
 
-/// MCP command for step synchronization
+/// MCP command for signalling a step is complete
 #[derive(Parser, Clone)]
-pub struct AcpHiveSyncCommand {
-    #[arg(help = "Mission identifier")]
-    pub mission_id: String,
-
-    #[arg(help = "Target step to synchronize to")]
-    pub target_step: u64,
+pub struct AcpStepCommand {
+    #[arg(long, help = "Step number to complete (default 1)")]
+    pub step: Option<u64>,
 
-    #[arg(long, help = "Timeout in seconds", default_value = "60")]
-    pub timeout_seconds: u64,
+    #[command(flatten)]
+    pub hive: TransportArgs,
 }
 
-impl_mcp_tool!(AcpHiveSyncCommand, "b00t_acp_hive_sync", ["acp", "hive", "sync"]);
+impl_mcp_tool!(AcpStepCommand, "b00t_acp_step", ["acp", "step"]);
 
-/// MCP command for signaling step readiness
+/// MCP command for listening to step-sync and coordination envelopes
 #[derive(Parser, Clone)]
-pub struct AcpHiveReadyCommand {
-    #[arg(help = "Mission identifier")]
-    pub mission_id: String,
+pub struct AcpListenCommand {
+    #[arg(long, help = "Only this step")]
+    pub step: Option<u64>,
 
-    #[arg(help = "Step to signal readiness for")]
-    pub target_step: u64,
-}
+    #[arg(long, help = "Message type filter: STATUS, PROPOSE, STEP or COORDINATION")]
+    pub message_type: Option<String>,
 
-impl_mcp_tool!(AcpHiveReadyCommand, "b00t_acp_hive_ready", ["acp", "hive", "ready"]);
+    #[arg(long, help = "Seconds to listen (default 30)")]
+    pub timeout: Option<u64>,
 
-/// MCP command for showing hive status
-#[derive(Parser, Clone)]
-pub struct AcpHiveShowCommand {
-    #[arg(help = "Mission identifier (optional - shows all missions if not specified)")]
-    pub mission_id: Option<String>,
+    #[command(flatten)]
+    pub hive: TransportArgs,
 }
 
-impl_mcp_tool!(AcpHiveShowCommand, "b00t_acp_hive_show", ["acp", "hive", "show"]);
+impl_mcp_tool!(AcpListenCommand, "b00t_acp_listen", ["acp", "listen"]);
 
-/// MCP command for leaving hive mission
+/// MCP command for replaying a mission's recorded history
 #[derive(Parser, Clone)]
-pub struct AcpHiveLeaveCommand {
+pub struct AcpLogCommand {
     #[arg(help = "Mission identifier")]
     pub mission_id: String,
+
+    #[arg(long, help = "Replay from this step (default 0)")]
+    pub from_step: Option<u64>,
+
+    #[arg(long, help = "History URL: nats:// (JetStream) or redis:// (Streams); defaults to B00T_ACP_HISTORY")]
+    pub history: Option<String>,
+
+    #[arg(long, help = "Output JSON lines")]
+    pub json: bool,
 }
 
-impl_mcp_tool!(AcpHiveLeaveCommand, "b00t_acp_hive_leave", ["acp", "hive", "leave"]);
+impl_mcp_tool!(AcpLogCommand, "b00t_acp_log", ["acp", "log"]);
 
-// Custom implementations for ACP hive tools
-use crate::acp_tools::*;
+/// MCP command for listing delegated tasks and their leases
+#[derive(Parser, Clone)]
+pub struct AcpTasksCommand {
+    #[arg(long, help = "Only tasks in this state (pending, assigned, in-progress, done, failed)")]
+    pub state: Option<String>,
+
+    #[arg(long, help = "Output JSON lines")]
+    pub json: bool,
+}
+
+impl_mcp_tool!(AcpTasksCommand, "b00t_acp_tasks", ["acp", "tasks"]);
 
 /// Create and populate a registry with all available MCP tools
 pub fn create_mcp_registry() -> McpCommandRegistry {
+    create_mcp_registry_with(true)
+}
+
+/// `create_mcp_registry`, leaving the grok tools out unless `grok`
+pub fn create_mcp_registry_with(grok: bool) -> McpCommandRegistry {
     let mut builder = McpCommandRegistry::builder();
 
     // Register all MCP tools
@@ -671,27 +803,37 @@ pub fn create_mcp_registry() -> McpCommandRegistry {
         .register::<AgentDiscoverCommand>()
         .register::<AgentMessageCommand>()
         .register::<AgentDelegateCommand>()
+        .register::<AgentSuperviseCommand>()
         .register::<AgentCompleteCommand>()
         .register::<AgentProgressCommand>()
         .register::<AgentVoteCreateCommand>()
         .register::<AgentVoteSubmitCommand>()
         .register::<AgentWaitCommand>()
         .register::<AgentNotifyCommand>()
-        .register::<AgentCapabilityCommand>()
+        .register::<AgentCapabilityCommand>();
+
+    if grok {
         // Grok knowledgebase tools
-        .register::<GrokDigestCommand>()
-        .register::<GrokAskCommand>()
-        .register::<GrokLearnCommand>()
-        .register::<GrokStatusCommand>()
-        // ACP Hive coordination tools
-        .register::<AcpHiveJoinCommand>()
-        .register::<AcpHiveCreateCommand>()
-        .register::<AcpHiveStatusCommand>()
-        .register::<AcpHiveProposeCommand>()
-        .register::<AcpHiveSyncCommand>()
-        .register::<AcpHiveReadyCommand>()
-        .register::<AcpHiveShowCommand>()
-        .register::<AcpHiveLeaveCommand>();
+        builder
+            .register::<GrokDigestCommand>()
+            .register::<GrokAskCommand>()
+            .register::<GrokLearnCommand>()
+            .register::<GrokIngestCommand>()
+            .register::<GrokTopicsCommand>()
+            .register::<GrokForgetCommand>()
+            .register::<GrokExportCommand>()
+            .register::<GrokImportCommand>()
+            .register::<GrokStatusCommand>();
+    }
+
+    builder
+        // ACP envelope tools
+        .register::<AcpStatusCommand>()
+        .register::<AcpProposeCommand>()
+        .register::<AcpStepCommand>()
+        .register::<AcpListenCommand>()
+        .register::<AcpLogCommand>()
+        .register::<AcpTasksCommand>();
 
     builder.build()
 }
@@ -743,4 +885,49 @@ mod tests {
         let args = McpListCommand::params_to_args(&params);
         assert!(args.contains(&"--json".to_string()));
     }
+
+    #[test]
+    fn test_acp_status_passes_transport() {
+        let mut params = HashMap::new();
+        params.insert("description".to_string(), serde_json::json!("reviewing"));
+        params.insert("namespace".to_string(), serde_json::json!("account.hive"));
+        params.insert("transport".to_string(), serde_json::json!("redis://localhost:6379"));
+
+        assert_eq!(AcpStatusCommand::command_path(), ["acp", "status"]);
+        let args = AcpStatusCommand::cli_args(&params, &AcpStatusCommand::positional_args());
+        let transport = args.iter().position(|a| a == "--transport").unwrap();
+        assert_eq!(args[transport + 1], "redis://localhost:6379");
+        assert!(args.contains(&"--namespace".to_string()));
+    }
+
+    #[test]
+    fn test_one_tool_set_for_coordination() {
+        let names: Vec<String> = create_mcp_registry().get_tools().iter().map(|t| t.name.to_string()).collect();
+        assert!(names.contains(&"b00t_agent_delegate".to_string()));
+        assert!(names.contains(&"b00t_acp_step".to_string()));
+        assert!(!names.iter().any(|name| name.starts_with("b00t_acp_hive_") || name == "b00t_acp_send"));
+
+        let without_grok: Vec<String> = create_mcp_registry_with(false).get_tools().iter().map(|t| t.name.to_string()).collect();
+        assert!(!without_grok.iter().any(|name| name.starts_with("b00t_grok_")));
+        assert_eq!(without_grok.len() + 9, names.len());
+    }
+
+    #[test]
+    fn test_agent_wait_maps_filters_to_cli_flags() {
+        let mut params = HashMap::new();
+        params.insert("timeout".to_string(), serde_json::json!(30));
+        params.insert("message_type".to_string(), serde_json::json!("TaskCompletion"));
+        params.insert("task_id".to_string(), serde_json::json!("task-7"));
+
+        assert_eq!(AgentWaitCommand::command_path(), ["agent", "wait"]);
+        let args = AgentWaitCommand::cli_args(&params, &AgentWaitCommand::positional_args());
+        let flag = |name: &str| args.iter().position(|a| a == name).map(|i| args[i + 1].as_str());
+        assert_eq!(flag("--timeout"), Some("30"));
+        assert_eq!(flag("--message-type"), Some("TaskCompletion"));
+        assert_eq!(flag("--task-id"), Some("task-7"));
+
+        let parsed = AgentWaitCommand::try_parse_from(std::iter::once("wait".to_string()).chain(args)).unwrap();
+        assert_eq!(parsed.timeout, 30);
+        assert_eq!(parsed.task_id.as_deref(), Some("task-7"));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,352 @@
+//! Slash-command dispatcher
+//!
+//! Maps a k0mmand3r line such as `/grok ask --answer how do I rerank` onto a registered
+//! MCP tool: the verb (plus leading content words, for subcommands) names the tool, params are
+//! validated against its schema, and the remaining content fills its positional arguments.
+//! `/help` is generated from the registry.
+
+use anyhow::Result;
+use k0mmand3r::{KmdLine, KmdValue};
+use regex::Regex;
+use rmcp::model::Tool;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+use crate::clap_reflection::McpCommandRegistry;
+
+/// MCP tool that accepts a slash-command line, for chat front-ends
+pub const SLASH_TOOL_NAME: &str = "b00t_slash";
+
+const TOOL_PREFIX: &str = "b00t_";
+
+/// A slash line resolved against the registry, ready to execute
+#[derive(Debug, Clone, PartialEq)]
+pub enum SlashCommand {
+    /// `/help` or `/help <verb>`
+    Help(Option<String>),
+    Tool {
+        name: String,
+        params: HashMap<String, Value>,
+    },
+}
+
+/// Binds k0mmand3r verbs to the commands in a `McpCommandRegistry`
+#[derive(Clone)]
+pub struct SlashDispatcher {
+    registry: McpCommandRegistry,
+}
+
+impl SlashDispatcher {
+    pub fn new(registry: McpCommandRegistry) -> Self {
+        Self { registry }
+    }
+
+    /// The `b00t_slash` MCP tool definition
+    pub fn tool() -> Tool {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "line": {
+                    "type": "string",
+                    "description": "Slash command, e.g. '/grok ask --answer how do I rerank' or '/help'"
+                }
+            },
+            "required": ["line"]
+        });
+        Tool {
+            name: SLASH_TOOL_NAME.into(),
+            description: Some("Run a b00t command written as a /slash command; '/help' lists them".into()),
+            input_schema: std::sync::Arc::new(schema.as_object().cloned().unwrap_or_default()),
+            annotations: None,
+        }
+    }
+
+    /// Parse a line and map it onto a registered tool without running it
+    pub fn resolve(&self, line: &str) -> Result<SlashCommand> {
+        let parsed = KmdLine::try_parse(line).map_err(|e| anyhow::anyhow!("{}", e))?;
+        let verb = parsed.verb()
+            .ok_or_else(|| anyhow::anyhow!("Not a slash command; try /help"))?
+            .to_lowercase();
+        let mut labels = collect_params(&parsed, line);
+        let content = parsed.content().unwrap_or_default();
+
+        if verb == "help" {
+            return Ok(SlashCommand::Help(Some(content.trim().to_lowercase()).filter(|t| !t.is_empty())));
+        }
+
+        // 🤓 Subcommands arrive as content words: "/grok ask ..." → b00t_grok_ask
+        let words: Vec<String> = std::iter::once(verb.clone())
+            .chain(content.split_whitespace().take_while(|w| !w.starts_with("--")).map(str::to_lowercase))
+            .collect();
+        let tools = self.registry.get_tools();
+        let (tool, consumed) = (1..=words.len()).rev()
+            .find_map(|n| {
+                let name = format!("{}{}", TOOL_PREFIX, words[..n].join("_"));
+                tools.iter().find(|tool| tool.name == name).map(|tool| (tool, n - 1))
+            })
+            .ok_or_else(|| anyhow::anyhow!("Unknown command /{}; try /help", verb))?;
+        let name = tool.name.to_string();
+
+        // Params may also follow the subcommand: "/grok ask --answer ..." is re-parsed as "/grok --answer ..."
+        let mut rest = skip_words(content, consumed).to_string();
+        if consumed > 0 && rest.starts_with("--") {
+            let reparsed_line = format!("/{} {}", verb, rest);
+            let reparsed = KmdLine::try_parse(&reparsed_line).map_err(|e| anyhow::anyhow!("{}", e))?;
+            labels.extend(collect_params(&reparsed, &reparsed_line));
+            rest = reparsed.content().unwrap_or_default().to_string();
+        }
+
+        let params = self.bind(tool, labels, rest.trim())?;
+        Ok(SlashCommand::Tool { name, params })
+    }
+
+    /// Resolve and execute a line; `/help` renders the registry
+    pub fn dispatch(&self, line: &str) -> Result<String> {
+        match self.resolve(line)? {
+            SlashCommand::Help(topic) => Ok(self.help(topic.as_deref())),
+            SlashCommand::Tool { name, params } => self.registry.execute(&name, &params),
+        }
+    }
+
+    /// One line per command, or full usage for the commands under `topic`
+    pub fn help(&self, topic: Option<&str>) -> String {
+        let mut tools = self.registry.get_tools();
+        tools.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let Some(topic) = topic else {
+            let mut out = String::from("b00t slash commands (/help <command> for parameters):\n");
+            for tool in &tools {
+                out.push_str(&format!("  {:<40} {}\n", self.usage(tool), description(tool)));
+            }
+            return out;
+        };
+
+        let prefix = format!("{}{}", TOOL_PREFIX, topic.trim_start_matches('/').replace(' ', "_"));
+        let matching: Vec<&Tool> = tools.iter()
+            .filter(|tool| tool.name == prefix || tool.name.starts_with(&format!("{}_", prefix)))
+            .collect();
+        if matching.is_empty() {
+            return format!("No command matches /{}; try /help", topic);
+        }
+
+        let mut out = String::new();
+        for tool in matching {
+            out.push_str(&format!("{}\n  {}\n", self.usage(tool), description(tool)));
+            let positionals = self.registry.positional_args(&tool.name);
+            for (name, schema) in properties(tool).iter().filter(|(name, _)| !positionals.contains(name)) {
+                let flag = if schema_type(schema) == Some("boolean") {
+                    format!("--{}", flag_name(name))
+                } else {
+                    format!("--{}=<value>", flag_name(name))
+                };
+                out.push_str(&format!("    {:<28} {}\n", flag,
+                    schema["description"].as_str().unwrap_or_default()));
+            }
+        }
+        out
+    }
+
+    fn usage(&self, tool: &Tool) -> String {
+        let command = tool.name.strip_prefix(TOOL_PREFIX).unwrap_or(&tool.name).replace('_', " ");
+        let required = required(tool);
+        let positionals: Vec<String> = self.registry.positional_args(&tool.name).iter()
+            .map(|p| if required.contains(p) { format!("<{}>", p) } else { format!("[{}]", p) })
+            .collect();
+        std::iter::once(format!("/{}", command)).chain(positionals).collect::<Vec<_>>().join(" ")
+    }
+
+    /// Validate params against the tool schema and spread content over its positionals
+    fn bind(&self, tool: &Tool, labels: Vec<Param>, content: &str) -> Result<HashMap<String, Value>> {
+        let properties = properties(tool);
+        let mut params = HashMap::new();
+
+        for Param { label, value, token } in labels {
+            // 🤓 k0mmand3r labels are alphanumeric: --dryRun / --dryrun match dry_run
+            let (name, schema) = properties.iter()
+                .find(|(name, _)| normalize(name) == normalize(&label))
+                .ok_or_else(|| anyhow::anyhow!("{} has no --{} parameter; valid: {}", self.usage(tool), label,
+                    properties.keys().map(|k| format!("--{}", flag_name(k))).collect::<Vec<_>>().join(", ")))?;
+
+            let value = match (schema_type(schema), value) {
+                (Some("boolean"), None) => Value::Bool(true),
+                (Some("boolean"), Some(KmdValue::Boolean(b))) => Value::Bool(b),
+                (Some("boolean"), Some(other)) => anyhow::bail!("--{} is a flag; got '{}'", label, other),
+                (_, None) => anyhow::bail!("--{} needs a value, e.g. --{}=<value>", label, label),
+                (Some(kind @ ("integer" | "number")), Some(value)) => number_value(kind, &value)
+                    .ok_or_else(|| anyhow::anyhow!("--{} expects {}; got '{}'", label,
+                        if kind == "integer" { "an integer" } else { "a number" }, value))?,
+                // 🤓 The text as typed: a string "007" isn't the number 7
+                (_, Some(KmdValue::Number(n))) => Value::String(token.unwrap_or_else(|| n.to_string())),
+                (_, Some(value)) => Value::String(value.to_string()),
+            };
+            params.insert(name.clone(), value);
+        }
+
+        let open: Vec<&String> = self.registry.positional_args(&tool.name).iter()
+            .filter(|p| !params.contains_key(*p))
+            .collect();
+        if !content.is_empty() {
+            let Some((last, leading)) = open.split_last() else {
+                anyhow::bail!("{} takes no content; got '{}'", self.usage(tool), content);
+            };
+            // one word per positional, the last one takes the rest
+            let mut rest = content;
+            for name in leading {
+                if rest.is_empty() {
+                    break;
+                }
+                let (word, remaining) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+                params.insert((*name).clone(), Value::String(word.to_string()));
+                rest = remaining.trim_start();
+            }
+            if !rest.is_empty() {
+                params.insert((*last).clone(), Value::String(rest.to_string()));
+            }
+        }
+
+        let missing: Vec<String> = required(tool).into_iter().filter(|r| !params.contains_key(r)).collect();
+        if !missing.is_empty() {
+            anyhow::bail!("Missing {} for {}", missing.join(", "), self.usage(tool));
+        }
+        Ok(params)
+    }
+}
+
+/// A parsed `--label[=value]`, with the value's unquoted text as typed
+struct Param {
+    label: String,
+    value: Option<KmdValue>,
+    token: Option<String>,
+}
+
+fn collect_params(line: &KmdLine, source: &str) -> Vec<Param> {
+    line.params().into_iter()
+        .flat_map(|params| params.iter())
+        .map(|(label, value)| Param {
+            label: label.to_string(),
+            value: value.cloned(),
+            token: value.and_then(|_| raw_token(source, label)).map(str::to_string),
+        })
+        .collect()
+}
+
+/// Unquoted value of `--label` as it appears in `source` (k0mmand3r keeps only the typed value)
+fn raw_token<'a>(source: &'a str, label: &str) -> Option<&'a str> {
+    Regex::new(&format!(r#"--{}\s*=\s*([^\s"]+)"#, regex::escape(label))).ok()?
+        .captures(source)
+        .map(|c| c.get(1).map_or("", |m| m.as_str()))
+}
+
+fn skip_words(text: &str, count: usize) -> &str {
+    let mut rest = text.trim_start();
+    for _ in 0..count {
+        rest = rest.split_once(char::is_whitespace).map_or("", |(_, r)| r.trim_start());
+    }
+    rest
+}
+
+/// How to type a schema property as a k0mmand3r label: min_confidence → minConfidence
+fn flag_name(property: &str) -> String {
+    property.split(['_', '-']).enumerate()
+        .map(|(i, part)| if i == 0 { part.to_string() } else {
+            let mut chars = part.chars();
+            chars.next().map(|c| c.to_uppercase().chain(chars).collect()).unwrap_or_default()
+        })
+        .collect()
+}
+
+fn normalize(name: &str) -> String {
+    name.chars().filter(|c| *c != '_' && *c != '-').flat_map(char::to_lowercase).collect()
+}
+
+/// The property's JSON type, ignoring the "null" schemars adds for `Option`s
+fn schema_type(schema: &Value) -> Option<&str> {
+    match &schema["type"] {
+        Value::String(kind) => Some(kind),
+        Value::Array(kinds) => kinds.iter().filter_map(Value::as_str).find(|kind| *kind != "null"),
+        _ => None,
+    }
+}
+
+/// A k0mmand3r number as a JSON integer or number; `None` for other values and fractional integers
+fn number_value(kind: &str, value: &KmdValue) -> Option<Value> {
+    let text = value.as_number()?.to_string();
+    if kind == "integer" {
+        text.parse::<i64>().ok().map(Value::from)
+    } else {
+        text.parse::<f64>().ok().and_then(serde_json::Number::from_f64).map(Value::Number)
+    }
+}
+
+fn properties(tool: &Tool) -> Map<String, Value> {
+    tool.input_schema.get("properties").and_then(Value::as_object).cloned().unwrap_or_default()
+}
+
+fn required(tool: &Tool) -> Vec<String> {
+    tool.input_schema.get("required").and_then(Value::as_array)
+        .map(|r| r.iter().filter_map(Value::as_str).map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+fn description(tool: &Tool) -> &str {
+    tool.description.as_deref().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp_tools::create_mcp_registry;
+
+    fn dispatcher() -> SlashDispatcher {
+        SlashDispatcher::new(create_mcp_registry())
+    }
+
+    fn tool(name: &str, params: &[(&str, Value)]) -> SlashCommand {
+        SlashCommand::Tool {
+            name: name.to_string(),
+            params: params.iter().map(|(k, v)| (k.to_string(), v.clone())).collect(),
+        }
+    }
+
+    #[test]
+    fn test_resolve_subcommands_params_and_content() {
+        let d = dispatcher();
+        assert_eq!(d.resolve("/grok ask --answer --minconfidence=0.6 how do I rerank").unwrap(),
+            tool("b00t_grok_ask", &[("answer", json!(true)), ("min_confidence", json!(0.6)), ("query", json!("how do I rerank"))]));
+        assert_eq!(d.resolve("/advice --count=3 rust cannot borrow as mutable").unwrap(),
+            tool("b00t_advice", &[("count", json!(3)), ("tool", json!("rust")), ("query", json!("cannot borrow as mutable"))]));
+        assert_eq!(d.resolve("/whoami").unwrap(), tool("b00t_whoami", &[]));
+        assert_eq!(d.resolve("/help grok").unwrap(), SlashCommand::Help(Some("grok".to_string())));
+
+        // String parameters keep numeric-looking values as typed
+        assert_eq!(d.resolve("/grok ask --topic=007 --limit=+5 what").unwrap(),
+            tool("b00t_grok_ask", &[("topic", json!("007")), ("limit", json!(5)), ("query", json!("what"))]));
+    }
+
+    #[test]
+    fn test_resolve_rejects_invalid_lines() {
+        let d = dispatcher();
+        assert!(d.resolve("/nosuchverb").unwrap_err().to_string().contains("Unknown command"));
+        assert!(d.resolve("/grok ask --bogus what").unwrap_err().to_string().contains("no --bogus parameter"));
+        assert!(d.resolve("/grok ask --answer=maybe what").unwrap_err().to_string().contains("is a flag"));
+        assert!(d.resolve("/advice --count=abc rust borrow").unwrap_err().to_string().contains("expects an integer"));
+        assert!(d.resolve("/advice --count=1.5 rust borrow").unwrap_err().to_string().contains("expects an integer"));
+        assert!(d.resolve("/grok ask --minconfidence=high what").unwrap_err().to_string().contains("expects a number"));
+        assert!(d.resolve("/advice rust").unwrap_err().to_string().contains("Missing query"));
+        assert!(d.resolve("/whoami extra words").unwrap_err().to_string().contains("takes no content"));
+        assert!(d.resolve("just chatting").is_err());
+    }
+
+    #[test]
+    fn test_help_from_registry() {
+        let d = dispatcher();
+        let all = d.help(None);
+        assert!(all.contains("/grok ask <query>"));
+        assert!(all.contains("/advice <tool> <query>"));
+
+        let grok = d.help(Some("grok ask"));
+        assert!(grok.contains("--answer ") && grok.contains("--minConfidence=<value>"));
+        assert!(!grok.contains("/grok digest"));
+        assert!(d.help(Some("nope")).contains("No command matches"));
+    }
+}
@@ -84,26 +84,28 @@ this is just content, no verb!
 #![allow(unused_imports)]
 #![allow(dead_code)]
 #![allow(unused_variables)]
-//use indexmap::IndexMap;
+use indexmap::IndexMap;
 
-use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
 use decimal_rs::Decimal;
 
 #[cfg(feature = "lang-python")]
 use pyo3::prelude::*;
 
-use winnow::ascii::{alpha1, alphanumeric0, alphanumeric1, multispace0};
+use winnow::ascii::{alpha1, alphanumeric0, alphanumeric1, multispace0, multispace1};
 use winnow::combinator::alt; // encapsulates if/then/else ladder pattern
 use winnow::combinator::opt; // basic if then else
 use winnow::combinator::preceded; // an easy way to discard the prefix, using a provided combinators
 use winnow::combinator::{delimited, repeat, separated, separated_pair, *};
 use winnow::error::ErrMode;
 use winnow::error::ParserError;
+use winnow::error::{ContextError, StrContext, StrContextValue};
 use winnow::prelude::*;
 use winnow::seq;
 use winnow::stream::Stream; // choose between two parsers; and we're happy with either being used.
 use winnow::token::one_of; // one_of(('0'..='9', 'a'..='f', 'A'..='F')).parse_next(input)
-use winnow::{Parser};
+use winnow::{ModalResult, Parser};
 use winnow::token::take_while;
 
 use serde_json::json;
@@ -123,58 +125,78 @@ mod typescript_wasm;
 
 
 /* winnow parsers */
-fn parse_prefix_dash2x<'s>(input: &mut &'s str) -> winnow::Result<&'s str> {
+fn parse_prefix_dash2x<'s>(input: &mut &'s str) -> ModalResult<&'s str> {
     "--".parse_next(input)
 }
 
-fn parse_label<'i>(input: &mut &'i str) -> winnow::Result<&'i str> {
+fn parse_label<'i>(input: &mut &'i str) -> ModalResult<&'i str> {
     // first character alpha, followed by zero or more alphanumeric
     (alpha1, alphanumeric0).take().parse_next(input)
 }
 
-fn parse_value_quoted<'i>(input: &mut &'i str) -> winnow::Result<&'i str> {
-    // example: "a" or "1"
-    delimited('"', alphanumeric1, '"').parse_next(input)
+fn parse_value_quoted<'i>(input: &mut &'i str) -> ModalResult<&'i str> {
+    // example: "a" or "1" or "hello world"
+    let start = input.checkpoint();
+    let value = preceded('"', take_while(0.., |c: char| c != '"')).parse_next(input)?;
+    if opt('"').parse_next(input)?.is_none() {
+        // 🤓 point the caret at the opening quote, not the end of the line
+        input.reset(&start);
+        return cut_err(fail
+            .context(StrContext::Label("unterminated quote"))
+            .context(StrContext::Expected(StrContextValue::CharLiteral('"'))))
+            .parse_next(input);
+    }
+    Ok(value)
 }
 
-fn parse_value_unquoted<'i>(input: &mut &'i str) -> winnow::Result<&'i str> {
-    // example: a 1
-    alphanumeric1.parse_next(input)
+fn parse_value_unquoted<'i>(input: &mut &'i str) -> ModalResult<&'i str> {
+    // example: a 1 -2.5 @john_doe #general (runs until whitespace)
+    take_while(1.., |c: char| !c.is_whitespace() && c != '"').parse_next(input)
 }
 
-fn parse_value_quote_agnostic<'i>(input: &mut &'i str) -> winnow::Result<&'i str> {
+fn parse_value_quote_agnostic(input: &mut &str) -> ModalResult<KmdValue> {
     // example: "a" or "1" or a 1
     alt((
-        parse_value_quoted,   // Try parsing a quoted value first
-        parse_value_unquoted, // If that fails, try parsing an unquoted value
+        parse_value_quoted.map(|s: &str| KmdValue::String(s.to_string())), // quoted is always a string
+        parse_value_unquoted.map(KmdValue::from_token), // unquoted is typed by its shape
     ))
     .parse_next(input)
 }
 
-fn parse_slashcommand<'i>(input: &mut &'i str) -> winnow::Result<&'i str> {
+fn parse_slashcommand<'i>(input: &mut &'i str) -> ModalResult<&'i str> {
     // strips the / from a command-verb label
-    preceded("/", parse_label).parse_next(input)
+    preceded("/", cut_err(parse_label
+        .context(StrContext::Label("invalid verb"))
+        .context(StrContext::Expected(StrContextValue::Description("a verb starting with a letter")))))
+    .parse_next(input)
 }
 
-fn parse_KmdParameter<'i>(input: &mut &'i str) -> winnow::Result<(&'i str, &'i str)> {
-    // Parse the prefix "--"
-    // separated_pair(parse_label, "=", parse_values).parse_next(input)
+fn parse_KmdParameter<'i>(input: &mut &'i str) -> ModalResult<(&'i str, Option<KmdValue>)> {
+    // --label is a tag (None), --label=value (or --label = value) is a kvpair; "--label=" (followed by
+    // whitespace and another --param, or the end) is an empty string
+    // a bare "--" (followed by whitespace or the end) isn't a parameter, it's left for content
     preceded(
-        parse_prefix_dash2x,
-        separated_pair(
-            parse_label,
-            opt(delimited(multispace0, '=', multispace0)), // Make value part optional
-            opt(parse_value_quote_agnostic),               // Optional value
+        terminated(parse_prefix_dash2x, not(alt((multispace1, eof)))),
+        (
+            cut_err(parse_label
+                .context(StrContext::Label("invalid parameter label"))
+                .context(StrContext::Expected(StrContextValue::Description("a label starting with a letter")))),
+            opt(preceded(
+                (multispace0, '='),
+                opt(alt((
+                    preceded((multispace1, not("--")), parse_value_quote_agnostic),
+                    parse_value_quote_agnostic,
+                ))).map(|v| v.unwrap_or_else(|| KmdValue::String(String::new()))),
+            )),
         ),
     )
-    .map(|(label, value)| (label, value.unwrap_or(""))) // Default to empty string if no value
     .parse_next(input)
 }
 
 /**
  * Parse the content of a message
  */
-fn parse_content<'i>(input: &mut &'i str) -> winnow::Result<&'i str> {
+fn parse_content<'i>(input: &mut &'i str) -> ModalResult<&'i str> {
     let trimmed_input = input.trim();
 
     if trimmed_input.starts_with('/') {
@@ -196,17 +218,145 @@ fn parse_content<'i>(input: &mut &'i str) -> winnow::Result<&'i str> {
 }
 
 
+/// A typed parameter value
+/// 🤓 serializes as {"type": "number", "value": "40"}; numbers stay strings so no precision is lost
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", content = "value", rename_all = "lowercase")]
+pub enum KmdValue {
+    String(String),
+    Number(Decimal),
+    Boolean(bool),
+    /// @user, stored without the "@"
+    User(String),
+    /// #channel, stored without the "#"
+    Channel(String),
+}
+
+impl KmdValue {
+    /// Types an unquoted token: true/false, a number, @user, #channel, else a string
+    pub fn from_token(token: &str) -> Self {
+        match token {
+            "true" => return KmdValue::Boolean(true),
+            "false" => return KmdValue::Boolean(false),
+            _ => {}
+        }
+        if token.starts_with(|c: char| c.is_ascii_digit() || c == '-' || c == '+' || c == '.') {
+            if let Ok(number) = Decimal::from_str(token) {
+                return KmdValue::Number(number);
+            }
+        }
+        if let Some(user) = token.strip_prefix('@').filter(|name| is_handle(name)) {
+            return KmdValue::User(user.to_string());
+        }
+        if let Some(channel) = token.strip_prefix('#').filter(|name| is_handle(name)) {
+            return KmdValue::Channel(channel.to_string());
+        }
+        KmdValue::String(token.to_string())
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            KmdValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_number(&self) -> Option<Decimal> {
+        match self {
+            KmdValue::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            KmdValue::Boolean(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// The type name used in serialized output: string, number, boolean, user or channel
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            KmdValue::String(_) => "string",
+            KmdValue::Number(_) => "number",
+            KmdValue::Boolean(_) => "boolean",
+            KmdValue::User(_) => "user",
+            KmdValue::Channel(_) => "channel",
+        }
+    }
+}
+
+impl fmt::Display for KmdValue {
+    /// The value as it would be written in a kommand (users and channels keep their sigil)
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KmdValue::String(s) => write!(f, "{}", s),
+            KmdValue::Number(n) => write!(f, "{}", n),
+            KmdValue::Boolean(b) => write!(f, "{}", b),
+            KmdValue::User(u) => write!(f, "@{}", u),
+            KmdValue::Channel(c) => write!(f, "#{}", c),
+        }
+    }
+}
+
+// a letter followed by one or more alphanumeric, underscore or emoji characters
+fn is_handle(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(char::is_alphabetic)
+        && name.chars().count() > 1
+        && chars.all(|c| c.is_alphanumeric() || c == '_' || !c.is_ascii())
+}
+
+
+/// Parameters in the order they were given; a tag (--label) has no value
 #[derive(Debug, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
 pub struct KmdParams<'i> {
-    kvs: HashMap<&'i str, &'i str>,
+    params: IndexMap<&'i str, Option<KmdValue>>,
 }
 
 impl<'i> KmdParams<'i> {
-    pub fn parse(input: &mut &'i str) -> winnow::Result<Self> {
-        let kvs =
-            separated(0.., parse_KmdParameter, terminated(' ', multispace0)).parse_next(input)?;
+    pub fn parse(input: &mut &'i str) -> ModalResult<Self> {
+        let params: Vec<(&'i str, Option<KmdValue>)> =
+            separated(0.., parse_KmdParameter, multispace1).parse_next(input)?;
+
+        // a repeated label keeps its first position and its last value
+        Ok(Self { params: params.into_iter().collect() })
+    }
+
+    /// Value of a kvpair; None for tags and missing labels
+    pub fn get(&self, label: &str) -> Option<&KmdValue> {
+        self.params.get(label).and_then(Option::as_ref)
+    }
+
+    pub fn contains(&self, label: &str) -> bool {
+        self.params.contains_key(label)
+    }
+
+    pub fn is_tag(&self, label: &str) -> bool {
+        matches!(self.params.get(label), Some(None))
+    }
+
+    /// All parameters in order; tags yield None
+    pub fn iter(&self) -> impl Iterator<Item = (&'i str, Option<&KmdValue>)> + '_ {
+        self.params.iter().map(|(label, value)| (*label, value.as_ref()))
+    }
+
+    pub fn tags(&self) -> impl Iterator<Item = &'i str> + '_ {
+        self.params.iter().filter(|(_, value)| value.is_none()).map(|(label, _)| *label)
+    }
+
+    pub fn kvpairs(&self) -> impl Iterator<Item = (&'i str, &KmdValue)> + '_ {
+        self.params.iter().filter_map(|(label, value)| value.as_ref().map(|v| (*label, v)))
+    }
+
+    pub fn len(&self) -> usize {
+        self.params.len()
+    }
 
-        Ok(Self { kvs })
+    pub fn is_empty(&self) -> bool {
+        self.params.is_empty()
     }
 }
 
@@ -214,6 +364,56 @@ impl<'i> KmdParams<'i> {
 /* *********************** */
 
 
+/// Why a kommand failed to parse and where, for highlighting in a chat UI
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct KmdParseError {
+    /// Byte offset into the input
+    pub offset: usize,
+    pub message: String,
+    /// Tokens that would have been accepted at `offset`
+    pub expected: Vec<String>,
+    /// The offending line with a caret under `offset`
+    pub snippet: String,
+}
+
+impl KmdParseError {
+    fn new(input: &str, offset: usize, error: ContextError) -> Self {
+        let mut message = None;
+        let mut expected = Vec::new();
+        for context in error.context() {
+            match context {
+                StrContext::Label(label) => { message.get_or_insert_with(|| label.to_string()); },
+                StrContext::Expected(value) => expected.push(value.to_string()),
+                _ => {}
+            }
+        }
+        let message = message.unwrap_or_else(|| "invalid kommand".to_string());
+        Self::at(input, offset, message, expected)
+    }
+
+    fn at(input: &str, offset: usize, message: String, expected: Vec<String>) -> Self {
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..].find('\n').map_or(input.len(), |i| offset + i);
+        let column = input[line_start..offset].chars().count();
+        let snippet = format!("{}\n{}^", &input[line_start..line_end], " ".repeat(column));
+
+        KmdParseError { offset, message, expected, snippet }
+    }
+}
+
+impl fmt::Display for KmdParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at byte {}", self.message, self.offset)?;
+        if !self.expected.is_empty() {
+            write!(f, ", expected {}", self.expected.join(" or "))?;
+        }
+        write!(f, "\n{}", self.snippet)
+    }
+}
+
+impl std::error::Error for KmdParseError {}
+
+
 #[derive(Debug, PartialEq, Serialize)]
 pub struct KmdLine<'i> {
     // Verb of the command; None if it's just content
@@ -225,10 +425,41 @@ pub struct KmdLine<'i> {
 }
 
 impl<'i> KmdLine<'i> {
-    pub fn parse(input: &mut &'i str) -> winnow::Result<Self> {
+    pub fn verb(&self) -> Option<&str> {
+        self.verb.as_deref()
+    }
+
+    pub fn params(&self) -> Option<&KmdParams<'i>> {
+        self.params.as_ref()
+    }
+
+    pub fn content(&self) -> Option<&str> {
+        self.content.as_deref()
+    }
+
+    /// Parse a whole line, with a diagnostic error pointing at what's wrong
+    pub fn try_parse(input: &'i str) -> Result<Self, KmdParseError> {
+        let mut rest = input;
+        let line = KmdLine::parse(&mut rest).map_err(|e| {
+            let offset = input.len() - rest.len();
+            KmdParseError::new(input, offset, e.into_inner().unwrap_or_else(|_| ContextError::new()))
+        })?;
+
+        // 🤓 content can't start with '/', so a second verb is left unconsumed; reject it
+        let leftover = rest.trim_start();
+        if !leftover.is_empty() {
+            return Err(KmdParseError::at(input, input.len() - leftover.len(), "unexpected input".to_string(),
+                vec!["content that doesn't start with '/'".to_string()]));
+        }
+        Ok(line)
+    }
+
+    pub fn parse(input: &mut &'i str) -> ModalResult<Self> {
         let trimmed_input = input.trim();
 
         if trimmed_input.starts_with('/') {
+            let _ = multispace0.parse_next(input)?;
+
             // Parse the verb
             let verb = Some(parse_slashcommand(input)?.to_string());
 
@@ -351,7 +582,7 @@ mod tests {
     fn test_isolatedLabel() {
         let input = r#"--mylabel"#;
         let actual = parse_KmdParameter.parse(input).unwrap();
-        let expected = ("mylabel", "");
+        let expected = ("mylabel", None);
         assert_eq!(actual, expected)
     }
 
@@ -359,7 +590,7 @@ mod tests {
     fn test_dash2xlabelvalueQUOTED_for_KmdParameter() {
         let input = r#"--mylabel="40""#;
         let actual = parse_KmdParameter.parse(input).unwrap();
-        let expected = ("mylabel", "40");
+        let expected = ("mylabel", Some(KmdValue::String("40".to_string())));
         assert_eq!(actual, expected)
     }
 
@@ -367,17 +598,33 @@ mod tests {
     fn test_dash2xlabelvalueUNQUOTED_for_KmdParameter() {
         let input = r#"--mylabel=40"#;
         let actual = parse_KmdParameter.parse(input).unwrap();
-        let expected = ("mylabel", "40");
+        let expected = ("mylabel", Some(KmdValue::Number(Decimal::from(40))));
         assert_eq!(actual, expected)
     }
 
+    #[test]
+    fn test_spaces_around_equals() {
+        for input in ["--a = b", "--a =b", "--a= b", "--a=b"] {
+            assert_eq!(parse_KmdParameter.parse(input).unwrap(), ("a", Some(KmdValue::String("b".to_string()))), "{}", input);
+        }
+        // An empty value still ends at the next parameter
+        let actual = KmdParams::parse.parse("--a= --b = 2").unwrap();
+        let expected = KmdParams {
+            params: IndexMap::from([
+                ("a", Some(KmdValue::String(String::new()))),
+                ("b", Some(KmdValue::Number(Decimal::from(2)))),
+            ]),
+        };
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn test_parametersOne() {
         //let input = r#"--mylabel1="10" --mylabel2=20"#;
         let input = r#"--onelabel="10""#;
         let actual = KmdParams::parse.parse(input).unwrap();
         let expected = KmdParams {
-            kvs: HashMap::from([("onelabel", "10")]),
+            params: IndexMap::from([("onelabel", Some(KmdValue::String("10".to_string())))]),
         };
 
         assert_eq!(actual, expected)
@@ -389,7 +636,10 @@ mod tests {
         let input = r#"--mylabel="10" --yourlabel=20"#;
         let actual = KmdParams::parse.parse(input).unwrap();
         let expected = KmdParams {
-            kvs: HashMap::from([("mylabel", "10"), ("yourlabel", "20")]),
+            params: IndexMap::from([
+                ("mylabel", Some(KmdValue::String("10".to_string()))),
+                ("yourlabel", Some(KmdValue::Number(Decimal::from(20)))),
+            ]),
         };
 
         assert_eq!(actual, expected)
@@ -402,7 +652,7 @@ mod tests {
         let expected = KmdLine {
             verb: Some("save".to_string()),
             params: Some(KmdParams {
-                kvs: HashMap::from([("mylabel", "myvalue")]),
+                params: IndexMap::from([("mylabel", Some(KmdValue::String("myvalue".to_string())))]),
             }),
             content: Some("remaining content".to_string()),
         };
@@ -438,6 +688,81 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_parse_errors() {
+        let err = KmdLine::try_parse(r#"/say --note="unterminated and more"#).unwrap_err();
+        assert_eq!(err.offset, 12);
+        assert_eq!(err.message, "unterminated quote");
+        assert_eq!(err.expected, vec!["`\"`".to_string()]);
+        assert_eq!(err.snippet, "/say --note=\"unterminated and more\n            ^");
+
+        let err = KmdLine::try_parse("/say --1abc").unwrap_err();
+        assert_eq!((err.offset, err.message.as_str()), (7, "invalid parameter label"));
+        assert_eq!(err.expected, vec!["a label starting with a letter".to_string()]);
+        assert_eq!(err.to_string(), "invalid parameter label at byte 7, expected a label starting with a letter\n/say --1abc\n       ^");
+
+        let err = KmdLine::try_parse("  /9lives").unwrap_err();
+        assert_eq!((err.offset, err.message.as_str()), (3, "invalid verb"));
+        assert_eq!(err.snippet, "  /9lives\n   ^");
+
+        // a bare "--" is content, not a parameter
+        let line = KmdLine::try_parse("/say -- just words").unwrap();
+        assert_eq!(line.content(), Some("-- just words"));
+    }
+
+    #[test]
+    fn test_leftover_input() {
+        let err = KmdLine::try_parse("/say /other").unwrap_err();
+        assert_eq!((err.offset, err.message.as_str()), (5, "unexpected input"));
+        assert_eq!(err.snippet, "/say /other\n     ^");
+
+        let err = KmdLine::try_parse("/say --a=1 /x tail").unwrap_err();
+        assert_eq!((err.offset, err.message.as_str()), (11, "unexpected input"));
+
+        // whitespace may surround "=", so a value after "--note= " is the note, not content
+        let line = KmdLine::try_parse("/say --note= hello world").unwrap();
+        assert_eq!(line.params().unwrap().get("note").and_then(KmdValue::as_str), Some("hello"));
+        assert_eq!(line.content(), Some("world"));
+
+        // any whitespace separates parameters
+        let line = KmdLine::try_parse("/say --a=1\t--b=2 c").unwrap();
+        let labels: Vec<&str> = line.params().unwrap().iter().map(|(label, _)| label).collect();
+        assert_eq!(labels, vec!["a", "b"]);
+        assert_eq!(line.content(), Some("c"));
+    }
+
+    #[test]
+    fn test_value_types() {
+        assert_eq!(KmdValue::from_token("-2.50"), KmdValue::Number(Decimal::from_str("-2.50").unwrap()));
+        assert_eq!(KmdValue::from_token("true"), KmdValue::Boolean(true));
+        assert_eq!(KmdValue::from_token("@john_doe"), KmdValue::User("john_doe".to_string()));
+        assert_eq!(KmdValue::from_token("#general"), KmdValue::Channel("general".to_string()));
+        assert_eq!(KmdValue::from_token("#1"), KmdValue::String("#1".to_string()));
+        assert_eq!(KmdValue::from_token("1.2.3"), KmdValue::String("1.2.3".to_string()));
+        assert_eq!(KmdValue::from_token("@john_doe").to_string(), "@john_doe");
+    }
+
+    #[test]
+    fn test_params_ordered_with_tags() {
+        let mut input = r#"/deploy --zeta=1 --force --user=@john_doe --note="ship it" --alpha=false --channel=#general go now"#;
+        let actual = KmdLine::parse(&mut input).unwrap();
+        let params = actual.params().unwrap();
+
+        let labels: Vec<&str> = params.iter().map(|(label, _)| label).collect();
+        assert_eq!(labels, vec!["zeta", "force", "user", "note", "alpha", "channel"]);
+        assert!(params.is_tag("force"));
+        assert_eq!(params.tags().collect::<Vec<_>>(), vec!["force"]);
+        assert_eq!(params.get("note").and_then(KmdValue::as_str), Some("ship it"));
+        assert_eq!(params.get("alpha").and_then(KmdValue::as_bool), Some(false));
+        assert_eq!(params.get("user"), Some(&KmdValue::User("john_doe".to_string())));
+        assert_eq!(actual.content(), Some("go now"));
+
+        assert_eq!(
+            serde_json::to_string(params).unwrap(),
+            r#"{"zeta":{"type":"number","value":"1"},"force":null,"user":{"type":"user","value":"john_doe"},"note":{"type":"string","value":"ship it"},"alpha":{"type":"boolean","value":false},"channel":{"type":"channel","value":"general"}}"#
+        );
+    }
+
 }
 
 
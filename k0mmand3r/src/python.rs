@@ -1,11 +1,15 @@
 
 // file src/python.rs
-use std::collections::HashMap;
 use pyo3::prelude::*;
 use pyo3::wrap_pyfunction;
-use pyo3::types::PyModule;
+use pyo3::types::{PyBool, PyDict, PyModule, PyString};
 use pyo3::Bound;
-use crate::KmdLine;  // Replace with actual module path as needed
+use std::str::FromStr;
+use crate::{KmdLine, KmdParseError, KmdValue};
+
+// Raised by parse_kmd_line; carries offset, expected and snippet attributes
+#[cfg(feature = "lang-python")]
+pyo3::create_exception!(k0mmand3r, KmdParseException, pyo3::exceptions::PyValueError);
 
 
 // PyKmdLine is a Python-friendly version of KmdLine
@@ -13,16 +17,55 @@ use crate::KmdLine;  // Replace with actual module path as needed
 #[pyclass]
 pub struct PyKmdLine {
     verb: Option<String>,
-    params: Option<HashMap<String, String>>, // Adjust as needed
+    // 🤓 ordered (label, value) pairs; None is a tag
+    params: Option<Vec<(String, Option<KmdValue>)>>,
     content: Option<String>,
 }
 
+// str, decimal.Decimal or bool; users and channels are str with their "@"/"#" sigil
+#[cfg(feature = "lang-python")]
+fn value_to_py<'py>(py: Python<'py>, value: &KmdValue) -> PyResult<Bound<'py, PyAny>> {
+    match value {
+        KmdValue::Number(n) => py.import("decimal")?.getattr("Decimal")?.call1((n.to_string(),)),
+        KmdValue::Boolean(b) => Ok(b.into_pyobject(py)?.to_owned().into_any()),
+        other => Ok(other.to_string().into_pyobject(py)?.into_any()),
+    }
+}
+
+// The inverse of value_to_py: None is a tag, str is typed by its "@"/"#" sigil, numbers go through Decimal
+#[cfg(feature = "lang-python")]
+fn value_from_py(value: &Bound<'_, PyAny>) -> PyResult<Option<KmdValue>> {
+    if value.is_none() {
+        return Ok(None);
+    }
+    if let Ok(flag) = value.downcast::<PyBool>() {
+        return Ok(Some(KmdValue::Boolean(flag.is_true())));
+    }
+    if let Ok(text) = value.downcast::<PyString>() {
+        let text = text.to_cow()?;
+        return Ok(Some(match KmdValue::from_token(&text) {
+            typed @ (KmdValue::User(_) | KmdValue::Channel(_)) => typed,
+            _ => KmdValue::String(text.into_owned()),
+        }));
+    }
+    let number = value.str()?.to_cow()?.into_owned();
+    decimal_rs::Decimal::from_str(&number)
+        .map(|n| Some(KmdValue::Number(n)))
+        .map_err(|_| pyo3::exceptions::PyTypeError::new_err(format!("unsupported parameter value {}", number)))
+}
+
 #[cfg(feature = "lang-python")]
 #[pymethods]
 impl PyKmdLine {
     #[new]
-    pub fn new(verb: Option<String>, params: Option<HashMap<String, String>>, content: Option<String>) -> Self {
-        PyKmdLine { verb, params, content }
+    #[pyo3(signature = (verb=None, params=None, content=None))]
+    pub fn new(verb: Option<String>, params: Option<&Bound<'_, PyDict>>, content: Option<String>) -> PyResult<Self> {
+        let params = params.map(|dict| {
+            dict.iter()
+                .map(|(label, value)| Ok((label.extract::<String>()?, value_from_py(&value)?)))
+                .collect::<PyResult<Vec<_>>>()
+        }).transpose()?;
+        Ok(PyKmdLine { verb, params, content })
     }
 
     #[getter]
@@ -30,9 +73,35 @@ impl PyKmdLine {
         self.verb.clone()
     }
 
+    /// Parameters as an ordered dict; tags map to None
     #[getter]
-    pub fn params(&self) -> Option<HashMap<String, String>> {
-        self.params.clone()
+    pub fn params<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        let Some(params) = &self.params else { return Ok(None) };
+        let dict = PyDict::new(py);
+        for (label, value) in params {
+            match value {
+                Some(value) => dict.set_item(label, value_to_py(py, value)?)?,
+                None => dict.set_item(label, py.None())?,
+            }
+        }
+        Ok(Some(dict))
+    }
+
+    /// Labels of the value-less parameters, in order
+    #[getter]
+    pub fn tags(&self) -> Vec<String> {
+        self.params.iter().flatten()
+            .filter(|(_, value)| value.is_none())
+            .map(|(label, _)| label.clone())
+            .collect()
+    }
+
+    /// Type of each kvpair: string, number, boolean, user or channel
+    #[getter]
+    pub fn param_types(&self) -> Vec<(String, String)> {
+        self.params.iter().flatten()
+            .filter_map(|(label, value)| value.as_ref().map(|v| (label.clone(), v.type_name().to_string())))
+            .collect()
     }
 
     #[getter]
@@ -52,7 +121,7 @@ fn convert_to_pykmdline(kmdline: KmdLine) -> PyKmdLine {
     PyKmdLine {
         verb: kmdline.verb,
         params: kmdline.params.map(|params| {
-            params.kvs.into_iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+            params.iter().map(|(label, value)| (label.to_string(), value.cloned())).collect()
         }),
         content: kmdline.content,
     }
@@ -61,25 +130,22 @@ fn convert_to_pykmdline(kmdline: KmdLine) -> PyKmdLine {
 
 #[cfg(feature = "lang-python")]
 #[pyfunction]
-pub fn parse_kmd_line(input: String) -> PyResult<PyKmdLine> {
-    let mut input_str = &input[..];
-    match KmdLine::parse(&mut input_str) {
-        Ok(kmdline) => {
-            Ok(convert_to_pykmdline(kmdline))
-        },
-        // Ok(kmdline) => {
-        //     // Serialize KmdLine to JSON string
-        //     serde_json::to_string(&kmdline)
-        //         .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
-        //             format!("Serialization error: {:?}", e)
-        //         ))
-        // },
-        Err(e) => {
-            Err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
-                format!("Parse error: {:?}", e)
-            ))
-        }
-    }
+pub fn parse_kmd_line(py: Python<'_>, input: String) -> PyResult<PyKmdLine> {
+    KmdLine::try_parse(&input)
+        .map(convert_to_pykmdline)
+        .map_err(|e| to_py_err(py, &e))
+}
+
+#[cfg(feature = "lang-python")]
+fn to_py_err(py: Python<'_>, error: &KmdParseError) -> PyErr {
+    let err = KmdParseException::new_err(error.to_string());
+    let value = err.value(py);
+    // 🦨 setattr on a fresh exception instance only fails on OOM; keep the message regardless
+    let _ = value.setattr("offset", error.offset);
+    let _ = value.setattr("message", error.message.clone());
+    let _ = value.setattr("expected", error.expected.clone());
+    let _ = value.setattr("snippet", error.snippet.clone());
+    err
 }
 
 
@@ -90,6 +156,7 @@ pub fn parse_kmd_line(input: String) -> PyResult<PyKmdLine> {
 #[pymodule]
 pub fn k0mmand3r(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(parse_kmd_line, m)?)?;
+    m.add("KmdParseError", m.py().get_type::<KmdParseException>())?;
     Ok(())
 }
 
@@ -3,14 +3,15 @@
 use wasm_bindgen::prelude::*;
 use std::convert::TryFrom;
 use wasm_bindgen::JsValue;
-use crate::KmdLine;
+use crate::{KmdLine, KmdParseError};
 
 
 #[cfg(target_arch = "wasm32")]
 #[wasm_bindgen]
 pub struct KmdLineWasm {
     verb: Option<String>,
-    params: Option<String>,  // JSON string
+    params: Option<String>,  // JSON string, ordered: {"label": {"type": "number", "value": "40"}, "tag": null}
+    tags: Vec<String>,
     content: Option<String>,
 }
 
@@ -21,6 +22,7 @@ impl KmdLineWasm {
         KmdLineWasm {
             verb: None,
             params: None,
+            tags: Vec::new(),
             content: None,
         }
     }
@@ -33,19 +35,63 @@ impl KmdLineWasm {
         self.params.clone()
     }
 
+    /// Labels of the value-less parameters, in order
+    pub fn tags(&self) -> Vec<String> {
+        self.tags.clone()
+    }
+
     pub fn content(&self) -> Option<String> {
         self.content.clone()
     }
 
+    /// Throws a KmdParseErrorWasm on invalid input
     pub fn parse(input: &str) -> Result<KmdLineWasm, JsValue> {
-        let mut input_str = input;
-        match KmdLine::parse(&mut input_str) {
+        match KmdLine::try_parse(input) {
             Ok(kmdline) => KmdLineWasm::try_from(&kmdline),
-            Err(e) => Err(JsValue::from_str(&format!("Parse error: {:?}", e))),
+            Err(e) => Err(KmdParseErrorWasm::from(e).into()),
         }
     }
 }
 
+// KmdParseError for JS: byte offset, message, expected tokens and a caret-annotated snippet
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub struct KmdParseErrorWasm {
+    error: KmdParseError,
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+impl KmdParseErrorWasm {
+    pub fn offset(&self) -> usize {
+        self.error.offset
+    }
+
+    pub fn message(&self) -> String {
+        self.error.message.clone()
+    }
+
+    pub fn expected(&self) -> Vec<String> {
+        self.error.expected.clone()
+    }
+
+    pub fn snippet(&self) -> String {
+        self.error.snippet.clone()
+    }
+
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_string(&self) -> String {
+        self.error.to_string()
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl From<KmdParseError> for KmdParseErrorWasm {
+    fn from(error: KmdParseError) -> Self {
+        KmdParseErrorWasm { error }
+    }
+}
+
 // impl From<&KmdLine<'_>> for KmdLineWasm {
 //     fn from(kmdline: &KmdLine) -> Self {
 //         let verb = kmdline.verb.clone();
@@ -73,12 +119,16 @@ impl TryFrom<&KmdLine<'_>> for KmdLineWasm {
         let content = kmdline.content.clone();
 
         let params = kmdline.params.as_ref()
-            .map(|kmdparams| serde_json::to_string(&kmdparams.kvs).unwrap_or_default())
+            .map(|kmdparams| serde_json::to_string(kmdparams).unwrap_or_default())
             .filter(|s| !s.is_empty()); // Only Some if string is not empty
+        let tags = kmdline.params.as_ref()
+            .map(|kmdparams| kmdparams.tags().map(str::to_string).collect())
+            .unwrap_or_default();
 
         Ok(KmdLineWasm {
             verb,
             params,
+            tags,
             content,
         })
     }
@@ -97,12 +147,12 @@ mod tests {
         let input = "/verb --param1=value1 --param2=value2";
         let result = KmdLineWasm::parse(input).unwrap();
         assert_eq!(result.verb(), Some("verb".to_string()));
-        // Additional assertions for 'params' and 'content'
+        assert_eq!(result.params(), Some(r#"{"param1":{"type":"string","value":"value1"},"param2":{"type":"string","value":"value2"}}"#.to_string()));
     }
 
     #[test]
     fn test__WASM__parse_invalid_input() {
-        let input = "invalid input";
+        let input = "/verb --1abc";
         let result = KmdLineWasm::parse(input);
         assert!(result.is_err());
         // You can also test for specific error messages if desired
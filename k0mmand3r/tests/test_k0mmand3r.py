@@ -28,6 +28,29 @@ class TestK0mmand3r(unittest.TestCase):
         # Check content
         self.assertEqual(result.content, "random content")
 
+    def test_typed_params(self):
+        from decimal import Decimal
+
+        result = k0mmand3r_py.parse_kmd_line(
+            '/deploy --zeta=2.5 --force --who=@john_doe --note="ship it" --dry=false'
+        )
+        self.assertEqual(list(result.params), ["zeta", "force", "who", "note", "dry"])
+        self.assertEqual(result.params["zeta"], Decimal("2.5"))
+        self.assertIsNone(result.params["force"])
+        self.assertEqual(result.params["who"], "@john_doe")
+        self.assertEqual(result.params["note"], "ship it")
+        self.assertIs(result.params["dry"], False)
+        self.assertEqual(result.tags, ["force"])
+        self.assertIn(("who", "user"), result.param_types)
+
+    def test_parse_error(self):
+        with self.assertRaises(k0mmand3r_py.KmdParseError) as ctx:
+            k0mmand3r_py.parse_kmd_line("/say --1abc")
+        self.assertEqual(ctx.exception.offset, 7)
+        self.assertEqual(ctx.exception.message, "invalid parameter label")
+        self.assertEqual(ctx.exception.expected, ["a label starting with a letter"])
+        self.assertEqual(ctx.exception.snippet, "/say --1abc\n       ^")
+
     def test_content_only(self):
         input_str = "this is just content, no verb!"
         result = k0mmand3r_py.parse_kmd_line(input_str)
@@ -7,7 +7,7 @@ describe('Wasm Tests', () => {
     it('should correctly parse KmdLine with verb and parameter', () => {
         const result = KmdLineWasm.parse('/verb --param1=value1');
         assert.strictEqual(result.verb(), 'verb');
-        assert.strictEqual(result.params(), '{"param1":"value1"}');
+        assert.strictEqual(result.params(), '{"param1":{"type":"string","value":"value1"}}');
       });
 
       it('should correctly parse content without a command', () => {
@@ -19,7 +19,7 @@ describe('Wasm Tests', () => {
       it('should correctly parse multiple parameters', () => {
         const result = KmdLineWasm.parse('/multiverb --param1=value1 --param2=value2');
         assert.strictEqual(result.verb(), 'multiverb');
-        assert.strictEqual(result.params(), '{"param1":"value1","param2":"value2"}');
+        assert.strictEqual(result.params(), '{"param1":{"type":"string","value":"value1"},"param2":{"type":"string","value":"value2"}}');
       });
 
       it('should handle only verb without parameters', () => {
@@ -31,7 +31,24 @@ describe('Wasm Tests', () => {
       it('should correctly parse command with tags', () => {
         const result = KmdLineWasm.parse('/tagverb --tag1 --tag2');
         assert.strictEqual(result.verb(), 'tagverb');
-        assert.strictEqual(result.params(), '{"tag1":"","tag2":""}');
+        assert.strictEqual(result.params(), '{"tag1":null,"tag2":null}');
+        assert.deepStrictEqual(result.tags(), ['tag1', 'tag2']);
+      });
+
+      it('should type parameter values', () => {
+        const result = KmdLineWasm.parse('/typed --count=3 --ok=true --who=@john_doe --where=#general');
+        assert.strictEqual(result.params(),
+          '{"count":{"type":"number","value":"3"},"ok":{"type":"boolean","value":true},' +
+          '"who":{"type":"user","value":"john_doe"},"where":{"type":"channel","value":"general"}}');
+      });
+
+      it('should report where a command is invalid', () => {
+        assert.throws(() => KmdLineWasm.parse('/say --note="unterminated'), (err: any) => {
+          assert.strictEqual(err.offset(), 12);
+          assert.strictEqual(err.message(), 'unterminated quote');
+          assert.strictEqual(err.snippet(), '/say --note="unterminated\n            ^');
+          return true;
+        });
       });
 
   // Additional test cases